@@ -253,7 +253,12 @@ class ApiService {
       }
     }
     if (res.statusCode != 200) {
-      // throw Exception('Failed to fetch Fab library: ${res.statusCode} ${res.body}');
+      // Upstream failures come back as { ok: false, code, message } (e.g., 502 when Epic returned nothing)
+      String? message;
+      try {
+        message = (jsonDecode(res.body) as Map<String, dynamic>)['message']?.toString();
+      } catch (_) {}
+      throw Exception(message ?? 'Failed to fetch Fab library: ${res.statusCode}');
     }
     // The backend returns either the full JSON object or sometimes a string body on edge cases.
     final dynamic decoded = jsonDecode(res.body);
@@ -280,8 +285,12 @@ class ApiService {
       }
     }
     if (res.statusCode != 200) {
-      // Surface server error
-      throw Exception('Failed to refresh Fab library: ${res.statusCode} ${res.body}');
+      // Surface server error; JSON errors carry a readable message
+      String? message;
+      try {
+        message = (jsonDecode(res.body) as Map<String, dynamic>)['message']?.toString();
+      } catch (_) {}
+      throw Exception(message ?? 'Failed to refresh Fab library: ${res.statusCode} ${res.body}');
     }
     final dynamic decoded = jsonDecode(res.body);
     if (decoded is Map<String, dynamic>) {
@@ -0,0 +1,364 @@
+//! Download recovery endpoints.
+//!
+//! - GET /incomplete-downloads to list asset folders that never reached `.download_complete`.
+//! - POST /resume-incomplete to re-run them one at a time in the background, under one job id.
+//! - POST /discard-download to delete one stuck download and its temp chunks.
+//! - DELETE /downloaded-asset/{name} to delete a downloaded asset and reclaim its disk space.
+//! - GET /skipped-files to see which manifest files EAM_SKIP_PATTERNS left out.
+
+use actix_web::{delete, get, post, web, HttpResponse};
+use actix_web::http::StatusCode;
+use std::collections::HashMap;
+
+use crate::models;
+use crate::utils;
+
+/// Lists downloads that were started but never completed (no `.download_complete` marker).
+///
+/// Each entry includes the recorded artifact info from `.download_info.json` when available;
+/// downloads started before the info marker existed report `info: null` and cannot be resumed.
+///
+/// Example (curl):
+/// - curl -s http://localhost:8080/incomplete-downloads | jq
+#[get("/incomplete-downloads")]
+pub async fn list_incomplete_downloads() -> HttpResponse {
+    let root = utils::get_default_downloads_dir_path();
+    let incomplete = utils::find_incomplete_downloads(&root);
+    HttpResponse::Ok().json(models::IncompleteDownloadsResponse {
+        downloads_directory: root.to_string_lossy().to_string(),
+        incomplete,
+    })
+}
+
+/// Re-triggers every incomplete download with recorded artifact info, one at a time, under one job id.
+///
+/// Query params:
+/// - jobId or job_id: optional; a job id is generated when omitted and returned in the response.
+///
+/// Returns 202 Accepted with { job_id, outcomes: [ { asset_name, version, status, message, job_id } ] } right away
+/// (200 OK when nothing could be queued). Each queued download runs as its own job (the outcome's `job_id`) once
+/// the previous one has finished; after each, a download:progress event on the top-level `job_id` reports its
+/// outcome in `details` ({ asset_name, version, status: complete|failed|cancelled, message, job_id }). The top-level
+/// job ends with download:complete, or download:error when any download failed. Cancelling it cancels the
+/// running download and leaves the rest unstarted. Downloads without recorded info are skipped.
+///
+/// Example (curl):
+/// - curl -s -X POST "http://localhost:8080/resume-incomplete?jobId=resume-1" | jq
+#[post("/resume-incomplete")]
+pub async fn resume_incomplete_downloads(query: web::Query<HashMap<String, String>>) -> HttpResponse {
+    let job_id = query
+        .get("jobId")
+        .cloned()
+        .or_else(|| query.get("job_id").cloned())
+        .unwrap_or_else(|| utils::new_job_id("resume"));
+
+    let root = utils::get_default_downloads_dir_path();
+    let incomplete = utils::find_incomplete_downloads(&root);
+    let mut outcomes: Vec<models::ResumeOutcome> = Vec::new();
+    let mut queue: Vec<QueuedResume> = Vec::new();
+
+    for item in incomplete {
+        let Some(info) = item.info else {
+            outcomes.push(models::ResumeOutcome {
+                asset_name: item.asset_name,
+                version: item.version,
+                status: "skipped".to_string(),
+                message: "No recorded artifact info; download it again from the library".to_string(),
+                job_id: None,
+            });
+            continue;
+        };
+        let download_job = format!("{}-{}", job_id, queue.len() + 1);
+        outcomes.push(models::ResumeOutcome {
+            asset_name: item.asset_name.clone(),
+            version: item.version.clone(),
+            status: "queued".to_string(),
+            message: "Download queued".to_string(),
+            job_id: Some(download_job.clone()),
+        });
+        queue.push(QueuedResume { asset_name: item.asset_name, version: item.version, info, job_id: download_job });
+    }
+
+    if queue.is_empty() {
+        return HttpResponse::Ok().json(models::ResumeIncompleteResponse { job_id, outcomes });
+    }
+    utils::emit_event(Some(&job_id), models::Phase::DownloadStart, format!("Resuming {} downloads", queue.len()), Some(0.0), None);
+    actix_web::rt::spawn(utils::with_log_job(Some(job_id.clone()), run_resume_queue(job_id.clone(), queue)));
+    HttpResponse::Accepted().json(models::ResumeIncompleteResponse { job_id, outcomes })
+}
+
+/// One download waiting in a /resume-incomplete queue.
+struct QueuedResume {
+    asset_name: String,
+    version: Option<String>,
+    info: models::DownloadInfo,
+    /// The download's own job id.
+    job_id: String,
+}
+
+/// Runs `queue` in order, reporting each outcome on `job_id`. A cancel of `job_id` is forwarded to the
+/// running download and stops the queue.
+async fn run_resume_queue(job_id: String, queue: Vec<QueuedResume>) {
+    let total = queue.len();
+    let mut failed = 0usize;
+    for (i, item) in queue.into_iter().enumerate() {
+        if utils::check_if_job_is_cancelled(Some(&job_id)) {
+            // /cancel-job already reported the cancel on this job
+            utils::acknowledge_cancel(&job_id);
+            return;
+        }
+        let mut q: HashMap<String, String> = HashMap::new();
+        q.insert("jobId".to_string(), item.job_id.clone());
+        if let Some(ref ue) = item.info.ue { q.insert("ue".to_string(), ue.clone()); }
+        let path = web::Path::from((item.info.namespace, item.info.asset_id, item.info.artifact_id));
+
+        let download = utils::with_log_job(Some(item.job_id.clone()), utils::download_asset_handler(path, web::Query(q)));
+        tokio::pin!(download);
+        let mut forwarded = false;
+        let result = loop {
+            tokio::select! {
+                result = &mut download => break result,
+                _ = tokio::time::sleep(std::time::Duration::from_millis(250)) => {
+                    if !forwarded && utils::check_if_job_is_cancelled(Some(&job_id)) {
+                        utils::cancel_job(&item.job_id);
+                        forwarded = true;
+                    }
+                }
+            }
+        };
+        if forwarded {
+            utils::acknowledge_cancel(&job_id);
+            return;
+        }
+        let (status, message) = match result {
+            Ok(resp) if resp.status().is_success() => ("complete", "Download complete".to_string()),
+            Err(resp) if resp.status().is_success() => ("cancelled", "Download cancelled".to_string()),
+            Ok(resp) | Err(resp) => ("failed", format!("Download failed with HTTP {}", resp.status())),
+        };
+        if status == "failed" {
+            failed += 1;
+            // As for ?async=true downloads, a failure ends the download's own job too
+            utils::emit_event(Some(&item.job_id), models::Phase::DownloadError, message.clone(), None, None);
+        }
+        utils::emit_event(
+            Some(&job_id),
+            models::Phase::DownloadProgress,
+            format!("{} / {}: {}: {}", i + 1, total, item.asset_name, message),
+            Some((i + 1) as f32 * 100.0 / total as f32),
+            Some(serde_json::json!({
+                "asset_name": item.asset_name,
+                "version": item.version,
+                "status": status,
+                "message": message,
+                "job_id": item.job_id,
+            })),
+        );
+    }
+    if failed == 0 {
+        utils::emit_event(Some(&job_id), models::Phase::DownloadComplete, format!("Resumed {} downloads", total), Some(100.0), None);
+    } else {
+        utils::emit_event(Some(&job_id), models::Phase::DownloadError, format!("{} of {} resumed downloads failed", failed, total), None, None);
+    }
+}
+
+/// Discards one interrupted download: removes its folder (partial `.part` files included) and its temp/ chunk cache.
+///
+/// Route:
+/// - POST /discard-download
+///
+/// JSON body:
+/// - namespace, asset_id, artifact_id: identify the download via its recorded `.download_info.json`, or
+/// - asset_name: folder name under downloads/ (e.g., "Industry Props Pack 6").
+/// - ue: Optional version subfolder (e.g., "5.6").
+/// - force: Optional; required to delete a download with a valid `.download_complete` marker.
+///
+/// Only paths inside the downloads directory are ever removed. 409 while a download into the folder is
+/// running, or a download of the same title still uses its temp/ chunk folder. Errors are `ErrorResponse` bodies; once some folders were removed
+/// they are listed in `removed`.
+///
+/// Example (curl):
+///   curl -s -X POST http://127.0.0.1:8080/discard-download \
+///        -H "Content-Type: application/json" \
+///        -d '{"asset_name":"Industry Props Pack 6","ue":"5.6"}' | jq
+#[post("/discard-download")]
+pub async fn discard_download(body: web::Json<models::DiscardDownloadRequest>) -> HttpResponse {
+    let req = body.into_inner();
+    let root = utils::get_default_downloads_dir_path();
+    let force = req.force.unwrap_or(false);
+    let ue = req.ue.as_deref().map(|s| s.trim()).filter(|s| !s.is_empty());
+
+    let targets: Vec<std::path::PathBuf> = match (&req.namespace, &req.asset_id, &req.artifact_id, &req.asset_name) {
+        (Some(ns), Some(aid), Some(art), _) => utils::find_download_dirs_by_ids(&root, ns, aid, art, ue),
+        (_, _, _, Some(name)) if !name.trim().is_empty() => {
+            let folder = utils::sanitize_title_for_folder(name);
+            let mut dir = utils::find_asset_directory(&root, &folder);
+            if let Some(v) = ue { dir = dir.join(v); }
+            if dir.exists() { vec![dir] } else { Vec::new() }
+        }
+        _ => {
+            return super::err(StatusCode::BAD_REQUEST, "missing_parameter", "Provide namespace/asset_id/artifact_id or asset_name");
+        }
+    };
+
+    if targets.is_empty() {
+        return super::err(StatusCode::NOT_FOUND, "asset_not_found", "No matching download folder found");
+    }
+
+    let mut removed: Vec<String> = Vec::new();
+    for dir in targets {
+        if !utils::is_within_root(&root, &dir) {
+            return discard_error(
+                StatusCode::BAD_REQUEST,
+                "unsafe_path",
+                format!("Refusing to delete outside the downloads directory: {}", dir.display()),
+                removed,
+            );
+        }
+        if utils::is_download_complete(&dir) && !force {
+            return discard_error(
+                StatusCode::CONFLICT,
+                "confirmation_required",
+                format!("Download at {} is complete; pass force=true to delete it", dir.display()),
+                removed,
+            );
+        }
+        if utils::download_running_under(&dir) {
+            return discard_error(
+                StatusCode::CONFLICT,
+                "conflict",
+                format!("A download into {} is still running; cancel it first", dir.display()),
+                removed,
+            );
+        }
+        // A version folder's temp/ belongs to its title and is in use while any version of it downloads;
+        // downloads/temp (unversioned assets) is shared by every asset and is simply kept while busy
+        let own_temp = dir.parent().is_some_and(|p| p != root.as_path());
+        if own_temp && utils::chunk_temp_dir_in_use(&dir) {
+            return discard_error(
+                StatusCode::CONFLICT,
+                "conflict",
+                format!("A download is still using the chunk folder next to {}; cancel it first", dir.display()),
+                removed,
+            );
+        }
+        let title_dir = if dir.parent().map_or(false, |p| utils::is_within_root(&root, p)) { dir.parent().map(|p| p.to_path_buf()) } else { None };
+        if let Err(e) = std::fs::remove_dir_all(&dir) {
+            return discard_error(StatusCode::INTERNAL_SERVER_ERROR, "io_error", format!("Failed to remove {}: {}", dir.display(), e), removed);
+        }
+        removed.push(dir.to_string_lossy().to_string());
+
+        // Versioned downloads keep their chunk cache in <Title>/temp; drop it and the title folder once empty
+        if let Some(title_dir) = title_dir {
+            let temp_dir = title_dir.join("temp");
+            if temp_dir.is_dir() && utils::remove_chunk_temp_dir(&dir) && !temp_dir.exists() {
+                removed.push(temp_dir.to_string_lossy().to_string());
+            }
+            if std::fs::read_dir(&title_dir).map(|mut it| it.next().is_none()).unwrap_or(false) {
+                let _ = std::fs::remove_dir(&title_dir);
+            }
+        }
+    }
+
+    HttpResponse::Ok().json(models::DiscardDownloadResponse {
+        ok: true,
+        message: format!("Discarded {} folder(s)", removed.len()),
+        removed,
+    })
+}
+
+/// Error body for /discard-download that keeps the folders already removed by the same request.
+fn discard_error(status: StatusCode, code: &str, message: String, removed: Vec<String>) -> HttpResponse {
+    HttpResponse::build(status).json(models::ErrorResponse {
+        removed: (!removed.is_empty()).then_some(removed),
+        ..models::ErrorResponse::new(code, message)
+    })
+}
+
+/// Deletes a downloaded asset folder (every version, temp/ chunks included) to reclaim disk space.
+///
+/// Route:
+/// - DELETE /downloaded-asset/{name}
+///
+/// `name` is the asset title or its folder name under downloads/, matched case-insensitively.
+/// Only folders strictly inside the downloads directory are ever removed. Afterwards the
+/// `downloaded` flags in cache/fab_list.json are recomputed so the UI reflects the change.
+///
+/// Returns:
+/// - 200 OK with JSON { ok, message, path, bytes_freed, cache_updated }
+/// - 400 Bad Request when the name resolves outside the downloads directory
+/// - 404 Not Found when no such download folder exists
+/// - 409 Conflict while a download into the folder is still running (wherever it keeps its chunks)
+/// - 500 Internal Server Error when the folder cannot be removed
+///
+/// Example (curl):
+/// - curl -s -X DELETE "http://127.0.0.1:8080/downloaded-asset/Industry%20Props%20Pack%206" | jq
+#[delete("/downloaded-asset/{name}")]
+pub async fn delete_downloaded_asset(path: web::Path<String>) -> HttpResponse {
+    let name = path.into_inner();
+    let root = utils::get_default_downloads_dir_path();
+    let folder = utils::sanitize_title_for_folder(&name);
+    if folder.is_empty() {
+        return super::err(StatusCode::BAD_REQUEST, "invalid_parameter", "Asset name is empty");
+    }
+    let dir = utils::find_asset_directory(&root, &folder);
+    if !dir.is_dir() {
+        return super::err(StatusCode::NOT_FOUND, "asset_not_found", format!("No downloaded asset named '{}'", name));
+    }
+    if !utils::is_within_root(&root, &dir) {
+        return super::err(StatusCode::BAD_REQUEST, "unsafe_path", format!("Refusing to delete outside the downloads directory: {}", dir.display()));
+    }
+    if utils::download_running_under(&dir) {
+        return super::err(StatusCode::CONFLICT, "conflict", format!("A download of {} is still running; cancel it first", name));
+    }
+
+    let bytes_freed = utils::dir_size(&dir);
+    if let Err(e) = std::fs::remove_dir_all(&dir) {
+        return super::err(StatusCode::INTERNAL_SERVER_ERROR, "io_error", format!("Failed to remove {}: {}", dir.display(), e));
+    }
+    log::info!("Deleted downloaded asset {} ({} bytes)", dir.display(), bytes_freed);
+
+    let cache_updated = match utils::reannotate_fab_cache() {
+        Ok(res) => res.is_some(),
+        Err(e) => {
+            log::warn!("Deleted {} but could not update the FAB cache: {}", dir.display(), e);
+            false
+        }
+    };
+
+    HttpResponse::Ok().json(models::DeleteDownloadedAssetResponse {
+        ok: true,
+        message: format!("Deleted {}", dir.display()),
+        path: dir.to_string_lossy().to_string(),
+        bytes_freed,
+        cache_updated,
+    })
+}
+
+/// Lists files that were deliberately left out of a completed download (EAM_SKIP_PATTERNS or include/exclude).
+///
+/// Route:
+/// - GET /skipped-files?asset_name=<folder>&ue=<major.minor>
+///
+/// Returns the completion marker details: { status, partial, skip_patterns, skipped_files, include_patterns,
+/// exclude_patterns, excluded_files }. `partial` is true when any manifest file was left out.
+/// 404 when the download folder has no completion marker.
+#[get("/skipped-files")]
+pub async fn list_skipped_files(query: web::Query<HashMap<String, String>>) -> HttpResponse {
+    let name = match query.get("asset_name").map(|s| s.trim()).filter(|s| !s.is_empty()) {
+        Some(n) => n.to_string(),
+        None => return super::err(StatusCode::BAD_REQUEST, "missing_parameter", "Missing required query parameter: asset_name"),
+    };
+    let root = utils::get_default_downloads_dir_path();
+    let mut dir = utils::find_asset_directory(&root, &utils::sanitize_title_for_folder(&name));
+    if let Some(ue) = query.get("ue").map(|s| s.trim()).filter(|s| !s.is_empty()) {
+        dir = dir.join(ue);
+    }
+    let dir = match utils::resolve_within_root(&root, &dir) {
+        Ok(d) => d,
+        Err(e) => return super::err(StatusCode::BAD_REQUEST, "invalid_parameter", format!("Invalid asset_name/ue: {}", e)),
+    };
+    match utils::read_completion_marker(&dir) {
+        Some(marker) => HttpResponse::Ok().json(marker),
+        None => super::err(StatusCode::NOT_FOUND, "asset_not_found", format!("No completed download at {}", dir.display())),
+    }
+}
@@ -2,7 +2,9 @@
 //!
 //! Handlers related to listing and refreshing the user's Fab library.
 
-use actix_web::{get, HttpResponse};
+use actix_web::{get, post, web, HttpResponse};
+use actix_web::http::StatusCode;
+use std::collections::HashMap;
 use std::fs;
 use std::io::Read;
 use serde_json;
@@ -14,16 +16,86 @@ use crate::utils;
 /// Behavior:
 /// - If cache/fab_list.json exists and is readable, the raw JSON (enriched with local flags when possible)
 ///   is returned as application/json.
+/// - Each asset carries `userTags` and `favorite` from the local tags store (see /asset-tags).
 /// - Otherwise, it falls back to performing a refresh (same behavior as /refresh-fab-list).
 ///
+/// Query parameters:
+/// - require_version (optional): UE major.minor (e.g., 5.6). When set, an asset's `downloaded` flag in
+///   the response is true only if that specific version is downloaded, rather than any version.
+///   The cache itself keeps the default "any version" semantics.
+/// - category (optional): only return assets in this category (case-insensitive); see /categories.
+/// - sort (optional): title, downloaded or size (`downloadedBytes`, 0 when unknown); ties keep library order.
+/// - order (optional): asc (default) or desc.
+/// - offset, limit (optional): slice of the sorted results. With any of sort/order/offset/limit the
+///   response is `{ total, offset, limit, results }`, where total counts the assets before slicing.
+///
 /// Example (curl):
 /// - curl -s http://localhost:8080/get-fab-list | jq
+/// - curl -s 'http://localhost:8080/get-fab-list?require_version=5.6' | jq
+/// - curl -s 'http://localhost:8080/get-fab-list?sort=size&order=desc&offset=0&limit=50' | jq '.total'
 ///
 /// Status codes:
 /// - 200 OK on success (JSON body)
+/// - 400 Bad Request for an invalid require_version, sort, order, offset or limit
+/// - 502 Bad Gateway with JSON { ok: false, code: "upstream_error", message } when there is no cache and Epic returns no account details or library
 #[get("/get-fab-list")]
-pub async fn get_fab_list() -> HttpResponse {
+pub async fn get_fab_list(query: web::Query<HashMap<String, String>>) -> HttpResponse {
+    let require_version = match query.get("require_version").map(|s| s.trim()).filter(|s| !s.is_empty()) {
+        Some(v) => match utils::normalize_engine_association(v) {
+            Some(mm) => Some(mm),
+            None => return super::err(StatusCode::BAD_REQUEST, "invalid_parameter", format!("Invalid require_version '{}'; expected e.g. 5.6", v)),
+        },
+        None => None,
+    };
+    let category = query.get("category").map(|s| s.trim()).filter(|s| !s.is_empty());
+    let page = match parse_list_page(&query) {
+        Ok(p) => p,
+        Err(msg) => return super::err(StatusCode::BAD_REQUEST, "invalid_parameter", msg),
+    };
+    if let Some(resp) = serve_cached_fab_list(require_version.as_deref(), category, page.as_ref()) {
+        return resp;
+    }
+    // Fallback: refresh and cache
+    let resp = utils::handle_refresh_fab_list(None, false, false).await;
+    if (require_version.is_some() || category.is_some() || page.is_some()) && resp.status().is_success() {
+        // Re-serve from the freshly written cache so the filters apply
+        if let Some(cached) = serve_cached_fab_list(require_version.as_deref(), category, page.as_ref()) {
+            return cached;
+        }
+    }
+    resp
+}
+
+/// Read sort/order/offset/limit; None when none of them is given (plain library response).
+fn parse_list_page(query: &HashMap<String, String>) -> Result<Option<utils::library_filter::ListPage>, String> {
+    use utils::library_filter::{ListPage, SortKey};
+    if !["sort", "order", "offset", "limit"].iter().any(|k| query.contains_key(*k)) {
+        return Ok(None);
+    }
+    let sort = match query.get("sort") {
+        Some(s) => Some(SortKey::parse(s).ok_or_else(|| format!("Invalid sort '{}'; expected title, downloaded or size", s))?),
+        None => None,
+    };
+    let descending = match query.get("order").map(|s| s.trim().to_ascii_lowercase()) {
+        None => false,
+        Some(o) if o == "asc" => false,
+        Some(o) if o == "desc" => true,
+        Some(o) => return Err(format!("Invalid order '{}'; expected asc or desc", o)),
+    };
+    let number = |key: &str| -> Result<Option<usize>, String> {
+        query.get(key)
+            .map(|s| s.trim().parse::<usize>().map_err(|_| format!("{} must be a non-negative integer", key)))
+            .transpose()
+    };
+    Ok(Some(ListPage { sort, descending, offset: number("offset")?.unwrap_or(0), limit: number("limit")? }))
+}
+
+/// Serve cache/fab_list.json (re-annotated, with tags merged), or None when there is no readable cache.
+/// With `page`, the response is the sorted slice `{ total, offset, limit, results }` instead of the library.
+fn serve_cached_fab_list(require_version: Option<&str>, category: Option<&str>, page: Option<&utils::library_filter::ListPage>) -> Option<HttpResponse> {
     let path = utils::get_fab_cache_file_path();
+    // Hold the cache lock while reading and (possibly) re-annotating so concurrent writers don't interleave
+    let guard = utils::lock_fab_cache();
     if path.exists() {
         if let Ok(mut f) = fs::File::open(&path) {
             let mut buf = Vec::new();
@@ -34,29 +106,133 @@ pub async fn get_fab_list() -> HttpResponse {
                         let (_total, _marked, changed) = utils::annotate_downloaded_flags(&mut val);
                         if changed {
                             if let Ok(bytes) = serde_json::to_vec_pretty(&val) {
-                                if let Err(e) = fs::write(&path, &bytes) {
-                                    eprintln!("Warning: failed to update FAB cache while serving: {}", e);
+                                if let Err(e) = utils::write_fab_cache_atomic(&path, &bytes) {
+                                    log::warn!("Failed to update FAB cache while serving: {}", e);
                                 }
                             }
-                            // println!("Using cached FAB list from {} (re-annotated)", path.display());
+                            log::debug!("Using cached FAB list from {} (re-annotated)", path.display());
                         } else {
-                            // println!("Using cached FAB list from {} (no changes)", path.display());
+                            log::debug!("Using cached FAB list from {} (no changes)", path.display());
                         }
-                        return HttpResponse::Ok().json(val);
+                        if let Some(mm) = require_version {
+                            utils::apply_required_version(&mut val, mm);
+                        }
+                        if let Some(c) = category {
+                            utils::categories::retain_category(&mut val, c);
+                        }
+                        // Local tags/favorites are merged into the response only, never into the cache
+                        utils::merge_asset_tags(&mut val, &utils::load_asset_tags());
+                        if let Some(page) = page {
+                            return Some(HttpResponse::Ok().json(utils::library_filter::paginate(&val, page)));
+                        }
+                        return Some(HttpResponse::Ok().json(val));
                     }
                     Err(_) => {
                         // If parsing failed, fall back to returning raw bytes.
-                        // println!("Using cached FAB list from {} (raw)", path.display());
-                        return HttpResponse::Ok()
+                        log::debug!("Using cached FAB list from {} (raw)", path.display());
+                        return Some(HttpResponse::Ok()
                             .content_type("application/json")
-                            .body(buf);
+                            .body(buf));
                     }
                 }
             }
         }
     }
-    // Fallback: refresh and cache
-    utils::handle_refresh_fab_list().await
+    // Release before refreshing; the refresh takes the lock itself when writing
+    drop(guard);
+    None
+}
+
+/// Recomputes the `downloaded` flags in cache/fab_list.json from the current downloads/ folder.
+///
+/// Route:
+/// - POST /reannotate
+///
+/// For a "refresh download status" button after files were added or removed by hand: no network
+/// access, unlike /refresh-fab-list. The cache is rewritten only when a flag changed, under the same
+/// lock as every other cache writer.
+///
+/// Returns:
+/// - 200 OK with JSON { total, marked, changed }
+/// - 404 Not Found when there is no cached library yet (call /refresh-fab-list first)
+/// - 500 Internal Server Error when the cache cannot be parsed or written
+#[post("/reannotate")]
+pub async fn reannotate() -> HttpResponse {
+    match utils::reannotate_fab_cache() {
+        Ok(Some((total, marked, changed))) => {
+            log::info!("Re-annotated FAB cache: {} assets, {} downloaded, changed={}", total, marked, changed);
+            HttpResponse::Ok().json(serde_json::json!({ "total": total, "marked": marked, "changed": changed }))
+        }
+        Ok(None) => super::err(StatusCode::NOT_FOUND, "library_not_cached", "No cached library yet; call /refresh-fab-list first"),
+        Err(e) => super::err(StatusCode::INTERNAL_SERVER_ERROR, "internal_error", e),
+    }
+}
+
+/// Searches the cached library on the server so constrained UIs don't have to download all of it.
+///
+/// Route:
+/// - GET /search-fab-list
+///
+/// Query parameters (all optional, combined with AND):
+/// - q: case-insensitive substring of the asset title.
+/// - downloaded: true or false, matched against the asset-level `downloaded` flag.
+/// - engine: UE version (e.g., 5.4 or UE_5.4) listed in any `projectVersions[].engineVersions`.
+///
+/// Reads cache/fab_list.json only (no auth, no refresh); local tags/favorites are merged in.
+///
+/// Returns:
+/// - 200 OK with a JSON array of the matching assets, in library order
+/// - 400 Bad Request when `downloaded` is not true/false
+/// - 404 Not Found when there is no cached library yet (call /refresh-fab-list first)
+///
+/// Example (curl):
+/// - curl -s 'http://localhost:8080/search-fab-list?q=props&downloaded=false&engine=5.4' | jq length
+#[get("/search-fab-list")]
+pub async fn search_fab_list(query: web::Query<HashMap<String, String>>) -> HttpResponse {
+    let downloaded = match query.get("downloaded").map(|s| s.trim().to_ascii_lowercase()) {
+        None => None,
+        Some(v) if v == "true" || v == "1" => Some(true),
+        Some(v) if v == "false" || v == "0" => Some(false),
+        Some(v) => return super::err(StatusCode::BAD_REQUEST, "invalid_parameter", format!("Invalid downloaded '{}'; expected true or false", v)),
+    };
+    let filter = utils::library_filter::LibraryFilter {
+        text: query.get("q").cloned(),
+        downloaded,
+        engine: query.get("engine").cloned(),
+    };
+    let value: Option<serde_json::Value> = {
+        let _guard = utils::lock_fab_cache();
+        fs::read(utils::get_fab_cache_file_path()).ok().and_then(|b| serde_json::from_slice(&b).ok())
+    };
+    let Some(mut value) = value else {
+        return super::err(StatusCode::NOT_FOUND, "library_not_cached", "No cached library yet; call /refresh-fab-list first");
+    };
+    utils::merge_asset_tags(&mut value, &utils::load_asset_tags());
+    HttpResponse::Ok().json(utils::library_filter::search_results(&value, &filter))
+}
+
+/// Lists the distinct categories in the cached library with asset counts, for a category sidebar.
+///
+/// Route:
+/// - GET /categories
+///
+/// Reads cache/fab_list.json only (no auth, no refresh); returns an empty list when there is no cache.
+/// Assets without category fields are simply not counted. Filter the list with /get-fab-list?category=<name>.
+///
+/// Returns JSON: { categories: [{ name: "Props", asset_count: 12 }, ...] } sorted by name.
+#[get("/categories")]
+pub async fn list_categories() -> HttpResponse {
+    let value: Option<serde_json::Value> = {
+        let _guard = utils::lock_fab_cache();
+        fs::read(utils::get_fab_cache_file_path()).ok().and_then(|b| serde_json::from_slice(&b).ok())
+    };
+    let categories: Vec<serde_json::Value> = value
+        .map(|v| utils::categories::category_counts(&v))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, asset_count)| serde_json::json!({ "name": name, "asset_count": asset_count }))
+        .collect();
+    HttpResponse::Ok().json(serde_json::json!({ "categories": categories }))
 }
 
 /// Forces a refresh of the user's Fab library from Epic Games Services and caches it.
@@ -65,10 +241,33 @@ pub async fn get_fab_list() -> HttpResponse {
 /// details and Fab library items, serializes them to cache/fab_list.json, and returns the
 /// JSON list in the response.
 ///
+/// Query parameters:
+/// - jobId (optional): lets /cancel-job abort the refresh once the library is fetched (the annotation pass
+///   included); the cache is then not rewritten and the fetched library is kept for `resume`.
+/// - force (optional): "true" rebuilds the cache from the fetched library even when it is unchanged.
+/// - resume (optional): "true" finishes the last cancelled refresh from its kept library, without logging
+///   in or fetching from Epic again.
+///
+/// When the fetched library is identical to the one the cache was built from, the cached JSON is only
+/// re-annotated (local fields are kept). The response has a top-level `refreshed` flag: true when the
+/// library changed (or force was set), false otherwise.
+///
+/// Status codes:
+/// - 200 OK with the library JSON
+/// - 401 Unauthorized with JSON { unauthenticated, auth_url, message } when there are no cached credentials
+/// - 404 Not Found with code "nothing_to_resume" for `resume=true` without a cancelled refresh
+/// - 409 Conflict with JSON { ok: false, code: "cancelled", message } when the job was cancelled
+/// - 502 Bad Gateway with JSON { ok: false, code: "upstream_error", message } when Epic returns no account details or library
+///
 /// Example (curl):
 /// - curl -s http://localhost:8080/refresh-fab-list | jq '.results | length'
+/// - curl -s 'http://localhost:8080/refresh-fab-list?jobId=refresh-1'
+/// - curl -s 'http://localhost:8080/refresh-fab-list?resume=true'
 #[get("/refresh-fab-list")]
-pub async fn refresh_fab_list() -> HttpResponse {
+pub async fn refresh_fab_list(query: web::Query<HashMap<String, String>>) -> HttpResponse {
+    let job_id = query.get("jobId").or_else(|| query.get("job_id")).cloned();
+    let force = query.get("force").map_or(false, |v| v == "1" || v.eq_ignore_ascii_case("true"));
+    let resume = query.get("resume").map_or(false, |v| v == "1" || v.eq_ignore_ascii_case("true"));
     // Respond with the list of Fab Assets and cache it
-    utils::handle_refresh_fab_list().await
+    utils::handle_refresh_fab_list(job_id.as_deref(), force, resume).await
 }
@@ -0,0 +1,41 @@
+//! Runtime log verbosity.
+//!
+//! - GET /log-level returns the active filter in RUST_LOG syntax.
+//! - POST /log-level changes the level for one target (or the default) without a restart.
+//!
+//! Download progress details log under the `download` target; module targets such as
+//! `egs_client::utils` work as well and cover their submodules.
+
+use actix_web::{get, post, web, HttpResponse};
+use actix_web::http::StatusCode;
+
+use crate::models;
+use crate::utils;
+use crate::utils::log_filter::Level;
+
+/// Returns { "filter": "<RUST_LOG spec>" }.
+///
+/// Example (curl):
+/// - curl -s http://localhost:8080/log-level | jq
+#[get("/log-level")]
+pub async fn get_log_level() -> HttpResponse {
+    HttpResponse::Ok().json(serde_json::json!({ "filter": utils::log_filter_spec() }))
+}
+
+/// Sets the level (off, error, warn, info, debug, trace) for `target`, or the default level when
+/// `target` is omitted. Returns 400 for an unknown level.
+///
+/// Example (curl):
+///   curl -s -X POST http://127.0.0.1:8080/log-level \
+///        -H "Content-Type: application/json" \
+///        -d '{"level":"debug","target":"download"}' | jq
+#[post("/log-level")]
+pub async fn set_log_level(body: web::Json<models::LogLevelUpdate>) -> HttpResponse {
+    let update = body.into_inner();
+    let Some(level) = Level::parse(&update.level) else {
+        return super::err(StatusCode::BAD_REQUEST, "invalid_parameter", format!("unknown log level '{}' (expected off, error, warn, info, debug or trace)", update.level));
+    };
+    let filter = utils::set_log_level(update.target.as_deref(), level);
+    log::info!("Log filter changed to {}", filter);
+    HttpResponse::Ok().json(serde_json::json!({ "ok": true, "filter": filter }))
+}
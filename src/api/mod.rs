@@ -9,6 +9,7 @@
 //! - GET /get-fab-list — returns the cached list if available, otherwise refreshes first.
 //! - GET /refresh-fab-list — forces a refresh from EGS and updates the cache file.
 //! - GET /download-asset/{namespace}/{asset_id}/{artifact_id} — downloads an asset to the downloads/ directory.
+//! - GET /incomplete-downloads, POST /resume-incomplete — list and finish interrupted downloads (one at a time, under one job id).
 //!
 //! Requirements and environment:
 //! - Authentication to EGS is handled via the utils module. The service attempts to reuse cached tokens and may fall back to an auth-code flow.
@@ -50,6 +51,7 @@
 //! - All endpoints return HttpResponse and are designed for a UI frontend to consume.
 
 use actix_web::{get, post, HttpResponse, web, Responder, HttpRequest};
+use actix_web::http::StatusCode;
 use crate::utils;
 use crate::models;
 use crate::utils::EPIC_LOGIN_URL;
@@ -60,10 +62,9 @@ use serde::{Deserialize};
 use serde_json;
 use std::path::{Path, PathBuf};
 use std::time::Instant;
-use std::collections::{HashMap, VecDeque};
+use std::collections::HashMap;
 use actix_web::web::Query;
 use egs_api::EpicGames;
-use crate::utils::get_sender;
 
 /// Default directory names used when no config/environment override is provided.
 pub const DEFAULT_CACHE_DIR_NAME: &str = "cache";
@@ -71,67 +72,32 @@ pub const DEFAULT_DOWNLOADS_DIR_NAME: &str = "downloads";
 
 // Submodules exposing logically grouped endpoints. Re-export their public handlers so callers
 // can continue using `crate::api::...` without change.
+pub mod downloads;
 pub mod fab;
+pub mod logging;
+pub mod tags;
 pub mod ws;
-pub use fab::{get_fab_list, refresh_fab_list};
-pub use ws::{websocket_upgrade_endpoint, cancel_background_job_endpoint};
+pub use downloads::{delete_downloaded_asset, discard_download, list_incomplete_downloads, list_skipped_files, resume_incomplete_downloads};
+pub use fab::{get_fab_list, list_categories, reannotate, refresh_fab_list, search_fab_list};
+pub use logging::{get_log_level, set_log_level};
+pub use tags::{get_asset_tags, set_asset_tags};
+pub use ws::{websocket_upgrade_endpoint, cancel_background_job_endpoint, job_events, job_status, list_jobs, pause_job, resume_job};
 
-/// Note: cache and downloads directories are configurable; see helpers below for effective paths.
+// Note: cache and downloads directories are configurable; see helpers below for effective paths.
 
+/// Error response with a JSON `ErrorResponse { ok: false, code, message }` body.
+pub fn err(status: StatusCode, code: &str, message: impl Into<String>) -> HttpResponse {
+    HttpResponse::build(status).json(models::ErrorResponse::new(code, message))
+}
 
+/// 404 for an engine that matched the request but has no editor binary, with the same explanation
+/// /list-unreal-engines reports in `reason`.
+fn editor_not_found(engine: &models::UnrealEngineInfo) -> HttpResponse {
+    let problem = engine.reason.clone().unwrap_or_else(|| utils::missing_editor_message(Path::new(&engine.path)));
+    err(StatusCode::NOT_FOUND, "editor_not_found", format!("Engine {} is not launchable: {}", engine.version, problem))
+}
 
-/// Returns the user's Fab library, preferring a cached JSON file when possible.
-///
-/// Behavior:
-/// - If cache/fab_list.json exists and is readable, the raw JSON bytes are sent back as application/json.
-/// - Otherwise, it falls back to performing a refresh (same behavior as /refresh-fab-list).
-///
-/// Example (curl):
-/// - curl -s http://localhost:8080/get-fab-list | jq
-///
-/// Status codes:
-/// - 200 OK on success (JSON body)
-/// - 200 OK with a plain string body in some edge cases (e.g., "No details found")
-/// Returns the user's Fab library, preferring a cached JSON file when possible.
-///
-/// Behavior:
-/// - If cache/fab_list.json exists and is readable, the raw JSON (enriched with local flags when possible)
-///   is returned as application/json.
-/// - Otherwise, it falls back to performing a refresh (same behavior as /refresh-fab-list).
-///
-/// Example (curl):
-/// - curl -s http://localhost:8080/get-fab-list | jq
-///
-/// Status codes:
-/// - 200 OK on success (JSON body)
-
-/// WebSocket endpoint used to stream progress/events to the Flutter UI.
-///
-/// Query params:
-/// - jobId or job_id: logical job identifier; messages are broadcast per job.
-///
-/// Behavior:
-/// - Subscribes client to a per-job broadcast channel.
-/// - Flushes buffered events for late subscribers, then streams live updates.
-
-/// Forces a refresh of the user's Fab library from Epic Games Services and caches it.
-///
-/// This endpoint performs authentication (attempts cached token first), retrieves account
-/// details and Fab library items, serializes them to cache/fab_list.json, and returns the
-/// JSON list in the response.
-///
-/// Example (curl):
-/// - curl -s http://localhost:8080/refresh-fab-list | jq '.results | length'
-/// Forces a refresh of the user's Fab library from Epic Games Services and caches it.
-///
-/// This endpoint performs authentication (attempts cached token first), retrieves account
-/// details and Fab library items, serializes them to cache/fab_list.json, and returns the
-/// JSON list in the response.
-///
-/// Example (curl):
-/// - curl -s http://localhost:8080/refresh-fab-list | jq '.results | length'
-
-
+/// Returns the Epic login URL the UI should open to obtain an authorization code for /auth/complete.
 #[get("/auth/start")]
 pub async fn auth_start() -> HttpResponse {
     HttpResponse::Ok().json(serde_json::json!({
@@ -145,30 +111,21 @@ pub async fn auth_start() -> HttpResponse {
 pub async fn auth_complete(body: web::Json<models::AuthCompleteRequest>) -> HttpResponse {
     let code = body.code.trim().trim_matches('"').to_string();
     if code.is_empty() {
-        return HttpResponse::BadRequest().json(serde_json::json!({
-            "ok": false,
-            "message": "Missing 'code' field in body"
-        }));
+        return err(StatusCode::BAD_REQUEST, "missing_parameter", "Missing 'code' field in body");
     }
     let mut epic = utils::create_epic_games_services();
     let auth_ok = epic.auth_code(None, Some(code)).await;
     if !auth_ok {
-        return HttpResponse::Unauthorized().json(serde_json::json!({
-            "ok": false,
-            "message": "Auth code was not accepted by Epic servers"
-        }));
+        return err(StatusCode::UNAUTHORIZED, "auth_failed", "Auth code was not accepted by Epic servers");
     }
     // Complete login and persist tokens
     let logged_in = epic.login().await;
     if !logged_in {
-        return HttpResponse::Unauthorized().json(serde_json::json!({
-            "ok": false,
-            "message": "Login failed after exchanging auth code"
-        }));
+        return err(StatusCode::UNAUTHORIZED, "auth_failed", "Login failed after exchanging auth code");
     }
     let ud = epic.user_details();
     if let Err(e) = utils::save_user_details(&ud) {
-        eprintln!("Warning: failed to save tokens: {}", e);
+        log::warn!("Failed to save tokens: {}", e);
     }
     HttpResponse::Ok().json(serde_json::json!({
         "ok": true,
@@ -177,6 +134,70 @@ pub async fn auth_complete(body: web::Json<models::AuthCompleteRequest>) -> Http
 }
 
 
+/// Reports whether cached credentials look usable, without any network call, browser or stdin prompt.
+///
+/// Route:
+/// - GET /auth/status
+///
+/// Reads the token cache only. `authenticated` is true when the access token is unexpired, or when a
+/// refresh token is present and unexpired (the next API call will refresh silently). Timestamps that
+/// cannot be parsed are treated as still valid.
+///
+/// Returns JSON: { authenticated, expires_at, refresh_expires_at, account_display_name }
+#[get("/auth/status")]
+pub async fn auth_status() -> HttpResponse {
+    let auth = cached_auth();
+    HttpResponse::Ok().json(serde_json::json!({
+        "authenticated": auth.authenticated,
+        "expires_at": auth.expires_at,
+        "refresh_expires_at": auth.refresh_expires_at,
+        "account_display_name": auth.display_name,
+    }))
+}
+
+/// What the token cache says about the sign-in; shared by /auth/status and /health.
+struct CachedAuth {
+    authenticated: bool,
+    expires_at: Option<String>,
+    refresh_expires_at: Option<String>,
+    display_name: Option<String>,
+}
+
+fn cached_auth() -> CachedAuth {
+    let user = utils::load_user_details().and_then(|u| serde_json::to_value(&u).ok());
+    let field = |keys: &[&str]| -> Option<String> {
+        let v = user.as_ref()?;
+        keys.iter().find_map(|k| v.get(*k).and_then(|x| x.as_str()).map(|s| s.trim().to_string())).filter(|s| !s.is_empty())
+    };
+    let access_token = field(&["access_token", "accessToken"]);
+    let refresh_token = field(&["refresh_token", "refreshToken"]);
+    let expires_at = field(&["expires_at", "expiresAt"]);
+    let refresh_expires_at = field(&["refresh_expires_at", "refreshExpiresAt"]);
+    let display_name = field(&["display_name", "displayName"]);
+
+    let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map(|d| d.as_secs() as i64).unwrap_or(0);
+    let still_valid = |ts: &Option<String>| ts.as_deref().and_then(utils::timefmt::parse_rfc3339).map_or(true, |t| t > now);
+    let authenticated = (access_token.is_some() && still_valid(&expires_at))
+        || (refresh_token.is_some() && still_valid(&refresh_expires_at));
+    CachedAuth { authenticated, expires_at, refresh_expires_at, display_name }
+}
+
+/// Signs out by deleting the cached Epic tokens.
+///
+/// Route:
+/// - POST /auth/logout
+///
+/// Succeeds when no tokens were cached. No EpicGames session is kept in memory (each request logs in
+/// from the token file), so removing the file is enough; the next library call will ask to log in.
+#[post("/auth/logout")]
+pub async fn auth_logout() -> HttpResponse {
+    match utils::clear_user_details() {
+        Ok(()) => HttpResponse::Ok().json(models::SimpleResponse { ok: true, message: "Signed out; cached credentials removed".to_string() }),
+        Err(e) => err(StatusCode::INTERNAL_SERVER_ERROR, "io_error", format!("Failed to remove cached credentials: {}", e)),
+    }
+}
+
+
 /// Downloads a specific Fab asset to the local filesystem.
 ///
 /// Route:
@@ -196,22 +217,73 @@ pub async fn auth_complete(body: web::Json<models::AuthCompleteRequest>) -> Http
 ///   sanitized for filesystem safety; falls back to a namespace-asset-artifact folder name.
 /// - Invokes utils::download_asset to perform the actual download into downloads/.
 ///
+/// Query parameters:
+/// - jobId (optional): WebSocket job id for progress events.
+/// - file_concurrency (optional, 1..=16) / chunk_concurrency (optional, 1..=64): per-download tuning.
+///   Precedence: these params > config.json > EAM_FILE_CONCURRENCY/EAM_CHUNK_CONCURRENCY > defaults (2/4).
+///   The effective values are echoed in the download:start event details.
+///   file_concurrency is per download; EAM_GLOBAL_FILE_CONCURRENCY (default 8) additionally caps file
+///   tasks across all running downloads, so queued assets share the network instead of stacking up.
+/// - download_rate_limit (optional, bytes/second > 0): caps the aggregate download rate across all
+///   chunk connections. Overrides EAM_MAX_BYTES_PER_SEC; unlimited when neither is set. Echoed as
+///   `max_bytes_per_sec` in the download:start details.
+/// - detailed_progress (optional): when "true", download:progress details also carry
+///   `folders: [{ name, files_done, files_total }]`, one entry per first-level folder under data/.
+///   Off by default to keep events small for large manifests.
+///   Progress details always include `bytes_per_sec` (rolling 3s average of bytes fetched) and
+///   `eta_seconds` (null while the speed is zero).
+/// - async (optional): when "true", run the download in the background and return 202 Accepted
+///   immediately with `{ "jobId", "status": "queued" }`; a jobId is generated when none is given.
+///   Completion and failure are then reported only over the WebSocket (/ws?jobId=...).
+///
 /// Returns:
 /// - 200 OK "Download complete" on success.
+/// - 202 Accepted in async mode.
 /// - 400 Bad Request if the manifest cannot be fetched.
+/// - 401 Unauthorized (auth_failed) when the Epic session is about to expire and cannot be refreshed.
 /// - 500 InternalServerError if all distribution points fail.
 ///
 /// Example (curl):
 /// - curl -v http://localhost:8080/download-asset/89efe5924d3d467c839449ab6ab52e7f/28b7df0e7f5e4202be89a20d362860c3/Industryf4a3f3ff297fV1
+/// - curl -s 'http://localhost:8080/download-asset/<ns>/<asset>/<artifact>?async=true&jobId=dl-1'
 #[get("/download-asset/{namespace}/{asset_id}/{artifact_id}")]
 pub async fn download_asset(path: web::Path<(String, String, String)>, query: web::Query<HashMap<String, String>>) -> HttpResponse {
-    println!("¬ download_asset");
-    match utils::download_asset_handler(path, query).await {
+    log::debug!("¬ download_asset");
+    let async_mode = query.get("async").map_or(false, |v| v == "1" || v.eq_ignore_ascii_case("true"));
+    if async_mode {
+        let q = query.into_inner();
+        let job_id = q.get("jobId").or_else(|| q.get("job_id")).cloned().unwrap_or_else(|| utils::new_job_id("download"));
+        spawn_download_job(path, q, job_id.clone());
+        return HttpResponse::Accepted().json(serde_json::json!({ "jobId": job_id, "status": "queued" }));
+    }
+    let job = query.get("jobId").or_else(|| query.get("job_id")).cloned();
+    match utils::with_log_job(job, utils::download_asset_handler(path, query)).await {
         Ok(value) => value,
         Err(value) => return value,
     }
 }
 
+/// Queues a download as a background job under `job_id` and returns immediately.
+///
+/// Not tied to the request: the client follows progress and the outcome over /ws, and a failed
+/// download ends the job with download:error.
+fn spawn_download_job(path: web::Path<(String, String, String)>, mut q: HashMap<String, String>, job_id: String) {
+    q.insert("jobId".to_string(), job_id.clone());
+    utils::emit_event(Some(&job_id), models::Phase::DownloadStart, "Download queued", Some(0.0), None);
+    actix_web::rt::spawn(async move {
+        let job = Some(job_id);
+        match utils::with_log_job(job.clone(), utils::download_asset_handler(path, web::Query(q))).await {
+            Ok(resp) if !resp.status().is_success() => {
+                utils::emit_event(job.as_deref(), models::Phase::DownloadError, format!("Download failed with HTTP {}", resp.status()), None, None);
+            }
+            Err(resp) if !resp.status().is_success() => {
+                utils::emit_event(job.as_deref(), models::Phase::DownloadError, format!("Download failed with HTTP {}", resp.status()), None, None);
+            }
+            _ => {}
+        }
+    });
+}
+
 
 
 
@@ -221,7 +293,7 @@ pub async fn download_asset(path: web::Path<(String, String, String)>, query: we
 /// - GET /list-unreal-projects
 ///
 /// Query parameters:
-/// - base: Optional override for the base directory. Defaults to $HOME/Documents/Unreal Projects.
+/// - base: Optional override for the base directory. Defaults to $HOME/Documents/Unreal Projects (%USERPROFILE% on Windows).
 ///
 /// Returns:
 /// - 200 OK with JSON body: {
@@ -298,42 +370,23 @@ pub async fn list_unreal_projects(query: web::Query<std::collections::HashMap<St
 /// - GET /list-unreal-engines
 ///
 /// Query parameters:
-/// - base: Optional base directory containing engine folders. Defaults to $HOME/UnrealEngines.
+/// - base: Optional base directory containing engine folders. Defaults to $HOME/UnrealEngines (%ProgramFiles%\Epic Games on Windows).
 ///
 /// Notes:
 /// - Version is read from Engine/Build/Build.version when available; otherwise parsed heuristically from folder name.
-/// - Editor path detection currently targets Linux layouts (Engine/Binaries/Linux/UnrealEditor or UE4Editor).
+/// - Installs sharing a version and BuildId with another are reported with `duplicate_of` so the UI can warn.
+/// - Editor path detection checks the host platform's layout first, then the other platforms' (Linux, Win64, Mac).
+///   Engines without an editor binary are listed with editor_available=false and a `reason`.
+/// - Engines are sorted numerically by version (5.9 before 5.10); unknown versions come last.
 #[get("/list-unreal-engines")]
 pub async fn list_unreal_engines(query: web::Query<std::collections::HashMap<String, String>>) -> impl Responder {
     let base_dir = query.get("base").map(|s| PathBuf::from(s)).unwrap_or_else(utils::default_unreal_engines_dir);
 
-    let mut engines: Vec<models::UnrealEngineInfo> = Vec::new();
-    if base_dir.is_dir() {
-        if let Ok(entries) = fs::read_dir(&base_dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.is_dir() {
-                    // Heuristic: consider any directory that has Engine/Binaries
-                    if path.join("Engine").join("Binaries").is_dir() {
-                        let name = path.file_name().and_then(|s| s.to_str()).unwrap_or("").to_string();
-                        let version = utils::read_build_version(&path)
-                            .or_else(|| utils::parse_version_from_name(&name))
-                            .unwrap_or_else(|| "unknown".to_string());
-                        let editor_path = utils::find_editor_binary(&path).map(|p| p.to_string_lossy().to_string());
-                        engines.push(models::UnrealEngineInfo {
-                            name,
-                            version,
-                            path: path.to_string_lossy().to_string(),
-                            editor_path,
-                        });
-                    }
-                }
-            }
-        }
-    }
+    // Heuristic: consider any directory that has Engine/Binaries; duplicates carry a duplicate_of hint
+    let mut engines: Vec<models::UnrealEngineInfo> = utils::discover_engines(&base_dir);
 
     // Sort by version then name
-    engines.sort_by(|a, b| a.version.cmp(&b.version).then(a.name.cmp(&b.name)));
+    engines.sort_by(|a, b| utils::compare_versions(&a.version, &b.version).then(a.name.cmp(&b.name)));
 
     let resp = models::UnrealEnginesResponse {
         base_directory: base_dir.to_string_lossy().to_string(),
@@ -344,6 +397,88 @@ pub async fn list_unreal_engines(query: web::Query<std::collections::HashMap<Str
 }
 
 
+/// Deletes an Unreal project folder, or moves it into the projects directory's .trash folder.
+///
+/// Route:
+/// - POST /delete-unreal-project
+///
+/// JSON body fields:
+/// - project: String — Project name under the projects dir, project directory, or path to its .uproject. Required.
+/// - confirm: bool — Must be true. Required.
+/// - archive: Optional<bool> — Move to <projects dir>/.trash/<name>-<unix seconds> instead of deleting. Default false.
+///
+/// Only folders with a .uproject directly inside, strictly inside the projects directory, are ever removed;
+/// the projects directory itself and its ancestors never are.
+///
+/// Returns:
+/// - 200 OK with JSON { ok, message, path, archived_to, bytes_freed } (bytes_freed is 0 when archived)
+/// - 400 Bad Request without confirm=true, when the folder has no .uproject, or with code "unsafe_path" when it is
+///   outside the projects directory or contains it
+/// - 404 Not Found when the project cannot be resolved
+/// - 500 Internal Server Error when the folder cannot be removed or moved
+///
+/// Example (curl):
+/// - curl -s -X POST http://127.0.0.1:8080/delete-unreal-project -H "Content-Type: application/json" \
+///        -d '{"project":"MyGame","confirm":true,"archive":true}' | jq
+#[post("/delete-unreal-project")]
+pub async fn delete_unreal_project(body: web::Json<models::DeleteUnrealProjectRequest>) -> HttpResponse {
+    let req = body.into_inner();
+    if !req.confirm {
+        return err(StatusCode::BAD_REQUEST, "confirmation_required", "Refusing to delete without \"confirm\": true");
+    }
+    let project = req.project.trim();
+    if project.is_empty() {
+        return err(StatusCode::BAD_REQUEST, "missing_parameter", "project is required");
+    }
+    let dir = match utils::resolve_project_dir_from_param(project) {
+        Some(d) if d.is_dir() => d,
+        _ => return err(StatusCode::NOT_FOUND, "project_not_found", format!("No Unreal project found for '{}'", project)),
+    };
+    let projects_root = utils::default_unreal_projects_dir();
+    let dir = match utils::project_trash::check_delete_target(&dir, std::slice::from_ref(&projects_root)) {
+        Ok(d) => d,
+        Err(utils::project_trash::DeleteRefusal::ContainsRoot(root)) => {
+            return err(StatusCode::BAD_REQUEST, "unsafe_path", format!("Refusing to delete {}: it contains the projects directory {}", dir.display(), root.display()));
+        }
+        Err(utils::project_trash::DeleteRefusal::OutsideRoots) => {
+            return err(StatusCode::BAD_REQUEST, "unsafe_path", format!("Refusing to delete {}: it is not inside the projects directory {}", dir.display(), projects_root.display()));
+        }
+    };
+    if !utils::project_trash::contains_uproject(&dir) {
+        return err(StatusCode::BAD_REQUEST, "not_a_project", format!("Refusing to delete {}: it has no .uproject", dir.display()));
+    }
+
+    let size = utils::dir_size(&dir);
+    if req.archive {
+        let name = dir.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_else(|| "project".to_string());
+        let secs = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        let trash = projects_root.join(utils::project_trash::TRASH_DIR_NAME);
+        let dest = utils::project_trash::archive_destination(&trash, &name, secs);
+        if let Err(e) = utils::project_trash::move_dir(&dir, &dest) {
+            return err(StatusCode::INTERNAL_SERVER_ERROR, "io_error", format!("Failed to archive {} to {}: {}", dir.display(), dest.display(), e));
+        }
+        log::info!("Archived project {} to {}", dir.display(), dest.display());
+        return HttpResponse::Ok().json(models::DeleteUnrealProjectResponse {
+            ok: true,
+            message: format!("Archived {} to {}", dir.display(), dest.display()),
+            path: dir.to_string_lossy().to_string(),
+            archived_to: Some(dest.to_string_lossy().to_string()),
+            bytes_freed: 0,
+        });
+    }
+    if let Err(e) = fs::remove_dir_all(&dir) {
+        return err(StatusCode::INTERNAL_SERVER_ERROR, "io_error", format!("Failed to remove {}: {}", dir.display(), e));
+    }
+    log::info!("Deleted project {} ({} bytes)", dir.display(), size);
+    HttpResponse::Ok().json(models::DeleteUnrealProjectResponse {
+        ok: true,
+        message: format!("Deleted {}", dir.display()),
+        path: dir.to_string_lossy().to_string(),
+        archived_to: None,
+        bytes_freed: size,
+    })
+}
+
 /// Launches Unreal Editor for a given project using a specified engine version.
 ///
 /// Route:
@@ -351,9 +486,19 @@ pub async fn list_unreal_engines(query: web::Query<std::collections::HashMap<Str
 ///
 /// Query parameters:
 /// - project: Name of the project folder, a project directory path, or a .uproject file path.
-/// - version: Optional engine version to use (e.g., 5.3 or 5.3.2). If omitted, the server reads EngineAssociation from the .uproject and picks the matching engine. Exact match is preferred; prefix match is accepted.
+/// - version: Optional engine version to use (e.g., 5.3 or 5.3.2). If omitted, the server reads EngineAssociation from the .uproject and picks the matching engine.
+///   A major.minor version picks the newest installed patch (5.4 → 5.4.3 over 5.4.0); a full version must match.
+/// - exact: Optional; "true" requires an engine with exactly `version` (5.4 then only matches 5.4 / 5.4.0) and returns 404 otherwise.
 /// - engine_base: Optional base directory to search for engines (defaults to $HOME/UnrealEngines).
 /// - projects_base: Optional base directory containing UE projects when using a project name (defaults to $HOME/Documents/Unreal Projects).
+/// - jobId: Optional; captures editor output and emits `editor:error` (with the output tail) on an early or non-zero exit.
+///   Output goes to `<cache>/editor-logs/<jobId>.log` rather than an in-memory ring buffer, so the editor
+///   never blocks on a pipe; only the newest 20 logs from the last 7 days are kept.
+/// - fix_permissions: Optional; "true" restores the execute bit on an editor binary that lost it (otherwise a launch
+///   failure caused by a missing execute bit is reported with a chmod hint).
+/// - vulkan, d3d12, log, fullscreen: Optional; "true" adds -vulkan, -d3d12, -log or -fullscreen.
+/// - extra_args: Optional; more editor arguments, space-separated or a JSON array of strings. Each is passed
+///   to the editor as its own argument (no shell); the response echoes the final list in `args`.
 ///
 /// Required fields: project. Optional: version, engine_base, projects_base.
 ///
@@ -378,6 +523,10 @@ pub async fn list_unreal_engines(query: web::Query<std::collections::HashMap<Str
 ///
 /// Returns:
 /// - 200 OK with JSON describing the launch when the editor was spawned.
+/// - 409 Conflict with code "ambiguous_project" and a `candidates` list when a bare project name matches
+///   several .uproject files.
+/// - 404 Not Found with code "editor_not_found" when the matching engine has no editor binary (its
+///   /list-unreal-engines entry has editor_available=false and the same reason).
 /// - 4xx/5xx with JSON message explaining the error otherwise.
 #[get("/open-unreal-project")]
 pub async fn open_unreal_project(query: web::Query<std::collections::HashMap<String, String>>) -> impl Responder {
@@ -385,7 +534,7 @@ pub async fn open_unreal_project(query: web::Query<std::collections::HashMap<Str
     let raw_project = match query.get("project") {
         Some(p) => p.clone(),
         None => {
-            return HttpResponse::BadRequest().body("Missing required query parameter: project (name, path to .uproject, or project dir)");
+            return err(StatusCode::BAD_REQUEST, "missing_parameter", "Missing required query parameter: project (name, path to .uproject, or project dir)");
         }
     };
     let version_param_opt = query.get("version").map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
@@ -394,46 +543,43 @@ pub async fn open_unreal_project(query: web::Query<std::collections::HashMap<Str
         .get("projects_base")
         .map(|s| PathBuf::from(s))
         .unwrap_or_else(utils::default_unreal_projects_dir);
-    println!("Project Base: {}", projects_base.to_string_lossy());
-    println!("Raw Project: {}", raw_project);
-    println!("Engine Base: {}", engine_base.to_string_lossy());
-    println!("Version (requested): {}", version_param_opt.clone().unwrap_or_else(|| "<auto> from .uproject".to_string()));
+    log::debug!("Project Base: {}", projects_base.to_string_lossy());
+    log::debug!("Raw Project: {}", raw_project);
+    log::debug!("Engine Base: {}", engine_base.to_string_lossy());
+    log::debug!("Version (requested): {}", version_param_opt.clone().unwrap_or_else(|| "<auto> from .uproject".to_string()));
+    let extra_args = match utils::editor_args::from_query(&query) {
+        Ok(a) => a,
+        Err(e) => return err(StatusCode::BAD_REQUEST, "invalid_parameter", e),
+    };
 
     // First try to resolve as path/dir; if that fails, treat `raw_project` as a project name
     let project_path = match utils::resolve_project_path(&raw_project) {
         Some(p) => {
-            println!("Resolve Project Path: {}", p.to_string_lossy());
+            log::debug!("Resolve Project Path: {}", p.to_string_lossy());
             Some(p)
         },
         None => {
-            // Interpret as a name: search projects_base/<name> for a .uproject file
-            let candidate_dir = projects_base.join(&raw_project);
-            println!("Candidate Dir: {}", candidate_dir.to_string_lossy());
-            if candidate_dir.is_dir() {
-                // Find the first .uproject file in that folder
-                if let Ok(entries) = fs::read_dir(&candidate_dir) {
-                    let mut found: Option<PathBuf> = None;
-                    for e in entries.flatten() {
-                        let fp = e.path();
-                        if fp.is_file() {
-                            if let Some(ext) = fp.extension() { if ext == "uproject" { found = Some(fp); break; } }
-                        }
-                    }
-                    found
-                } else { None }
-            } else {
-                None
+            // Interpret as a name under projects_base (never outside it); only open when exactly one project matches
+            let mut candidates = utils::uproject_candidates_in(&raw_project, &projects_base);
+            if candidates.len() > 1 {
+                let paths: Vec<String> = candidates.iter().map(|p| p.to_string_lossy().to_string()).collect();
+                let message = format!("Project name '{}' matches {} projects; pass one of the candidate paths as project", raw_project, paths.len());
+                return HttpResponse::Conflict().json(models::ErrorResponse {
+                    candidates: Some(paths),
+                    ..models::ErrorResponse::new("ambiguous_project", message)
+                });
             }
+            candidates.pop()
         }
     };
 
     let project_path = match project_path {
         Some(p) => {
-            println!("Using project: {}", p.to_string_lossy());
+            log::info!("Using project: {}", p.to_string_lossy());
             p
         },
         None => {
-            return HttpResponse::BadRequest().body("Project not found by path or name, or no .uproject in directory");
+            return err(StatusCode::BAD_REQUEST, "project_not_found", "Project not found by path or name, or no .uproject in directory");
         }
     };
 
@@ -450,73 +596,62 @@ pub async fn open_unreal_project(query: web::Query<std::collections::HashMap<Str
                         match crate::utils::resolve_engine_association_to_mm(&assoc) {
                             Some(mm) => mm,
                             None => {
-                                return HttpResponse::NotFound().body("Could not resolve EngineAssociation from project to a version");
+                                return err(StatusCode::NOT_FOUND, "engine_not_found", "Could not resolve EngineAssociation from project to a version");
                             }
                         }
                     }
                     None => {
-                        return HttpResponse::BadRequest().body("Project .uproject missing EngineAssociation and no version provided");
+                        return err(StatusCode::BAD_REQUEST, "invalid_project", "Project .uproject missing EngineAssociation and no version provided");
                     }
                 }
             }
             Err(_) => {
-                return HttpResponse::BadRequest().body("Failed to read project .uproject file to determine engine version");
+                return err(StatusCode::BAD_REQUEST, "invalid_project", "Failed to read project .uproject file to determine engine version");
             }
         }
     };
-    println!("Requested engine version (resolved): {}", requested_version);
+    log::debug!("Requested engine version (resolved): {}", requested_version);
 
     // Discover engines
-    let mut engines: Vec<models::UnrealEngineInfo> = Vec::new();
-    if engine_base.is_dir() {
-        if let Ok(entries) = fs::read_dir(&engine_base) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.is_dir() {
-                    if path.join("Engine").join("Binaries").is_dir() {
-                        let name = path.file_name().and_then(|s| s.to_str()).unwrap_or("").to_string();
-                        let version = utils::read_build_version(&path)
-                            .or_else(|| utils::parse_version_from_name(&name))
-                            .unwrap_or_else(|| "unknown".to_string());
-                        let editor_path = utils::find_editor_binary(&path).map(|p| p.to_string_lossy().to_string());
-                        engines.push(models::UnrealEngineInfo { name, version, path: path.to_string_lossy().to_string(), editor_path });
-                    }
-                }
-            }
-        }
-    }
+    let engines: Vec<models::UnrealEngineInfo> = utils::discover_engines(&engine_base);
 
     if engines.is_empty() {
-        return HttpResponse::NotFound().body("No Unreal Engine installations found in engine_base");
+        return err(StatusCode::NOT_FOUND, "engine_not_found", "No Unreal Engine installations found in engine_base");
     }
 
-    let chosen = match utils::pick_engine_for_version(&engines, &requested_version) {
+    let exact = query.get("exact").is_some_and(|v| v == "1" || v.eq_ignore_ascii_case("true"));
+    let chosen = match utils::pick_engine_for_version(&engines, &requested_version, exact) {
         Some(e) => e,
         None => {
-            return HttpResponse::NotFound().body(format!("Requested version '{}' not found among discovered engines", requested_version));
+            let kind = if exact { "exactly matching" } else { "matching" };
+            return err(StatusCode::NOT_FOUND, "engine_not_found", format!("No engine {} version '{}' among discovered engines", kind, requested_version));
         }
     };
 
     let editor_path = match &chosen.editor_path {
         Some(p) => PathBuf::from(p),
-        None => return HttpResponse::NotFound().body("Engine found but Editor binary not located"),
+        None => return editor_not_found(chosen),
     };
-    println!("Using editor: {}", editor_path.to_string_lossy());
+    log::info!("Using editor: {}", editor_path.to_string_lossy());
 
-    // Spawn the editor without waiting for it to exit
-    let spawn_res = std::process::Command::new(&editor_path)
-        .arg(&project_path)
-        .spawn();
-    println!("Spawn Result: {:?}", spawn_res);
+    // Spawn the editor without waiting for it to exit; with a jobId, launch failures are reported over WS
+    let job_id = query.get("jobId").cloned().or_else(|| query.get("job_id").cloned());
+    utils::fix_editor_permissions_if_requested(&query, &editor_path);
+    let args = utils::editor_args::editor_args(&project_path.to_string_lossy(), false, &extra_args);
+    let mut cmd = std::process::Command::new(&editor_path);
+    cmd.args(&args);
+    let spawn_res = utils::spawn_editor_watched(cmd, job_id.as_deref());
+    log::debug!("Spawn Result: {:?}", spawn_res);
 
     match spawn_res {
-        Ok(_child) => {
+        Ok(()) => {
             let resp = models::OpenProjectResponse {
                 launched: true,
                 engine_name: Some(chosen.name.clone()),
                 engine_version: Some(chosen.version.clone()),
                 editor_path: Some(editor_path.to_string_lossy().to_string()),
                 project: project_path.to_string_lossy().to_string(),
+                args,
                 message: "Launched Unreal Editor".to_string(),
             };
             HttpResponse::Ok().json(resp)
@@ -528,7 +663,8 @@ pub async fn open_unreal_project(query: web::Query<std::collections::HashMap<Str
                 engine_version: Some(chosen.version.clone()),
                 editor_path: Some(editor_path.to_string_lossy().to_string()),
                 project: project_path.to_string_lossy().to_string(),
-                message: format!("Failed to launch editor: {}", e),
+                args,
+                message: format!("Failed to launch editor: {}", utils::describe_spawn_error(&editor_path, &e)),
             };
             HttpResponse::InternalServerError().json(resp)
         }
@@ -545,23 +681,38 @@ pub async fn open_unreal_project(query: web::Query<std::collections::HashMap<Str
 /// - project: String — Project identifier. Accepts one of:
 ///   - Bare project folder name under the default projects dir (e.g., "MyGame").
 ///   - A project directory path (e.g., "$HOME/Documents/Unreal Projects/MyGame").
-///   - A direct path to a .uproject file (e.g., "/path/to/MyGame.uproject"). Required.
+///   - A direct path to a .uproject file (e.g., "/path/to/MyGame.uproject"). Required unless `projects` is given.
+/// - projects: Optional<Vec<String>> — More project identifiers; the asset is copied into `project` and each of these in turn.
+/// - continue_on_error: Optional<bool> — With several projects, keep going after one fails. Default false; a cancel always stops.
 /// - target_subdir: Optional<String> — Subfolder inside Project/Content to copy into (e.g., "Imported/Industry"). Optional.
+/// - target: Optional<String> — "content" (default) copies into <Project>/Content/<asset>; "plugin" copies into
+///   <Project>/Plugins/<asset>/Content together with the asset's .uplugin descriptor (target_subdir is ignored).
 /// - overwrite: Optional<bool> — When true, overwrite existing files; when false, keep existing files and count them as skipped. Default false.
+/// - on_conflict: Optional<String> — What to do with files that already exist, overriding `overwrite`:
+///   "skip", "overwrite", "rename_new" (write the incoming file as name_1.ext, name_2.ext, ...) or "newer" (overwrite only when the source is newer).
+/// - dry_run: Optional<bool> — When true, resolve source and destination and report the planned counts without copying. Default false.
 ///
 /// Behavior:
 /// - Copies all files from downloads/<asset_name>/data/Content into <Project>/Content (or the provided target_subdir).
 /// - Creates missing directories as needed.
-/// - Skips existing files unless overwrite=true.
+/// - Skips existing files unless overwrite=true or another on_conflict policy is chosen.
 /// - Returns counts for files copied and skipped, along with timing information.
+/// - With dry_run=true, files_copied/files_skipped are what a real import would do; no folders are created.
 ///
 /// Returns:
-/// - 200 OK with JSON { ok, message, files_copied, files_skipped, source, destination, elapsed_ms } on success.
-/// - 400 Bad Request if required fields are missing or the project cannot be resolved.
+/// - 200 OK with JSON { ok, message, files_copied, files_skipped, files_overwritten, files_renamed, on_conflict, source, destination, destination_type, elapsed_ms } on success.
+///   files_copied counts every file written; files_overwritten and files_renamed break it down.
+/// - With `projects`: JSON { ok, message, source, destination_type, results: [{ project, ok, message, files_copied, files_skipped, files_overwritten, files_renamed, destination }], elapsed_ms }
+///   with 200 when every project succeeded, 207 Multi-Status when some did, 500 when none did.
+/// - 400 Bad Request if required fields are missing, `target` or `on_conflict` is unknown or the project cannot be resolved.
 /// - 404 Not Found if the source Content folder for the asset does not exist.
 /// - 500 Internal Server Error on copy failures.
 ///
 /// Example requests:
+/// - As a project plugin:
+///   curl -X POST http://127.0.0.1:8080/import-asset \
+///        -H "Content-Type: application/json" \
+///        -d '{"asset_name":"Some Marketplace Plugin","project":"MyGame","target":"plugin"}'
 /// - Basic import using project name (defaults to $HOME/Documents/Unreal Projects):
 ///   curl -X POST http://127.0.0.1:8080/import-asset \
 ///        -H "Content-Type: application/json" \
@@ -574,23 +725,295 @@ pub async fn open_unreal_project(query: web::Query<std::collections::HashMap<Str
 ///   curl -X POST http://127.0.0.1:8080/import-asset \
 ///        -H "Content-Type: application/json" \
 ///        -d '{"asset_name":"Industry Props Pack 6","project":"$HOME/Documents/Unreal Projects/MyGame/MyGame.uproject"}'
+/// - Preview how many existing files an overwrite would replace:
+///   curl -X POST http://127.0.0.1:8080/import-asset \
+///        -H "Content-Type: application/json" \
+///        -d '{"asset_name":"Industry Props Pack 6","project":"MyGame","overwrite":true,"dry_run":true}'
+/// - Keep the project's edits and only take files that changed upstream since:
+///   curl -X POST http://127.0.0.1:8080/import-asset \
+///        -H "Content-Type: application/json" \
+///        -d '{"asset_name":"Industry Props Pack 6","project":"MyGame","on_conflict":"newer"}'
+/// - Into several projects, skipping past failures:
+///   curl -X POST http://127.0.0.1:8080/import-asset \
+///        -H "Content-Type: application/json" \
+///        -d '{"asset_name":"Industry Props Pack 6","projects":["MyGame","OtherGame"],"continue_on_error":true}'
 #[post("/import-asset")]
 pub async fn import_asset(body: web::Json<models::ImportAssetRequest>) -> impl Responder {
     let request_body = body.into_inner();
+    utils::with_log_job(request_body.job_id.clone(), import_asset_job(request_body)).await
+}
+
+/// Body of /import-asset, run inside the job's log context.
+async fn import_asset_job(request_body: models::ImportAssetRequest) -> impl Responder {
     let job_id = request_body.job_id.clone();
-    println!("¬ import_asset");
+    log::debug!("¬ import_asset");
     utils::emit_event(job_id.as_deref(), models::Phase::ImportStart, format!("Importing '{}'", request_body.asset_name), Some(0.0), None);
 
-    // Determine downloads base (same logic as create_unreal_project)
-    let mut downloads_base = PathBuf::from("downloads");
-    if !downloads_base.exists() {
-        if let Ok(exe) = std::env::current_exe() {
-            if let Some(exe_dir) = exe.parent() {
-                let alt = exe_dir.join("downloads");
-                if alt.exists() { downloads_base = alt; }
+    if request_body.project.trim().is_empty() && request_body.projects.as_ref().map_or(true, |ps| ps.iter().all(|p| p.trim().is_empty())) {
+        return err(StatusCode::BAD_REQUEST, "missing_parameter", "project or projects is required");
+    }
+    let target = match models::ImportTarget::parse(request_body.target.as_deref()) {
+        Ok(t) => t,
+        Err(msg) => return err(StatusCode::BAD_REQUEST, "invalid_parameter", msg),
+    };
+    let src_content = match resolve_import_source(&request_body).await {
+        Ok(p) => p,
+        Err(resp) => return resp,
+    };
+
+    let policy = match utils::ConflictPolicy::from_request(request_body.on_conflict.as_deref(), request_body.overwrite.unwrap_or(false)) {
+        Ok(p) => p,
+        Err(msg) => return err(StatusCode::BAD_REQUEST, "invalid_parameter", msg),
+    };
+    let copy_workers = utils::import_copy_workers(request_body.copy_workers);
+    let started = Instant::now();
+
+    let extra_projects: Vec<String> = request_body.projects.clone().unwrap_or_default()
+        .into_iter()
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .collect();
+    if extra_projects.is_empty() {
+        // Single project: original response shapes
+        return match import_into_project(&request_body, &request_body.project, &src_content, target, policy, copy_workers, (0.0, 100.0)) {
+            Ok(resp) => {
+                let verb = if request_body.dry_run.unwrap_or(false) { "Planned import of" } else { "Imported" };
+                utils::emit_event(job_id.as_deref(), models::Phase::ImportComplete, format!("{} '{}'", verb, request_body.asset_name.trim()), Some(100.0), None);
+                HttpResponse::Ok().json(resp)
             }
-        }
+            Err(ProjectImportError::Unresolved(msg)) => err(StatusCode::BAD_REQUEST, "project_not_found", msg),
+            Err(ProjectImportError::Cancelled) => HttpResponse::Ok().body("cancelled"),
+            Err(ProjectImportError::CopyFailed { response, .. }) => {
+                utils::emit_event(job_id.as_deref(), models::Phase::ImportError, response.message.clone(), None, None);
+                HttpResponse::InternalServerError().json(response)
+            }
+        };
+    }
+
+    // Several projects: copy into each in turn, sharing one 0..100 progress range
+    let mut projects: Vec<String> = Vec::new();
+    if !request_body.project.trim().is_empty() {
+        projects.push(request_body.project.trim().to_string());
+    }
+    for p in extra_projects {
+        if !projects.contains(&p) { projects.push(p); }
     }
+    let continue_on_error = request_body.continue_on_error.unwrap_or(false);
+    let count = projects.len();
+    let results = utils::batch::run_in_order(
+        projects,
+        continue_on_error,
+        |i, project| {
+            let span = (i as f32 * 100.0 / count as f32, (i + 1) as f32 * 100.0 / count as f32);
+            utils::emit_event(
+                job_id.as_deref(),
+                models::Phase::ImportCopying,
+                format!("Project {}/{}: {}", i + 1, count, project),
+                Some(span.0),
+                Some(serde_json::json!({ "project": project, "project_index": i, "project_count": count })),
+            );
+            let outcome = match import_into_project(&request_body, &project, &src_content, target, policy, copy_workers, span) {
+                Ok(resp) => utils::batch::ItemOutcome::Done(models::ImportProjectResult {
+                    project,
+                    ok: true,
+                    message: resp.message,
+                    files_copied: resp.files_copied,
+                    files_skipped: resp.files_skipped,
+                    files_overwritten: resp.files_overwritten,
+                    files_renamed: resp.files_renamed,
+                    destination: Some(resp.destination),
+                }),
+                Err(e) => {
+                    let cancelled = e.is_cancel();
+                    let (message, destination) = match e {
+                        ProjectImportError::Unresolved(msg) => (msg, None),
+                        ProjectImportError::Cancelled => ("Cancelled".to_string(), None),
+                        ProjectImportError::CopyFailed { response, .. } => (response.message, Some(response.destination)),
+                    };
+                    log::warn!("Import into '{}' failed: {}", project, message);
+                    let result = models::ImportProjectResult { project, ok: false, message, files_copied: 0, files_skipped: 0, files_overwritten: 0, files_renamed: 0, destination };
+                    if cancelled { utils::batch::ItemOutcome::Cancelled(result) } else { utils::batch::ItemOutcome::Failed(result) }
+                }
+            };
+            std::future::ready(outcome)
+        },
+        |project, why| models::ImportProjectResult {
+            project,
+            ok: false,
+            message: format!("Not attempted: {}", not_attempted_reason(why, "project")),
+            files_copied: 0,
+            files_skipped: 0,
+            files_overwritten: 0,
+            files_renamed: 0,
+            destination: None,
+        },
+    )
+    .await;
+
+    let succeeded = results.iter().filter(|r| r.ok).count();
+    let message = format!("Imported '{}' into {} of {} projects", request_body.asset_name.trim(), succeeded, count);
+    if succeeded == count {
+        utils::emit_event(job_id.as_deref(), models::Phase::ImportComplete, message.clone(), Some(100.0), None);
+    } else {
+        utils::emit_event(job_id.as_deref(), models::Phase::ImportError, message.clone(), None, None);
+    }
+    let resp = models::MultiImportResponse {
+        ok: succeeded == count,
+        message,
+        source: src_content.to_string_lossy().to_string(),
+        destination_type: target.as_str(),
+        results,
+        elapsed_ms: started.elapsed().as_millis(),
+    };
+    if succeeded == count {
+        HttpResponse::Ok().json(resp)
+    } else if succeeded > 0 {
+        HttpResponse::MultiStatus().json(resp)
+    } else {
+        HttpResponse::InternalServerError().json(resp)
+    }
+}
+
+/// Import several downloaded assets into one UE project, one after another.
+///
+/// Route:
+/// - POST /import-assets
+///
+/// JSON body fields:
+/// - assets: Vec<Object> — /import-asset bodies (asset_name, Fab ids, target, target_subdir, overwrite, on_conflict, dry_run, ...). Required.
+///   Their `project`, `projects` and `job_id` are replaced by the batch's.
+/// - project: String — Project identifier, as for /import-asset. Required.
+/// - job_id: Optional<String> — Job id shared by every asset; progress events carry `asset_index`/`asset_count` details.
+/// - continue_on_error: Optional<bool> — Keep going after an asset fails. Default false; a cancel (including one during an asset's download) always stops the batch.
+///
+/// Returns:
+/// - JSON { ok, message, project, results: [{ asset_name, ok, message, result }], elapsed_ms } where `result` is the
+///   asset's /import-asset response, with 200 when every asset succeeded, 207 Multi-Status when some did, 500 when none did.
+///   Assets after a failure (or a cancel) are reported as not attempted.
+/// - 400 Bad Request if `project` or `assets` is missing.
+///
+/// Example:
+///   curl -X POST http://127.0.0.1:8080/import-assets \
+///        -H "Content-Type: application/json" \
+///        -d '{"project":"MyGame","assets":[{"asset_name":"Industry Props Pack 6"},{"asset_name":"Some Marketplace Plugin","target":"plugin"}]}'
+#[post("/import-assets")]
+pub async fn import_assets(body: web::Json<models::ImportAssetsRequest>) -> impl Responder {
+    let request_body = body.into_inner();
+    utils::with_log_job(request_body.job_id.clone(), import_assets_job(request_body)).await
+}
+
+/// Body of /import-assets, run inside the job's log context.
+async fn import_assets_job(batch: models::ImportAssetsRequest) -> HttpResponse {
+    let job_id = batch.job_id.clone();
+    log::debug!("¬ import_assets");
+    let project = batch.project.trim().to_string();
+    if project.is_empty() {
+        return err(StatusCode::BAD_REQUEST, "missing_parameter", "project is required");
+    }
+    if batch.assets.is_empty() {
+        return err(StatusCode::BAD_REQUEST, "missing_parameter", "assets is required");
+    }
+    let count = batch.assets.len();
+    utils::emit_event(job_id.as_deref(), models::Phase::ImportStart, format!("Importing {} assets", count), Some(0.0), None);
+
+    let continue_on_error = batch.continue_on_error.unwrap_or(false);
+    let started = Instant::now();
+    let job = job_id.as_deref();
+    let results = utils::batch::run_in_order(
+        batch.assets,
+        continue_on_error,
+        |i, mut asset| {
+            let asset_name = asset.asset_name.trim().to_string();
+            asset.project = project.clone();
+            asset.projects = None;
+            asset.job_id = job_id.clone();
+            let span = (i as f32 * 100.0 / count as f32, (i + 1) as f32 * 100.0 / count as f32);
+            utils::emit_event(
+                job,
+                models::Phase::ImportCopying,
+                format!("Asset {} / {}: {}", i + 1, count, asset_name),
+                Some(span.0),
+                Some(serde_json::json!({ "asset_name": asset_name, "asset_index": i, "asset_count": count })),
+            );
+            async move {
+                match import_batch_asset(&asset, span).await {
+                    Ok(resp) => utils::batch::ItemOutcome::Done(models::BatchImportResult { asset_name, ok: true, message: resp.message.clone(), result: Some(resp) }),
+                    Err(e) => {
+                        // A cancel between two copies is only seen through the flag
+                        let cancelled = e.is_cancel() || utils::check_if_job_is_cancelled(job);
+                        let (message, result) = match e {
+                            ProjectImportError::Unresolved(msg) => (msg, None),
+                            ProjectImportError::Cancelled => ("Cancelled".to_string(), None),
+                            ProjectImportError::CopyFailed { response, .. } => (response.message.clone(), Some(response)),
+                        };
+                        log::warn!("Import of '{}' failed: {}", asset_name, message);
+                        let result = models::BatchImportResult { asset_name, ok: false, message, result };
+                        if cancelled { utils::batch::ItemOutcome::Cancelled(result) } else { utils::batch::ItemOutcome::Failed(result) }
+                    }
+                }
+            }
+        },
+        |asset, why| models::BatchImportResult {
+            asset_name: asset.asset_name.trim().to_string(),
+            ok: false,
+            message: format!("Not attempted: {}", not_attempted_reason(why, "asset")),
+            result: None,
+        },
+    )
+    .await;
+
+    let succeeded = results.iter().filter(|r| r.ok).count();
+    let message = format!("Imported {} of {} assets into '{}'", succeeded, count, project);
+    if succeeded == count {
+        utils::emit_event(job_id.as_deref(), models::Phase::ImportComplete, message.clone(), Some(100.0), None);
+    } else {
+        utils::emit_event(job_id.as_deref(), models::Phase::ImportError, message.clone(), None, None);
+    }
+    let resp = models::BatchImportResponse { ok: succeeded == count, message, project, results, elapsed_ms: started.elapsed().as_millis() };
+    if succeeded == count {
+        HttpResponse::Ok().json(resp)
+    } else if succeeded > 0 {
+        HttpResponse::MultiStatus().json(resp)
+    } else {
+        HttpResponse::InternalServerError().json(resp)
+    }
+}
+
+/// Message for a batch item that was not attempted; `item` names what the batch iterates over.
+fn not_attempted_reason(why: utils::batch::NotAttempted, item: &str) -> String {
+    match why {
+        utils::batch::NotAttempted::Cancelled => "cancelled".to_string(),
+        utils::batch::NotAttempted::EarlierFailure => format!("an earlier {} failed", item),
+    }
+}
+
+/// Resolve and import one asset of an /import-assets batch into its `project`, within `span` of the job's progress.
+async fn import_batch_asset(asset: &models::ImportAssetRequest, span: (f32, f32)) -> Result<models::ImportAssetResponse, ProjectImportError> {
+    let target = models::ImportTarget::parse(asset.target.as_deref()).map_err(ProjectImportError::Unresolved)?;
+    let policy = utils::ConflictPolicy::from_request(asset.on_conflict.as_deref(), asset.overwrite.unwrap_or(false)).map_err(ProjectImportError::Unresolved)?;
+    let src_content = match resolve_import_source(asset).await {
+        Ok(p) => p,
+        // The only successful "error" is the download's cancel, already reported on the job
+        Err(resp) if resp.status().is_success() => return Err(ProjectImportError::Cancelled),
+        Err(resp) => return Err(ProjectImportError::Unresolved(response_message(resp).await)),
+    };
+    let copy_workers = utils::import_copy_workers(asset.copy_workers);
+    import_into_project(asset, &asset.project, &src_content, target, policy, copy_workers, span)
+}
+
+/// Text of an error response, for reporting it inside a larger JSON result.
+async fn response_message(resp: HttpResponse) -> String {
+    let status = resp.status();
+    let body = actix_web::body::to_bytes(resp.into_body()).await.ok().map(|b| String::from_utf8_lossy(&b).trim().to_string()).unwrap_or_default();
+    if body.is_empty() { status.to_string() } else { body }
+}
+
+/// Find the Content folder to import for `request_body`, downloading the asset first when Fab
+/// identifiers are given. Errors are the response to send back.
+async fn resolve_import_source(request_body: &models::ImportAssetRequest) -> Result<PathBuf, HttpResponse> {
+    let job_id = request_body.job_id.clone();
+    // Determine downloads base (same logic as create_unreal_project)
+    let downloads_base = utils::find_downloads_directory();
 
     // If Fab identifiers are provided, run the exact same download process first
     if let (Some(namespace), Some(asset_id), Some(artifact_id)) = (request_body.namespace.clone(), request_body.asset_id.clone(), request_body.artifact_id.clone()) {
@@ -607,7 +1030,7 @@ pub async fn import_asset(body: web::Json<models::ImportAssetRequest>) -> impl R
             Ok(resp) => {
                 if !resp.status().is_success() {
                     // Fatal failure (e.g., no distribution point succeeded) — bubble it up
-                    return resp;
+                    return Err(resp);
                 }
                 // Success — proceed to import using the same folder naming as the downloader
                 let mut epic_services = utils::create_epic_games_services();
@@ -623,18 +1046,9 @@ pub async fn import_asset(body: web::Json<models::ImportAssetRequest>) -> impl R
                     // Nothing to do here — asset_dir will be recomputed consistently below as well
                 }
             }
-            Err(resp) => {
-                if !resp.status().is_success() {
-                    // Download error — bubble it up
-                    return resp;
-                }
-                // Cancelled path returns 200 OK with body "cancelled" — do not proceed to import
-                if utils::check_if_job_is_cancelled(job_id.as_deref()) {
-                    if let Some(ref j) = job_id { utils::acknowledge_cancel(j); }
-                    return HttpResponse::Ok().body("cancelled");
-                }
-                // If somehow we reached here with a 200 from Err but not cancelled, treat as non-fatal and proceed.
-            }
+            // Download error, or its cancel (200 OK "cancelled", already reported and acknowledged by
+            // the download) — either way nothing is imported
+            Err(resp) => return Err(resp),
         }
     }
 
@@ -643,7 +1057,7 @@ pub async fn import_asset(body: web::Json<models::ImportAssetRequest>) -> impl R
     // 2) Otherwise, use the provided asset_name with case-insensitive match
     let safe_name = request_body.asset_name.trim();
     if safe_name.is_empty() {
-        return HttpResponse::BadRequest().body("asset_name is required");
+        return Err(err(StatusCode::BAD_REQUEST, "missing_parameter", "asset_name is required"));
     }
 
     let mut asset_dir: PathBuf;
@@ -676,13 +1090,19 @@ pub async fn import_asset(body: web::Json<models::ImportAssetRequest>) -> impl R
         }
     }
 
+    // Never read outside downloads/, whatever asset_name/ue contain
+    if downloads_base.exists() {
+        if let Err(e) = utils::resolve_within_root(&downloads_base, &asset_dir) {
+            return Err(err(StatusCode::BAD_REQUEST, "invalid_parameter", format!("Invalid asset_name: {}", e)));
+        }
+    }
     // Require that the asset exists locally now
     if !asset_dir.exists() {
-        return HttpResponse::NotFound().body(format!("Asset folder not found under downloads (looked in {})", downloads_base.display()));
+        return Err(err(StatusCode::NOT_FOUND, "asset_not_found", format!("Asset folder not found under downloads (looked in {})", downloads_base.display())));
     }
     // If a completion marker is used by downloads, ensure it's complete as well
     if !utils::is_download_complete(&asset_dir) {
-        return HttpResponse::NotFound().body("Asset is not fully downloaded. Please download it first via /download-asset.");
+        return Err(err(StatusCode::NOT_FOUND, "asset_not_found", "Asset is not fully downloaded. Please download it first via /download-asset."));
     }
     // Locate the source Content folder. Assets may place it at different depths (e.g., data/Content or data/Engine/Plugins/Marketplace/.../content)
     let data_dir = asset_dir.join("data");
@@ -694,101 +1114,232 @@ pub async fn import_asset(body: web::Json<models::ImportAssetRequest>) -> impl R
             src_content = alt;
         } else {
             // Search recursively for a folder named Content/content (case-insensitive)
-            let max_depth = 10usize;
-            let mut queue: VecDeque<(PathBuf, usize)> = VecDeque::new();
-            queue.push_back((data_dir.clone(), 0));
-            let mut found: Option<PathBuf> = None;
-            let mut found_marketplace: Option<PathBuf> = None;
-            'bfs: while let Some((dir, depth)) = queue.pop_front() {
-                if depth > max_depth { continue; }
-                if let Ok(entries) = fs::read_dir(&dir) {
-                    for ent in entries.flatten() {
-                        let p = ent.path();
-                        if p.is_dir() {
-                            if let Some(name) = p.file_name().and_then(|s| s.to_str()) {
-                                if name.eq_ignore_ascii_case("Content") {
-                                    let lower = p.to_string_lossy().to_lowercase();
-                                    if lower.contains("plugins/marketplace") {
-                                        found_marketplace = Some(p.clone());
-                                        break 'bfs;
-                                    }
-                                    if found.is_none() { found = Some(p.clone()); }
-                                }
-                            }
-                            queue.push_back((p, depth + 1));
-                        }
-                    }
-                }
-            }
-            if let Some(p) = found_marketplace.or(found) {
+            let found = match utils::find_content_dir_bfs(&data_dir, utils::search_limits(10)) {
+                Ok(found) => found,
+                Err(e) => return Err(err(StatusCode::BAD_REQUEST, "invalid_parameter", e.to_string())),
+            };
+            if let Some(p) = found {
                 src_content = p;
             } else {
-                return HttpResponse::NotFound().body(format!("Source Content folder not found under {}", data_dir.display()));
+                return Err(err(StatusCode::NOT_FOUND, "asset_not_found", format!("Source Content folder not found under {}", data_dir.display())));
             }
         }
     }
+    Ok(src_content)
+}
+
+/// Why importing into one project failed.
+enum ProjectImportError {
+    /// The project (or its destination folder) could not be resolved; nothing was copied.
+    Unresolved(String),
+    /// The asset's download was cancelled before anything was copied.
+    Cancelled,
+    /// Copying failed part-way; `cancelled` is set when the job was cancelled.
+    CopyFailed { response: models::ImportAssetResponse, cancelled: bool },
+}
 
+impl ProjectImportError {
+    /// True when the job was cancelled, which stops a batch even with continue_on_error.
+    fn is_cancel(&self) -> bool {
+        matches!(self, ProjectImportError::Cancelled | ProjectImportError::CopyFailed { cancelled: true, .. })
+    }
+}
+
+/// Copy `src_content` into one project's Content/<target_subdir>/<asset folder> (or, for a plugin,
+/// Plugins/<asset folder>/Content plus its .uplugin), reporting copy progress within `span`.
+/// Emits no terminal event; callers decide how to finish the job.
+fn import_into_project(
+    request_body: &models::ImportAssetRequest,
+    project: &str,
+    src_content: &Path,
+    target: models::ImportTarget,
+    policy: utils::ConflictPolicy,
+    copy_workers: usize,
+    span: (f32, f32),
+) -> Result<models::ImportAssetResponse, ProjectImportError> {
+    let job_id = request_body.job_id.as_deref();
     // Resolve project directory and destination Content
-    let project_dir = match utils::resolve_project_dir_from_param(&request_body.project) {
+    let project_dir = match utils::resolve_project_dir_from_param(project) {
         Some(p) => p,
-        None => return HttpResponse::BadRequest().body("Project could not be resolved to a valid Unreal project"),
+        None => return Err(ProjectImportError::Unresolved("Project could not be resolved to a valid Unreal project".to_string())),
     };
-    let mut dest_content = project_dir.join("Content");
-    if let Some(sub) = &request_body.target_subdir {
-        let trimmed = sub.trim_matches(['/', '\\']);
-        if !trimmed.is_empty() {
-            dest_content = dest_content.join(trimmed);
-        }
-    }
-    // Always create an asset-named subfolder inside the project's Content and copy into it.
+    // Always create an asset-named subfolder inside the project's Content (or Plugins) and copy into it.
     // Use a friendly, filesystem-safe folder name derived from the requested asset_name.
     let asset_folder_name = utils::get_friendly_folder_name(request_body.asset_name.clone()).unwrap_or_else(|| request_body.asset_name.clone());
-    let dest_content = dest_content.join(asset_folder_name);
+    let (dest_root, dest_content) = match target {
+        models::ImportTarget::Content => {
+            let mut dest_content = project_dir.join("Content");
+            if let Some(sub) = &request_body.target_subdir {
+                let trimmed = sub.trim_matches(['/', '\\']);
+                if !trimmed.is_empty() {
+                    dest_content = dest_content.join(trimmed);
+                }
+            }
+            (project_dir.join("Content"), dest_content.join(asset_folder_name))
+        }
+        models::ImportTarget::Plugin => (project_dir.join("Plugins"), project_dir.join("Plugins").join(asset_folder_name).join("Content")),
+    };
+    // target_subdir and the asset folder name must not lead outside the project's Content (or Plugins) folder.
+    // A dry run creates nothing, so a missing root is checked against the project folder instead.
+    let dry_run = request_body.dry_run.unwrap_or(false);
+    let guarded = if dry_run && !dest_root.exists() {
+        utils::resolve_within_root(&project_dir, &dest_content).and_then(|p| {
+            let root = fs::canonicalize(&project_dir).map_err(utils::PathGuardError::RootUnavailable)?;
+            match dest_root.file_name() {
+                Some(name) if p.starts_with(root.join(name)) => Ok(p),
+                _ => Err(utils::PathGuardError::OutsideRoot(dest_content.clone())),
+            }
+        })
+    } else {
+        let _ = fs::create_dir_all(&dest_root);
+        utils::resolve_within_root(&dest_root, &dest_content)
+    };
+    let dest_content = match guarded {
+        Ok(p) => p,
+        Err(e) => return Err(ProjectImportError::Unresolved(format!("Invalid target_subdir: {}", e))),
+    };
 
-    let overwrite = request_body.overwrite.unwrap_or(false);
     let started = Instant::now();
-    utils::emit_event(job_id.as_deref(), models::Phase::ImportCopying, format!("Copying files into {}", dest_content.display()), Some(0.0), None);
-    match utils::copy_dir_recursive_with_progress(&src_content, &dest_content, overwrite, job_id.as_deref(), models::Phase::ImportCopying) {
-        Ok((copied, skipped)) => {
-            utils::emit_event(job_id.as_deref(), models::Phase::ImportComplete, format!("Imported '{}'", request_body.asset_name.trim()), Some(100.0), None);
-            let resp = models::ImportAssetResponse {
+    if dry_run {
+        return plan_import(src_content, &dest_content, target, policy)
+            .map(|counts| models::ImportAssetResponse {
                 ok: true,
-                message: format!("Imported into project at {}", project_dir.display()),
-                files_copied: copied,
-                files_skipped: skipped,
+                message: format!("Dry run: would copy {} and skip {} files in project at {}", counts.copied, counts.skipped, project_dir.display()),
+                files_copied: counts.copied,
+                files_skipped: counts.skipped,
+                files_overwritten: counts.overwritten,
+                files_renamed: counts.renamed,
+                on_conflict: policy.as_str(),
                 source: src_content.to_string_lossy().to_string(),
                 destination: dest_content.to_string_lossy().to_string(),
+                destination_type: target.as_str(),
                 elapsed_ms: started.elapsed().as_millis(),
-            };
-            HttpResponse::Ok().json(resp)
-        }
-        Err(e) => {
-            utils::emit_event(job_id.as_deref(), models::Phase::ImportError, format!("Failed to import: {}", e), None, None);
-            let resp = models::ImportAssetResponse {
+            })
+            .map_err(|e| ProjectImportError::Unresolved(format!("Failed to scan {}: {}", src_content.display(), e)));
+    }
+    utils::emit_event(job_id, models::Phase::ImportCopying, format!("Copying files into {}", dest_content.display()), Some(span.0), None);
+    let copied = utils::copy_dir_parallel_with_progress(src_content, &dest_content, policy, job_id, models::Phase::ImportCopying, copy_workers, span)
+        .and_then(|mut counts| match target {
+            models::ImportTarget::Plugin => copy_plugin_descriptor(src_content, &dest_content, policy).map(|descriptor| {
+                if let Some(action) = descriptor { counts.record(&action); }
+                counts
+            }),
+            models::ImportTarget::Content => Ok(counts),
+        });
+    match copied {
+        Ok(counts) => Ok(models::ImportAssetResponse {
+            ok: true,
+            message: format!("Imported into project at {}", project_dir.display()),
+            files_copied: counts.copied,
+            files_skipped: counts.skipped,
+            files_overwritten: counts.overwritten,
+            files_renamed: counts.renamed,
+            on_conflict: policy.as_str(),
+            source: src_content.to_string_lossy().to_string(),
+            destination: dest_content.to_string_lossy().to_string(),
+            destination_type: target.as_str(),
+            elapsed_ms: started.elapsed().as_millis(),
+        }),
+        Err(e) => Err(ProjectImportError::CopyFailed {
+            cancelled: e.kind() == std::io::ErrorKind::Interrupted,
+            response: models::ImportAssetResponse {
                 ok: false,
                 message: format!("Failed to import: {}", e),
                 files_copied: 0,
                 files_skipped: 0,
+                files_overwritten: 0,
+                files_renamed: 0,
+                on_conflict: policy.as_str(),
                 source: src_content.to_string_lossy().to_string(),
                 destination: dest_content.to_string_lossy().to_string(),
+                destination_type: target.as_str(),
                 elapsed_ms: started.elapsed().as_millis(),
-            };
-            HttpResponse::InternalServerError().json(resp)
+            },
+        }),
+    }
+}
+
+/// The counts an import would produce, including the plugin descriptor.
+fn plan_import(src_content: &Path, dest_content: &Path, target: models::ImportTarget, policy: utils::ConflictPolicy) -> std::io::Result<utils::CopyCounts> {
+    let mut counts = utils::parallel_copy::plan_copy(src_content, dest_content, policy)?;
+    if target == models::ImportTarget::Plugin {
+        if let (Some(descriptor), Some(plugin_dir)) = (utils::find_uplugin_for_content(src_content), dest_content.parent()) {
+            if let Some(name) = descriptor.file_name() {
+                counts.record(&utils::conflict::decide(policy, &descriptor, &plugin_dir.join(name)));
+            }
         }
     }
+    Ok(counts)
 }
 
+/// Copy the .uplugin found next to `src_content` beside `dest_content` (the plugin's folder) under `policy`.
+/// Returns what was done, or None when the asset has no descriptor (a content pack imported as a plugin).
+fn copy_plugin_descriptor(src_content: &Path, dest_content: &Path, policy: utils::ConflictPolicy) -> std::io::Result<Option<utils::conflict::ConflictAction>> {
+    let Some(descriptor) = utils::find_uplugin_for_content(src_content) else {
+        log::warn!("No .uplugin next to {}; the plugin folder will not load until one is added", src_content.display());
+        return Ok(None);
+    };
+    let (Some(plugin_dir), Some(file_name)) = (dest_content.parent(), descriptor.file_name()) else { return Ok(None) };
+    let action = utils::conflict::decide(policy, &descriptor, &plugin_dir.join(file_name));
+    utils::conflict::apply(&action, &descriptor)?;
+    Ok(Some(action))
+}
 
-/// Simple health check endpoint to verify the service is running.
+/// Readiness check for containers/systemd and the UI.
 ///
 /// Route:
 /// - GET /health
 ///
-/// Returns:
-/// - 200 OK with body "OK".
+/// Probes the effective cache and downloads directories by writing a temporary file, checks the cached
+/// credentials (as /auth/status does, offline), counts installed engines and checks the projects directory.
+///
+/// Returns JSON: { status, cache_writable, downloads_writable, authenticated, engines_found, projects_dir_exists }
+/// - 200 OK with status "ok", or "degraded" when only non-critical checks fail (not signed in, no engines,
+///   no projects directory).
+/// - 503 Service Unavailable with status "unavailable" when the cache or downloads directory is not writable.
 #[get("/health")]
 pub async fn health() -> HttpResponse {
-    HttpResponse::Ok().body("OK")
+    let cache_writable = utils::dir_writable(&utils::default_cache_dir());
+    let downloads_writable = utils::dir_writable(&utils::get_default_downloads_dir_path());
+    let authenticated = cached_auth().authenticated;
+    let engines_found = utils::discover_engines(&utils::default_unreal_engines_dir()).len();
+    let projects_dir_exists = utils::default_unreal_projects_dir().is_dir();
+
+    let critical_ok = cache_writable && downloads_writable;
+    let status = if !critical_ok {
+        "unavailable"
+    } else if authenticated && engines_found > 0 && projects_dir_exists {
+        "ok"
+    } else {
+        "degraded"
+    };
+    let resp = models::HealthResponse {
+        status: status.to_string(),
+        cache_writable,
+        downloads_writable,
+        authenticated,
+        engines_found,
+        projects_dir_exists,
+    };
+    if critical_ok { HttpResponse::Ok().json(resp) } else { HttpResponse::ServiceUnavailable().json(resp) }
+}
+
+/// Download counters for monitoring, in the Prometheus text format.
+///
+/// Route:
+/// - GET /metrics
+///
+/// Counters (since launch): egs_downloads_{started,completed,failed,cancelled}_total, egs_downloaded_bytes_total.
+/// Gauges: egs_downloads_active, egs_jobs_active (running jobs of any kind), egs_download_bytes_per_second.
+#[get("/metrics")]
+pub async fn metrics() -> HttpResponse {
+    let active_jobs = utils::list_job_states()
+        .iter()
+        .filter(|j| matches!(j.state, models::JobState::Running | models::JobState::Paused | models::JobState::CancelRequested))
+        .count();
+    HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4")
+        .body(utils::download_metrics().render(Instant::now(), active_jobs))
 }
 
 /// Welcome endpoint providing quick pointers to common routes.
@@ -822,6 +1373,64 @@ pub async fn get_version() -> HttpResponse {
     HttpResponse::Ok().json(body)
 }
 
+/// List every UE version supported somewhere in the library, for a version filter dropdown.
+///
+/// Route:
+/// - GET /library-ue-versions
+///
+/// Computed from cache/fab_list.json (no auth needed) and recomputed whenever the cache changes.
+///
+/// Returns JSON: { versions: [{ version: "5.4", asset_count: 12 }, ...] } sorted ascending.
+#[get("/library-ue-versions")]
+pub async fn get_library_ue_versions() -> HttpResponse {
+    HttpResponse::Ok().json(serde_json::json!({ "versions": utils::library_ue_versions() }))
+}
+
+/// Home-screen summary in one call: auth status, account name, library size and download stats.
+///
+/// Route:
+/// - GET /overview
+///
+/// Library count comes from cache/fab_list.json and download stats from a scan of downloads/,
+/// so no library refresh happens. Auth status is checked live using cached tokens; when offline
+/// or unauthenticated, `authenticated` is false and the display name falls back to the cached token data.
+///
+/// Returns JSON: { authenticated, display_name, library_count, downloaded_assets, downloaded_versions, downloaded_bytes }
+#[get("/overview")]
+pub async fn get_overview() -> HttpResponse {
+    // Cached display name (no network); token fields are never read into the response
+    let cached_name = utils::load_user_details()
+        .and_then(|u| serde_json::to_value(&u).ok())
+        .and_then(|v| {
+            v.get("display_name").or_else(|| v.get("displayName")).and_then(|x| x.as_str()).map(|s| s.to_string())
+        });
+
+    let mut epic = utils::create_epic_games_services();
+    let authenticated = utils::try_cached_login(&mut epic).await;
+    let display_name = if authenticated {
+        serde_json::to_value(&epic.user_details()).ok()
+            .and_then(|v| v.get("display_name").or_else(|| v.get("displayName")).and_then(|x| x.as_str()).map(|s| s.to_string()))
+            .or(cached_name)
+    } else {
+        cached_name
+    };
+
+    let library_count: Option<usize> = fs::read(utils::get_fab_cache_file_path()).ok()
+        .and_then(|b| serde_json::from_slice::<serde_json::Value>(&b).ok())
+        .and_then(|v| v.get("results").and_then(|r| r.as_array()).map(|a| a.len()));
+
+    let (downloaded_assets, downloaded_versions, downloaded_bytes) = utils::downloads_summary(&utils::get_default_downloads_dir_path());
+
+    HttpResponse::Ok().json(serde_json::json!({
+        "authenticated": authenticated,
+        "display_name": display_name,
+        "library_count": library_count,
+        "downloaded_assets": downloaded_assets,
+        "downloaded_versions": downloaded_versions,
+        "downloaded_bytes": downloaded_bytes,
+    }))
+}
+
 /// Set/override the Unreal Engine version (EngineAssociation) in a .uproject.
 ///
 /// Route:
@@ -836,11 +1445,11 @@ pub async fn set_unreal_project_version(body: web::Json<models::SetProjectEngine
     let mut s = req.version.trim().to_string();
     if let Some(rest) = s.strip_prefix("UE_") { s = rest.to_string(); }
     let parts: Vec<&str> = s.split('.').collect();
-    if parts.len() < 2 { return HttpResponse::BadRequest().body("version must be like 5.6 or UE_5.6 (patch allowed)"); }
+    if parts.len() < 2 { return err(StatusCode::BAD_REQUEST, "invalid_parameter", "version must be like 5.6 or UE_5.6 (patch allowed)"); }
     let major = parts[0].trim();
     let minor = parts[1].trim();
     if major.is_empty() || minor.is_empty() || !major.chars().all(|c| c.is_ascii_digit()) || !minor.chars().all(|c| c.is_ascii_digit()) {
-        return HttpResponse::BadRequest().body("version must be like 5.6 or UE_5.6 (patch allowed)");
+        return err(StatusCode::BAD_REQUEST, "invalid_parameter", "version must be like 5.6 or UE_5.6 (patch allowed)");
     }
     let mm = format!("{}.{}", major, minor);
 
@@ -862,33 +1471,113 @@ pub async fn set_unreal_project_version(body: web::Json<models::SetProjectEngine
     }
     let uproject = match uproject_path {
         Some(p) => p,
-        None => return HttpResponse::BadRequest().body("Project could not be resolved to a .uproject"),
+        None => return err(StatusCode::BAD_REQUEST, "project_not_found", "Project could not be resolved to a .uproject"),
     };
 
     // Read, modify, write JSON
     let content = match fs::read_to_string(&uproject) {
         Ok(s) => s,
-        Err(e) => return HttpResponse::InternalServerError().body(format!("Failed to read .uproject: {}", e)),
+        Err(e) => return err(StatusCode::INTERNAL_SERVER_ERROR, "io_error", format!("Failed to read .uproject: {}", e)),
     };
     let mut v: serde_json::Value = match serde_json::from_str(&content) {
         Ok(j) => j,
-        Err(e) => return HttpResponse::BadRequest().body(format!(".uproject is not valid JSON: {}", e)),
+        Err(e) => return err(StatusCode::BAD_REQUEST, "invalid_project", format!(".uproject is not valid JSON: {}", e)),
     };
     // Set EngineAssociation to normalized major.minor
     if let Some(obj) = v.as_object_mut() {
         let _ = obj.insert("EngineAssociation".to_string(), serde_json::Value::String(mm.clone()))
             .is_some();
     } else {
-        return HttpResponse::BadRequest().body(".uproject JSON is not an object");
+        return err(StatusCode::BAD_REQUEST, "invalid_project", ".uproject JSON is not an object");
     }
     let pretty = serde_json::to_string_pretty(&v).unwrap_or_else(|_| v.to_string());
     if let Err(e) = fs::write(&uproject, pretty) {
-        return HttpResponse::InternalServerError().body(format!("Failed to write .uproject: {}", e));
+        return err(StatusCode::INTERNAL_SERVER_ERROR, "io_error", format!("Failed to write .uproject: {}", e));
     }
 
     HttpResponse::Ok().json(models::SimpleResponse { ok: true, message: format!("Set EngineAssociation to {}", mm) })
 }
 
+/// Re-reads every project's .uproject and re-resolves its EngineAssociation against the engines installed now.
+///
+/// Route:
+/// - POST /reindex-projects
+///
+/// JSON body (all optional):
+/// - base: projects base directory (defaults to the configured projects directory)
+/// - write_back: when true, a GUID association that now resolves to an installed engine is replaced
+///   by its major.minor version (e.g., "5.6"). Associations that are already versions are never rewritten.
+///
+/// Returns JSON: { base_directory, resolved, unresolved, written, projects: [{ name, uproject_file,
+/// engine_association, engine_version, written, error? }] }
+#[post("/reindex-projects")]
+pub async fn reindex_projects(body: web::Json<models::ReindexProjectsRequest>) -> HttpResponse {
+    let req = body.into_inner();
+    let base_dir = req.base.as_deref()
+        .map(utils::trim_quotes_and_expand_home)
+        .filter(|s| !s.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(utils::default_unreal_projects_dir);
+
+    let mut projects: Vec<models::ReindexedProject> = Vec::new();
+    if let Ok(entries) = fs::read_dir(&base_dir) {
+        for entry in entries.flatten() {
+            let dir = entry.path();
+            if !dir.is_dir() { continue; }
+            let uproject = match fs::read_dir(&dir).ok().and_then(|sub| {
+                sub.flatten().map(|f| f.path()).find(|p| p.is_file() && p.extension().map_or(false, |ext| ext == "uproject"))
+            }) {
+                Some(p) => p,
+                None => continue,
+            };
+            let mut item = models::ReindexedProject {
+                name: dir.file_name().and_then(|s| s.to_str()).unwrap_or("").to_string(),
+                uproject_file: uproject.to_string_lossy().to_string(),
+                engine_association: String::new(),
+                engine_version: String::new(),
+                written: false,
+                error: None,
+            };
+            let mut v: serde_json::Value = match fs::read_to_string(&uproject).map_err(|e| e.to_string())
+                .and_then(|c| serde_json::from_str(&c).map_err(|e| e.to_string())) {
+                Ok(v) => v,
+                Err(e) => {
+                    item.error = Some(format!("Failed to read .uproject: {}", e));
+                    projects.push(item);
+                    continue;
+                }
+            };
+            item.engine_association = v.get("EngineAssociation").and_then(|x| x.as_str()).unwrap_or("").trim().to_string();
+            if let Some(mm) = utils::resolve_engine_association_to_mm(&item.engine_association) {
+                item.engine_version = mm;
+            }
+            let is_guid = !item.engine_version.is_empty() && utils::normalize_engine_association(&item.engine_association).is_none();
+            if req.write_back && is_guid {
+                if let Some(obj) = v.as_object_mut() {
+                    obj.insert("EngineAssociation".to_string(), serde_json::Value::String(item.engine_version.clone()));
+                    let pretty = serde_json::to_string_pretty(&v).unwrap_or_else(|_| v.to_string());
+                    match fs::write(&uproject, pretty) {
+                        Ok(_) => item.written = true,
+                        Err(e) => item.error = Some(format!("Failed to write .uproject: {}", e)),
+                    }
+                }
+            }
+            projects.push(item);
+        }
+    }
+    projects.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+
+    let resolved = projects.iter().filter(|p| !p.engine_version.is_empty()).count();
+    let written = projects.iter().filter(|p| p.written).count();
+    HttpResponse::Ok().json(models::ReindexProjectsResponse {
+        base_directory: base_dir.to_string_lossy().to_string(),
+        resolved,
+        unresolved: projects.len() - resolved,
+        written,
+        projects,
+    })
+}
+
 /// Creates a new Unreal Engine project from a template/sample `.uproject` using UnrealEditor `-CopyProject`.
 ///
 /// Route:
@@ -900,11 +1589,17 @@ pub async fn set_unreal_project_version(body: web::Json<models::SetProjectEngine
 /// - template_project: String — Path to a template/sample `.uproject`, or a directory containing one. Required unless `asset_name` is provided.
 /// - asset_name: Optional<String> — Convenience: name of a downloaded sample under `downloads/` (e.g., "Stack O Bot").
 ///   When provided and `template_project` is empty, the server searches `downloads/<asset_name>/` recursively for a `.uproject` to use as the template.
-/// - output_dir: String — Directory where the new project folder will be created. Required.
-/// - project_name: String — Name of the new project folder to create under `output_dir`. Required.
+/// - output_dir: String — Directory where the new project folder will be created. Required unless saved in /config/create-defaults.
+/// - project_name: String — Name of the new project folder to create under `output_dir`. Required; letters, digits and underscores only, not starting with a digit, at most 20 characters.
 /// - project_type: Optional<String> — "bp" for Blueprint-only (adds -NoCompile to skip compiling C++ targets on open) or "cpp". Default: "bp".
+///   For "cpp", the template's module (Source/<Template>, Target/Build rules, _API macros, .uproject modules) is renamed to project_name.
 /// - open_after_create: Optional<bool> — When true, the server will launch Unreal Editor to open the created project after copying. Default: false.
+/// - no_compile: Optional<bool> — Open with -NoCompile. Default: true for "bp", false for "cpp".
+/// - extra_args: Optional<Vec<String>> — More editor arguments, one per entry, appended when opening.
 /// - dry_run: Optional<bool> — When true, returns the constructed command without executing UnrealEditor. Optional.
+/// - verify_template: Optional<bool> — When true and the template comes from a managed download, re-verify the
+///   download (sizes + hashes recorded at download time) before copying and refuse to create from a corrupt copy
+///   (409 Conflict, suggesting a re-download). Templates outside downloads/ are not checked. Default: false.
 ///
 /// Behavior:
 /// - Locates UnrealEditor under the given engine_path or auto-discovers from the default engines directory.
@@ -913,12 +1608,20 @@ pub async fn set_unreal_project_version(body: web::Json<models::SetProjectEngine
 /// - Copies the template project directory to the new location (excluding Binaries/DerivedDataCache/Intermediate/Saved/etc.).
 /// - Builds an "open" command for UnrealEditor but does not run it unless `open_after_create=true`.
 /// - If `dry_run=true`, returns the command preview without launching the editor.
+/// - With a job_id, /cancel-job is honored in every phase (download, copy, before launch): the job stops,
+///   a project directory created by this request is removed, `cancelled` is emitted and the response is
+///   200 "cancelled". A pre-existing output folder is never deleted.
 /// - Response is returned immediately after project creation (and spawn when applicable), without waiting for Unreal Editor to exit.
 ///
 /// Returns:
-/// - 200 OK with JSON { ok: true, message, command, project_path } on success or dry-run.
+/// - 200 OK with JSON { ok: true, message, command, project_path, failed_files? } on success or dry-run.
+///   Files that could not be copied (e.g. locked or permission denied) don't stop the copy; they are listed in
+///   failed_files as { path, error } and each one is reported as a create:copying event with details { path, error, failed: true }.
 /// - 400 Bad Request if inputs are invalid or UnrealEditor cannot be located.
-/// - 500 Internal Server Error only for copy/creation failures (opening the editor is optional; failures are reported in message with ok=true).
+/// - 500 Internal Server Error only for creation failures, when the .uproject itself could not be copied, or when a
+///   cpp template's module could not be renamed to the project name (JSON with ok=false and failed_files; a project
+///   folder created by this request is removed again and create:error is emitted); opening the editor is optional, so its
+///   failures are reported in message with ok=true.
 ///
 /// Example (dry run):
 /// - Direct template path:
@@ -945,18 +1648,24 @@ pub async fn set_unreal_project_version(body: web::Json<models::SetProjectEngine
 #[post("/create-unreal-project")]
 pub async fn create_unreal_project(body: web::Json<models::CreateUnrealProjectRequest>) -> impl Responder {
     let req = body.into_inner();
+    utils::with_log_job(req.job_id.clone(), create_unreal_project_job(req)).await
+}
+
+/// Body of /create-unreal-project, run inside the job's log context.
+async fn create_unreal_project_job(mut req: models::CreateUnrealProjectRequest) -> impl Responder {
+    utils::create_defaults::apply(&mut req, &utils::load_create_defaults());
     let job_id = req.job_id.clone();
-    println!("¬ create_unreal_project");
-    println!("¬ req: {:?}", req);
-    println!("¬ job_id: {:?}", job_id);
-    println!("¬ asset_name: {:?}", req.asset_name);
+    log::debug!("¬ create_unreal_project");
+    log::debug!("¬ req: {:?}", req);
+    log::debug!("¬ job_id: {:?}", job_id);
+    log::debug!("¬ asset_name: {:?}", req.asset_name);
 
 
     utils::emit_event(job_id.as_deref(), models::Phase::CreateStart, format!("create_unreal_project: Creating project {}", req.project_name), None, None);
 
     // Handle Fab asset download if identifiers are provided
     if let Some(response) = utils::handle_fab_download(&req, &job_id).await {
-        println!("¬ within the await for handle_fab_download");
+        log::debug!("¬ within the await for handle_fab_download");
         return response;
     }
 
@@ -974,9 +1683,7 @@ pub async fn create_unreal_project(body: web::Json<models::CreateUnrealProjectRe
     // Locate editor binary
     let editor_path = match utils::find_editor_binary(&engine_path) {
         Some(p) => p,
-        None => return HttpResponse::BadRequest().body(
-            "Unable to locate Unreal Editor binary under engine_path (tried UE5 'UnrealEditor' and UE4 'UE4Editor')"
-        ),
+        None => return err(StatusCode::NOT_FOUND, "editor_not_found", utils::missing_editor_message(&engine_path)),
     };
 
     // Resolve template .uproject file
@@ -984,12 +1691,46 @@ pub async fn create_unreal_project(body: web::Json<models::CreateUnrealProjectRe
         Ok(path) => path,
         Err(response) => return response,
     };
+    // The template may have been downloaded just now; never carry on to copying after a cancel
+    if utils::check_if_job_is_cancelled(job_id.as_deref()) {
+        return utils::cancel_create(job_id.as_deref(), None);
+    }
+
+    // Optionally refuse to propagate a corrupt download into a new project
+    if req.verify_template.unwrap_or(false) {
+        match utils::managed_download_root(&template_path) {
+            Some(root) => match utils::verify_managed_download(&root, job_id.as_deref()).await {
+                Some(bad) if !bad.is_empty() => {
+                    let msg = format!(
+                        "Template download at {} is corrupt: {} file(s) missing or failing verification (first: {}). Re-download the asset and try again.",
+                        root.to_string_lossy(), bad.len(), bad[0]
+                    );
+                    utils::emit_event(
+                        job_id.as_deref(),
+                        models::Phase::CreateError,
+                        msg.clone(),
+                        None,
+                        Some(serde_json::json!({ "corrupt_files": bad.iter().take(50).collect::<Vec<_>>(), "total_corrupt": bad.len() })),
+                    );
+                    return err(StatusCode::CONFLICT, "conflict", msg);
+                }
+                Some(_) => log::info!("Template download verified: {}", root.to_string_lossy()),
+                None => log::info!("Template download at {} has no recorded file list; skipping verification", root.to_string_lossy()),
+            },
+            None => log::info!("Template is not a managed download; skipping verification"),
+        }
+    }
+
+    if utils::check_if_job_is_cancelled(job_id.as_deref()) {
+        return utils::cancel_create(job_id.as_deref(), None);
+    }
 
     // Setup output directory
-    let (out_dir, new_project_dir) = match utils::setup_output_directory(&req) {
+    let (out_dir, project_dir) = match utils::setup_output_directory(&req) {
         Ok(dirs) => dirs,
         Err(response) => return response,
     };
+    let new_project_dir = project_dir.path().to_path_buf();
 
     let template_dir = template_path.parent().unwrap_or(Path::new(".")).to_path_buf();
 
@@ -998,17 +1739,28 @@ pub async fn create_unreal_project(body: web::Json<models::CreateUnrealProjectRe
         return utils::handle_dry_run(&req, &template_dir, &new_project_dir, &editor_path, &template_path);
     }
 
-    // Copy project files
-    let (copied_files, skipped_files) = match utils::copy_project_files(
+    // Copy project files; a cancel mid-copy removes the partially created project
+    let (copied_files, skipped_files, failed_files) = match utils::copy_project_files(
         &template_dir,
-        &new_project_dir,
+        &project_dir,
         &req.project_name,
-        &template_path,
         &job_id,
     ) {
-        Ok(counts) => counts,
+        Ok((utils::project_copy::CopyOutcome::Completed { copied, skipped, .. }, failed)) => (copied, skipped, failed),
+        Ok((utils::project_copy::CopyOutcome::Cancelled { .. }, _)) => return utils::cancel_create(job_id.as_deref(), Some(&project_dir)),
         Err(response) => return response,
     };
+    // Last chance to cancel before the project is finalized and the editor launched
+    if utils::check_if_job_is_cancelled(job_id.as_deref()) {
+        return utils::cancel_create(job_id.as_deref(), Some(&project_dir));
+    }
+
+    // Update .uproject metadata; a cpp project whose module could not be renamed would not compile,
+    // so the create fails and the copy is removed
+    let target_uproject = utils::finalize_uproject(&new_project_dir, &req, &template_path);
+    if let Err(msg) = utils::rename_cpp_modules(&new_project_dir, &req, &template_path) {
+        return utils::fail_create(job_id.as_deref(), &project_dir, msg);
+    }
 
     utils::emit_event(
         job_id.as_deref(),
@@ -1018,15 +1770,12 @@ pub async fn create_unreal_project(body: web::Json<models::CreateUnrealProjectRe
         None,
     );
 
-    // Update .uproject metadata
-    let target_uproject = utils::finalize_uproject(&new_project_dir, &req, &template_path);
-
     // Build and optionally execute open command
-    let command_preview = utils::build_editor_command(&editor_path, &target_uproject, &req.project_type);
-    // println!("UnrealEditor: {}", editor_path.to_string_lossy());
-    // println!("Open Command: {}", command_preview);
+    let editor_args = utils::build_editor_args(&target_uproject, &req);
+    log::debug!("UnrealEditor: {}", editor_path.to_string_lossy());
+    log::debug!("Open Command: {}", utils::format_command(&editor_path, &editor_args));
 
-    utils::execute_project_open(&req, copied_files, skipped_files, command_preview, &new_project_dir)
+    utils::execute_project_open(&req, copied_files, skipped_files, failed_files, &editor_path, editor_args, &new_project_dir)
 }
 
 
@@ -1036,18 +1785,25 @@ pub async fn create_unreal_project(body: web::Json<models::CreateUnrealProjectRe
 /// - GET /open-unreal-engine
 ///
 /// Query parameters:
-/// - version: Engine version to use (e.g., 5.3 or 5.3.2). Exact match is preferred; prefix match is accepted.
+/// - version: Engine version to use (e.g., 5.3 or 5.3.2). A major.minor version picks the newest installed patch.
+/// - exact: Optional; "true" requires an engine with exactly `version`.
 /// - engine_base: Optional base directory to search for engines (defaults to $HOME/UnrealEngines).
+/// - jobId: Optional; captures editor output and emits `editor:error` (with the output tail) on an early or non-zero exit.
+///   Output goes to `<cache>/editor-logs/<jobId>.log` rather than an in-memory ring buffer, so the editor
+///   never blocks on a pipe; only the newest 20 logs from the last 7 days are kept.
+/// - fix_permissions: Optional; "true" restores the execute bit on an editor binary that lost it (otherwise a launch
+///   failure caused by a missing execute bit is reported with a chmod hint).
 ///
 /// Returns:
 /// - 200 OK with JSON describing the launch when the editor was spawned.
+/// - 404 Not Found with code "editor_not_found" when the matching engine has no editor binary.
 /// - 4xx/5xx with JSON message explaining the error otherwise.
 #[get("/open-unreal-engine")]
 pub async fn open_unreal_engine(query: web::Query<std::collections::HashMap<String, String>>) -> impl Responder {
     let version_param = match query.get("version") {
         Some(v) => v.clone(),
         None => {
-            return HttpResponse::BadRequest().body("Missing required query parameter: version (e.g., 5.3.2 or 5.3)");
+            return err(StatusCode::BAD_REQUEST, "missing_parameter", "Missing required query parameter: version (e.g., 5.3.2 or 5.3)");
         }
     };
     let engine_base = query
@@ -1055,53 +1811,39 @@ pub async fn open_unreal_engine(query: web::Query<std::collections::HashMap<Stri
         .map(|s| PathBuf::from(s))
         .unwrap_or_else(utils::default_unreal_engines_dir);
 
-    println!("Engine Base: {}", engine_base.to_string_lossy());
-    println!("Version: {}", version_param);
+    log::debug!("Engine Base: {}", engine_base.to_string_lossy());
+    log::debug!("Version: {}", version_param);
 
     // Discover engines
-    let mut engines: Vec<models::UnrealEngineInfo> = Vec::new();
-    if engine_base.is_dir() {
-        if let Ok(entries) = fs::read_dir(&engine_base) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.is_dir() {
-                    if path.join("Engine").join("Binaries").is_dir() {
-                        let name = path.file_name().and_then(|s| s.to_str()).unwrap_or("").to_string();
-                        let version = utils::read_build_version(&path)
-                            .or_else(|| utils::parse_version_from_name(&name))
-                            .unwrap_or_else(|| "unknown".to_string());
-                        let editor_path = utils::find_editor_binary(&path).map(|p| p.to_string_lossy().to_string());
-                        engines.push(models::UnrealEngineInfo { name, version, path: path.to_string_lossy().to_string(), editor_path });
-                    }
-                }
-            }
-        }
-    }
+    let engines: Vec<models::UnrealEngineInfo> = utils::discover_engines(&engine_base);
 
     if engines.is_empty() {
-        return HttpResponse::NotFound().body("No Unreal Engine installations found in engine_base");
+        return err(StatusCode::NOT_FOUND, "engine_not_found", "No Unreal Engine installations found in engine_base");
     }
 
-    let chosen = match utils::pick_engine_for_version(&engines, &version_param) {
+    let exact = query.get("exact").is_some_and(|v| v == "1" || v.eq_ignore_ascii_case("true"));
+    let chosen = match utils::pick_engine_for_version(&engines, &version_param, exact) {
         Some(e) => e,
         None => {
-            return HttpResponse::NotFound().body("Requested version not found among discovered engines");
+            return err(StatusCode::NOT_FOUND, "engine_not_found", "Requested version not found among discovered engines");
         }
     };
 
     let editor_path = match &chosen.editor_path {
         Some(p) => PathBuf::from(p),
-        None => return HttpResponse::NotFound().body("Engine found but Editor binary not located"),
+        None => return editor_not_found(chosen),
     };
 
-    println!("Using editor: {}", editor_path.to_string_lossy());
+    log::info!("Using editor: {}", editor_path.to_string_lossy());
 
-    // Spawn the editor without waiting for it to exit (no project argument)
-    let spawn_res = std::process::Command::new(&editor_path).spawn();
-    println!("Spawn Result: {:?}", spawn_res);
+    // Spawn the editor without waiting for it to exit (no project argument); jobId opts into crash capture
+    let job_id = query.get("jobId").cloned().or_else(|| query.get("job_id").cloned());
+    utils::fix_editor_permissions_if_requested(&query, &editor_path);
+    let spawn_res = utils::spawn_editor_watched(std::process::Command::new(&editor_path), job_id.as_deref());
+    log::debug!("Spawn Result: {:?}", spawn_res);
 
     match spawn_res {
-        Ok(_child) => {
+        Ok(()) => {
             let resp = models::OpenEngineResponse {
                 launched: true,
                 engine_name: Some(chosen.name.clone()),
@@ -1117,7 +1859,7 @@ pub async fn open_unreal_engine(query: web::Query<std::collections::HashMap<Stri
                 engine_name: Some(chosen.name.clone()),
                 engine_version: Some(chosen.version.clone()),
                 editor_path: Some(editor_path.to_string_lossy().to_string()),
-                message: format!("Failed to launch editor: {}", e),
+                message: format!("Failed to launch editor: {}", utils::describe_spawn_error(&editor_path, &e)),
             };
             HttpResponse::InternalServerError().json(resp)
         }
@@ -1127,47 +1869,219 @@ pub async fn open_unreal_engine(query: web::Query<std::collections::HashMap<Stri
 
 #[get("/config/paths")]
 pub async fn get_paths_config() -> HttpResponse {
-    let cfg = utils::load_paths_config();
-    let status = models::PathsStatus {
-        configured: cfg.clone(),
+    HttpResponse::Ok().json(paths_status(utils::load_paths_config(), Vec::new()))
+}
+
+/// Configured and effective directories, plus the checks made while saving them.
+fn paths_status(cfg: models::PathsConfig, checks: Vec<models::PathCheck>) -> models::PathsStatus {
+    models::PathsStatus {
+        configured: cfg,
         effective_projects_dir: utils::default_unreal_projects_dir().to_string_lossy().to_string(),
         effective_engines_dir: utils::default_unreal_engines_dir().to_string_lossy().to_string(),
         effective_cache_dir: utils::default_cache_dir().to_string_lossy().to_string(),
         effective_downloads_dir: utils::get_default_downloads_dir_path().to_string_lossy().to_string(),
-    };
-    HttpResponse::Ok().json(status)
+        checks,
+    }
 }
 
 
+/// Updates configured directories. Returns 400 when cache_dir and downloads_dir would coincide or nest.
+///
+/// Posted directories are saved with `~`/`$HOME` expanded (an empty string clears one) and each is
+/// checked: it must be absolute and an existing or creatable directory, and cache_dir/downloads_dir
+/// must be writable. Failed checks come back in `checks` with ok=false; the value is saved regardless.
 #[post("/config/paths")]
 pub async fn set_paths_config(body: web::Json<models::PathsUpdate>) -> HttpResponse {
     let mut cfg = utils::load_paths_config();
     // Merge updates
-    if let Some(p) = &body.projects_dir {
-        cfg.projects_dir = Some(p.trim().to_string());
+    let mut checks: Vec<models::PathCheck> = Vec::new();
+    for (field, posted, slot, needs_write) in [
+        ("projects_dir", &body.projects_dir, &mut cfg.projects_dir, false),
+        ("engines_dir", &body.engines_dir, &mut cfg.engines_dir, false),
+        ("cache_dir", &body.cache_dir, &mut cfg.cache_dir, true),
+        ("downloads_dir", &body.downloads_dir, &mut cfg.downloads_dir, true),
+    ] {
+        let Some(raw) = posted else { continue };
+        let expanded = utils::trim_quotes_and_expand_home(raw);
+        if expanded.is_empty() {
+            *slot = None;
+            continue;
+        }
+        let (ok, message) = match utils::check_configured_dir(Path::new(&expanded), needs_write) {
+            Ok(note) => (true, note),
+            Err(problem) => (false, problem),
+        };
+        checks.push(models::PathCheck { field: field.to_string(), ok, message });
+        *slot = Some(expanded);
     }
-    if let Some(e) = &body.engines_dir {
-        cfg.engines_dir = Some(e.trim().to_string());
+    if let Some(n) = body.file_concurrency {
+        if n != 0 && !utils::FILE_CONCURRENCY_RANGE.contains(&n) {
+            return err(StatusCode::BAD_REQUEST, "invalid_parameter", "file_concurrency must be between 1 and 16 (0 clears it)");
+        }
+        cfg.file_concurrency = Some(n).filter(|&n| n > 0);
     }
-    if let Some(c) = &body.cache_dir {
-        cfg.cache_dir = Some(c.trim().to_string());
+    if let Some(n) = body.chunk_concurrency {
+        if n != 0 && !utils::CHUNK_CONCURRENCY_RANGE.contains(&n) {
+            return err(StatusCode::BAD_REQUEST, "invalid_parameter", "chunk_concurrency must be between 1 and 64 (0 clears it)");
+        }
+        cfg.chunk_concurrency = Some(n).filter(|&n| n > 0);
     }
-    if let Some(d) = &body.downloads_dir {
-        cfg.downloads_dir = Some(d.trim().to_string());
+    if let Some(conflict) = utils::cache_downloads_conflict(&cfg) {
+        return err(StatusCode::BAD_REQUEST, "invalid_parameter", conflict);
     }
     if let Err(e) = utils::save_paths_config(&cfg) {
-        return HttpResponse::InternalServerError().body(format!("Failed to save config: {}", e));
+        return err(StatusCode::INTERNAL_SERVER_ERROR, "io_error", format!("Failed to save config: {}", e));
     }
-    let status = models::PathsStatus {
-        configured: cfg.clone(),
-        effective_projects_dir: utils::default_unreal_projects_dir().to_string_lossy().to_string(),
-        effective_engines_dir: utils::default_unreal_engines_dir().to_string_lossy().to_string(),
-        effective_cache_dir: utils::default_cache_dir().to_string_lossy().to_string(),
-        effective_downloads_dir: utils::get_default_downloads_dir_path().to_string_lossy().to_string(),
+    HttpResponse::Ok().json(paths_status(cfg, checks))
+}
+
+
+/// Returns the saved create-project defaults (fields never saved are omitted).
+///
+/// Route:
+/// - GET /config/create-defaults
+#[get("/config/create-defaults")]
+pub async fn get_create_defaults() -> HttpResponse {
+    HttpResponse::Ok().json(utils::load_create_defaults())
+}
+
+
+/// Replaces the create-project defaults used when /create-unreal-project omits engine_path, output_dir,
+/// project_type or open_after_create. Empty or omitted fields are cleared; `~` and `$HOME` are expanded.
+///
+/// Route:
+/// - POST /config/create-defaults {"output_dir":"~/Unreal Projects","project_type":"cpp","open_after_create":true}
+#[post("/config/create-defaults")]
+pub async fn set_create_defaults(body: web::Json<models::CreateDefaults>) -> HttpResponse {
+    let body = body.into_inner();
+    let path = |v: Option<String>| v.map(|s| utils::trim_quotes_and_expand_home(&s)).filter(|s| !s.is_empty());
+    let project_type = body.project_type.map(|t| t.trim().to_lowercase()).filter(|t| !t.is_empty());
+    if project_type.as_deref().is_some_and(|t| t != "bp" && t != "cpp") {
+        return err(StatusCode::BAD_REQUEST, "invalid_parameter", "project_type must be 'bp' or 'cpp'");
+    }
+    let defaults = models::CreateDefaults {
+        engine_path: path(body.engine_path),
+        output_dir: path(body.output_dir),
+        project_type,
+        open_after_create: body.open_after_create,
+    };
+    if let Err(e) = utils::save_create_defaults(&defaults) {
+        return err(StatusCode::INTERNAL_SERVER_ERROR, "io_error", format!("Failed to save create defaults: {}", e));
+    }
+    HttpResponse::Ok().json(defaults)
+}
+
+
+/// Resets configuration to defaults and returns the resulting paths status.
+///
+/// Route:
+/// - POST /config/reset                                  → delete config.json (every path and tuning value)
+/// - POST /config/reset {"fields":["downloads_dir"]}     → clear just those fields and save the rest
+///
+/// Field names are those of GET /config/paths `configured`; an unknown one is a 400 and nothing is
+/// changed. Asset tags and create defaults live in their own files and are left alone.
+#[post("/config/reset")]
+pub async fn reset_config(body: web::Bytes) -> HttpResponse {
+    let fields = if body.iter().all(u8::is_ascii_whitespace) {
+        Vec::new()
+    } else {
+        match serde_json::from_slice::<models::ConfigResetRequest>(&body) {
+            Ok(req) => req.fields,
+            Err(e) => return err(StatusCode::BAD_REQUEST, "invalid_parameter", format!("Invalid body: {}", e)),
+        }
     };
-    HttpResponse::Ok().json(status)
+
+    if fields.is_empty() {
+        let path = utils::config_file_path();
+        if let Err(e) = utils::config_reset::reset_file(&path) {
+            return err(StatusCode::INTERNAL_SERVER_ERROR, "io_error", format!("Failed to delete {}: {}", path.to_string_lossy(), e));
+        }
+        return HttpResponse::Ok().json(paths_status(utils::load_paths_config(), Vec::new()));
+    }
+
+    let mut cfg = utils::load_paths_config();
+    if let Err(e) = utils::config_reset::clear_fields(&mut cfg, &fields) {
+        return err(StatusCode::BAD_REQUEST, "invalid_parameter", e);
+    }
+    if let Err(e) = utils::save_paths_config(&cfg) {
+        return err(StatusCode::INTERNAL_SERVER_ERROR, "io_error", format!("Failed to save config: {}", e));
+    }
+    HttpResponse::Ok().json(paths_status(cfg, Vec::new()))
 }
 
 
+/// Exports the portable configuration: configured paths and the asset tags store.
+///
+/// Route:
+/// - GET /config/export
+///
+/// Secrets are never included. The token cache location is reported for reference only;
+/// after importing on a new machine, log in again (see /auth/start).
+#[get("/config/export")]
+pub async fn export_config() -> HttpResponse {
+    let bundle = models::ConfigBundle {
+        paths: utils::load_paths_config(),
+        asset_tags: utils::load_asset_tags(),
+        token_cache_path: Some(utils::token_cache_path().to_string_lossy().to_string()),
+        note: Some("Tokens are not exported. Re-authenticate via /auth/start on the new machine.".to_string()),
+    };
+    HttpResponse::Ok().json(bundle)
+}
+
 
+/// Applies a bundle produced by /config/export.
+///
+/// Route:
+/// - POST /config/import
+///
+/// Every configured path must be absolute (after `~`/`$HOME` expansion) and, if it exists, be a directory;
+/// otherwise nothing is applied and 400 (code "invalid_parameter") lists them in `problems`. `token_cache_path` is ignored.
+#[post("/config/import")]
+pub async fn import_config(body: web::Json<models::ConfigBundle>) -> HttpResponse {
+    let bundle = body.into_inner();
+    let mut cfg = bundle.paths.clone();
+    let mut problems: Vec<String> = Vec::new();
+    for (label, slot) in [
+        ("projects_dir", &mut cfg.projects_dir),
+        ("engines_dir", &mut cfg.engines_dir),
+        ("cache_dir", &mut cfg.cache_dir),
+        ("downloads_dir", &mut cfg.downloads_dir),
+    ] {
+        if let Some(raw) = slot.as_ref() {
+            let expanded = utils::trim_quotes_and_expand_home(raw);
+            if expanded.is_empty() {
+                *slot = None;
+                continue;
+            }
+            let p = PathBuf::from(&expanded);
+            if !p.is_absolute() {
+                problems.push(format!("{} must be an absolute path: {}", label, expanded));
+            } else if p.exists() && !p.is_dir() {
+                problems.push(format!("{} exists but is not a directory: {}", label, expanded));
+            }
+            *slot = Some(expanded);
+        }
+    }
+    if let Some(conflict) = utils::cache_downloads_conflict(&cfg) {
+        problems.push(conflict);
+    }
+    if !problems.is_empty() {
+        let message = format!("Config bundle has {} problem(s); nothing was imported", problems.len());
+        return HttpResponse::BadRequest().json(models::ErrorResponse {
+            problems: Some(problems),
+            ..models::ErrorResponse::new("invalid_parameter", message)
+        });
+    }
 
+    if let Err(e) = utils::save_paths_config(&cfg) {
+        return err(StatusCode::INTERNAL_SERVER_ERROR, "io_error", format!("Failed to save config: {}", e));
+    }
+    let _guard = utils::lock_asset_tags();
+    if let Err(e) = utils::save_asset_tags(&bundle.asset_tags) {
+        return err(StatusCode::INTERNAL_SERVER_ERROR, "io_error", format!("Failed to save asset tags: {}", e));
+    }
+    HttpResponse::Ok().json(models::SimpleResponse {
+        ok: true,
+        message: "Configuration imported. Log in again to restore credentials.".to_string(),
+    })
+}
@@ -0,0 +1,66 @@
+//! Local asset organization endpoints.
+//!
+//! - GET /asset-tags returns the tags/favorites store.
+//! - POST /asset-tags updates tags and/or the favorite flag for one asset.
+//!
+//! The store lives next to config.json and is keyed by Fab assetId, so it survives title changes
+//! and library refreshes. Entries are merged into /get-fab-list responses as `userTags`/`favorite`.
+
+use actix_web::{get, post, web, HttpResponse};
+use actix_web::http::StatusCode;
+
+use crate::models;
+use crate::utils;
+
+/// Returns the whole tags store as { "<assetId>": { "tags": [...], "favorite": bool }, ... }.
+///
+/// Example (curl):
+/// - curl -s http://localhost:8080/asset-tags | jq
+#[get("/asset-tags")]
+pub async fn get_asset_tags() -> HttpResponse {
+    HttpResponse::Ok().json(utils::load_asset_tags())
+}
+
+/// Updates tags and/or the favorite flag for a single asset; omitted fields are left unchanged.
+///
+/// Tags are trimmed and de-duplicated. An entry with no tags and favorite=false is removed.
+///
+/// Example (curl):
+///   curl -s -X POST http://127.0.0.1:8080/asset-tags \
+///        -H "Content-Type: application/json" \
+///        -d '{"asset_id":"28b7df0e7f5e4202be89a20d362860c3","tags":["props","industrial"],"favorite":true}' | jq
+#[post("/asset-tags")]
+pub async fn set_asset_tags(body: web::Json<models::AssetTagsUpdate>) -> HttpResponse {
+    let update = body.into_inner();
+    let asset_id = update.asset_id.trim().to_string();
+    if asset_id.is_empty() {
+        return super::err(StatusCode::BAD_REQUEST, "missing_parameter", "asset_id is required");
+    }
+
+    let _guard = utils::lock_asset_tags();
+    let mut store = utils::load_asset_tags();
+    let mut entry = store.get(&asset_id).cloned().unwrap_or_default();
+    if let Some(tags) = update.tags {
+        let mut cleaned: Vec<String> = tags.into_iter().map(|t| t.trim().to_string()).filter(|t| !t.is_empty()).collect();
+        cleaned.sort();
+        cleaned.dedup();
+        entry.tags = cleaned;
+    }
+    if let Some(fav) = update.favorite {
+        entry.favorite = fav;
+    }
+    if entry.tags.is_empty() && !entry.favorite {
+        store.remove(&asset_id);
+    } else {
+        store.insert(asset_id.clone(), entry.clone());
+    }
+
+    if let Err(e) = utils::save_asset_tags(&store) {
+        return super::err(StatusCode::INTERNAL_SERVER_ERROR, "io_error", format!("Failed to save asset tags: {}", e));
+    }
+    HttpResponse::Ok().json(serde_json::json!({
+        "asset_id": asset_id,
+        "tags": entry.tags,
+        "favorite": entry.favorite,
+    }))
+}
@@ -1,13 +1,20 @@
 //! WebSocket and job-control endpoints.
 //!
 //! - /ws for subscribing to job-scoped progress events.
+//! - /job-events for the same events over plain HTTP (SSE or NDJSON), for clients without WebSocket support.
 //! - /cancel-job to request cancellation of a running job.
+//! - /pause-job and /resume-job to hold a download between chunks without losing its temp/ cache.
+//! - /jobs to list known jobs with their last phase and progress.
+//! - /job-status for the state of a single job (running, cancel_requested, cancelled, done, failed).
 
 use actix_web::{get, post, HttpRequest, HttpResponse, web};
+use actix_web::http::StatusCode;
 use actix_web_actors::ws;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+use tokio::sync::broadcast;
 
-use crate::utils::{self, get_sender};
+use crate::utils::{self, SubscribeError};
 
 /// WebSocket endpoint used to stream progress/events to the Flutter UI.
 ///
@@ -17,6 +24,8 @@ use crate::utils::{self, get_sender};
 /// Behavior:
 /// - Subscribes client to a per-job broadcast channel.
 /// - Flushes buffered events for late subscribers, then streams live updates.
+/// - Without a jobId the client joins the "default" channel, which mirrors events from every
+///   job (each payload keeps its own job_id). Set EGS_WS_DEFAULT_FIREHOSE=0 to disable mirroring.
 #[get("/ws")]
 pub async fn websocket_upgrade_endpoint(
     req: HttpRequest,
@@ -27,8 +36,8 @@ pub async fn websocket_upgrade_endpoint(
         .get("jobId")
         .cloned()
         .or_else(|| query.get("job_id").cloned())
-        .unwrap_or_else(|| "default".to_string());
-    println!(
+        .unwrap_or_else(|| utils::DEFAULT_JOB_ID.to_string());
+    log::debug!(
         "[WS] connect: job_id={}, peer={}",
         job_id,
         req
@@ -36,11 +45,133 @@ pub async fn websocket_upgrade_endpoint(
             .map(|a| a.to_string())
             .unwrap_or_else(|| "unknown".into())
     );
-    let rx = get_sender(&job_id).subscribe();
-    let resp = ws::start(utils::WsSession { rx, job_id }, &req, stream);
+    // Jobs are usually subscribed before they start, so unknown ids are accepted here
+    let (replay, rx) = utils::subscribe_job(&job_id);
+    let resp = ws::start(utils::WsSession { replay, rx, job_id }, &req, stream);
     resp
 }
 
+/// Wire format of GET /job-events.
+#[derive(Clone, Copy, PartialEq)]
+enum EventStreamFormat {
+    /// text/event-stream: each event as a `data:` line.
+    Sse,
+    /// application/x-ndjson: one JSON event per line.
+    Ndjson,
+}
+
+impl EventStreamFormat {
+    fn content_type(self) -> &'static str {
+        match self {
+            EventStreamFormat::Sse => "text/event-stream",
+            EventStreamFormat::Ndjson => "application/x-ndjson",
+        }
+    }
+
+    fn frame(self, event_json: &str) -> web::Bytes {
+        match self {
+            EventStreamFormat::Sse => web::Bytes::from(format!("data: {}\n\n", event_json)),
+            EventStreamFormat::Ndjson => web::Bytes::from(format!("{}\n", event_json)),
+        }
+    }
+}
+
+/// Idle time after which an SSE comment is sent so proxies keep the connection open.
+const SSE_KEEPALIVE: Duration = Duration::from_secs(15);
+
+/// Logs when the client goes away; actix drops the stream (and this) on disconnect.
+struct EventStreamGuard(String);
+
+impl Drop for EventStreamGuard {
+    fn drop(&mut self) {
+        log::debug!("[events] stream closed for job {}", self.0);
+    }
+}
+
+/// True when a serialized ProgressEvent carries a terminal phase (complete, error, cancelled).
+fn is_terminal_event(event_json: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(event_json).ok()
+        .and_then(|v| v.get("phase").cloned())
+        .and_then(|p| serde_json::from_value::<crate::models::Phase>(p).ok())
+        .map_or(false, |p| p.is_terminal())
+}
+
+/// Streams a job's events over plain HTTP, for clients and proxies that handle WebSockets poorly.
+///
+/// Route:
+/// - GET /job-events?jobId=...&format=sse|ndjson
+///
+/// Replays the buffered events (like /ws), then streams live ones from the same broadcast channel; each
+/// event is sent once. `format` defaults to SSE unless the Accept header asks for application/x-ndjson.
+/// The response ends after the job's terminal event; without a jobId it follows the "default" channel
+/// and never ends. A client disconnect only drops the subscription; it never cancels the job.
+///
+/// Unlike /ws, the job must already exist: 404 job_not_found for a job that has not emitted any event
+/// yet, and 410 job_finished for one that finished longer ago than the replay grace window
+/// (EGS_WS_TERMINAL_GRACE_SECS); use /job-status for its outcome.
+///
+/// Example: curl -N "http://127.0.0.1:8080/job-events?jobId=abc&format=ndjson"
+#[get("/job-events")]
+pub async fn job_events(req: HttpRequest, query: web::Query<HashMap<String, String>>) -> HttpResponse {
+    let job_id = query
+        .get("jobId")
+        .cloned()
+        .or_else(|| query.get("job_id").cloned())
+        .unwrap_or_else(|| utils::DEFAULT_JOB_ID.to_string());
+    let format = match query.get("format").map(|f| f.trim().to_ascii_lowercase()) {
+        Some(f) if f == "sse" => EventStreamFormat::Sse,
+        Some(f) if f == "ndjson" => EventStreamFormat::Ndjson,
+        Some(other) => return super::err(StatusCode::BAD_REQUEST, "invalid_parameter", format!("unsupported format '{}': use sse or ndjson", other)),
+        None => {
+            let accept = req.headers().get("accept").and_then(|v| v.to_str().ok()).unwrap_or("");
+            if accept.contains("application/x-ndjson") { EventStreamFormat::Ndjson } else { EventStreamFormat::Sse }
+        }
+    };
+    log::debug!("[events] connect: job_id={}, format={}", job_id, format.content_type());
+
+    let (replay, rx) = match utils::subscribe_existing_job(&job_id) {
+        Ok(sub) => sub,
+        Err(SubscribeError::UnknownJob) => {
+            return super::err(StatusCode::NOT_FOUND, "job_not_found", format!("unknown job {}", job_id));
+        }
+        Err(SubscribeError::Finished(state)) => {
+            let state = serde_json::to_value(state).ok().and_then(|v| v.as_str().map(str::to_string)).unwrap_or_default();
+            return super::err(StatusCode::GONE, "job_finished", format!("job {} already finished ({}); see /job-status", job_id, state));
+        }
+    };
+    let replay: VecDeque<String> = replay.into();
+    let ends_on_terminal = job_id != utils::DEFAULT_JOB_ID;
+    let guard = EventStreamGuard(job_id);
+
+    let stream = futures_util::stream::unfold((replay, rx, guard, false), move |(mut replay, mut rx, guard, finished)| async move {
+        if finished {
+            return None;
+        }
+        let text = match replay.pop_front() {
+            Some(t) => t,
+            None => loop {
+                match tokio::time::timeout(SSE_KEEPALIVE, rx.recv()).await {
+                    Ok(Ok(t)) => break t,
+                    Ok(Err(broadcast::error::RecvError::Lagged(_))) => continue,
+                    Ok(Err(broadcast::error::RecvError::Closed)) => return None,
+                    Err(_) if format == EventStreamFormat::Sse => {
+                        let ping = web::Bytes::from_static(b": keepalive\n\n");
+                        return Some((Ok::<_, actix_web::Error>(ping), (replay, rx, guard, false)));
+                    }
+                    Err(_) => continue,
+                }
+            },
+        };
+        let finished = ends_on_terminal && is_terminal_event(&text);
+        Some((Ok(format.frame(&text)), (replay, rx, guard, finished)))
+    });
+
+    HttpResponse::Ok()
+        .content_type(format.content_type())
+        .insert_header(("Cache-Control", "no-cache"))
+        .streaming(stream)
+}
+
 /// Request cancellation of a background job. Emits a final Cancelled event.
 #[post("/cancel-job")]
 pub async fn cancel_background_job_endpoint(query: web::Query<HashMap<String, String>>) -> HttpResponse {
@@ -56,5 +187,101 @@ pub async fn cancel_background_job_endpoint(query: web::Query<HashMap<String, St
         );
         return HttpResponse::Ok().json(serde_json::json!({"ok": true, "message": "cancelled"}));
     }
-    HttpResponse::BadRequest().body("missing jobId")
+    super::err(StatusCode::BAD_REQUEST, "missing_parameter", "missing jobId")
+}
+
+/// 404 response when `job_id` is unknown or already finished, so pause/resume cannot target it.
+fn require_running_job(job_id: &str) -> Result<(), HttpResponse> {
+    match utils::job_status(job_id) {
+        None => Err(super::err(StatusCode::NOT_FOUND, "job_not_found", format!("unknown job {}", job_id))),
+        Some(summary) if summary.state.is_finished() => {
+            Err(super::err(StatusCode::NOT_FOUND, "job_not_found", format!("job {} already finished", job_id)))
+        }
+        Some(_) => Ok(()),
+    }
+}
+
+/// Pause a download job. Chunks already in flight finish; no new chunk starts until /resume-job.
+///
+/// Route:
+/// - POST /pause-job?jobId=...
+///
+/// Unlike /cancel-job, the temp/ chunk cache is left untouched so resuming continues where it stopped.
+/// Emits a download:paused event. Returns JSON { ok, message }; ok is false when the job was already paused.
+/// 404 job_not_found for unknown or finished jobs.
+#[post("/pause-job")]
+pub async fn pause_job(query: web::Query<HashMap<String, String>>) -> HttpResponse {
+    let Some(job_id) = query.get("jobId").cloned().or_else(|| query.get("job_id").cloned()) else {
+        return super::err(StatusCode::BAD_REQUEST, "missing_parameter", "missing jobId");
+    };
+    if let Err(resp) = require_running_job(&job_id) {
+        return resp;
+    }
+    if !utils::pause_job(&job_id) {
+        return HttpResponse::Ok().json(serde_json::json!({"ok": false, "message": "already paused"}));
+    }
+    utils::emit_event(Some(&job_id), crate::models::Phase::DownloadPaused, "Download paused", None, None);
+    HttpResponse::Ok().json(serde_json::json!({"ok": true, "message": "paused"}))
+}
+
+/// Resume a job paused with /pause-job.
+///
+/// Route:
+/// - POST /resume-job?jobId=...
+///
+/// Returns JSON { ok, message }; ok is false when the job was not paused. 404 job_not_found for unknown
+/// or finished jobs.
+#[post("/resume-job")]
+pub async fn resume_job(query: web::Query<HashMap<String, String>>) -> HttpResponse {
+    let Some(job_id) = query.get("jobId").cloned().or_else(|| query.get("job_id").cloned()) else {
+        return super::err(StatusCode::BAD_REQUEST, "missing_parameter", "missing jobId");
+    };
+    if let Err(resp) = require_running_job(&job_id) {
+        return resp;
+    }
+    if !utils::resume_job(&job_id) {
+        return HttpResponse::Ok().json(serde_json::json!({"ok": false, "message": "not paused"}));
+    }
+    utils::emit_event(Some(&job_id), crate::models::Phase::DownloadProgress, "Download resumed", None, None);
+    HttpResponse::Ok().json(serde_json::json!({"ok": true, "message": "resumed"}))
+}
+
+/// Lists jobs seen since startup with their last phase, progress and event time.
+///
+/// Route:
+/// - GET /jobs
+///
+/// Lets the UI rediscover running jobs after a reload and then resubscribe via /ws?jobId=...
+/// Returns JSON: { jobs: [{ job_id, state, phase, progress, last_event_ms }] }, most recently active first.
+#[get("/jobs")]
+pub async fn list_jobs() -> HttpResponse {
+    HttpResponse::Ok().json(serde_json::json!({ "jobs": utils::list_job_states() }))
+}
+
+/// Reports the state of one job so polling clients can tell "Cancelling…" from "Cancelled".
+///
+/// Route:
+/// - GET /job-status?jobId=<id>
+///
+/// `state` is one of:
+/// - running: no terminal event yet
+/// - paused: /pause-job was called and the download is waiting for /resume-job
+/// - cancel_requested: /cancel-job was called and the job has not stopped yet
+/// - cancelled: the job acknowledged the cancel and stopped
+/// - done / failed: the job finished with a complete / error event
+///
+/// `last_event` is the most recent ProgressEvent, retained even after the replay buffer was drained,
+/// so a client reconnecting late can still tell whether a download already finished.
+///
+/// Returns JSON { job_id, state, phase, progress, last_event_ms, last_event }; 404 for unknown jobs.
+#[get("/job-status")]
+pub async fn job_status(query: web::Query<HashMap<String, String>>) -> HttpResponse {
+    let job_id = match query.get("jobId").or_else(|| query.get("job_id")) {
+        Some(j) => j.clone(),
+        None => return super::err(StatusCode::BAD_REQUEST, "missing_parameter", "missing jobId"),
+    };
+    match utils::job_status(&job_id) {
+        Some(summary) => HttpResponse::Ok().json(summary),
+        None => super::err(StatusCode::NOT_FOUND, "job_not_found", format!("unknown job {}", job_id)),
+    }
 }
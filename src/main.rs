@@ -15,7 +15,19 @@
 //! Environment and logs:
 //! - Uses env_logger. To increase verbosity, run:
 //!   RUST_LOG=info cargo run
+//!   or change it while running: POST /log-level {"level":"debug","target":"download"}
+//! - For log pipelines, pass --json-logs (or set EGS_LOG_FORMAT=json) to emit one JSON object per line,
+//!   tagged with job_id while a download/create/import job runs.
 //! - The server binds to 127.0.0.1:8080 by default. Override with env vars: BIND_ADDR or PORT.
+//!   A port in use is retried EGS_BIND_MAX_RETRIES times (default 5), EGS_BIND_RETRY_DELAY_MS apart
+//!   (default 2000), then the process exits with status 1 unless EGS_AUTO_PORT=1 (the default in Both
+//!   and Browser modes) picks the next free port.
+//! - Run mode: --mode=backend|frontend|both|browser (or the same word as the first argument, or EGS_MODE).
+//!   Browser starts the backend and opens the default browser on it; pair it with EGS_STATIC_DIR for the web UI.
+//! - Ctrl+C pauses running downloads and waits up to EGS_SHUTDOWN_TIMEOUT_SECS (default 30) for chunks in
+//!   flight before stopping; they show up under /incomplete-downloads on the next launch. Press twice to force.
+//! - Set EGS_STATIC_DIR to a Flutter web build (e.g. Flutter_EGL/build/web) to serve the UI from the
+//!   same origin as the API. API routes and /ws are registered first and always take precedence.
 //!
 //! Minimal architecture diagram:
 //!   main.rs (this file) -> constructs Actix App -> registers api services -> runs HttpServer
@@ -47,11 +59,14 @@ enum RunMode {
     Backend,
     Frontend,
     Both,
+    /// Backend plus the system's default browser pointed at it (headless machines, or no desktop UI build).
+    Browser,
 }
 
 fn parse_mode() -> RunMode {
     // Priority: CLI arg --mode=..., then positional arg, then env EGS_MODE,
-    // else auto-detect: if a Flutter binary is present, default to Both; otherwise Backend
+    // else auto-detect: if a Flutter binary is present, default to Both; otherwise Backend.
+    // Browser is never auto-detected; ask for it with --mode=browser, `browser` or EGS_MODE=browser.
     let mut mode_str: Option<String> = None;
     let args: Vec<String> = env::args().collect();
     for a in &args {
@@ -64,7 +79,7 @@ fn parse_mode() -> RunMode {
         if args.len() > 1 {
             // Allow: `egs_client both` as a shorthand
             let p = args[1].to_lowercase();
-            if ["backend", "frontend", "both"].contains(&p.as_str()) {
+            if ["backend", "frontend", "both", "browser"].contains(&p.as_str()) {
                 mode_str = Some(p);
             }
         }
@@ -75,9 +90,10 @@ fn parse_mode() -> RunMode {
         }
     }
     if let Some(s) = mode_str.as_deref() {
-        return match s {
+        return match s.trim().to_lowercase().as_str() {
             "frontend" => RunMode::Frontend,
             "both" => RunMode::Both,
+            "browser" => RunMode::Browser,
             _ => RunMode::Backend,
         };
     }
@@ -89,6 +105,16 @@ fn parse_mode() -> RunMode {
     }
 }
 
+/// Open the system's default browser on the server root: the web UI when EGS_STATIC_DIR is set,
+/// otherwise the API welcome page. Failing to open one is not fatal; the URL is printed instead.
+fn open_browser(bind_addr: &str) {
+    let url = format!("http://{}/", utils::bind_retry::client_reachable_addr(bind_addr));
+    log::info!("Opening {} in the default browser", url);
+    if let Err(e) = webbrowser::open(&url) {
+        log::warn!("Could not open a browser ({}); visit {} manually.", e, url);
+    }
+}
+
 fn resolve_flutter_binary() -> Option<PathBuf> {
     // Determine the directory of the running executable for exe-relative resolution
     let exe_dir = match env::current_exe() {
@@ -114,10 +140,10 @@ fn resolve_flutter_binary() -> Option<PathBuf> {
     if let Ok(p) = env::var("FLUTTER_APP_PATH") {
         let pb = PathBuf::from(p);
         if let Some(found) = try_paths(&exe_dir, &pb) {
-            println!("Flutter binary: using FLUTTER_APP_PATH override: {}", found.display());
+            log::info!("Flutter binary: using FLUTTER_APP_PATH override: {}", found.display());
             return Some(found);
         } else {
-            eprintln!(
+            log::warn!(
                 "FLUTTER_APP_PATH is set but path does not exist (checked absolute and exe-relative): {}",
                 pb.display()
             );
@@ -127,7 +153,7 @@ fn resolve_flutter_binary() -> Option<PathBuf> {
     // Next: build-mode specific constant paths defined at the top of this file.
     // If compiled in debug (dev) mode, prefer the dev path; otherwise prefer the prod path.
     let debug_build = cfg!(debug_assertions);
-    println!(
+    log::info!(
         "Rust build mode detected: {} (path preference: {} first)",
         if debug_build { "debug" } else { "release" },
         if debug_build { "DEV_FLUTTER_APP_PATH" } else { "PROD_FLUTTER_APP_PATH" }
@@ -140,10 +166,10 @@ fn resolve_flutter_binary() -> Option<PathBuf> {
     for c in mode_pref {
         let p = Path::new(c);
         if let Some(found) = try_paths(&exe_dir, p) {
-            println!("Flutter binary: selected {} (exists)", found.display());
+            log::info!("Flutter binary: selected {} (exists)", found.display());
             return Some(found);
         } else {
-            println!("Flutter binary candidate not found: {} (tested as-is and exe-relative)", p.display());
+            log::debug!("Flutter binary candidate not found: {} (tested as-is and exe-relative)", p.display());
         }
     }
 
@@ -160,17 +186,64 @@ fn resolve_flutter_binary() -> Option<PathBuf> {
         for c in candidates {
             let p = Path::new(c);
             if let Some(found) = try_paths(&exe_dir, p) {
-                println!("Flutter binary: selected fallback candidate: {}", found.display());
+                log::info!("Flutter binary: selected fallback candidate: {}", found.display());
                 return Some(found);
             } else {
-                println!("Flutter binary fallback candidate not found: {} (tested as-is and exe-relative)", p.display());
+                log::debug!("Flutter binary fallback candidate not found: {} (tested as-is and exe-relative)", p.display());
             }
         }
     }
-    println!("Flutter binary not found via env, configured paths, or fallbacks.");
+    log::warn!("Flutter binary not found via env, configured paths, or fallbacks.");
     None
 }
 
+/// Best-effort check that an executable matches the host architecture by reading its ELF/Mach-O header.
+///
+/// Returns Err with a human-readable explanation on a definite mismatch; unknown formats pass.
+fn check_binary_arch(path: &Path) -> Result<(), String> {
+    use std::io::Read;
+    let mut header = [0u8; 20];
+    let mut f = match std::fs::File::open(path) {
+        Ok(f) => f,
+        Err(_) => return Ok(()),
+    };
+    if f.read_exact(&mut header).is_err() {
+        return Ok(());
+    }
+    let host = env::consts::ARCH;
+    let binary_arch: Option<&str> = if header[0..4] == [0x7f, b'E', b'L', b'F'] {
+        // e_machine at offset 18, endianness from EI_DATA (1 = little, 2 = big)
+        let machine = if header[5] == 2 {
+            u16::from_be_bytes([header[18], header[19]])
+        } else {
+            u16::from_le_bytes([header[18], header[19]])
+        };
+        match machine {
+            3 => Some("x86"),
+            40 => Some("arm"),
+            62 => Some("x86_64"),
+            183 => Some("aarch64"),
+            _ => None,
+        }
+    } else if header[0..4] == [0xcf, 0xfa, 0xed, 0xfe] {
+        // 64-bit Mach-O (little endian); universal binaries are not checked
+        match u32::from_le_bytes([header[4], header[5], header[6], header[7]]) {
+            0x0100_0007 => Some("x86_64"),
+            0x0100_000c => Some("aarch64"),
+            _ => None,
+        }
+    } else {
+        None
+    };
+    match binary_arch {
+        Some(arch) if arch != host => Err(format!(
+            "Flutter UI binary {} is built for {} but this machine is {}. Rebuild the UI on this host (`just flutter-build-linux`) or point FLUTTER_APP_PATH at a {} bundle.",
+            path.display(), arch, host, host
+        )),
+        _ => Ok(()),
+    }
+}
+
 fn spawn_flutter(ui_path: &Path, bind_addr: &str) -> std::io::Result<Child> {
     // Canonicalize to avoid issues with relative paths and ensure parent dir is valid
     let path = match std::fs::canonicalize(ui_path) {
@@ -180,6 +253,11 @@ fn spawn_flutter(ui_path: &Path, bind_addr: &str) -> std::io::Result<Child> {
     let parent = path.parent().unwrap_or(Path::new("."));
     let program_name = path.file_name().unwrap_or_default();
 
+    // Catch cross-architecture bundles (e.g., x64 bundle on arm64) before exec fails opaquely
+    if let Err(msg) = check_binary_arch(&path) {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, msg));
+    }
+
     // On Unix, ensure the binary is executable (some VCS or copy ops may strip +x)
     #[cfg(unix)]
     {
@@ -205,29 +283,153 @@ fn spawn_flutter(ui_path: &Path, bind_addr: &str) -> std::io::Result<Child> {
     };
     cmd.current_dir(parent);
 
-    // If the Flutter app adds support for overriding API base, pass it here.
-    cmd.env("EGS_BASE_URL", format!("http://{}", bind_addr))
+    // If the Flutter app adds support for overriding API base, pass it here. A wildcard bind
+    // (0.0.0.0, e.g. from PORT) is not an address the UI can connect to, so it gets loopback.
+    cmd.env("EGS_BASE_URL", format!("http://{}", utils::bind_retry::client_reachable_addr(bind_addr)))
         .stdin(Stdio::null())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit());
-    cmd.spawn()
+    cmd.spawn().map_err(|e| {
+        // ENOEXEC (8): "Exec format error" — wrong architecture or not an executable for this OS
+        if e.raw_os_error() == Some(8) {
+            std::io::Error::new(
+                e.kind(),
+                format!(
+                    "Flutter UI binary {} cannot be executed on this host ({}): exec format error. It was likely built for a different architecture; rebuild it here or set FLUTTER_APP_PATH.",
+                    path.display(),
+                    env::consts::ARCH
+                ),
+            )
+        } else {
+            e
+        }
+    })
+}
+
+/// Directory to serve as static files (EGS_STATIC_DIR), typically a Flutter web build.
+///
+/// Returns None when unset or not a directory. The static UI carries no auth of its own, so when
+/// the server binds beyond loopback anyone who can reach the port gets the full API through it.
+fn resolve_static_dir(bind_addr: &str) -> Option<PathBuf> {
+    let raw = env::var("EGS_STATIC_DIR").ok().filter(|s| !s.trim().is_empty())?;
+    let dir = PathBuf::from(raw.trim());
+    if !dir.is_dir() {
+        log::warn!("EGS_STATIC_DIR is not a directory, static serving disabled: {}", dir.display());
+        return None;
+    }
+    if !dir.join("index.html").is_file() {
+        log::warn!("EGS_STATIC_DIR has no index.html: {}", dir.display());
+    }
+    let loopback = bind_addr.parse::<std::net::SocketAddr>().map(|a| a.ip().is_loopback()).unwrap_or(false);
+    if !loopback {
+        log::warn!("Serving static UI on non-loopback address {}; the API is unauthenticated and reachable by anyone on the network", bind_addr);
+    }
+    log::info!("Serving static files from {}", dir.display());
+    Some(dir)
+}
+
+/// Whether structured JSON log lines were requested via `--json-logs`, EGS_LOG_FORMAT=json or EGS_JSON_LOGS=1.
+fn json_logs_requested() -> bool {
+    if env::args().any(|a| a == "--json-logs") {
+        return true;
+    }
+    if env::var("EGS_LOG_FORMAT").is_ok_and(|v| v.trim().eq_ignore_ascii_case("json")) {
+        return true;
+    }
+    matches!(env::var("EGS_JSON_LOGS").as_deref(), Ok("1") | Ok("true"))
+}
+
+/// env_logger formatting behind the runtime filter in utils (POST /log-level), which replaces
+/// env_logger's own startup-only filter.
+struct RuntimeFilteredLogger {
+    inner: env_logger::Logger,
+}
+
+impl log::Log for RuntimeFilteredLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        utils::log_enabled(metadata.target(), metadata.level())
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            log::Log::log(&self.inner, record);
+        }
+    }
+
+    fn flush(&self) {
+        log::Log::flush(&self.inner);
+    }
+}
+
+/// Initialize logging (honors RUST_LOG; adjustable at runtime via POST /log-level). Human-readable by
+/// default; one JSON object per line ({"ts","level","target","msg","job_id"}) when JSON logs are requested.
+/// job_id is set for lines logged while a download, create or import job runs (utils::with_log_job).
+/// Messages are passed through utils::redact_secrets so tokens never reach a log line.
+fn init_logging() {
+    use std::io::Write;
+    // Let everything through env_logger; RuntimeFilteredLogger decides what is written
+    let mut builder = env_logger::Builder::new();
+    builder.filter_level(log::LevelFilter::Trace);
+    if json_logs_requested() {
+        builder.format(|buf, record| {
+            let mut line = serde_json::json!({
+                "ts": buf.timestamp().to_string(),
+                "level": record.level().to_string(),
+                "target": record.target(),
+                "msg": utils::redact_secrets(&record.args().to_string()),
+            });
+            if let Some(job_id) = utils::current_log_job() {
+                line["job_id"] = serde_json::Value::String(job_id);
+            }
+            writeln!(buf, "{}", line)
+        });
+    } else {
+        builder.format(|buf, record| {
+            let job = utils::current_log_job().map(|j| format!(" job={}", j)).unwrap_or_default();
+            writeln!(buf, "[{} {} {}{}] {}", buf.timestamp(), record.level(), record.target(), job, utils::redact_secrets(&record.args().to_string()))
+        });
+    }
+    let logger = RuntimeFilteredLogger { inner: builder.build() };
+    if log::set_boxed_logger(Box::new(logger)).is_ok() {
+        log::set_max_level(utils::log_max_level());
+    }
+}
+
+/// Pause running downloads and wait (up to EGS_SHUTDOWN_TIMEOUT_SECS) for chunks in flight, so temp/
+/// only holds whole chunks and the downloads resume cleanly on the next launch.
+async fn drain_downloads() {
+    let paused = utils::pause_jobs_for_shutdown();
+    let in_flight = utils::in_flight_chunk_count();
+    if paused.is_empty() && in_flight == 0 {
+        return;
+    }
+    let timeout = utils::shutdown_drain_timeout();
+    log::warn!("Paused {} download(s); waiting up to {}s for {} chunk(s) in flight", paused.len(), timeout.as_secs(), in_flight);
+    let drained = tokio::task::spawn_blocking(move || utils::wait_for_in_flight_chunks(timeout)).await.unwrap_or(false);
+    if !drained {
+        log::warn!("Shutdown timeout reached with {} chunk(s) still in flight; they will be re-downloaded on resume", utils::in_flight_chunk_count());
+    }
 }
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    // Initialize env_logger to honor RUST_LOG levels (e.g., RUST_LOG=info)
-    env_logger::init();
+    // Initialize env_logger to honor RUST_LOG levels (e.g., RUST_LOG=info); --json-logs switches to JSON lines
+    init_logging();
 
     // Explicitly log Rust build mode early for visibility
-    println!("Rust build mode: {}", if cfg!(debug_assertions) { "debug" } else { "release" });
+    log::info!("Rust build mode: {}", if cfg!(debug_assertions) { "debug" } else { "release" });
 
     let mode = parse_mode();
 
+    if let Some(conflict) = crate::utils::cache_downloads_conflict(&crate::utils::load_paths_config()) {
+        log::warn!("{}", conflict);
+    }
+
     // Ensure runtime directories exist (non-fatal if they cannot be created)
-    for dir in [api::DEFAULT_CACHE_DIR_NAME, api::DEFAULT_DOWNLOADS_DIR_NAME] {
-        // Create cache and downloads directories locally in project folder
-        if let Err(e) = std::fs::create_dir_all(dir) {
-            eprintln!("Warning: failed to create directory '{}': {}", dir, e);
+    for dir in [crate::utils::default_cache_dir(), crate::utils::get_default_downloads_dir_path()] {
+        // Configured directories, else project-local (debug) or per-user (release) defaults
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            log::warn!("Failed to create directory '{}': {}", dir.display(), e);
         }
     }
 
@@ -243,18 +445,18 @@ async fn main() -> std::io::Result<()> {
     // Frontend-only mode: run the Flutter UI without starting backend (assumes external backend)
     if mode == RunMode::Frontend {
         if let Some(ui_bin) = resolve_flutter_binary() {
-            println!("Launching Flutter UI: {}", ui_bin.display());
+            log::info!("Launching Flutter UI: {}", ui_bin.display());
             let mut child = spawn_flutter(&ui_bin, &bind_addr)?;
             let status = child.wait().expect("failed waiting for Flutter UI");
-            println!("Flutter UI exited with status: {}", status);
+            log::info!("Flutter UI exited with status: {}", status);
             return Ok(());
         } else {
-            eprintln!("Flutter UI binary not found. Build it first (see justfile tasks) or set FLUTTER_APP_PATH.");
+            log::error!("Flutter UI binary not found. Build it first (see justfile tasks) or set FLUTTER_APP_PATH.");
             std::process::exit(2);
         }
     }
 
-    println!("Starting egs_client HTTP server on {} (mode: {:?})", bind_addr, mode);
+    log::info!("Starting egs_client HTTP server on {} (mode: {:?})", bind_addr, mode);
 
     // In BOTH mode, enable shutdown on WS close (frontend lifecycle drives backend)
     if mode == RunMode::Both {
@@ -268,36 +470,88 @@ async fn main() -> std::io::Result<()> {
     // Shared child handle for Ctrl+C handling when in BOTH mode
     let flutter_child: Arc<Mutex<Option<Child>>> = Arc::new(Mutex::new(None));
 
-    // Create a listener. If the requested address is unavailable, fall back to a dynamic free port.
-    let listener = match std::net::TcpListener::bind(&bind_addr) {
-        Ok(l) => l,
-        Err(e) => {
-            eprintln!("Failed to bind to {}: {} — falling back to dynamic port (127.0.0.1:0)", bind_addr, e);
-            std::net::TcpListener::bind("127.0.0.1:0").expect("Unable to bind to any port on 127.0.0.1")
+    // Create a listener, retrying a taken port a few times. Auto-port is on by default in Both and
+    // Browser modes, where the UI is started with whatever address was actually bound.
+    let bind_settings = utils::bind_retry::BindSettings::from_env(&|k| env::var(k).ok(), matches!(mode, RunMode::Both | RunMode::Browser));
+    let listener = match utils::bind_retry::bind_with_retry(&bind_addr, &bind_settings, std::net::TcpListener::bind, std::thread::sleep) {
+        Ok((l, addr)) => {
+            if addr != bind_addr {
+                log::warn!("{} is in use; bound {} instead (EGS_AUTO_PORT)", bind_addr, addr);
+            }
+            l
+        }
+        Err(msg) => {
+            log::error!("{}", msg);
+            std::process::exit(1);
         }
     };
     let actual_addr = listener.local_addr().map(|a| a.to_string()).unwrap_or(bind_addr.clone());
-    println!("HTTP server will listen on {}", actual_addr);
+    log::info!("HTTP server will listen on {}", actual_addr);
 
-    let server = HttpServer::new(|| {
+    // Optional single-origin hosting of the Flutter web build
+    let static_dir = resolve_static_dir(&actual_addr);
+
+    let server = HttpServer::new(move || {
+        let static_dir = static_dir.clone();
         App::new()
             // Public HTTP endpoints
+            .service(api::health)
+            .service(api::metrics)
             .service(api::get_fab_list)
             .service(api::refresh_fab_list)
+            .service(api::reannotate)
+            .service(api::search_fab_list)
             .service(api::download_asset)
             .service(api::list_unreal_projects)
             .service(api::list_unreal_engines)
             .service(api::open_unreal_project)
+            .service(api::delete_unreal_project)
             .service(api::open_unreal_engine)
             .service(api::import_asset)
+            .service(api::import_assets)
             .service(api::create_unreal_project)
             .service(api::websocket_upgrade_endpoint)
+            .service(api::job_events)
+            .service(api::cancel_background_job_endpoint)
+            .service(api::pause_job)
+            .service(api::resume_job)
+            .service(api::list_jobs)
+            .service(api::job_status)
             .service(api::get_paths_config)
             .service(api::set_paths_config)
+            .service(api::reset_config)
+            .service(api::get_create_defaults)
+            .service(api::set_create_defaults)
+            .service(api::export_config)
+            .service(api::import_config)
             .service(api::auth_start)
             .service(api::auth_complete)
+            .service(api::auth_logout)
+            .service(api::auth_status)
             .service(api::get_version)
+            .service(api::get_overview)
+            .service(api::get_library_ue_versions)
+            .service(api::list_categories)
             .service(api::set_unreal_project_version)
+            .service(api::reindex_projects)
+            .service(api::list_incomplete_downloads)
+            .service(api::resume_incomplete_downloads)
+            .service(api::discard_download)
+            .service(api::delete_downloaded_asset)
+            .service(api::list_skipped_files)
+            .service(api::get_asset_tags)
+            .service(api::set_asset_tags)
+            .service(api::get_log_level)
+            .service(api::set_log_level)
+            // Static files last so every API route (and /ws) wins over a same-named file
+            .configure(move |cfg| {
+                if let Some(dir) = static_dir {
+                    cfg.service(actix_files::Files::new("/", dir).index_file("index.html"));
+                } else {
+                    // Without a web build, "/" still answers so Browser mode doesn't open a 404
+                    cfg.service(api::root);
+                }
+            })
     })
     .listen(listener)?;
 
@@ -308,7 +562,7 @@ async fn main() -> std::io::Result<()> {
     if mode == RunMode::Both {
         match resolve_flutter_binary() {
             Some(ui_bin) => {
-                println!("Launching Flutter UI: {}", ui_bin.display());
+                log::info!("Launching Flutter UI: {}", ui_bin.display());
                 match spawn_flutter(&ui_bin, &actual_addr) {
                     Ok(child) => {
                         // Store child handle
@@ -325,14 +579,14 @@ async fn main() -> std::io::Result<()> {
                                     if let Some(ch) = g.as_mut() {
                                         match ch.try_wait() {
                                             Ok(Some(status)) => {
-                                                eprintln!("Flutter UI exited with status: {} — stopping backend...", status);
+                                                log::info!("Flutter UI exited with status: {} — stopping backend...", status);
                                                 let h = srv_handle2.clone();
                                                 tokio::spawn(async move { h.stop(true).await; });
                                                 break;
                                             }
                                             Ok(None) => {}
                                             Err(e) => {
-                                                eprintln!("Error monitoring Flutter UI process: {}", e);
+                                                log::error!("Error monitoring Flutter UI process: {}", e);
                                             }
                                         }
                                     } else {
@@ -343,39 +597,67 @@ async fn main() -> std::io::Result<()> {
                         });
                     }
                     Err(err) => {
-                        eprintln!("Failed to spawn Flutter UI: {}", err);
+                        log::error!("Failed to spawn Flutter UI: {}", err);
                     }
                 }
             }
             None => {
-                eprintln!("Flutter UI binary not found. Build it first (see justfile tasks) or set FLUTTER_APP_PATH.");
+                log::error!("Flutter UI binary not found. Build it first (see justfile tasks) or set FLUTTER_APP_PATH, or use --mode=browser.");
             }
         }
     }
 
-    // Ctrl+C handling: stop server and kill Flutter child if present
+    // If BROWSER mode, open the default browser once the listener is up
+    if mode == RunMode::Browser {
+        open_browser(&actual_addr);
+    }
+
+    // Ctrl+C handling: drain downloads, then stop server and kill Flutter child if present.
+    // A second Ctrl+C skips the drain and stops at once.
     {
-        let flutter_child = Arc::clone(&flutter_child);
+        let (ctrlc_tx, mut ctrlc_rx) = broadcast::channel::<()>(1);
+        let flutter_child_force = Arc::clone(&flutter_child);
+        let pressed = std::sync::atomic::AtomicBool::new(false);
         let _ = ctrlc::set_handler(move || {
-            eprintln!("\nCtrl+C received — shutting down...");
+            if !pressed.swap(true, std::sync::atomic::Ordering::SeqCst) {
+                log::warn!("Ctrl+C received — pausing downloads and shutting down (press again to force)...");
+                if ctrlc_tx.send(()).is_ok() {
+                    return;
+                }
+            }
             // Request Actix system stop (thread-safe); avoids needing a Tokio runtime here
             actix_web::rt::System::current().stop();
             // Kill Flutter child if running
-            if let Ok(mut guard) = flutter_child.lock() {
+            if let Ok(mut guard) = flutter_child_force.lock() {
                 if let Some(child) = guard.as_mut() {
                     let _ = child.kill();
                 }
             }
         });
+
+        let srv_handle = srv.handle();
+        let flutter_child = Arc::clone(&flutter_child);
+        tokio::spawn(async move {
+            if ctrlc_rx.recv().await.is_ok() {
+                drain_downloads().await;
+                if let Ok(mut guard) = flutter_child.lock() {
+                    if let Some(child) = guard.as_mut() {
+                        let _ = child.kill();
+                    }
+                }
+                srv_handle.stop(true).await;
+            }
+        });
     }
 
-    // Listen for WS-close-triggered shutdown requests and stop the server
+    // Listen for WS-close-triggered shutdown requests and stop the server (after draining downloads)
     {
         let srv_handle3 = srv.handle();
         let mut rx = shutdown_tx.subscribe();
         tokio::spawn(async move {
             if rx.recv().await.is_ok() {
-                eprintln!("Shutdown requested (WS close) — stopping backend...");
+                log::warn!("Shutdown requested (WS close) — stopping backend...");
+                drain_downloads().await;
                 let h = srv_handle3.clone();
                 tokio::spawn(async move { h.stop(true).await; });
             }
@@ -25,10 +25,18 @@ pub enum Phase {
     DownloadStart,
     #[serde(rename = "download:progress")]
     DownloadProgress,
+    #[serde(rename = "download:verifying")]
+    DownloadVerifying,
     #[serde(rename = "download:complete")]
     DownloadComplete,
     #[serde(rename = "download:error")]
     DownloadError,
+    #[serde(rename = "download:paused")]
+    DownloadPaused,
+    #[serde(rename = "editor:exited")]
+    EditorExited,
+    #[serde(rename = "editor:error")]
+    EditorError,
     #[serde(rename = "cancelled")]
     Cancelled,
     #[serde(rename = "cancel")]
@@ -50,14 +58,32 @@ impl Phase {
             Phase::CreateError => "create:error",
             Phase::DownloadStart => "download:start",
             Phase::DownloadProgress => "download:progress",
+            Phase::DownloadVerifying => "download:verifying",
             Phase::DownloadComplete => "download:complete",
             Phase::DownloadError => "download:error",
+            Phase::DownloadPaused => "download:paused",
+            Phase::EditorExited => "editor:exited",
+            Phase::EditorError => "editor:error",
             Phase::Cancelled => "cancelled",
             Phase::Cancel => "cancel",
         }
     }
 }
 
+impl Phase {
+    /// True for events that end a job (complete, error, cancelled).
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            Phase::ImportComplete | Phase::ImportError
+                | Phase::CreateComplete | Phase::CreateError
+                | Phase::DownloadComplete | Phase::DownloadError
+                | Phase::EditorExited | Phase::EditorError
+                | Phase::Cancelled
+        )
+    }
+}
+
 impl std::fmt::Display for Phase {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.as_str())
@@ -65,6 +91,30 @@ impl std::fmt::Display for Phase {
 }
 
 
+/// Body of every error response: `code` is a stable machine-readable identifier (e.g. "project_not_found"),
+/// `message` is for people. A few endpoints attach extra detail fields, omitted everywhere else.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ErrorResponse {
+    pub ok: bool,
+    pub code: String,
+    pub message: String,
+    /// Paths the caller can pick from when a name was ambiguous (GET /open-unreal-project).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub candidates: Option<Vec<String>>,
+    /// Every validation problem found, not just the first (POST /config/import).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub problems: Option<Vec<String>>,
+    /// Folders already removed before the request failed (POST /discard-download).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub removed: Option<Vec<String>>,
+}
+
+impl ErrorResponse {
+    pub fn new(code: &str, message: impl Into<String>) -> Self {
+        ErrorResponse { ok: false, code: code.to_string(), message: message.into(), candidates: None, problems: None, removed: None }
+    }
+}
+
 #[derive(Serialize)]
 pub struct OpenProjectResponse {
     pub launched: bool,
@@ -72,6 +122,8 @@ pub struct OpenProjectResponse {
     pub engine_version: Option<String>,
     pub editor_path: Option<String>,
     pub project: String,
+    /// Arguments passed to the editor after its path, exactly as spawned.
+    pub args: Vec<String>,
     pub message: String,
 }
 
@@ -100,23 +152,134 @@ pub struct ImportAssetRequest {
     /// Optional Unreal Engine major.minor version subfolder (e.g., "5.4").
     pub ue: Option<String>,
     /// Project identifier: name, project directory, or path to .uproject
+    #[serde(default)]
     pub project: String,
+    /// Optional list of project identifiers to import into, one after another (in addition to `project`).
+    pub projects: Option<Vec<String>>,
+    /// With several projects: keep going after a project fails (default false = stop at the first failure).
+    pub continue_on_error: Option<bool>,
     /// Optional subfolder inside Project/Content to copy into (e.g., "Imported/Industry").
     pub target_subdir: Option<String>,
+    /// Where to put the asset: "content" (default, Project/Content/<asset>) or "plugin"
+    /// (Project/Plugins/<asset>/Content plus the .uplugin descriptor).
+    pub target: Option<String>,
     /// When true, overwrite existing files. When false, skip existing files.
     pub overwrite: Option<bool>,
+    /// Conflict policy for existing files: "skip", "overwrite", "rename_new" or "newer".
+    /// Takes precedence over `overwrite` when set.
+    pub on_conflict: Option<String>,
+    /// When true, only report how many files would be copied/skipped; nothing is written.
+    pub dry_run: Option<bool>,
     /// Optional job id to stream progress over WebSocket
     pub job_id: Option<String>,
+    /// Optional number of copy threads (falls back to EGS_IMPORT_COPY_WORKERS, default 1 = serial).
+    pub copy_workers: Option<usize>,
 }
 
 #[derive(Serialize)]
 pub struct ImportAssetResponse {
     pub ok: bool,
     pub message: String,
+    /// Every file written, including overwritten and renamed ones.
     pub files_copied: usize,
     pub files_skipped: usize,
+    /// Existing files replaced (on_conflict "overwrite" or "newer").
+    pub files_overwritten: usize,
+    /// Files written under a new name next to an existing one (on_conflict "rename_new").
+    pub files_renamed: usize,
+    /// The conflict policy that was applied.
+    pub on_conflict: &'static str,
     pub source: String,
     pub destination: String,
+    /// "content" or "plugin" (see ImportTarget).
+    pub destination_type: &'static str,
+    pub elapsed_ms: u128,
+}
+
+/// Destination kind for /import-asset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportTarget {
+    /// <Project>/Content/[target_subdir/]<asset>
+    Content,
+    /// <Project>/Plugins/<asset>/Content, with the asset's .uplugin next to it
+    Plugin,
+}
+
+impl ImportTarget {
+    /// Parse the request's `target`; None (or blank) means Content, unknown values are an error.
+    pub fn parse(raw: Option<&str>) -> Result<ImportTarget, String> {
+        match raw.map(|s| s.trim().to_ascii_lowercase()).as_deref() {
+            None | Some("") | Some("content") => Ok(ImportTarget::Content),
+            Some("plugin") => Ok(ImportTarget::Plugin),
+            Some(other) => Err(format!("target must be \"content\" or \"plugin\", got \"{}\"", other)),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportTarget::Content => "content",
+            ImportTarget::Plugin => "plugin",
+        }
+    }
+}
+
+/// Outcome of importing into one project of a multi-project import.
+#[derive(Serialize)]
+pub struct ImportProjectResult {
+    pub project: String,
+    pub ok: bool,
+    pub message: String,
+    pub files_copied: usize,
+    pub files_skipped: usize,
+    pub files_overwritten: usize,
+    pub files_renamed: usize,
+    /// Destination folder; None when the project could not be resolved or was not attempted.
+    pub destination: Option<String>,
+}
+
+/// Response for POST /import-asset when `projects` is given.
+#[derive(Serialize)]
+pub struct MultiImportResponse {
+    pub ok: bool,
+    pub message: String,
+    pub source: String,
+    /// "content" or "plugin", the same for every project.
+    pub destination_type: &'static str,
+    pub results: Vec<ImportProjectResult>,
+    pub elapsed_ms: u128,
+}
+
+/// Request body for POST /import-assets: several assets imported one after another into one project.
+#[derive(serde::Deserialize)]
+pub struct ImportAssetsRequest {
+    /// Assets to import, in order. Each entry is a normal /import-asset body; `project` and `job_id`
+    /// come from the batch, and `projects` is ignored.
+    pub assets: Vec<ImportAssetRequest>,
+    /// Project identifier: name, project directory, or path to .uproject
+    pub project: String,
+    /// Optional job id to stream progress over WebSocket (shared by every asset)
+    pub job_id: Option<String>,
+    /// Keep going after an asset fails (default false = stop at the first failure).
+    pub continue_on_error: Option<bool>,
+}
+
+/// Outcome for one asset of POST /import-assets.
+#[derive(Serialize)]
+pub struct BatchImportResult {
+    pub asset_name: String,
+    pub ok: bool,
+    pub message: String,
+    /// The import's response; None when the asset could not be resolved or was not attempted.
+    pub result: Option<ImportAssetResponse>,
+}
+
+/// Response for POST /import-assets.
+#[derive(Serialize)]
+pub struct BatchImportResponse {
+    pub ok: bool,
+    pub message: String,
+    pub project: String,
+    pub results: Vec<BatchImportResult>,
     pub elapsed_ms: u128,
 }
 
@@ -134,12 +297,22 @@ pub struct CreateUnrealProjectRequest {
     pub artifact_id: Option<String>,
     /// Optional Unreal Engine major.minor version (e.g., "5.6") to select engine and set EngineAssociation.
     pub ue: Option<String>,
+    /// Falls back to the saved create defaults (GET /config/create-defaults) when empty.
+    #[serde(default)]
     pub output_dir: String,
     pub project_name: String,
     pub project_type: Option<String>, // "bp" or "cpp"
-    /// When true, launch Unreal Editor to open the created project after copying. Defaults to false.
+    /// When true, launch Unreal Editor to open the created project after copying. Defaults to the
+    /// saved create defaults, then false.
     pub open_after_create: Option<bool>,
+    /// Open with `-NoCompile`. Defaults to true for "bp" projects and false for "cpp".
+    pub no_compile: Option<bool>,
+    /// Extra editor arguments appended when opening after create, one entry per argument.
+    pub extra_args: Option<Vec<String>>,
     pub dry_run: Option<bool>,
+    /// When true and the template lives in a managed download, re-hash its files against the
+    /// manifest recorded at download time and refuse to create from a corrupt copy. Defaults to false.
+    pub verify_template: Option<bool>,
     /// Optional job id to stream progress over WebSocket
     pub job_id: Option<String>,
 }
@@ -149,7 +322,12 @@ pub struct CreateUnrealProjectResponse {
     pub ok: bool,
     pub message: String,
     pub command: String,
+    /// Arguments passed to the editor after its path, exactly as spawned.
+    pub args: Vec<String>,
     pub project_path: Option<String>,
+    /// Template files that could not be copied (e.g. locked or permission denied).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub failed_files: Vec<FailedFile>,
 }
 
 // === WebSocket progress broadcasting ===
@@ -164,6 +342,80 @@ pub struct ProgressEvent {
     pub details: Option<serde_json::Value>,
 }
 
+#[derive(Serialize, Clone, Debug)]
+pub struct FailedFile {
+    /// Path relative to the template folder.
+    pub path: String,
+    pub error: String,
+}
+
+/// Coarse lifecycle state of a job, derived from its last event and the cancel registry.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum JobState {
+    /// No terminal event yet.
+    Running,
+    /// /pause-job was called; the download is waiting for /resume-job.
+    Paused,
+    /// /cancel-job was called but the job has not acknowledged it yet (still winding down).
+    CancelRequested,
+    /// The job stopped after a cancel request.
+    Cancelled,
+    /// Finished successfully (complete, or the editor exited).
+    Done,
+    /// Finished with an error event.
+    Failed,
+}
+
+impl JobState {
+    /// State for a job whose last event had `phase`, given whether a cancel flag is still pending.
+    pub fn derive(last_phase: &str, cancel_pending: bool) -> JobState {
+        if cancel_pending {
+            return JobState::CancelRequested;
+        }
+        match last_phase {
+            p if p == Phase::Cancelled.as_str() => JobState::Cancelled,
+            p if p == Phase::DownloadPaused.as_str() => JobState::Paused,
+            p if p.ends_with(":complete") || p == Phase::EditorExited.as_str() => JobState::Done,
+            p if p.ends_with(":error") => JobState::Failed,
+            _ => JobState::Running,
+        }
+    }
+
+    /// True once the job has stopped for good (cancelled, done or failed).
+    pub fn is_finished(self) -> bool {
+        matches!(self, JobState::Cancelled | JobState::Done | JobState::Failed)
+    }
+}
+
+/// Last known state of a job, as recorded by emit_event (see GET /jobs and GET /job-status).
+#[derive(Serialize, Clone, Debug)]
+pub struct JobSummary {
+    pub job_id: String,
+    pub state: JobState,
+    /// Phase of the most recent event (e.g., "download:progress").
+    pub phase: String,
+    /// Most recent progress value reported for the job, if any event carried one.
+    pub progress: Option<f32>,
+    /// Unix time (milliseconds) of the most recent event.
+    pub last_event_ms: u64,
+    /// The most recent event itself (only in /job-status; /jobs leaves it out).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_event: Option<ProgressEvent>,
+}
+
+/// Readiness report for GET /health. `status` is "ok", "degraded" (a non-critical check failed) or
+/// "unavailable" (cache or downloads directory not writable; served with 503).
+#[derive(Serialize)]
+pub struct HealthResponse {
+    pub status: String,
+    pub cache_writable: bool,
+    pub downloads_writable: bool,
+    pub authenticated: bool,
+    pub engines_found: usize,
+    pub projects_dir_exists: bool,
+}
+
 // ===== Configuration: Paths for Projects and Engines =====
 #[derive(Serialize, Deserialize)]
 pub struct PathsStatus {
@@ -172,6 +424,17 @@ pub struct PathsStatus {
     pub effective_engines_dir: String,
     pub effective_cache_dir: String,
     pub effective_downloads_dir: String,
+    /// Result of checking each directory posted to POST /config/paths (empty for GET).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub checks: Vec<PathCheck>,
+}
+
+/// Outcome of checking one configured directory. Problems are reported, not rejected: the value is saved anyway.
+#[derive(Serialize, Deserialize)]
+pub struct PathCheck {
+    pub field: String,
+    pub ok: bool,
+    pub message: String,
 }
 
 #[derive(Deserialize)]
@@ -180,6 +443,16 @@ pub struct PathsUpdate {
     pub engines_dir: Option<String>,
     pub cache_dir: Option<String>,
     pub downloads_dir: Option<String>,
+    /// Persisted download tuning; 0 clears the value (falls back to env/default).
+    pub file_concurrency: Option<usize>,
+    pub chunk_concurrency: Option<usize>,
+}
+
+/// Optional body of POST /config/reset: clear only these PathsConfig fields instead of the whole file.
+#[derive(Deserialize)]
+pub struct ConfigResetRequest {
+    #[serde(default)]
+    pub fields: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize, Default, Clone)]
@@ -188,6 +461,27 @@ pub struct PathsConfig {
     pub engines_dir: Option<String>,
     pub cache_dir: Option<String>,
     pub downloads_dir: Option<String>,
+    /// Default number of files downloaded in parallel (overrides EAM_FILE_CONCURRENCY).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_concurrency: Option<usize>,
+    /// Default number of chunks fetched in parallel per file (overrides EAM_CHUNK_CONCURRENCY).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunk_concurrency: Option<usize>,
+}
+
+/// Per-download overrides passed to utils::download_asset. None means "use config/env/default".
+#[derive(Default, Clone, Debug)]
+pub struct DownloadOptions {
+    pub file_concurrency: Option<usize>,
+    pub chunk_concurrency: Option<usize>,
+    /// Aggregate bandwidth cap in bytes/second across all chunk tasks (overrides EAM_MAX_BYTES_PER_SEC).
+    pub max_bytes_per_sec: Option<u64>,
+    /// Include a per-top-level-folder files done/total summary in download:progress details.
+    pub detailed_progress: bool,
+    /// Only download manifest files matching one of these globs (all files when empty).
+    pub include_patterns: Vec<String>,
+    /// Never download manifest files matching any of these globs.
+    pub exclude_patterns: Vec<String>,
 }
 
 #[derive(Serialize)]
@@ -208,8 +502,34 @@ pub struct UnrealProjectsResponse {
 pub struct UnrealEngineInfo {
     pub name: String,
     pub version: String,
+    /// Where `version` came from: "build.version", "folder-name" or "unknown".
+    pub version_source: String,
     pub path: String,
     pub editor_path: Option<String>,
+    /// Whether an editor binary (UnrealEditor/UE4Editor) exists; false for e.g. an engine that only
+    /// built the game target. Such an engine cannot open projects.
+    pub editor_available: bool,
+    /// Why editor_available is false: the paths checked and the suspected platform.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// False when the engine cannot be launched from here; the UI should disable "open" for it.
+    /// Today that is exactly when the editor is missing (see editor_available/reason).
+    pub launchable: bool,
+    /// Explanation when not launchable.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub launch_problem: Option<String>,
+    /// Path of another install with the same version/BuildId when this one looks like a copy.
+    /// Pass an explicit engine_path to choose between duplicates.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duplicate_of: Option<String>,
+}
+
+/// One UE major.minor version found across the cached library, for building a version filter.
+#[derive(Serialize, Clone)]
+pub struct LibraryUeVersion {
+    pub version: String,
+    /// Number of library assets with at least one project version supporting this UE version.
+    pub asset_count: usize,
 }
 
 #[derive(Serialize)]
@@ -231,6 +551,39 @@ pub struct SetProjectEngineRequest {
     pub version: String, // e.g., "5.6" or "5.6.1" or "UE_5.6"
 }
 
+/// Request payload for POST /reindex-projects.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct ReindexProjectsRequest {
+    /// Projects base directory; defaults to the configured projects directory.
+    pub base: Option<String>,
+    /// When true, replace GUID EngineAssociations that now resolve with their major.minor version.
+    pub write_back: bool,
+}
+
+#[derive(Serialize)]
+pub struct ReindexedProject {
+    pub name: String,
+    pub uproject_file: String,
+    /// Raw EngineAssociation as found in the .uproject (empty when missing).
+    pub engine_association: String,
+    /// Resolved major.minor, or empty when it could not be resolved with the current engines.
+    pub engine_version: String,
+    /// True when EngineAssociation was rewritten to engine_version.
+    pub written: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct ReindexProjectsResponse {
+    pub base_directory: String,
+    pub resolved: usize,
+    pub unresolved: usize,
+    pub written: usize,
+    pub projects: Vec<ReindexedProject>,
+}
+
 #[derive(Serialize)]
 pub struct SimpleResponse {
     pub ok: bool,
@@ -240,4 +593,166 @@ pub struct SimpleResponse {
 #[derive(Deserialize)]
 pub struct AuthCompleteRequest {
     pub code: String
-}
\ No newline at end of file
+}
+
+/// Artifact identifiers recorded alongside an in-progress download (.download_info.json),
+/// so incomplete downloads can be listed and resumed after a crash or network drop.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DownloadInfo {
+    pub namespace: String,
+    pub asset_id: String,
+    pub artifact_id: String,
+    /// UE major.minor version subfolder, when the download was versioned.
+    pub ue: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct IncompleteDownload {
+    /// Asset folder name under downloads/ (e.g., "Industry Props Pack 6").
+    pub asset_name: String,
+    /// Version subfolder (e.g., "5.6") or None for legacy, unversioned downloads.
+    pub version: Option<String>,
+    pub path: String,
+    /// Recorded artifact info; None when the download predates the info marker.
+    pub info: Option<DownloadInfo>,
+}
+
+#[derive(Serialize)]
+pub struct IncompleteDownloadsResponse {
+    pub downloads_directory: String,
+    pub incomplete: Vec<IncompleteDownload>,
+}
+
+#[derive(Serialize)]
+pub struct ResumeOutcome {
+    pub asset_name: String,
+    pub version: Option<String>,
+    /// "queued" (follow `job_id` over /ws) or "skipped".
+    pub status: String,
+    pub message: String,
+    /// Download job of a queued resume.
+    pub job_id: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct ResumeIncompleteResponse {
+    /// Job that reports each queued download's outcome and ends after the last one.
+    pub job_id: String,
+    pub outcomes: Vec<ResumeOutcome>,
+}
+
+/// Request payload for discarding one stuck/incomplete download.
+///
+/// Identify the download either by Fab identifiers (matched against recorded `.download_info.json`)
+/// or by `asset_name` (folder name under downloads/). `ue` narrows to a version subfolder.
+#[derive(Deserialize)]
+pub struct DiscardDownloadRequest {
+    pub namespace: Option<String>,
+    pub asset_id: Option<String>,
+    pub artifact_id: Option<String>,
+    pub asset_name: Option<String>,
+    pub ue: Option<String>,
+    /// When true, also delete a download that has a valid `.download_complete` marker.
+    pub force: Option<bool>,
+}
+
+#[derive(Serialize)]
+pub struct DiscardDownloadResponse {
+    pub ok: bool,
+    pub message: String,
+    pub removed: Vec<String>,
+}
+
+/// Request body for POST /delete-unreal-project.
+#[derive(Deserialize)]
+pub struct DeleteUnrealProjectRequest {
+    /// Project identifier: name, project directory, or path to .uproject
+    pub project: String,
+    /// Must be true; guards against accidental deletes.
+    #[serde(default)]
+    pub confirm: bool,
+    /// Move the project into the projects dir's .trash folder instead of deleting it.
+    #[serde(default)]
+    pub archive: bool,
+}
+
+#[derive(Serialize)]
+pub struct DeleteUnrealProjectResponse {
+    pub ok: bool,
+    pub message: String,
+    /// Project folder that was removed or archived.
+    pub path: String,
+    /// Where the project was moved to when archived.
+    pub archived_to: Option<String>,
+    /// Disk space reclaimed: the project's size when deleted, 0 when archived.
+    pub bytes_freed: u64,
+}
+
+#[derive(Serialize)]
+pub struct DeleteDownloadedAssetResponse {
+    pub ok: bool,
+    pub message: String,
+    /// Folder that was removed.
+    pub path: String,
+    /// Total size of the regular files that were removed.
+    pub bytes_freed: u64,
+    /// Whether the `downloaded` flags in the cached library were refreshed afterwards.
+    pub cache_updated: bool,
+}
+
+/// User-defined organization metadata for one asset, stored locally (never sent to Epic).
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+pub struct AssetTagEntry {
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub favorite: bool,
+}
+
+/// Local tags store: Fab assetId -> tags/favorite. Keyed by the stable asset id, not the title.
+pub type AssetTagsStore = std::collections::BTreeMap<String, AssetTagEntry>;
+
+/// Request payload for POST /asset-tags. Omitted fields keep their current value.
+#[derive(Deserialize)]
+pub struct AssetTagsUpdate {
+    pub asset_id: String,
+    pub tags: Option<Vec<String>>,
+    pub favorite: Option<bool>,
+}
+
+/// Request payload for POST /log-level. Without `target` the default level changes.
+#[derive(Deserialize)]
+pub struct LogLevelUpdate {
+    pub level: String,
+    pub target: Option<String>,
+}
+
+/// Settings /create-unreal-project falls back to when a request omits them, stored in
+/// create_defaults.json next to config.json. Unset fields fall through to the usual defaults.
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+pub struct CreateDefaults {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub engine_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_dir: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_after_create: Option<bool>,
+}
+
+/// Portable configuration bundle for GET /config/export and POST /config/import.
+///
+/// Contains no secrets: tokens are referenced by location only and must be re-created by
+/// logging in again on the new machine.
+#[derive(Serialize, Deserialize)]
+pub struct ConfigBundle {
+    pub paths: PathsConfig,
+    #[serde(default)]
+    pub asset_tags: AssetTagsStore,
+    /// Where this machine keeps its token cache (informational; ignored on import).
+    #[serde(default)]
+    pub token_cache_path: Option<String>,
+    #[serde(default)]
+    pub note: Option<String>,
+}
@@ -0,0 +1,44 @@
+//! Concurrency-safe access to the asset tags store (asset_tags.json next to config.json).
+//!
+//! POST /asset-tags and /config/import hold lock_asset_tags() across their read-modify-write, and
+//! save() replaces the file through a temp file + rename, so concurrent edits are not lost and a
+//! reader never sees a truncated store.
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+use crate::models;
+
+// Process-wide guard for asset_tags.json read-modify-write cycles.
+static ASSET_TAGS_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+
+/// Acquire the process-wide lock guarding asset_tags.json. A poisoned lock is recovered since the
+/// guarded data lives on disk, not in the mutex.
+pub fn lock_asset_tags() -> MutexGuard<'static, ()> {
+    ASSET_TAGS_LOCK
+        .get_or_init(|| Mutex::new(()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// The store at `path`; empty when it is missing or unreadable.
+pub fn load(path: &Path) -> models::AssetTagsStore {
+    fs::read(path)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .unwrap_or_default()
+}
+
+/// Atomically replace the store at `path`. Callers should hold lock_asset_tags() while calling this.
+pub fn save(path: &Path, store: &models::AssetTagsStore) -> std::io::Result<()> {
+    let s = serde_json::to_string_pretty(store).unwrap_or_else(|_| "{}".to_string());
+    let tmp_path = path.with_extension("json.tmp");
+    {
+        let mut f = fs::File::create(&tmp_path)?;
+        f.write_all(s.as_bytes())?;
+        f.sync_all()?;
+    }
+    fs::rename(&tmp_path, path)
+}
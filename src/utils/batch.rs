@@ -0,0 +1,54 @@
+//! Running the items of a batch request one after another (/import-assets, and /import-asset with
+//! several projects). A failed item stops the batch unless continue_on_error is set; a cancel always does.
+
+use std::future::Future;
+
+/// How one item of a batch ended, with the result to report for it.
+pub enum ItemOutcome<T> {
+    Done(T),
+    Failed(T),
+    /// The job was cancelled while this item ran.
+    Cancelled(T),
+}
+
+/// Why an item was not attempted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotAttempted {
+    Cancelled,
+    EarlierFailure,
+}
+
+/// Run `run(index, item)` for each item in order and collect the results. After a cancel, or a failure
+/// without `continue_on_error`, the remaining items are reported by `not_attempted(item, why)` instead.
+pub async fn run_in_order<I, T, Fut>(
+    items: Vec<I>,
+    continue_on_error: bool,
+    mut run: impl FnMut(usize, I) -> Fut,
+    mut not_attempted: impl FnMut(I, NotAttempted) -> T,
+) -> Vec<T>
+where
+    Fut: Future<Output = ItemOutcome<T>>,
+{
+    let mut results = Vec::with_capacity(items.len());
+    let mut stop: Option<NotAttempted> = None;
+    for (i, item) in items.into_iter().enumerate() {
+        if let Some(why) = stop {
+            results.push(not_attempted(item, why));
+            continue;
+        }
+        match run(i, item).await {
+            ItemOutcome::Done(result) => results.push(result),
+            ItemOutcome::Failed(result) => {
+                results.push(result);
+                if !continue_on_error {
+                    stop = Some(NotAttempted::EarlierFailure);
+                }
+            }
+            ItemOutcome::Cancelled(result) => {
+                results.push(result);
+                stop = Some(NotAttempted::Cancelled);
+            }
+        }
+    }
+    results
+}
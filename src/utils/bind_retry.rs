@@ -0,0 +1,100 @@
+//! Binding the HTTP listener with bounded retries.
+//!
+//! A port still held by a previous instance that is shutting down usually frees up within seconds,
+//! so "address in use" is retried EGS_BIND_MAX_RETRIES times, EGS_BIND_RETRY_DELAY_MS apart. After
+//! that the bind fails, unless auto-port (EGS_AUTO_PORT) is on: then the next ports on the same
+//! address are tried, and finally any free port. Other errors (bad address, permission denied) are
+//! not retried.
+
+use std::io;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+pub const DEFAULT_MAX_RETRIES: u32 = 5;
+pub const DEFAULT_RETRY_DELAY_MS: u64 = 2000;
+/// Ports tried after the requested one when auto-port is on.
+pub const AUTO_PORT_SPAN: u16 = 20;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BindSettings {
+    pub max_retries: u32,
+    pub retry_delay: Duration,
+    pub auto_port: bool,
+}
+
+impl BindSettings {
+    /// Settings from EGS_BIND_MAX_RETRIES, EGS_BIND_RETRY_DELAY_MS and EGS_AUTO_PORT; `auto_port_default`
+    /// applies when EGS_AUTO_PORT is unset or not a recognised value.
+    pub fn from_env(env: &dyn Fn(&str) -> Option<String>, auto_port_default: bool) -> BindSettings {
+        let num = |key: &str| env(key).and_then(|v| v.trim().parse::<u64>().ok());
+        let auto_port = match env("EGS_AUTO_PORT").as_deref().map(str::trim) {
+            Some("1") | Some("true") | Some("yes") => true,
+            Some("0") | Some("false") | Some("no") => false,
+            _ => auto_port_default,
+        };
+        BindSettings {
+            max_retries: num("EGS_BIND_MAX_RETRIES").map_or(DEFAULT_MAX_RETRIES, |n| n.min(u32::MAX as u64) as u32),
+            retry_delay: Duration::from_millis(num("EGS_BIND_RETRY_DELAY_MS").unwrap_or(DEFAULT_RETRY_DELAY_MS)),
+            auto_port,
+        }
+    }
+}
+
+/// Addresses tried after `addr` with auto-port: the next AUTO_PORT_SPAN ports on the same IP, then
+/// port 0 (any free port). Empty when `addr` is not an ip:port pair.
+pub fn auto_port_candidates(addr: &str) -> Vec<String> {
+    let Ok(sock) = addr.parse::<SocketAddr>() else { return Vec::new() };
+    (1..=AUTO_PORT_SPAN)
+        .filter_map(|i| sock.port().checked_add(i))
+        .chain(std::iter::once(0))
+        .map(|port| SocketAddr::new(sock.ip(), port).to_string())
+        .collect()
+}
+
+/// Address a local client such as the Flutter child should connect to for a server bound to `bind`.
+/// Unspecified hosts (0.0.0.0, [::]) are not connectable and become 127.0.0.1; host names and other
+/// addresses are kept.
+pub fn client_reachable_addr(bind: &str) -> String {
+    match bind.trim().parse::<SocketAddr>() {
+        Ok(sock) if sock.ip().is_unspecified() => format!("127.0.0.1:{}", sock.port()),
+        Ok(sock) => sock.to_string(),
+        Err(_) => bind.trim().to_string(),
+    }
+}
+
+/// Bind `addr` with `bind`, retrying "address in use" per `settings` (waiting with `sleep`), then
+/// trying auto_port_candidates when auto-port is on. Returns the listener and the address passed to
+/// `bind` for it, or a message explaining the failure.
+pub fn bind_with_retry<T>(
+    addr: &str,
+    settings: &BindSettings,
+    mut bind: impl FnMut(&str) -> io::Result<T>,
+    mut sleep: impl FnMut(Duration),
+) -> Result<(T, String), String> {
+    let mut retries = 0;
+    let err = loop {
+        match bind(addr) {
+            Ok(listener) => return Ok((listener, addr.to_string())),
+            Err(e) if e.kind() == io::ErrorKind::AddrInUse && retries < settings.max_retries => {
+                retries += 1;
+                log::warn!("{} is in use; retrying in {:?} ({}/{})", addr, settings.retry_delay, retries, settings.max_retries);
+                sleep(settings.retry_delay);
+            }
+            Err(e) => break e,
+        }
+    };
+    if err.kind() != io::ErrorKind::AddrInUse {
+        return Err(format!("Failed to bind {}: {}", addr, err));
+    }
+    if settings.auto_port {
+        for candidate in auto_port_candidates(addr) {
+            if let Ok(listener) = bind(&candidate) {
+                return Ok((listener, candidate));
+            }
+        }
+    }
+    Err(format!(
+        "Failed to bind {}: {} (gave up after {} retries). Stop the process using it, choose another address with BIND_ADDR or PORT, or set EGS_AUTO_PORT=1 to use the next free port.",
+        addr, err, retries
+    ))
+}
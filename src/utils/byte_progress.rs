@@ -0,0 +1,54 @@
+//! Byte-based progress for one asset download.
+//!
+//! The progress callback and every download:progress event read the same counter: manifest bytes
+//! accounted for so far. A file found up to date adds its whole size; a chunk part adds its size once
+//! it is on disk, whether fetched or reused from temp/. Network bytes are not used here: chunk
+//! containers are compressed and can be shared between files, so they don't add up to the file sizes.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+pub struct ByteProgress {
+    total: u64,
+    done: AtomicU64,
+}
+
+impl ByteProgress {
+    pub fn new(total: u64) -> Self {
+        ByteProgress { total, done: AtomicU64::new(0) }
+    }
+
+    /// Account for `n` more bytes and return the bytes done so far (never above the total).
+    pub fn add(&self, n: u64) -> u64 {
+        (self.done.fetch_add(n, Ordering::SeqCst) + n).min(self.total)
+    }
+
+    pub fn done(&self) -> u64 {
+        self.done.load(Ordering::SeqCst).min(self.total)
+    }
+
+    /// Percentage for progress events.
+    pub fn percent(&self) -> f32 {
+        percent(self.done(), self.total)
+    }
+
+    /// Whole percentage for the progress callback; reaches 100 only once every byte is accounted for.
+    pub fn whole_percent(&self) -> u32 {
+        whole_percent(self.done(), self.total)
+    }
+}
+
+/// `done` as a percentage of `total`, capped at 100. An empty download counts as complete.
+pub fn percent(done: u64, total: u64) -> f32 {
+    if total == 0 {
+        return 100.0;
+    }
+    (done.min(total) as f64 / total as f64 * 100.0) as f32
+}
+
+/// Like percent, rounded down.
+pub fn whole_percent(done: u64, total: u64) -> u32 {
+    if total == 0 {
+        return 100;
+    }
+    (done.min(total) as u128 * 100 / total as u128) as u32
+}
@@ -0,0 +1,53 @@
+//! Category extraction from cached Fab library JSON.
+//!
+//! Library items are not consistent about where categories live, so this accepts a `categories`
+//! array of strings or objects (`name`, then `path`, then `id`) and a plain `category` string.
+
+use std::collections::BTreeMap;
+
+/// Distinct category names for one asset, in first-seen order. Empty when the asset has none.
+pub fn asset_categories(asset: &serde_json::Value) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    let mut push = |s: &str| {
+        let s = s.trim();
+        if !s.is_empty() && !out.iter().any(|c| c.eq_ignore_ascii_case(s)) {
+            out.push(s.to_string());
+        }
+    };
+    if let Some(arr) = asset.get("categories").and_then(|v| v.as_array()) {
+        for c in arr {
+            if let Some(s) = c.as_str() {
+                push(s);
+            } else if let Some(s) = ["name", "path", "id"].iter().find_map(|k| c.get(*k).and_then(|v| v.as_str())) {
+                push(s);
+            }
+        }
+    }
+    if let Some(s) = asset.get("category").and_then(|v| v.as_str()) {
+        push(s);
+    }
+    out
+}
+
+/// Category -> number of assets in `value["results"]`, sorted by name.
+///
+/// Names differing only in case are merged under the first spelling seen.
+pub fn category_counts(value: &serde_json::Value) -> BTreeMap<String, usize> {
+    let mut by_key: BTreeMap<String, (String, usize)> = BTreeMap::new();
+    if let Some(results) = value.get("results").and_then(|r| r.as_array()) {
+        for asset in results {
+            for c in asset_categories(asset) {
+                by_key.entry(c.to_lowercase()).or_insert_with(|| (c, 0)).1 += 1;
+            }
+        }
+    }
+    by_key.into_values().collect()
+}
+
+/// Keep only assets having `category` (case-insensitive).
+pub fn retain_category(value: &mut serde_json::Value, category: &str) {
+    let wanted = category.trim();
+    if let Some(results) = value.get_mut("results").and_then(|r| r.as_array_mut()) {
+        results.retain(|asset| asset_categories(asset).iter().any(|c| c.eq_ignore_ascii_case(wanted)));
+    }
+}
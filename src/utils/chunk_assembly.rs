@@ -0,0 +1,81 @@
+//! Streaming reads of chunk data during assembly.
+//!
+//! Assembly used to read every cached chunk fully into memory (and clone it to keep a fallback for
+//! raw blobs). Here only the fixed header is read to classify the file; uncompressed chunk data and
+//! raw blobs are then copied straight from disk with a seek, so memory use does not grow with chunk size.
+//! Compressed containers still need decoding and are left to the caller.
+
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+use super::chunk_check::{CHUNK_MAGIC, MIN_CHUNK_LEN};
+
+/// EChunkStorageFlags bits in the header's `stored_as` byte.
+const STORED_COMPRESSED: u8 = 0x01;
+const STORED_ENCRYPTED: u8 = 0x02;
+/// Offset of `stored_as`: magic, version, header size, compressed size (4 bytes each), GUID (16), rolling hash (8).
+const STORED_AS_OFFSET: usize = 40;
+
+/// Where the payload of a cached chunk file lives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkLayout {
+    /// Not a chunk container: the whole file is the data (some FAB distribution links serve these).
+    Raw { len: u64 },
+    /// Chunk container with uncompressed data stored right after the header.
+    Stored { data_start: u64, data_len: u64 },
+    /// Compressed or encrypted container; the payload has to be decoded before slicing.
+    Encoded,
+}
+
+impl ChunkLayout {
+    /// Byte range of the payload within the file, or None when it has to be decoded first.
+    pub fn data_region(&self) -> Option<(u64, u64)> {
+        match *self {
+            ChunkLayout::Raw { len } => Some((0, len)),
+            ChunkLayout::Stored { data_start, data_len } => Some((data_start, data_len)),
+            ChunkLayout::Encoded => None,
+        }
+    }
+}
+
+/// Classify a chunk file from its first MIN_CHUNK_LEN bytes and total length.
+pub fn read_layout<R: Read + Seek>(src: &mut R) -> io::Result<ChunkLayout> {
+    let file_len = src.seek(SeekFrom::End(0))?;
+    src.seek(SeekFrom::Start(0))?;
+    let mut header = [0u8; MIN_CHUNK_LEN as usize];
+    let mut n = 0;
+    while n < header.len() {
+        match src.read(&mut header[n..])? {
+            0 => break,
+            read => n += read,
+        }
+    }
+    let le = |at: usize| u32::from_le_bytes([header[at], header[at + 1], header[at + 2], header[at + 3]]);
+    if n < 4 || le(0) != CHUNK_MAGIC {
+        return Ok(ChunkLayout::Raw { len: file_len });
+    }
+    if n < header.len() {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "chunk header truncated"));
+    }
+    let header_size = le(8) as u64;
+    let data_size = le(12) as u64;
+    if header[STORED_AS_OFFSET] & (STORED_COMPRESSED | STORED_ENCRYPTED) != 0 {
+        return Ok(ChunkLayout::Encoded);
+    }
+    if header_size < MIN_CHUNK_LEN || header_size + data_size > file_len {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("chunk header claims {} + {} bytes but the file has {}", header_size, data_size, file_len),
+        ));
+    }
+    Ok(ChunkLayout::Stored { data_start: header_size, data_len: data_size })
+}
+
+/// Copy exactly `len` bytes starting at `start` from `src` to `out`, reading nothing else.
+pub fn copy_range<R: Read + Seek, W: Write>(src: &mut R, start: u64, len: u64, out: &mut W) -> io::Result<u64> {
+    src.seek(SeekFrom::Start(start))?;
+    let copied = io::copy(&mut src.take(len), out)?;
+    if copied != len {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, format!("expected {} bytes at {}, got {}", len, start, copied)));
+    }
+    Ok(copied)
+}
@@ -0,0 +1,134 @@
+//! Plausibility checks for chunk files cached under temp/.
+//!
+//! A CDN can answer a chunk request with an HTML error page and a 200 status. Without a check
+//! that page is cached as `<GUID>.chunk` and reused on every retry, so assembly keeps failing.
+//! A killed process can also leave a half-written chunk behind; the ledger in temp/manifest.json
+//! records which chunks were fully written so only those are reused after a restart.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Magic number at the start of every Epic chunk file (FChunkHeader), stored little-endian.
+pub const CHUNK_MAGIC: u32 = 0xB1FE_3AA2;
+
+/// Smallest possible chunk file: the fixed part of the header.
+pub const MIN_CHUNK_LEN: u64 = 41;
+
+/// True when `prefix` (the first bytes of a cached chunk) and the file length look like a real chunk.
+pub fn is_plausible_chunk(prefix: &[u8], file_len: u64) -> bool {
+    if file_len < MIN_CHUNK_LEN || prefix.len() < 4 {
+        return false;
+    }
+    u32::from_le_bytes([prefix[0], prefix[1], prefix[2], prefix[3]]) == CHUNK_MAGIC
+}
+
+/// Check a cached chunk before reuse. Implausible files are deleted so the caller re-downloads them.
+///
+/// Returns true when the cached file can be reused, false when it is missing or was discarded.
+pub fn validate_cached_chunk(path: &Path) -> bool {
+    let len = match fs::metadata(path) {
+        Ok(m) if m.is_file() => m.len(),
+        _ => return false,
+    };
+    let mut prefix = [0u8; 4];
+    let ok = fs::File::open(path)
+        .and_then(|mut f| f.read_exact(&mut prefix))
+        .map(|_| is_plausible_chunk(&prefix, len))
+        .unwrap_or(false);
+    if !ok {
+        let _ = fs::remove_file(path);
+    }
+    ok
+}
+
+/// Pending ledger entries that trigger a save, whatever the time since the last one.
+pub const LEDGER_SAVE_EVERY: usize = 64;
+
+/// Longest a recorded chunk waits in memory before the ledger is saved.
+pub const LEDGER_SAVE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Record of chunks known to be completely written: GUID -> size in bytes (temp/manifest.json).
+///
+/// Saves are batched (see `record`); entries lost to a crash before a save only mean those chunks
+/// are downloaded again.
+pub struct ChunkLedger {
+    path: PathBuf,
+    verified: BTreeMap<String, u64>,
+    unsaved: usize,
+    last_save: Instant,
+}
+
+impl ChunkLedger {
+    /// Load the ledger stored in `temp_dir` (empty when missing or unreadable).
+    pub fn load(temp_dir: &Path) -> Self {
+        let path = temp_dir.join("manifest.json");
+        let verified = read_entries(&path);
+        ChunkLedger { path, verified, unsaved: 0, last_save: Instant::now() }
+    }
+
+    /// True when `guid` was recorded as complete with exactly `len` bytes.
+    pub fn is_verified(&self, guid: &str, len: u64) -> bool {
+        self.verified.get(guid) == Some(&len)
+    }
+
+    pub fn contains(&self, guid: &str) -> bool {
+        self.verified.contains_key(guid)
+    }
+
+    pub fn forget(&mut self, guid: &str) {
+        self.verified.remove(guid);
+    }
+
+    /// Record a fully written chunk. The file is rewritten once LEDGER_SAVE_EVERY entries are pending
+    /// or LEDGER_SAVE_INTERVAL has passed since the last save, not for every chunk.
+    pub fn record(&mut self, guid: &str, len: u64) -> std::io::Result<()> {
+        self.verified.insert(guid.to_string(), len);
+        self.unsaved += 1;
+        if self.unsaved >= LEDGER_SAVE_EVERY || self.last_save.elapsed() >= LEDGER_SAVE_INTERVAL {
+            self.save()
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Persist pending entries, merging with entries other writers saved meanwhile. No-op when
+    /// nothing is pending.
+    pub fn save(&mut self) -> std::io::Result<()> {
+        if self.unsaved == 0 {
+            return Ok(());
+        }
+        for (g, l) in read_entries(&self.path) {
+            self.verified.entry(g).or_insert(l);
+        }
+        let data = serde_json::to_vec_pretty(&serde_json::json!({ "verified": self.verified }))
+            .map_err(|e| std::io::Error::other(e))?;
+        let tmp = self.path.with_extension("json.tmp");
+        fs::write(&tmp, data)?;
+        fs::rename(&tmp, &self.path)?;
+        self.unsaved = 0;
+        self.last_save = Instant::now();
+        Ok(())
+    }
+}
+
+impl Drop for ChunkLedger {
+    /// Best-effort save of pending entries when a download ends early (error, cancel, pause at shutdown).
+    fn drop(&mut self) {
+        if self.unsaved > 0 && self.path.parent().is_some_and(Path::is_dir) {
+            let _ = self.save();
+        }
+    }
+}
+
+fn read_entries(path: &Path) -> BTreeMap<String, u64> {
+    fs::read(path)
+        .ok()
+        .and_then(|b| serde_json::from_slice::<serde_json::Value>(&b).ok())
+        .and_then(|v| v.get("verified").and_then(|m| m.as_object()).map(|m| {
+            m.iter().filter_map(|(k, v)| v.as_u64().map(|n| (k.clone(), n))).collect()
+        }))
+        .unwrap_or_default()
+}
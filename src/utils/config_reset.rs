@@ -0,0 +1,34 @@
+//! POST /config/reset: deleting the paths config, or clearing some of its fields, so the directories
+//! fall back to their built-in defaults.
+
+use std::io;
+use std::path::Path;
+
+use crate::models;
+
+/// Delete the paths config at `config_path`. A config that does not exist is already reset.
+pub fn reset_file(config_path: &Path) -> io::Result<()> {
+    match std::fs::remove_file(config_path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// Clear `fields` (names as in GET /config/paths `configured`) in `cfg`. An unknown name is an error
+/// and leaves `cfg` unchanged.
+pub fn clear_fields(cfg: &mut models::PathsConfig, fields: &[String]) -> Result<(), String> {
+    let mut cleared = cfg.clone();
+    for field in fields {
+        match field.trim() {
+            "projects_dir" => cleared.projects_dir = None,
+            "engines_dir" => cleared.engines_dir = None,
+            "cache_dir" => cleared.cache_dir = None,
+            "downloads_dir" => cleared.downloads_dir = None,
+            "file_concurrency" => cleared.file_concurrency = None,
+            "chunk_concurrency" => cleared.chunk_concurrency = None,
+            other => return Err(format!("Unknown config field: {}", other)),
+        }
+    }
+    *cfg = cleared;
+    Ok(())
+}
@@ -0,0 +1,127 @@
+//! What to do when an imported file already exists in the project.
+//!
+//! `Skip` and `Overwrite` are the two behaviours the old `overwrite` flag selected. `RenameNew` keeps
+//! the project's file and writes the incoming one next to it as `name_1.ext`, `name_2.ext`, ...;
+//! `Newer` overwrites only when the incoming file was modified more recently than the existing one.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Conflict policy for copies into an existing tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    Skip,
+    Overwrite,
+    RenameNew,
+    Newer,
+}
+
+impl ConflictPolicy {
+    /// Policy for a request: `on_conflict` wins when given, otherwise the legacy `overwrite` flag decides.
+    pub fn from_request(on_conflict: Option<&str>, overwrite: bool) -> Result<ConflictPolicy, String> {
+        match on_conflict.map(|s| s.trim().to_ascii_lowercase()).as_deref() {
+            None | Some("") => Ok(if overwrite { ConflictPolicy::Overwrite } else { ConflictPolicy::Skip }),
+            Some("skip") => Ok(ConflictPolicy::Skip),
+            Some("overwrite") => Ok(ConflictPolicy::Overwrite),
+            Some("rename_new") | Some("rename-new") | Some("rename") => Ok(ConflictPolicy::RenameNew),
+            Some("newer") => Ok(ConflictPolicy::Newer),
+            Some(other) => Err(format!("Unknown on_conflict '{}' (expected skip, overwrite, rename_new or newer)", other)),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ConflictPolicy::Skip => "skip",
+            ConflictPolicy::Overwrite => "overwrite",
+            ConflictPolicy::RenameNew => "rename_new",
+            ConflictPolicy::Newer => "newer",
+        }
+    }
+}
+
+/// What a copy does with one source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConflictAction {
+    /// The target does not exist yet.
+    Create(PathBuf),
+    /// Replace the existing target.
+    Overwrite(PathBuf),
+    /// Keep the existing target and write to this free name next to it.
+    Rename(PathBuf),
+    /// Leave the existing target alone.
+    Skip,
+}
+
+/// Per-action totals of a copy. `copied` counts every file written, including overwritten and renamed ones.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CopyCounts {
+    pub copied: usize,
+    pub skipped: usize,
+    pub overwritten: usize,
+    pub renamed: usize,
+}
+
+impl CopyCounts {
+    pub fn record(&mut self, action: &ConflictAction) {
+        match action {
+            ConflictAction::Create(_) => self.copied += 1,
+            ConflictAction::Overwrite(_) => {
+                self.copied += 1;
+                self.overwritten += 1;
+            }
+            ConflictAction::Rename(_) => {
+                self.copied += 1;
+                self.renamed += 1;
+            }
+            ConflictAction::Skip => self.skipped += 1,
+        }
+    }
+}
+
+/// Decide what to do with `src` given its destination `target`. Only reads metadata.
+pub fn decide(policy: ConflictPolicy, src: &Path, target: &Path) -> ConflictAction {
+    if !target.exists() {
+        return ConflictAction::Create(target.to_path_buf());
+    }
+    match policy {
+        ConflictPolicy::Skip => ConflictAction::Skip,
+        ConflictPolicy::Overwrite => ConflictAction::Overwrite(target.to_path_buf()),
+        ConflictPolicy::RenameNew => ConflictAction::Rename(free_name(target)),
+        ConflictPolicy::Newer => {
+            let modified = |p: &Path| fs::metadata(p).and_then(|m| m.modified()).ok();
+            match (modified(src), modified(target)) {
+                (Some(s), Some(t)) if s > t => ConflictAction::Overwrite(target.to_path_buf()),
+                _ => ConflictAction::Skip,
+            }
+        }
+    }
+}
+
+/// Carry out `action` for `src`, creating the parent folder as needed.
+pub fn apply(action: &ConflictAction, src: &Path) -> io::Result<()> {
+    let to = match action {
+        ConflictAction::Create(p) | ConflictAction::Overwrite(p) | ConflictAction::Rename(p) => p,
+        ConflictAction::Skip => return Ok(()),
+    };
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::copy(src, to).map(|_| ())
+}
+
+/// First `stem_N.ext` next to `target` that does not exist (N from 1).
+pub fn free_name(target: &Path) -> PathBuf {
+    let stem = target.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+    let ext = target.extension().map(|e| e.to_string_lossy().into_owned());
+    (1..)
+        .map(|n| {
+            let name = match &ext {
+                Some(ext) => format!("{}_{}.{}", stem, n, ext),
+                None => format!("{}_{}", stem, n),
+            };
+            target.with_file_name(name)
+        })
+        .find(|p| !p.exists())
+        .unwrap_or_else(|| target.to_path_buf())
+}
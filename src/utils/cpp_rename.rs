@@ -0,0 +1,208 @@
+//! Module renaming for C++ projects created from a template.
+//!
+//! Copying a cpp template and renaming its .uproject is not enough: Source/<Template>, the
+//! `.Target.cs`/`.Build.cs` rules, `<TEMPLATE>_API` macros, `IMPLEMENT_PRIMARY_GAME_MODULE` and the
+//! .uproject "Modules" list all still name the template, so the project won't compile. Only module
+//! names are rewritten, not class names; `/Script/<Template>` references in Config/*.ini are moved
+//! to the new module and DefaultEngine.ini gets ActiveGameNameRedirects so Blueprints saved against
+//! the template's module still load.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Files under Source/ whose contents are rewritten.
+const SOURCE_EXTENSIONS: &[&str] = &["cs", "h", "hpp", "cpp", "inl"];
+
+/// Suffixes UE templates put after the module name for related modules and target rules.
+const NAME_SUFFIXES: &[&str] = &["", "Editor", "Target", "EditorTarget"];
+
+/// What rename_modules changed, relative to the project directory.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct RenameReport {
+    pub renamed: Vec<(PathBuf, PathBuf)>,
+    pub rewritten: Vec<PathBuf>,
+}
+
+/// The template's primary game module: the .uproject module named like the project file when there is
+/// one, otherwise the first module listed.
+pub fn primary_module_name(uproject_text: &str, uproject_stem: &str) -> Option<String> {
+    let json: serde_json::Value = serde_json::from_str(uproject_text).ok()?;
+    let names: Vec<&str> = json.get("Modules")?.as_array()?.iter().filter_map(|m| m.get("Name")?.as_str()).collect();
+    names.iter().find(|n| **n == uproject_stem).or_else(|| names.first()).map(|n| n.to_string())
+}
+
+/// Replace whole identifiers found in `map`, leaving longer identifiers that merely contain them alone.
+pub fn replace_identifiers(text: &str, map: &HashMap<String, String>) -> String {
+    let is_ident = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(is_ident) {
+        out.push_str(&rest[..start]);
+        let word_len = rest[start..].find(|c: char| !is_ident(c)).unwrap_or(rest.len() - start);
+        let word = &rest[start..start + word_len];
+        out.push_str(map.get(word).map_or(word, String::as_str));
+        rest = &rest[start + word_len..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Identifier map for renaming module `old` to `new`: module names, their related target/editor names,
+/// and the `<MODULE>_API` export macros.
+pub fn identifier_map(old: &str, new: &str) -> HashMap<String, String> {
+    let mut map = HashMap::new();
+    for suffix in NAME_SUFFIXES {
+        map.insert(format!("{}{}", old, suffix), format!("{}{}", new, suffix));
+    }
+    for suffix in ["", "EDITOR"] {
+        map.insert(format!("{}{}_API", old.to_uppercase(), suffix), format!("{}{}_API", new.to_uppercase(), suffix));
+    }
+    map
+}
+
+/// Point `/Script/<old>` references at `/Script/<new>`.
+pub fn replace_script_paths(text: &str, old: &str, new: &str) -> String {
+    let from = format!("/Script/{}", old);
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(i) = rest.find(&from) {
+        let after = &rest[i + from.len()..];
+        out.push_str(&rest[..i]);
+        if after.starts_with(|c: char| c.is_ascii_alphanumeric() || c == '_') {
+            out.push_str(&from);
+        } else {
+            out.push_str("/Script/");
+            out.push_str(new);
+        }
+        rest = after;
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Rename module `old` to `new` throughout the project at `project_dir` (see the module docs).
+pub fn rename_modules(project_dir: &Path, old: &str, new: &str) -> io::Result<RenameReport> {
+    let mut report = RenameReport::default();
+    if old == new {
+        return Ok(report);
+    }
+    let map = identifier_map(old, new);
+    let source = project_dir.join("Source");
+
+    // Module folders first (Source/<Old>, Source/<Old>Editor), then files named after a module or target
+    for suffix in ["", "Editor"] {
+        let from = source.join(format!("{}{}", old, suffix));
+        let to = source.join(format!("{}{}", new, suffix));
+        if from.is_dir() && !to.exists() {
+            fs::rename(&from, &to)?;
+            report.renamed.push((rel(project_dir, &from), rel(project_dir, &to)));
+        }
+    }
+    let mut files = Vec::new();
+    if source.is_dir() {
+        for entry in walkdir::WalkDir::new(&source).follow_links(false) {
+            let entry = entry.map_err(io::Error::other)?;
+            if entry.file_type().is_file() {
+                files.push(entry.into_path());
+            }
+        }
+    }
+    for path in files {
+        let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else { continue };
+        let (stem, ext_tail) = file_name.split_once('.').unwrap_or((file_name, ""));
+        let path = match map.get(stem) {
+            Some(new_stem) if !ext_tail.is_empty() => {
+                let to = path.with_file_name(format!("{}.{}", new_stem, ext_tail));
+                fs::rename(&path, &to)?;
+                report.renamed.push((rel(project_dir, &path), rel(project_dir, &to)));
+                to
+            }
+            _ => path,
+        };
+        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        if SOURCE_EXTENSIONS.iter().any(|e| e.eq_ignore_ascii_case(ext)) {
+            rewrite(project_dir, &path, &mut report, |text| replace_identifiers(text, &map))?;
+        }
+    }
+
+    // .uproject module list
+    if let Ok(entries) = fs::read_dir(project_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) == Some("uproject") {
+                rewrite(project_dir, &path, &mut report, |text| rename_uproject_modules(text, &map))?;
+            }
+        }
+    }
+
+    // Config: move /Script/<Old> references and redirect assets saved against the old module
+    let config = project_dir.join("Config");
+    if let Ok(entries) = fs::read_dir(&config) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()).is_some_and(|e| e.eq_ignore_ascii_case("ini")) {
+                // Redirect lines must keep naming the old module
+                rewrite(project_dir, &path, &mut report, |text| {
+                    text.split_inclusive('\n')
+                        .map(|line| if line.contains("ActiveGameNameRedirects") { line.to_string() } else { replace_script_paths(line, old, new) })
+                        .collect()
+                })?;
+            }
+        }
+    }
+    let engine_ini = config.join("DefaultEngine.ini");
+    if !report.renamed.is_empty() {
+        let text = fs::read_to_string(&engine_ini).unwrap_or_default();
+        let redirects = format!(
+            "[/Script/Engine.Engine]\n+ActiveGameNameRedirects=(OldGameName=\"{old}\",NewGameName=\"/Script/{new}\")\n+ActiveGameNameRedirects=(OldGameName=\"/Script/{old}\",NewGameName=\"/Script/{new}\")\n",
+            old = old,
+            new = new
+        );
+        if !text.contains(&redirects) {
+            let sep = if text.is_empty() || text.ends_with("\n\n") { "" } else if text.ends_with('\n') { "\n" } else { "\n\n" };
+            fs::create_dir_all(&config)?;
+            fs::write(&engine_ini, format!("{}{}{}", text, sep, redirects))?;
+            let engine_ini = rel(project_dir, &engine_ini);
+            if !report.rewritten.contains(&engine_ini) {
+                report.rewritten.push(engine_ini);
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Rename entries of the .uproject "Modules" list; other fields are left as they are.
+fn rename_uproject_modules(text: &str, map: &HashMap<String, String>) -> String {
+    let Ok(mut json) = serde_json::from_str::<serde_json::Value>(text) else { return text.to_string() };
+    let mut changed = false;
+    if let Some(modules) = json.get_mut("Modules").and_then(|m| m.as_array_mut()) {
+        for module in modules {
+            let renamed = module.get("Name").and_then(|n| n.as_str()).and_then(|n| map.get(n)).cloned();
+            if let (Some(name), Some(obj)) = (renamed, module.as_object_mut()) {
+                obj.insert("Name".to_string(), serde_json::Value::String(name));
+                changed = true;
+            }
+        }
+    }
+    if !changed {
+        return text.to_string();
+    }
+    serde_json::to_string_pretty(&json).unwrap_or_else(|_| text.to_string())
+}
+
+fn rewrite(project_dir: &Path, path: &Path, report: &mut RenameReport, f: impl Fn(&str) -> String) -> io::Result<()> {
+    // Non-UTF-8 files (e.g. UTF-16 sources) are left alone
+    let Ok(text) = fs::read_to_string(path) else { return Ok(()) };
+    let updated = f(&text);
+    if updated != text {
+        fs::write(path, updated)?;
+        report.rewritten.push(rel(project_dir, path));
+    }
+    Ok(())
+}
+
+fn rel(root: &Path, path: &Path) -> PathBuf {
+    path.strip_prefix(root).unwrap_or(path).to_path_buf()
+}
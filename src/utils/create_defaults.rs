@@ -0,0 +1,28 @@
+//! Saved create-project defaults (GET/POST /config/create-defaults) and how /create-unreal-project
+//! falls back to them for fields a request leaves out.
+
+use crate::models;
+
+/// Fill fields a create request left out from the saved defaults. The default engine_path is only
+/// used when the request names neither an engine_path nor a `ue` version to pick one by.
+pub fn apply(req: &mut models::CreateUnrealProjectRequest, defaults: &models::CreateDefaults) {
+    let blank = |v: &Option<String>| v.as_deref().map_or(true, |s| s.trim().is_empty());
+    if blank(&req.engine_path) && blank(&req.ue) {
+        if let Some(p) = defaults.engine_path.clone() {
+            req.engine_path = Some(p);
+        }
+    }
+    if req.output_dir.trim().is_empty() {
+        if let Some(dir) = defaults.output_dir.clone() {
+            req.output_dir = dir;
+        }
+    }
+    if blank(&req.project_type) {
+        if let Some(t) = defaults.project_type.clone() {
+            req.project_type = Some(t);
+        }
+    }
+    if req.open_after_create.is_none() {
+        req.open_after_create = defaults.open_after_create;
+    }
+}
@@ -0,0 +1,132 @@
+//! Per-platform default locations for config, cache, downloads, projects and engines.
+//!
+//! Linux and macOS follow XDG with $HOME fallbacks. Windows uses %APPDATA% / %LOCALAPPDATA% and
+//! %USERPROFILE%. Every function takes the platform name (as returned by fs_scan::host_platform)
+//! and an environment lookup, so each platform's layout can be checked from any host.
+//! Config file and EGS_* env overrides are applied by the callers before falling back to these.
+
+use std::path::{Path, PathBuf};
+
+/// Environment lookup; empty values count as unset.
+pub type EnvLookup<'a> = &'a dyn Fn(&str) -> Option<String>;
+
+fn var(env: EnvLookup, key: &str) -> Option<PathBuf> {
+    env(key).filter(|v| !v.trim().is_empty()).map(PathBuf::from)
+}
+
+/// The user's home directory: %USERPROFILE% (or %HOMEDRIVE%%HOMEPATH%) on Windows, $HOME elsewhere.
+pub fn home_dir(platform: &str, env: EnvLookup) -> Option<PathBuf> {
+    if platform == "Windows" {
+        return var(env, "USERPROFILE")
+            .or_else(|| {
+                let drive = env("HOMEDRIVE").filter(|v| !v.is_empty())?;
+                let path = env("HOMEPATH").filter(|v| !v.is_empty())?;
+                Some(PathBuf::from(format!("{}{}", drive, path)))
+            })
+            .or_else(|| var(env, "HOME"));
+    }
+    var(env, "HOME")
+}
+
+/// Expand a leading `~/` and any `$HOME` in `path` to home_dir. Left as is when there is no home directory.
+pub fn expand_home(path: &str, platform: &str, env: EnvLookup) -> String {
+    let Some(home) = home_dir(platform, env).map(|h| h.to_string_lossy().into_owned()) else {
+        return path.to_string();
+    };
+    let mut t = path.to_string();
+    if t.starts_with("~/") {
+        t = t.replacen('~', &home, 1);
+    }
+    if t.contains("$HOME") {
+        t = t.replace("$HOME", &home);
+    }
+    t
+}
+
+/// A directory from config.json with surrounding whitespace/quotes stripped and home expanded, or
+/// `fallback` when it is unset or blank.
+pub fn configured_or(configured: Option<&str>, fallback: PathBuf, platform: &str, env: EnvLookup) -> PathBuf {
+    configured
+        .map(|v| {
+            let t = v.trim();
+            let unquoted = ['"', '\'']
+                .iter()
+                .find_map(|q| t.strip_prefix(*q).and_then(|s| s.strip_suffix(*q)))
+                .unwrap_or(t);
+            expand_home(unquoted, platform, env)
+        })
+        .filter(|v| !v.trim().is_empty())
+        .map(PathBuf::from)
+        .unwrap_or(fallback)
+}
+
+/// The string `field` ("cache_dir", "downloads_dir", ...) of the paths config at `config_path`; None when
+/// the file is missing or unreadable or the entry is unset.
+pub fn config_entry(config_path: &Path, field: &str) -> Option<String> {
+    let text = std::fs::read_to_string(config_path).ok()?;
+    let value: serde_json::Value = serde_json::from_str(&text).ok()?;
+    value.get(field)?.as_str().map(str::to_string)
+}
+
+/// configured_or for the `field` entry of the paths config at `config_path`.
+pub fn configured_dir(config_path: &Path, field: &str, fallback: PathBuf, platform: &str, env: EnvLookup) -> PathBuf {
+    configured_or(config_entry(config_path, field).as_deref(), fallback, platform, env)
+}
+
+/// Base for config files: %APPDATA% on Windows, $XDG_CONFIG_HOME or ~/.config elsewhere.
+pub fn config_base(platform: &str, env: EnvLookup) -> PathBuf {
+    if platform == "Windows" {
+        return var(env, "APPDATA")
+            .or_else(|| home_dir(platform, env).map(|h| h.join("AppData").join("Roaming")))
+            .unwrap_or_else(|| PathBuf::from("AppData").join("Roaming"));
+    }
+    var(env, "XDG_CONFIG_HOME")
+        .or_else(|| home_dir(platform, env).map(|h| h.join(".config")))
+        .unwrap_or_else(|| PathBuf::from(".config"))
+}
+
+/// Base for caches: %LOCALAPPDATA% on Windows, $XDG_CACHE_HOME or ~/.cache elsewhere.
+pub fn cache_base(platform: &str, env: EnvLookup) -> PathBuf {
+    if platform == "Windows" {
+        return local_app_data(platform, env);
+    }
+    var(env, "XDG_CACHE_HOME")
+        .or_else(|| home_dir(platform, env).map(|h| h.join(".cache")))
+        .unwrap_or_else(|| PathBuf::from(".cache"))
+}
+
+/// Base for downloaded data: %LOCALAPPDATA% on Windows, $XDG_DATA_HOME or ~/.local/share elsewhere.
+pub fn data_base(platform: &str, env: EnvLookup) -> PathBuf {
+    if platform == "Windows" {
+        return local_app_data(platform, env);
+    }
+    var(env, "XDG_DATA_HOME")
+        .or_else(|| home_dir(platform, env).map(|h| h.join(".local").join("share")))
+        .unwrap_or_else(|| PathBuf::from(".local").join("share"))
+}
+
+fn local_app_data(platform: &str, env: EnvLookup) -> PathBuf {
+    var(env, "LOCALAPPDATA")
+        .or_else(|| home_dir(platform, env).map(|h| h.join("AppData").join("Local")))
+        .unwrap_or_else(|| PathBuf::from("AppData").join("Local"))
+}
+
+/// Where Unreal keeps projects by default: <home>/Documents/Unreal Projects on every platform.
+pub fn projects_dir(platform: &str, env: EnvLookup) -> PathBuf {
+    home_dir(platform, env)
+        .map(|h| h.join("Documents").join("Unreal Projects"))
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// Where engines are looked for by default: the Epic launcher's install folder
+/// (%ProgramFiles%\Epic Games) on Windows, ~/UnrealEngines elsewhere.
+pub fn engines_dir(platform: &str, env: EnvLookup) -> PathBuf {
+    if platform == "Windows" {
+        return var(env, "ProgramFiles")
+            .map(|p| p.join("Epic Games"))
+            .unwrap_or_else(|| PathBuf::from(r"C:\Program Files\Epic Games"));
+    }
+    home_dir(platform, env)
+        .map(|h| h.join("UnrealEngines"))
+        .unwrap_or_else(|| PathBuf::from("."))
+}
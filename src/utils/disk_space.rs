@@ -0,0 +1,61 @@
+//! Free-space preflight for downloads.
+//!
+//! The actual free-space query lives in utils/mod.rs (fs2); this module does the arithmetic, message
+//! formatting and the count of what an earlier run already wrote, so it can be tested without a real
+//! filesystem of a given size.
+
+use std::path::{Path, PathBuf};
+
+/// Extra headroom required on top of the download size, in percent.
+pub const SAFETY_MARGIN_PERCENT: u64 = 10;
+
+/// Bytes needed for a download of `bytes`, including the safety margin.
+pub fn required_with_margin(bytes: u64) -> u64 {
+    bytes.saturating_add((bytes as u128 * SAFETY_MARGIN_PERCENT as u128 / 100) as u64)
+}
+
+/// Closest ancestor of `path` (or `path` itself) that exists, so free space can be queried
+/// before the download folder is created. Relative paths fall back to the current directory.
+pub fn nearest_existing_ancestor(path: &Path) -> Option<PathBuf> {
+    let found = path.ancestors().find(|p| !p.as_os_str().is_empty() && p.exists()).map(Path::to_path_buf);
+    found.or_else(|| path.is_relative().then(|| PathBuf::from(".")))
+}
+
+fn gb(bytes: u64) -> f64 {
+    bytes as f64 / (1024.0 * 1024.0 * 1024.0)
+}
+
+/// Ok when `available` covers `needed` plus the safety margin; otherwise a
+/// "Need X GB, only Y GB free at <path>" message.
+pub fn check_free_space(needed: u64, available: u64, at: &Path) -> Result<(), String> {
+    let required = required_with_margin(needed);
+    if available >= required {
+        return Ok(());
+    }
+    Err(format!(
+        "Need {:.2} GB ({:.2} GB + {}% margin), only {:.2} GB free at {}",
+        gb(required),
+        gb(needed),
+        SAFETY_MARGIN_PERCENT,
+        gb(available),
+        at.display()
+    ))
+}
+
+/// Final output path for a manifest file: <out_root>/data/<filename> (no extra data/ when out_root already is one).
+pub fn resolve_output_file_path(out_root: &Path, filename: &str) -> PathBuf {
+    if out_root.file_name().is_some_and(|name| name == "data") {
+        out_root.join(filename)
+    } else {
+        out_root.join("data").join(filename)
+    }
+}
+
+/// Bytes of the manifest files `(name, expected size)` already written under `out_root` by an earlier run,
+/// each counted up to its expected size. These need no free space again.
+pub fn bytes_already_on_disk(out_root: &Path, files: &[(String, u64)]) -> u64 {
+    files
+        .iter()
+        .map(|(name, expected)| std::fs::metadata(resolve_output_file_path(out_root, name)).map_or(0, |m| m.len().min(*expected)))
+        .sum()
+}
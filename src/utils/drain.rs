@@ -0,0 +1,61 @@
+//! Counts chunk transfers in flight so shutdown can wait for them to finish.
+//!
+//! On Ctrl+C, running downloads are paused (no new chunk starts) and shutdown waits here until
+//! the chunks already being written are complete, so temp/ holds only whole chunks for the resume.
+
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+#[derive(Default)]
+pub struct InFlight {
+    count: Mutex<usize>,
+    idle: Condvar,
+}
+
+/// One unit of in-flight work; finishing (dropping) it may wake wait_idle.
+pub struct InFlightGuard<'a> {
+    owner: &'a InFlight,
+}
+
+impl InFlight {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, usize> {
+        self.count.lock().unwrap_or_else(|p| p.into_inner())
+    }
+
+    pub fn enter(&self) -> InFlightGuard<'_> {
+        *self.lock() += 1;
+        InFlightGuard { owner: self }
+    }
+
+    pub fn count(&self) -> usize {
+        *self.lock()
+    }
+
+    /// Block until nothing is in flight or `timeout` passes; true when idle.
+    pub fn wait_idle(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        let mut count = self.lock();
+        while *count > 0 {
+            let now = Instant::now();
+            if now >= deadline {
+                return false;
+            }
+            count = self.idle.wait_timeout(count, deadline - now).unwrap_or_else(|p| p.into_inner()).0;
+        }
+        true
+    }
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        let mut count = self.owner.lock();
+        *count = count.saturating_sub(1);
+        if *count == 0 {
+            self.owner.idle.notify_all();
+        }
+    }
+}
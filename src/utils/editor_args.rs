@@ -0,0 +1,54 @@
+//! Command-line arguments for launching Unreal Editor.
+//!
+//! The editor is always spawned with one `Command::arg` per argument, never through a shell, so
+//! caller-supplied arguments can't inject commands; paths with spaces also stay a single argument.
+
+use std::collections::HashMap;
+
+/// Query toggles for common editor switches and the argument each one adds.
+pub const TOGGLES: &[(&str, &str)] = &[("vulkan", "-vulkan"), ("d3d12", "-d3d12"), ("log", "-log"), ("fullscreen", "-fullscreen")];
+
+/// Parse `extra_args`: a JSON array of strings (`["-ExecCmds=stat fps", "-log"]`) or a
+/// whitespace-separated list (`-log -fullscreen`).
+pub fn parse_extra_args(raw: &str) -> Result<Vec<String>, String> {
+    let trimmed = raw.trim();
+    if trimmed.starts_with('[') {
+        let args: Vec<String> = serde_json::from_str(trimmed).map_err(|e| format!("extra_args is not a JSON array of strings: {}", e))?;
+        return Ok(args.into_iter().filter(|a| !a.trim().is_empty()).collect());
+    }
+    Ok(trimmed.split_whitespace().map(str::to_string).collect())
+}
+
+/// Extra editor arguments from an open request's query: enabled toggles first, then `extra_args`.
+pub fn from_query(query: &HashMap<String, String>) -> Result<Vec<String>, String> {
+    let mut args: Vec<String> = TOGGLES
+        .iter()
+        .filter(|(key, _)| query.get(*key).is_some_and(|v| matches!(v.trim(), "1" | "true" | "yes")))
+        .map(|(_, arg)| arg.to_string())
+        .collect();
+    if let Some(raw) = query.get("extra_args") {
+        args.extend(parse_extra_args(raw)?);
+    }
+    Ok(args)
+}
+
+/// Whether a newly created project opens with `-NoCompile`: Blueprint projects do unless the caller says otherwise.
+pub fn default_no_compile(project_type: Option<&str>, no_compile: Option<bool>) -> bool {
+    no_compile.unwrap_or_else(|| project_type.unwrap_or("bp").eq_ignore_ascii_case("bp"))
+}
+
+/// Full argument vector after the editor binary: the .uproject path, `-NoCompile` when asked, then
+/// `extra` with repeated switches dropped (compared case-insensitively, as the editor does).
+pub fn editor_args(uproject: &str, no_compile: bool, extra: &[String]) -> Vec<String> {
+    let mut args = vec![uproject.to_string()];
+    if no_compile {
+        args.push("-NoCompile".to_string());
+    }
+    for arg in extra {
+        let repeated = arg.starts_with('-') && args.iter().any(|a| a.eq_ignore_ascii_case(arg));
+        if !repeated {
+            args.push(arg.clone());
+        }
+    }
+    args
+}
@@ -0,0 +1,80 @@
+//! Per-job Unreal Editor output logs (<cache>/editor-logs/<job id>.log).
+//!
+//! A watched editor writes stdout/stderr straight into this file instead of a pipe, so it never blocks
+//! on a full pipe or dies from SIGPIPE once the backend exits. The watcher reads the tail afterwards.
+//! Old logs are pruned before each watched launch: only the newest `KEEP_LOGS` from the last
+//! `MAX_LOG_AGE` are kept.
+
+use std::fs::{self, File};
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+pub const EDITOR_LOG_DIR: &str = "editor-logs";
+/// Lines of output reported as `output_tail` when the editor exits.
+pub const TAIL_LINES: usize = 200;
+/// Only the end of the log is read, however large it grew.
+const TAIL_BYTES: u64 = 256 * 1024;
+/// Logs kept in the log folder, newest first.
+pub const KEEP_LOGS: usize = 20;
+/// Logs last written longer ago than this are removed.
+pub const MAX_LOG_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Log file for a job; characters outside `[A-Za-z0-9_-]` in the id become `_`.
+pub fn log_path(cache_dir: &Path, job_id: &str) -> PathBuf {
+    let name: String = job_id.chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' }).collect();
+    cache_dir.join(EDITOR_LOG_DIR).join(format!("{}.log", name))
+}
+
+/// Remove `.log` files in `dir` beyond the newest `keep` or last modified more than `max_age` ago.
+/// Returns how many were removed; files that cannot be removed are skipped.
+pub fn prune_logs(dir: &Path, keep: usize, max_age: Duration) -> usize {
+    let Ok(entries) = fs::read_dir(dir) else { return 0 };
+    let mut logs: Vec<(SystemTime, PathBuf)> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && p.extension().is_some_and(|ext| ext == "log"))
+        .map(|p| (fs::metadata(&p).and_then(|m| m.modified()).unwrap_or(SystemTime::UNIX_EPOCH), p))
+        .collect();
+    logs.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+    let now = SystemTime::now();
+    let mut removed = 0;
+    for (i, (modified, path)) in logs.iter().enumerate() {
+        let too_old = now.duration_since(*modified).is_ok_and(|age| age > max_age);
+        if (i >= keep || too_old) && fs::remove_file(path).is_ok() {
+            removed += 1;
+        }
+    }
+    removed
+}
+
+/// Create (or truncate) the log and return one handle for stdout and one for stderr.
+pub fn open_log(path: &Path) -> io::Result<(File, File)> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let out = File::create(path)?;
+    let err = out.try_clone()?;
+    Ok((out, err))
+}
+
+/// Last `max` lines of the log (invalid UTF-8 replaced); empty when it cannot be read.
+pub fn tail_lines(path: &Path, max: usize) -> Vec<String> {
+    let Ok(mut f) = File::open(path) else { return Vec::new() };
+    let len = f.metadata().map(|m| m.len()).unwrap_or(0);
+    let start = len.saturating_sub(TAIL_BYTES);
+    if f.seek(SeekFrom::Start(start)).is_err() {
+        return Vec::new();
+    }
+    let mut buf = Vec::new();
+    if f.read_to_end(&mut buf).is_err() {
+        return Vec::new();
+    }
+    let text = String::from_utf8_lossy(&buf);
+    let mut lines: Vec<&str> = text.lines().collect();
+    // Reading started mid-file: the first line is probably cut off
+    if start > 0 && !lines.is_empty() {
+        lines.remove(0);
+    }
+    lines[lines.len().saturating_sub(max)..].iter().map(|l| l.to_string()).collect()
+}
@@ -0,0 +1,33 @@
+//! Duplicate engine install detection.
+//!
+//! Two engine directories are considered duplicates when they resolve to the same version and
+//! their BuildIds agree (or at least one BuildId is unknown). The first directory by path order
+//! is treated as the original; the rest point at it via `duplicate_of`.
+
+/// For each `(version, build_id, path)` entry, returns the path of the original it duplicates, if any.
+pub fn find_duplicates(entries: &[(&str, Option<&str>, &str)]) -> Vec<Option<String>> {
+    let mut order: Vec<usize> = (0..entries.len()).collect();
+    order.sort_by(|&a, &b| entries[a].2.cmp(entries[b].2));
+
+    let mut result: Vec<Option<String>> = vec![None; entries.len()];
+    for (pos, &i) in order.iter().enumerate() {
+        let (version, build_id, _) = entries[i];
+        if version == "unknown" || version.is_empty() {
+            continue;
+        }
+        // Earliest (by path) non-duplicate with the same version and compatible BuildId
+        let original = order[..pos].iter().copied().find(|&j| {
+            let (v, b, _) = entries[j];
+            result[j].is_none()
+                && v == version
+                && match (b, build_id) {
+                    (Some(x), Some(y)) => x.eq_ignore_ascii_case(y),
+                    _ => true,
+                }
+        });
+        if let Some(j) = original {
+            result[i] = Some(entries[j].2.to_string());
+        }
+    }
+    result
+}
@@ -0,0 +1,68 @@
+//! Engine installs for /list-unreal-engines and engine selection: what scan_engine_dirs finds under a
+//! base folder, whether each install has an editor binary, and which installs are copies of another.
+
+use std::fs;
+use std::path::Path;
+
+use super::{engine_dupes, fs_scan};
+use crate::models;
+
+/// Read BuildId from Engine/Build/Build.version if present
+pub fn read_build_id(engine_dir: &Path) -> Option<String> {
+    let build_file = fs_scan::build_version_path(engine_dir);
+    if let Ok(bytes) = fs::read(&build_file) {
+        match serde_json::from_slice::<serde_json::Value>(&bytes) {
+            Ok(v) => {
+                if let Some(id) = v.get("BuildId").and_then(|x| x.as_str()) {
+                    let s = id.trim();
+                    if !s.is_empty() { return Some(s.to_string()); }
+                }
+            }
+            Err(e) => log::warn!("Cannot read BuildId from malformed {}: {}", build_file.display(), e),
+        }
+    }
+    None
+}
+
+/// Engine installs directly under `base`, sorted by path, with duplicates flagged.
+pub fn discover_engines(base: &Path) -> Vec<models::UnrealEngineInfo> {
+    let mut engines = Vec::new();
+    if !base.is_dir() {
+        return engines;
+    }
+
+    for e in fs_scan::scan_engine_dirs(base) {
+        let editor_available = e.editor_path.is_some();
+        engines.push(models::UnrealEngineInfo {
+            name: e.name,
+            version: e.version,
+            version_source: e.version_source.to_string(),
+            path: e.path.to_string_lossy().to_string(),
+            editor_path: e.editor_path.map(|pp| pp.to_string_lossy().to_string()),
+            editor_available,
+            reason: e.missing_editor.clone(),
+            launchable: editor_available,
+            launch_problem: e.missing_editor,
+            duplicate_of: None,
+        });
+    }
+    // Stable order so engine selection is deterministic, then flag copies of the same install
+    engines.sort_by(|a, b| a.path.cmp(&b.path));
+    flag_duplicate_engines(&mut engines);
+    engines
+}
+
+/// Set `duplicate_of` on engines that share a resolved version and BuildId with another install.
+pub fn flag_duplicate_engines(engines: &mut [models::UnrealEngineInfo]) {
+    let build_ids: Vec<Option<String>> = engines.iter().map(|e| read_build_id(Path::new(&e.path))).collect();
+    let entries: Vec<(&str, Option<&str>, &str)> = engines.iter().zip(build_ids.iter())
+        .map(|(e, b)| (e.version.as_str(), b.as_deref(), e.path.as_str()))
+        .collect();
+    let dupes = engine_dupes::find_duplicates(&entries);
+    for (engine, dup) in engines.iter_mut().zip(dupes) {
+        if let Some(ref original) = dup {
+            log::warn!("Engine {} looks like a duplicate of {} (same version {})", engine.path, original, engine.version);
+        }
+        engine.duplicate_of = dup;
+    }
+}
@@ -0,0 +1,73 @@
+//! Engines registered on this machine, for resolving a project's EngineAssociation.
+//!
+//! Launcher installs are associated by version ("5.3"), but a source-built engine registers itself
+//! under an identifier (usually a `{GUID}`, sometimes a custom name) that projects then record.
+//! Unreal keeps those registrations in HKCU\Software\Epic Games\Unreal Engine\Builds on Windows and
+//! in the [Installations] section of Install.ini elsewhere.
+
+use std::path::{Path, PathBuf};
+
+/// Registry key listing source-built engines on Windows (value name = identifier, data = engine dir).
+pub const WINDOWS_BUILDS_KEY: &str = r"HKCU\Software\Epic Games\Unreal Engine\Builds";
+
+/// Identifier as compared: trimmed with surrounding braces removed. Compare case-insensitively.
+pub fn normalize_id(id: &str) -> &str {
+    let t = id.trim();
+    t.strip_prefix('{').and_then(|s| s.strip_suffix('}')).unwrap_or(t).trim()
+}
+
+/// Where Unreal writes Install.ini under `home`; None on Windows, which uses the registry.
+pub fn install_ini_path(platform: &str, home: &Path) -> Option<PathBuf> {
+    match platform {
+        "Windows" => None,
+        "macOS" => Some(home.join("Library/Application Support/Epic/UnrealEngine/Install.ini")),
+        _ => Some(home.join(".config/Epic/UnrealEngine/Install.ini")),
+    }
+}
+
+/// `identifier=engine dir` entries of Install.ini's [Installations] section.
+pub fn parse_install_ini(text: &str) -> Vec<(String, PathBuf)> {
+    let mut in_installations = false;
+    let mut out = Vec::new();
+    for line in text.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_installations = line.eq_ignore_ascii_case("[Installations]");
+            continue;
+        }
+        if !in_installations || line.starts_with(';') || line.starts_with('#') {
+            continue;
+        }
+        if let Some((id, dir)) = line.split_once('=') {
+            let dir = dir.trim().trim_matches('"');
+            if !id.trim().is_empty() && !dir.is_empty() {
+                out.push((id.trim().to_string(), PathBuf::from(dir)));
+            }
+        }
+    }
+    out
+}
+
+/// Values printed by `reg query` for WINDOWS_BUILDS_KEY, one `<identifier>    REG_SZ    <engine dir>` per line.
+pub fn parse_reg_query(output: &str) -> Vec<(String, PathBuf)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (id, dir) = line.split_once("REG_SZ")?;
+            let (id, dir) = (id.trim(), dir.trim());
+            (!id.is_empty() && !dir.is_empty()).then(|| (id.to_string(), PathBuf::from(dir)))
+        })
+        .collect()
+}
+
+/// Engine directory registered under `assoc`, matching identifiers with or without braces.
+pub fn lookup<'a>(registered: &'a [(String, PathBuf)], assoc: &str) -> Option<&'a Path> {
+    let wanted = normalize_id(assoc);
+    if wanted.is_empty() {
+        return None;
+    }
+    registered
+        .iter()
+        .find(|(id, _)| normalize_id(id).eq_ignore_ascii_case(wanted))
+        .map(|(_, dir)| dir.as_path())
+}
@@ -0,0 +1,108 @@
+//! Per-job replay buffer for WebSocket events.
+//!
+//! Holds the most recent events for clients that subscribe late. Reading is non-destructive, so several
+//! tabs (or a reconnect) subscribing to the same job all get the same replay. Terminal events (complete,
+//! error, cancelled) are "sticky": the size cap never evicts them, and once one arrives the whole buffer
+//! becomes purgeable after its grace window (see is_expired).
+//!
+//! A JobChannel pairs the buffer with the job's live broadcast channel, so a subscriber receives each
+//! event exactly once: either in its replay or live.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use tokio::sync::broadcast;
+
+pub struct EventBuffer {
+    cap: usize,
+    events: VecDeque<(String, Option<Instant>)>,
+    purge_after: Option<Instant>,
+}
+
+impl EventBuffer {
+    pub fn new(cap: usize) -> Self {
+        EventBuffer { cap: cap.max(1), events: VecDeque::with_capacity(cap), purge_after: None }
+    }
+
+    /// Append an event. `sticky_for` marks a terminal event: it is kept across the cap for that long,
+    /// and the buffer may be purged once that window has passed.
+    pub fn push(&mut self, json: String, sticky_for: Option<Duration>) {
+        let now = Instant::now();
+        // Expired sticky events become ordinary ones
+        for (_, until) in self.events.iter_mut() {
+            if until.is_some_and(|u| u <= now) { *until = None; }
+        }
+        while self.events.len() >= self.cap {
+            // Evict the oldest ordinary event; only fall back to the oldest sticky one if nothing else is left
+            match self.events.iter().position(|(_, until)| until.is_none()) {
+                Some(i) => { self.events.remove(i); }
+                None => { self.events.pop_front(); }
+            }
+        }
+        let until = sticky_for.map(|d| now + d);
+        if until.is_some() { self.purge_after = until; }
+        self.events.push_back((json, until));
+    }
+
+    /// All buffered events in order, leaving the buffer untouched for other subscribers.
+    pub fn snapshot(&self) -> Vec<String> {
+        self.events.iter().map(|(json, _)| json.clone()).collect()
+    }
+
+    /// True once the job has emitted a terminal event and its grace window has passed.
+    pub fn is_expired(&self, now: Instant) -> bool {
+        self.purge_after.is_some_and(|t| t <= now)
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+/// A job's replay buffer and its live broadcast channel.
+///
+/// `publish` takes `&mut self` and `subscribe` takes `&self`, so while the caller holds the channel
+/// (e.g. a map entry) neither can interleave with the other: an event published before a subscribe is
+/// in that subscriber's replay, one published after reaches its receiver, and none shows up in both.
+pub struct JobChannel {
+    buffer: EventBuffer,
+    tx: broadcast::Sender<String>,
+}
+
+impl JobChannel {
+    /// `cap` bounds the replay buffer; the live channel holds up to 128 events per slow receiver.
+    pub fn new(cap: usize) -> Self {
+        let (tx, _rx) = broadcast::channel::<String>(128);
+        JobChannel { buffer: EventBuffer::new(cap), tx }
+    }
+
+    /// Send an event to current subscribers and buffer it for later ones (see EventBuffer::push).
+    pub fn publish(&mut self, json: String, sticky_for: Option<Duration>) {
+        let _ = self.tx.send(json.clone());
+        self.buffer.push(json, sticky_for);
+    }
+
+    /// The replay so far plus a receiver for every event published after it.
+    pub fn subscribe(&self) -> (Vec<String>, broadcast::Receiver<String>) {
+        (self.buffer.snapshot(), self.tx.subscribe())
+    }
+
+    /// True when nothing is buffered for replay.
+    pub fn replay_is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// Drop the replay once its grace window has passed. Returns true when the channel can be removed
+    /// too: the replay expired and nobody is still subscribed.
+    pub fn expire(&mut self, now: Instant) -> bool {
+        if !self.buffer.is_expired(now) {
+            return false;
+        }
+        self.buffer = EventBuffer::new(self.buffer.cap);
+        self.tx.receiver_count() == 0
+    }
+}
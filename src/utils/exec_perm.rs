@@ -0,0 +1,54 @@
+//! Executable-permission checks for the editor launch path.
+//!
+//! Spawning an editor binary that lost its execute bit (e.g., after an archive extract or a copy
+//! from another filesystem) fails with a bare "Permission denied". These helpers turn that into an
+//! actionable message and, when asked, restore the bit the same way main.rs does for the Flutter UI.
+
+use std::io;
+use std::path::Path;
+
+/// True when `path` has at least one execute bit set. Always true on non-Unix platforms.
+pub fn is_executable(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::metadata(path).is_ok_and(|m| m.permissions().mode() & 0o111 != 0)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        true
+    }
+}
+
+/// Add rwxr-xr-x to `path` when it is not executable. Returns whether the mode was changed.
+pub fn ensure_executable(path: &Path) -> io::Result<bool> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(path)?.permissions().mode();
+        if mode & 0o111 != 0 {
+            return Ok(false);
+        }
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode | 0o755))?;
+        Ok(true)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        Ok(false)
+    }
+}
+
+/// Describe a failed editor spawn. A permission error on a binary without an execute bit gets a
+/// remediation hint; anything else is relayed as the OS error.
+pub fn describe_spawn_error(path: &Path, err: &io::Error) -> String {
+    if err.kind() == io::ErrorKind::PermissionDenied && !is_executable(path) {
+        return format!(
+            "editor binary {} is not executable; run chmod +x \"{}\" or reinstall the engine (or retry with fix_permissions=true)",
+            path.display(),
+            path.display()
+        );
+    }
+    err.to_string()
+}
@@ -0,0 +1,39 @@
+//! Concurrency-safe access to the Fab library cache (cache/fab_list.json).
+//!
+//! Every handler that rewrites the cache (serving with re-annotation, refresh, download completion,
+//! /reannotate) holds lock_fab_cache() across its read-modify-write and replaces the file through
+//! write_fab_cache_atomic, so readers only ever see a complete file.
+
+use std::fs;
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+// Process-wide guard for fab_list.json read-modify-write cycles.
+static FAB_CACHE_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+
+/// Acquire the process-wide lock guarding fab_list.json.
+///
+/// Hold the returned guard across the whole read-modify-write so concurrent handlers
+/// (download completion, refresh, serving with re-annotation) cannot interleave.
+/// A poisoned lock is recovered since the guarded data lives on disk, not in the mutex.
+pub fn lock_fab_cache() -> MutexGuard<'static, ()> {
+    FAB_CACHE_LOCK
+        .get_or_init(|| Mutex::new(()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Atomically replace the FAB cache file: write to a sibling temp file, then rename over the target.
+///
+/// Callers should hold lock_fab_cache() while calling this.
+pub fn write_fab_cache_atomic(cache_path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+    if let Some(parent) = cache_path.parent() { fs::create_dir_all(parent)?; }
+    let tmp_path = cache_path.with_extension("json.tmp");
+    {
+        let mut f = fs::File::create(&tmp_path)?;
+        f.write_all(bytes)?;
+        f.sync_all()?;
+    }
+    fs::rename(&tmp_path, cache_path)
+}
@@ -0,0 +1,105 @@
+//! File hash verification for downloaded files.
+//!
+//! Manifests carry a hex digest per file. The algorithm is inferred from its length:
+//! 40 hex characters is SHA1, 64 is SHA256.
+
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashAlgo {
+    Sha1,
+    Sha256,
+}
+
+impl HashAlgo {
+    /// Algorithm for a hex digest, or None when it is neither a SHA1 nor a SHA256 hex string.
+    pub fn detect(expected: &str) -> Option<HashAlgo> {
+        let e = expected.trim();
+        if !e.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        match e.len() {
+            40 => Some(HashAlgo::Sha1),
+            64 => Some(HashAlgo::Sha256),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HashAlgo::Sha1 => "sha1",
+            HashAlgo::Sha256 => "sha256",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum HashError {
+    Io(std::io::Error),
+    /// The expected value is not a 40- or 64-character hex digest.
+    UnsupportedFormat(String),
+    Mismatch { algo: HashAlgo, expected: String, actual: String },
+}
+
+impl std::fmt::Display for HashError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HashError::Io(e) => write!(f, "failed to read file for hashing: {}", e),
+            HashError::UnsupportedFormat(h) => write!(f, "unsupported hash format '{}' (expected 40 or 64 hex chars)", h),
+            HashError::Mismatch { algo, expected, actual } => {
+                write!(f, "{} mismatch (expected {}, got {})", algo.as_str().to_uppercase(), expected, actual)
+            }
+        }
+    }
+}
+
+impl std::error::Error for HashError {}
+
+/// A downloaded file that must fail the whole download, not only its own task.
+#[derive(Debug)]
+pub enum FileRejected {
+    /// The assembled file does not match the manifest hash.
+    HashMismatch { file: String, error: HashError },
+}
+
+impl std::fmt::Display for FileRejected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileRejected::HashMismatch { file, error } => write!(f, "hash mismatch for {}: {}", file, error),
+        }
+    }
+}
+
+impl std::error::Error for FileRejected {}
+
+/// Hex digest of a file with the given algorithm, read in 1 MiB blocks.
+pub fn hash_file(path: &Path, algo: HashAlgo) -> std::io::Result<String> {
+    fn run<D: sha2::Digest>(mut hasher: D, path: &Path) -> std::io::Result<String> {
+        let mut f = fs::File::open(path)?;
+        let mut buffer = vec![0u8; 1024 * 1024];
+        loop {
+            let n = f.read(&mut buffer)?;
+            if n == 0 { break; }
+            hasher.update(&buffer[..n]);
+        }
+        Ok(hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect())
+    }
+    match algo {
+        HashAlgo::Sha1 => run(sha1::Sha1::default(), path),
+        HashAlgo::Sha256 => run(sha2::Sha256::default(), path),
+    }
+}
+
+/// Check a file against an expected hex digest, picking SHA1 or SHA256 from its length.
+pub fn verify_file_hash(path: &Path, expected: &str) -> Result<(), HashError> {
+    let algo = HashAlgo::detect(expected).ok_or_else(|| HashError::UnsupportedFormat(expected.to_string()))?;
+    let actual = hash_file(path, algo).map_err(HashError::Io)?;
+    let expected = expected.trim().to_ascii_lowercase();
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(HashError::Mismatch { algo, expected, actual })
+    }
+}
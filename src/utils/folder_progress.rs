@@ -0,0 +1,44 @@
+//! Per-folder completion tracking for detailed download progress.
+//!
+//! Groups manifest files by their first path component under data/ (e.g., "Content", "Config")
+//! and counts finished files per group. Files at the top level are grouped under ".".
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+pub struct FolderProgress {
+    folders: Mutex<BTreeMap<String, (usize, usize)>>,
+}
+
+/// First-level directory of a manifest path ("Content/Props/Crate.uasset" -> "Content").
+pub fn top_level_folder(filename: &str) -> &str {
+    let trimmed = filename.trim_start_matches(['/', '\\']);
+    match trimmed.find(['/', '\\']) {
+        Some(i) => &trimmed[..i],
+        None => ".",
+    }
+}
+
+impl FolderProgress {
+    pub fn new<'a>(filenames: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut folders: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+        for name in filenames {
+            folders.entry(top_level_folder(name).to_string()).or_default().1 += 1;
+        }
+        FolderProgress { folders: Mutex::new(folders) }
+    }
+
+    /// Count one more finished file in its folder; unknown files are ignored.
+    pub fn mark_done(&self, filename: &str) {
+        let mut folders = self.folders.lock().unwrap_or_else(|p| p.into_inner());
+        if let Some(entry) = folders.get_mut(top_level_folder(filename)) {
+            entry.0 = (entry.0 + 1).min(entry.1);
+        }
+    }
+
+    /// (folder, files_done, files_total) sorted by folder name.
+    pub fn snapshot(&self) -> Vec<(String, usize, usize)> {
+        let folders = self.folders.lock().unwrap_or_else(|p| p.into_inner());
+        folders.iter().map(|(name, (done, total))| (name.clone(), *done, *total)).collect()
+    }
+}
@@ -0,0 +1,623 @@
+//! Filesystem scanning helpers that operate on an explicit root.
+//!
+//! Nothing here reads env vars or default directories, so tests can point these at a temp tree.
+//! The wrappers in utils/mod.rs supply the real roots (downloads dir, projects dir, engines dir).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Checks whether a download directory contains a completion marker created after a successful download.
+pub fn is_download_complete(root: &Path) -> bool {
+    // Only trust the explicit completion marker to avoid false positives after cancellations.
+    root.join(".download_complete").is_file()
+}
+
+pub fn read_build_version(engine_dir: &Path) -> Option<String> {
+    match parse_build_version_file(engine_dir) {
+        Ok(v) => v,
+        Err(reason) => {
+            log::warn!("ignoring malformed {}: {}", build_version_path(engine_dir).display(), reason);
+            None
+        }
+    }
+}
+
+/// Engine/Build/Build.version under an engine root.
+pub fn build_version_path(engine_dir: &Path) -> PathBuf {
+    engine_dir.join("Engine").join("Build").join("Build.version")
+}
+
+/// Read Major/Minor/Patch from Engine/Build/Build.version.
+///
+/// Ok(None) when the file does not exist; Err(reason) when it exists but is unreadable, not JSON,
+/// or has no positive MajorVersion.
+pub fn parse_build_version_file(engine_dir: &Path) -> Result<Option<String>, String> {
+    let bytes = match fs::read(build_version_path(engine_dir)) {
+        Ok(b) => b,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.to_string()),
+    };
+    let v = serde_json::from_slice::<serde_json::Value>(&bytes).map_err(|e| format!("invalid JSON: {}", e))?;
+    let major = v.get("MajorVersion").and_then(|x| x.as_u64()).unwrap_or(0);
+    let minor = v.get("MinorVersion").and_then(|x| x.as_u64()).unwrap_or(0);
+    let patch = v.get("PatchVersion").and_then(|x| x.as_u64()).unwrap_or(0);
+    if major == 0 {
+        return Err("missing or invalid MajorVersion".to_string());
+    }
+    if patch > 0 {
+        Ok(Some(format!("{}.{}.{}", major, minor, patch)))
+    } else {
+        Ok(Some(format!("{}.{}", major, minor)))
+    }
+}
+
+/// Resolve an engine's version and where it came from: "build.version", "folder-name" or "unknown".
+pub fn detect_engine_version(engine_dir: &Path, name: &str) -> (String, &'static str) {
+    if let Some(v) = read_build_version(engine_dir) {
+        return (v, "build.version");
+    }
+    match parse_version_from_name(name) {
+        Some(v) => (v, "folder-name"),
+        None => ("unknown".to_string(), "unknown"),
+    }
+}
+
+/// Editor locations per platform, relative to the engine root (UE5 first, then UE4).
+pub const LINUX_EDITORS: &[&str] = &[
+    "Engine/Binaries/Linux/UnrealEditor",
+    "Engine/Binaries/Linux/UE4Editor",
+    "Engine/Binaries/Linux/UnrealEditor.app/Contents/MacOS/UnrealEditor", // in case of mac-like layout copied
+];
+pub const WINDOWS_EDITORS: &[&str] = &[
+    "Engine/Binaries/Win64/UnrealEditor.exe",
+    "Engine/Binaries/Win64/UE4Editor.exe",
+];
+pub const MAC_EDITORS: &[&str] = &[
+    "Engine/Binaries/Mac/UnrealEditor.app/Contents/MacOS/UnrealEditor",
+    "Engine/Binaries/Mac/UE4Editor.app/Contents/MacOS/UE4Editor",
+];
+
+const PLATFORM_EDITORS: &[(&str, &[&str])] = &[
+    ("Linux", LINUX_EDITORS),
+    ("Windows", WINDOWS_EDITORS),
+    ("macOS", MAC_EDITORS),
+];
+
+/// Name of the platform this binary was built for: "Windows", "macOS" or "Linux".
+pub fn host_platform() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "Windows"
+    } else if cfg!(target_os = "macos") {
+        "macOS"
+    } else {
+        "Linux"
+    }
+}
+
+/// Platform editor lists in search order: `platform` first, then every other platform as a
+/// fallback (e.g., an engine folder copied from another machine).
+fn editors_in_search_order(platform: &str) -> impl Iterator<Item = &'static (&'static str, &'static [&'static str])> + '_ {
+    let native = PLATFORM_EDITORS.iter().filter(move |(os, _)| *os == platform);
+    let others = PLATFORM_EDITORS.iter().filter(move |(os, _)| *os != platform);
+    native.chain(others)
+}
+
+/// Editor candidates relative to the engine root, in the order find_editor_binary_for probes them.
+pub fn editor_candidates_for(platform: &str) -> Vec<&'static str> {
+    editors_in_search_order(platform).flat_map(|(_, rels)| rels.iter().copied()).collect()
+}
+
+/// Locate the editor binary for the host platform, falling back to other platforms' layouts.
+pub fn find_editor_binary(engine_dir: &Path) -> Option<PathBuf> {
+    find_editor_binary_for(engine_dir, host_platform())
+}
+
+pub fn find_editor_binary_for(engine_dir: &Path, platform: &str) -> Option<PathBuf> {
+    for (os, rels) in editors_in_search_order(platform) {
+        if let Some(found) = rels.iter().map(|rel| engine_dir.join(rel)).find(|c| c.is_file()) {
+            if *os != platform {
+                log::warn!("no {} editor in {}; using {} editor {}", platform, engine_dir.display(), os, found.display());
+            }
+            return Some(found);
+        }
+    }
+    None
+}
+
+/// Explain why no editor was found in `engine_dir`: which paths were checked, and that the
+/// install may not be built yet.
+pub fn missing_editor_message(engine_dir: &Path) -> String {
+    let looked: Vec<String> = editor_candidates_for(host_platform()).iter().map(|rel| engine_dir.join(rel).to_string_lossy().to_string()).collect();
+    format!(
+        "No editor binary found in {} (looked for: {}). The engine may not be built yet (source installs need the editor target compiled).",
+        engine_dir.display(), looked.join(", ")
+    )
+}
+
+pub fn parse_version_from_name(name: &str) -> Option<String> {
+    // Extract first digit-sequence like 5, 5.2, 5.2.1
+    let mut version = String::new();
+    let mut seen_digit = false;
+    for ch in name.chars() {
+        if ch.is_ascii_digit() {
+            version.push(ch);
+            seen_digit = true;
+        } else if ch == '.' && seen_digit {
+            version.push(ch);
+        } else if seen_digit {
+            break;
+        }
+    }
+    if !version.is_empty() { Some(version) } else { None }
+}
+
+/// Bounds for directory searches. Depth alone does not bound a very wide tree, so the number of
+/// directory entries examined is capped as well.
+#[derive(Clone, Copy, Debug)]
+pub struct SearchLimits {
+    pub max_depth: usize,
+    pub max_entries: usize,
+}
+
+/// A search gave up after examining `limit` entries under `start`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct SearchScopeExceeded {
+    pub start: PathBuf,
+    pub limit: usize,
+}
+
+impl std::fmt::Display for SearchScopeExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "search scope exceeded: examined {} entries under {} without finding a match; specify the path explicitly",
+            self.limit,
+            self.start.display()
+        )
+    }
+}
+
+/// Counts examined entries against SearchLimits::max_entries.
+struct EntryBudget<'a> {
+    start: &'a Path,
+    seen: usize,
+    limit: usize,
+}
+
+impl EntryBudget<'_> {
+    fn take(&mut self) -> Result<(), SearchScopeExceeded> {
+        self.seen += 1;
+        if self.seen > self.limit {
+            return Err(SearchScopeExceeded { start: self.start.to_path_buf(), limit: self.limit });
+        }
+        Ok(())
+    }
+}
+
+/// Breadth-first search for a .uproject under `start` (or `start` itself when it is one).
+///
+/// Content/.git/.svn folders are not descended into. Errors when more than `limits.max_entries`
+/// directory entries would have to be examined.
+pub fn find_uproject_bfs(start: &Path, limits: SearchLimits) -> Result<Option<PathBuf>, SearchScopeExceeded> {
+    use std::collections::VecDeque;
+
+    if limits.max_depth == 0 {
+        return Ok(None);
+    }
+
+    let mut budget = EntryBudget { start, seen: 0, limit: limits.max_entries };
+    let mut queue: VecDeque<(PathBuf, usize)> = VecDeque::new();
+    queue.push_back((start.to_path_buf(), 0));
+
+    while let Some((dir, depth)) = queue.pop_front() {
+        // If it's a file, check if it's a .uproject
+        if dir.is_file() {
+            if dir.extension().and_then(|s| s.to_str()) == Some("uproject") {
+                return Ok(Some(dir));
+            }
+            continue;
+        }
+
+        if !dir.is_dir() {
+            continue;
+        }
+
+        // Check current directory for .uproject files, collecting subdirectories as we go
+        let mut subdirs = Vec::new();
+        if let Ok(entries) = fs::read_dir(&dir) {
+            for entry in entries.flatten() {
+                budget.take()?;
+                let p = entry.path();
+                if p.is_file() && p.extension().and_then(|s| s.to_str()) == Some("uproject") {
+                    return Ok(Some(p));
+                }
+                if p.is_dir() {
+                    subdirs.push(p);
+                }
+            }
+        }
+
+        if depth >= limits.max_depth {
+            continue;
+        }
+
+        // Enqueue subdirectories (excluding common non-project dirs)
+        for p in subdirs {
+            if let Some(name) = p.file_name().and_then(|s| s.to_str()) {
+                let lname = name.to_ascii_lowercase();
+                if lname == "content" || lname == ".git" || lname == ".svn" {
+                    continue;
+                }
+            }
+            queue.push_back((p, depth + 1));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Breadth-first search for an asset's Content folder under `data_dir` (case-insensitive name).
+///
+/// A Content folder inside Plugins/Marketplace wins immediately; otherwise the shallowest one found.
+/// Errors when more than `limits.max_entries` directory entries would have to be examined.
+pub fn find_content_dir_bfs(data_dir: &Path, limits: SearchLimits) -> Result<Option<PathBuf>, SearchScopeExceeded> {
+    use std::collections::VecDeque;
+
+    let mut budget = EntryBudget { start: data_dir, seen: 0, limit: limits.max_entries };
+    let mut queue: VecDeque<(PathBuf, usize)> = VecDeque::new();
+    queue.push_back((data_dir.to_path_buf(), 0));
+    let mut found: Option<PathBuf> = None;
+    while let Some((dir, depth)) = queue.pop_front() {
+        if depth > limits.max_depth { continue; }
+        if let Ok(entries) = fs::read_dir(&dir) {
+            for ent in entries.flatten() {
+                budget.take()?;
+                let p = ent.path();
+                if p.is_dir() {
+                    if let Some(name) = p.file_name().and_then(|s| s.to_str()) {
+                        if name.eq_ignore_ascii_case("Content") {
+                            let lower = p.to_string_lossy().to_lowercase();
+                            if lower.contains("plugins/marketplace") {
+                                return Ok(Some(p));
+                            }
+                            if found.is_none() { found = Some(p.clone()); }
+                        }
+                    }
+                    queue.push_back((p, depth + 1));
+                }
+            }
+        }
+    }
+    Ok(found)
+}
+
+/// The plugin descriptor (.uplugin) for an asset whose Content folder is `content_dir`, i.e. the first
+/// .uplugin (by name) directly inside its parent folder. None for plain content packs.
+pub fn find_uplugin_for_content(content_dir: &Path) -> Option<PathBuf> {
+    let plugin_root = content_dir.parent()?;
+    let mut found: Vec<PathBuf> = fs::read_dir(plugin_root).ok()?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && p.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("uplugin")))
+        .collect();
+    found.sort();
+    found.into_iter().next()
+}
+
+/// An engine install found by scan_engine_dirs.
+pub struct EngineDir {
+    pub name: String,
+    pub version: String,
+    /// "build.version", "folder-name" or "unknown" (see detect_engine_version).
+    pub version_source: &'static str,
+    pub path: PathBuf,
+    pub editor_path: Option<PathBuf>,
+    /// Why editor_path is None; None when an editor was found.
+    pub missing_editor: Option<String>,
+}
+
+/// List engine installs directly under `base` (folders containing Engine/Binaries).
+///
+/// The version comes from Build.version, then the folder name, else "unknown"; a malformed
+/// Build.version is logged and skipped.
+pub fn scan_engine_dirs(base: &Path) -> Vec<EngineDir> {
+    let mut out = Vec::new();
+    let Ok(entries) = fs::read_dir(base) else { return out };
+    for entry in entries.flatten() {
+        let p = entry.path();
+        if !p.is_dir() || !p.join("Engine").join("Binaries").exists() {
+            continue;
+        }
+        let name = p.file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or("")
+            .to_string();
+        let (version, version_source) = detect_engine_version(&p, &name);
+        let editor_path = find_editor_binary(&p);
+        let missing_editor = if editor_path.is_none() { Some(missing_editor_message(&p)) } else { None };
+        out.push(EngineDir { name, version, version_source, path: p, editor_path, missing_editor });
+    }
+    out
+}
+
+fn dir_has_uproject(dir: &Path) -> bool {
+    fs::read_dir(dir).map_or(false, |entries| {
+        entries.flatten().any(|e| e.path().extension().map_or(false, |ext| ext == "uproject"))
+    })
+}
+
+/// Resolve `param` to a project directory: either a directory containing a .uproject, or a
+/// project folder name under `projects_root`.
+pub fn project_dir_in(param: &str, projects_root: &Path) -> Option<PathBuf> {
+    let p = PathBuf::from(param);
+    // Require that it looks like a UE project (contains a .uproject)
+    if p.is_dir() && dir_has_uproject(&p) {
+        return Some(p);
+    }
+    // As a last resort, try treating it as a project name under the projects root (never outside it)
+    let candidate = super::paths::resolve_within_root(projects_root, Path::new(param)).ok()?;
+    if candidate.is_dir() && dir_has_uproject(&candidate) {
+        return Some(candidate);
+    }
+    None
+}
+
+/// Every .uproject a bare project `name` could refer to under `projects_root`, canonicalized and sorted.
+///
+/// Candidates are all .uproject files directly inside `<projects_root>/<name>/`, plus
+/// `<name>.uproject` (case-insensitive) inside any other direct subfolder of `projects_root`.
+/// A file reached through several spellings (a symlinked folder, `..`) counts once, and files that
+/// resolve outside `projects_root` are dropped. More than one result means the name is ambiguous.
+pub fn uproject_candidates_in(name: &str, projects_root: &Path) -> Vec<PathBuf> {
+    let Ok(root) = fs::canonicalize(projects_root) else { return Vec::new() };
+    let uprojects_in = |dir: &Path| -> Vec<PathBuf> {
+        fs::read_dir(dir).map(|entries| {
+            entries.flatten()
+                .map(|e| e.path())
+                .filter(|p| p.is_file() && p.extension().map_or(false, |ext| ext == "uproject"))
+                .collect()
+        }).unwrap_or_default()
+    };
+
+    let mut candidates: Vec<PathBuf> = Vec::new();
+    if let Ok(dir) = super::paths::resolve_within_root(&root, Path::new(name)) {
+        if dir.is_dir() {
+            candidates.extend(uprojects_in(&dir));
+        }
+    }
+    // Same-named .uproject files in sibling folders (e.g. a copy in "MyGame_Backup/MyGame.uproject")
+    let single_component = Path::new(name).components().count() == 1;
+    if single_component {
+        if let Ok(entries) = fs::read_dir(&root) {
+            for e in entries.flatten() {
+                let dir = e.path();
+                if !dir.is_dir() {
+                    continue;
+                }
+                candidates.extend(uprojects_in(&dir).into_iter().filter(|up| {
+                    up.file_stem().and_then(|s| s.to_str()).map_or(false, |s| s.eq_ignore_ascii_case(name))
+                }));
+            }
+        }
+    }
+    let mut found: Vec<PathBuf> = candidates
+        .into_iter()
+        .filter_map(|p| fs::canonicalize(p).ok())
+        .filter(|p| p.starts_with(&root))
+        .collect();
+    found.sort();
+    found.dedup();
+    found
+}
+
+/// Total size in bytes of all regular files under `root` (0 when missing).
+pub fn dir_size(root: &Path) -> u64 {
+    walkdir::WalkDir::new(root)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// True when a file can be created in `dir` (creating `dir` first if needed). The probe file is removed again.
+pub fn dir_writable(dir: &Path) -> bool {
+    if fs::create_dir_all(dir).is_err() {
+        return false;
+    }
+    let probe = dir.join(format!(".write_probe_{}", std::process::id()));
+    let ok = fs::write(&probe, b"").is_ok();
+    let _ = fs::remove_file(&probe);
+    ok
+}
+
+/// Check a directory about to be saved in the paths config, without creating anything.
+///
+/// Ok carries a note ("" when the directory is ready; otherwise that it will be created on first use).
+/// Err explains why the path won't work: relative, not a directory, or (with `needs_write`) not
+/// writable, including a missing directory whose nearest existing ancestor is not writable.
+pub fn check_configured_dir(dir: &Path, needs_write: bool) -> Result<String, String> {
+    if !dir.is_absolute() {
+        return Err(format!("{} is not an absolute path", dir.display()));
+    }
+    if dir.exists() {
+        if !dir.is_dir() {
+            return Err(format!("{} exists but is not a directory", dir.display()));
+        }
+        if needs_write && !dir_writable(dir) {
+            return Err(format!("{} is not writable", dir.display()));
+        }
+        return Ok(String::new());
+    }
+    let Some(ancestor) = dir.ancestors().skip(1).find(|a| a.exists()) else {
+        return Err(format!("{} does not exist and cannot be created", dir.display()));
+    };
+    if !ancestor.is_dir() {
+        return Err(format!("{} cannot be created: {} is not a directory", dir.display(), ancestor.display()));
+    }
+    if !dir_writable(ancestor) {
+        return Err(format!("{} does not exist and {} is not writable", dir.display(), ancestor.display()));
+    }
+    Ok(format!("{} does not exist yet; it will be created when first used", dir.display()))
+}
+
+/// Sidecar under the downloads root caching each asset folder's size as { "<folder>": { stamp, bytes } }.
+pub const SIZE_CACHE_FILE: &str = ".download_sizes.json";
+
+/// Latest modification time (ms since epoch) of `dir` and its direct subfolders.
+///
+/// Version folders and temp/ change whenever a download adds or removes files at their top level
+/// (the completion marker included), so this is a cheap stand-in for walking the whole tree.
+fn folder_stamp(dir: &Path) -> u64 {
+    let mtime = |p: &Path| {
+        fs::metadata(p).and_then(|m| m.modified()).ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map_or(0, |d| d.as_millis() as u64)
+    };
+    let children = fs::read_dir(dir).into_iter().flatten().flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .map(|p| mtime(&p));
+    children.fold(mtime(dir), u64::max)
+}
+
+/// Size of `dir`, reusing the entry from `previous` when its stamp still matches. The result is
+/// recorded in `next` under `folder`.
+fn cached_dir_size(dir: &Path, folder: &str, previous: &serde_json::Map<String, serde_json::Value>, next: &mut serde_json::Map<String, serde_json::Value>) -> u64 {
+    let stamp = folder_stamp(dir);
+    let cached = previous.get(folder)
+        .filter(|e| e.get("stamp").and_then(|v| v.as_u64()) == Some(stamp))
+        .and_then(|e| e.get("bytes").and_then(|v| v.as_u64()));
+    let bytes = cached.unwrap_or_else(|| dir_size(dir));
+    next.insert(folder.to_string(), serde_json::json!({ "stamp": stamp, "bytes": bytes }));
+    bytes
+}
+
+/// Annotate the provided FAB library JSON with `downloaded` flags based on folders under `downloads_root`.
+///
+/// Each asset also gets `downloadedBytes`: the size of its download folder (0 when there is none).
+/// Sizes are cached in SIZE_CACHE_FILE and only recomputed when a folder's stamp changes.
+///
+/// `folder_for_title` maps an asset title to its folder name. `is_cancelled` is polled before each asset;
+/// returns None when it reports true, otherwise (total_assets, marked_downloaded, changed).
+pub fn annotate_downloaded_flags_in(value: &mut serde_json::Value, downloads_root: &Path, folder_for_title: fn(&str) -> String, is_cancelled: &dyn Fn() -> bool) -> Option<(usize, usize, bool)> {
+    let mut total_assets = 0usize;
+    let mut marked_downloaded = 0usize;
+    let mut changed = false;
+    let size_cache_path = downloads_root.join(SIZE_CACHE_FILE);
+    let previous_sizes: serde_json::Map<String, serde_json::Value> = fs::read(&size_cache_path).ok()
+        .and_then(|b| serde_json::from_slice(&b).ok())
+        .unwrap_or_default();
+    let mut sizes = serde_json::Map::new();
+
+    if let Some(results) = value.get_mut("results").and_then(|v| v.as_array_mut()) {
+        for asset in results.iter_mut() {
+            if is_cancelled() {
+                return None;
+            }
+            total_assets += 1;
+            let title: String = asset.get("title").and_then(|v| v.as_str()).unwrap_or("").to_string();
+            // let namespace: String = asset.get("assetNamespace").and_then(|v| v.as_str()).unwrap_or("").to_string();
+            // let asset_id: String = asset.get("assetId").and_then(|v| v.as_str()).unwrap_or("").to_string();
+
+            let mut asset_downloaded = false;
+            let mut used_title_folder = false;
+            let mut version_folders: Vec<String> = Vec::new();
+            let mut downloaded_bytes = 0u64;
+
+            if !title.is_empty() {
+                let folder = folder_for_title(&title);
+                let path = downloads_root.join(&folder);
+                if path.exists() {
+                    if path.is_dir() {
+                        downloaded_bytes = cached_dir_size(&path, &folder, &previous_sizes, &mut sizes);
+                    }
+                    // Legacy: direct download into title folder
+                    if is_download_complete(&path) { asset_downloaded = true; used_title_folder = true; }
+                    // New: versioned subfolders under title
+                    if let Ok(entries) = fs::read_dir(&path) {
+                        for e in entries.flatten() {
+                            let p = e.path();
+                            if p.is_dir() {
+                                // folder name should be UE major.minor like 5.6 or 4.27
+                                if let Some(name) = p.file_name().and_then(|s| s.to_str()) {
+                                    let mm = name.trim();
+                                    if !mm.is_empty() && is_download_complete(&p) {
+                                        version_folders.push(mm.to_string());
+                                        asset_downloaded = true;
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            // Annotate per-version flags based ONLY on versioned title subfolders to avoid over-marking.
+            if let Some(versions) = asset.get_mut("projectVersions").and_then(|v| v.as_array_mut()) {
+                for ver in versions.iter_mut() {
+                    let mut ver_downloaded = false;
+                    if !version_folders.is_empty() {
+                        if let Some(ev) = ver.get("engineVersions").and_then(|v| v.as_array()) {
+                            'outer: for mm in version_folders.iter() {
+                                let token = format!("UE_{}", mm);
+                                for e in ev.iter() {
+                                    if e.as_str().map_or(false, |s| s.trim() == token) {
+                                        ver_downloaded = true; break 'outer;
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    if let Some(obj) = ver.as_object_mut() {
+                        let prev = obj.get("downloaded").and_then(|v| v.as_bool());
+                        if prev != Some(ver_downloaded) {
+                            obj.insert("downloaded".into(), serde_json::Value::Bool(ver_downloaded));
+                            changed = true;
+                        }
+                    }
+                }
+            }
+
+            // Record the exact downloaded UE versions at the asset root for precise UI logic
+            if let Some(obj) = asset.as_object_mut() {
+                // Set asset-level downloaded flag
+                if obj.get("downloaded").and_then(|v| v.as_bool()) != Some(asset_downloaded) {
+                    obj.insert("downloaded".into(), serde_json::Value::Bool(asset_downloaded));
+                    changed = true;
+                }
+                // Inject/update downloadedVersions array (sorted unique)
+                let mut versions_unique = version_folders.clone();
+                versions_unique.sort();
+                versions_unique.dedup();
+                let new_val = serde_json::Value::Array(versions_unique.into_iter().map(serde_json::Value::String).collect());
+                let prev = obj.get("downloadedVersions").cloned();
+                if prev.as_ref() != Some(&new_val) {
+                    obj.insert("downloadedVersions".into(), new_val);
+                    changed = true;
+                }
+                if obj.get("downloadedBytes").and_then(|v| v.as_u64()) != Some(downloaded_bytes) {
+                    obj.insert("downloadedBytes".into(), serde_json::Value::from(downloaded_bytes));
+                    changed = true;
+                }
+            }
+
+            if asset_downloaded { marked_downloaded += 1; }
+
+            // No blanket marking of versions when using title folder; handled above via subfolders
+        }
+    }
+
+    if sizes != previous_sizes {
+        if let Ok(bytes) = serde_json::to_vec(&sizes) {
+            let tmp = size_cache_path.with_extension("json.tmp");
+            if fs::write(&tmp, bytes).and_then(|_| fs::rename(&tmp, &size_cache_path)).is_err() {
+                let _ = fs::remove_file(&tmp);
+            }
+        }
+    }
+
+    Some((total_assets, marked_downloaded, changed))
+}
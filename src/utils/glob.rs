@@ -0,0 +1,131 @@
+//! Glob matching for manifest file paths (EAM_SKIP_PATTERNS and the include/exclude download filters).
+//!
+//! Patterns use `globset` syntax:
+//! - `*` matches any run of characters except `/`
+//! - `**` matches any run of characters including `/` (so `**/Windows/**` spans directories)
+//! - `?` matches exactly one character except `/`; `[...]` and `{a,b}` are supported too
+//!
+//! Matching is case-insensitive and treats `\` as `/`, since manifests mix separators.
+
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+
+/// A compiled list of patterns that remembers which one matched.
+#[derive(Default)]
+pub struct PatternSet {
+    patterns: Vec<String>,
+    set: GlobSet,
+}
+
+impl PatternSet {
+    /// Compile `patterns`; the error names the first invalid one.
+    pub fn new(patterns: &[String]) -> Result<Self, String> {
+        let mut builder = GlobSetBuilder::new();
+        for pat in patterns {
+            let glob = GlobBuilder::new(&normalize(pat))
+                .case_insensitive(true)
+                .literal_separator(true)
+                .backslash_escape(false)
+                .build()
+                .map_err(|e| format!("invalid glob '{}': {}", pat, e.kind()))?;
+            builder.add(glob);
+        }
+        let set = builder.build().map_err(|e| e.to_string())?;
+        Ok(PatternSet { patterns: patterns.to_vec(), set })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    pub fn patterns(&self) -> &[String] {
+        &self.patterns
+    }
+
+    /// The first pattern (in list order) that matches `path`, if any.
+    pub fn first_match(&self, path: &str) -> Option<&str> {
+        let idx = self.set.matches(normalize(path)).into_iter().min()?;
+        Some(self.patterns[idx].as_str())
+    }
+}
+
+fn normalize(s: &str) -> String {
+    s.replace('\\', "/")
+}
+
+/// True when `path` passes an include/exclude filter: it matches one of `include` (or `include`
+/// is empty) and none of `exclude`.
+pub fn is_selected(include: &PatternSet, exclude: &PatternSet, path: &str) -> bool {
+    (include.is_empty() || include.first_match(path).is_some()) && exclude.first_match(path).is_none()
+}
+
+/// Parse a comma/semicolon separated pattern list (e.g., the EAM_SKIP_PATTERNS value). Commas inside
+/// `{...}` belong to the pattern.
+pub fn parse_patterns(raw: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    for c in raw.chars() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            ',' | ';' if depth == 0 => {
+                out.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    out.push(current);
+    out.into_iter().map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect()
+}
+
+/// Which manifest files a download keeps, and which it leaves out and why.
+#[derive(Debug, Default, PartialEq)]
+pub struct FileSelection {
+    pub selected: Vec<String>,
+    /// Left out by the request's include/exclude filter.
+    pub excluded_by_filter: Vec<String>,
+    /// Left out by EAM_SKIP_PATTERNS.
+    pub skipped_by_pattern: Vec<String>,
+}
+
+impl FileSelection {
+    /// True when any manifest file was left out, for whatever reason.
+    pub fn partial(&self) -> bool {
+        !self.excluded_by_filter.is_empty() || !self.skipped_by_pattern.is_empty()
+    }
+
+    /// `.download_complete` contents: every file left out and the patterns responsible, so a later full
+    /// download knows this one was partial, plus the per-file size/hash list (`files`).
+    pub fn complete_marker(&self, include: &PatternSet, exclude: &PatternSet, skip: &PatternSet, files: Vec<serde_json::Value>) -> serde_json::Value {
+        serde_json::json!({
+            "status": "ok",
+            "partial": self.partial(),
+            "skip_patterns": skip.patterns(),
+            "skipped_files": self.skipped_by_pattern,
+            "include_patterns": include.patterns(),
+            "exclude_patterns": exclude.patterns(),
+            "excluded_files": self.excluded_by_filter,
+            "files": files,
+        })
+    }
+}
+
+/// Split manifest file names by the include/exclude filter, then the skip patterns. Lists are sorted.
+pub fn select_files<I: IntoIterator<Item = String>>(names: I, include: &PatternSet, exclude: &PatternSet, skip: &PatternSet) -> FileSelection {
+    let mut sel = FileSelection::default();
+    for name in names {
+        if !is_selected(include, exclude, &name) {
+            sel.excluded_by_filter.push(name);
+        } else if skip.first_match(&name).is_some() {
+            sel.skipped_by_pattern.push(name);
+        } else {
+            sel.selected.push(name);
+        }
+    }
+    sel.selected.sort();
+    sel.excluded_by_filter.sort();
+    sel.skipped_by_pattern.sort();
+    sel
+}
@@ -0,0 +1,138 @@
+//! Server-side filtering, sorting and paging of the cached Fab library (`/search-fab-list`,
+//! `/get-fab-list?sort=..&offset=..&limit=..`).
+//!
+//! Works on the raw `serde_json::Value` from fab_list.json, so it tolerates assets missing any of
+//! the fields it looks at.
+
+/// Criteria for search_results; `None` / empty fields match every asset.
+#[derive(Default, Debug, Clone)]
+pub struct LibraryFilter {
+    /// Case-insensitive substring of the asset title.
+    pub text: Option<String>,
+    /// Asset-level `downloaded` flag (a missing flag counts as false).
+    pub downloaded: Option<bool>,
+    /// UE version such as "5.4" (or "UE_5.4") that some `projectVersions[].engineVersions` must list.
+    pub engine: Option<String>,
+}
+
+fn strip_ue_prefix(s: &str) -> &str {
+    let s = s.trim();
+    match s.get(..3) {
+        Some(p) if p.eq_ignore_ascii_case("ue_") => &s[3..],
+        _ => s,
+    }
+}
+
+impl LibraryFilter {
+    /// True when `asset` satisfies every set criterion.
+    pub fn matches(&self, asset: &serde_json::Value) -> bool {
+        if let Some(text) = self.text.as_deref().map(str::trim).filter(|t| !t.is_empty()) {
+            let title = asset.get("title").and_then(|v| v.as_str()).unwrap_or("");
+            if !title.to_lowercase().contains(&text.to_lowercase()) {
+                return false;
+            }
+        }
+        if let Some(want) = self.downloaded {
+            if asset.get("downloaded").and_then(|v| v.as_bool()).unwrap_or(false) != want {
+                return false;
+            }
+        }
+        if let Some(engine) = self.engine.as_deref().map(strip_ue_prefix).filter(|e| !e.is_empty()) {
+            let supported = asset.get("projectVersions").and_then(|v| v.as_array()).is_some_and(|versions| {
+                versions.iter()
+                    .filter_map(|ver| ver.get("engineVersions").and_then(|v| v.as_array()))
+                    .flatten()
+                    .filter_map(|e| e.as_str())
+                    .any(|e| strip_ue_prefix(e).eq_ignore_ascii_case(engine))
+            });
+            if !supported {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Assets in `value["results"]` matching `filter`, in library order.
+pub fn search_results(value: &serde_json::Value, filter: &LibraryFilter) -> Vec<serde_json::Value> {
+    value.get("results")
+        .and_then(|r| r.as_array())
+        .map(|results| results.iter().filter(|a| filter.matches(a)).cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Field to sort library assets by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    /// Title, case-insensitive.
+    Title,
+    /// Asset-level `downloaded` flag (not downloaded first when ascending).
+    Downloaded,
+    /// `downloadedBytes` from the disk-usage annotation, 0 when absent.
+    Size,
+}
+
+impl SortKey {
+    pub fn parse(s: &str) -> Option<SortKey> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "title" => Some(SortKey::Title),
+            "downloaded" => Some(SortKey::Downloaded),
+            "size" => Some(SortKey::Size),
+            _ => None,
+        }
+    }
+
+    fn compare(self, a: &serde_json::Value, b: &serde_json::Value) -> std::cmp::Ordering {
+        match self {
+            SortKey::Title => {
+                let title = |v: &serde_json::Value| v.get("title").and_then(|t| t.as_str()).unwrap_or("").to_lowercase();
+                title(a).cmp(&title(b))
+            }
+            SortKey::Downloaded => {
+                let flag = |v: &serde_json::Value| v.get("downloaded").and_then(|d| d.as_bool()).unwrap_or(false);
+                flag(a).cmp(&flag(b))
+            }
+            SortKey::Size => {
+                let size = |v: &serde_json::Value| v.get("downloadedBytes").and_then(|d| d.as_u64()).unwrap_or(0);
+                size(a).cmp(&size(b))
+            }
+        }
+    }
+}
+
+/// Sorting and paging for a library listing.
+#[derive(Debug, Clone, Default)]
+pub struct ListPage {
+    pub sort: Option<SortKey>,
+    pub descending: bool,
+    pub offset: usize,
+    /// Maximum number of results; all remaining when None.
+    pub limit: Option<usize>,
+}
+
+/// Sort `value["results"]` (stable, so equal keys keep library order) and slice it into
+/// `{ total, offset, limit, results }`, where `total` counts the results before slicing.
+pub fn paginate(value: &serde_json::Value, page: &ListPage) -> serde_json::Value {
+    let mut results: Vec<&serde_json::Value> = value.get("results")
+        .and_then(|r| r.as_array())
+        .map(|r| r.iter().collect())
+        .unwrap_or_default();
+    if let Some(key) = page.sort {
+        if page.descending {
+            results.sort_by(|a, b| key.compare(b, a));
+        } else {
+            results.sort_by(|a, b| key.compare(a, b));
+        }
+    }
+    let total = results.len();
+    let sliced: Vec<&serde_json::Value> = results.into_iter()
+        .skip(page.offset)
+        .take(page.limit.unwrap_or(usize::MAX))
+        .collect();
+    serde_json::json!({
+        "total": total,
+        "offset": page.offset,
+        "limit": page.limit,
+        "results": sliced,
+    })
+}
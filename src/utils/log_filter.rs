@@ -0,0 +1,120 @@
+//! Runtime-adjustable log filter in RUST_LOG syntax ("info", "warn,download=debug").
+//!
+//! env_logger fixes its filter at startup; the process logger consults this filter instead, so
+//! POST /log-level can raise or lower verbosity for one target (and its `::` children) without a restart.
+
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    /// Case-insensitive level name, as accepted by RUST_LOG.
+    pub fn parse(s: &str) -> Option<Level> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "off" => Some(Level::Off),
+            "error" => Some(Level::Error),
+            "warn" => Some(Level::Warn),
+            "info" => Some(Level::Info),
+            "debug" => Some(Level::Debug),
+            "trace" => Some(Level::Trace),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Level::Off => "off",
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogFilter {
+    default: Level,
+    targets: BTreeMap<String, Level>,
+}
+
+impl Default for LogFilter {
+    /// Errors only, like env_logger without RUST_LOG.
+    fn default() -> Self {
+        LogFilter { default: Level::Error, targets: BTreeMap::new() }
+    }
+}
+
+impl LogFilter {
+    /// Parse comma-separated `level` / `target=level` directives; a bare target means trace.
+    /// Unknown levels are ignored, so a typo keeps the previous setting.
+    pub fn parse(spec: &str) -> Self {
+        let mut filter = LogFilter::default();
+        for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            match directive.split_once('=') {
+                Some((target, level)) => {
+                    if let Some(level) = Level::parse(level) {
+                        filter.set(Some(target), level);
+                    }
+                }
+                None => match Level::parse(directive) {
+                    Some(level) => filter.set(None, level),
+                    None => filter.set(Some(directive), Level::Trace),
+                },
+            }
+        }
+        filter
+    }
+
+    /// Filter for the process from its RUST_LOG value: `info` when RUST_LOG is unset or blank, so
+    /// startup and progress messages show without any configuration.
+    pub fn from_rust_log(value: Option<&str>) -> Self {
+        match value.map(str::trim).filter(|v| !v.is_empty()) {
+            Some(spec) => LogFilter::parse(spec),
+            None => LogFilter::parse("info"),
+        }
+    }
+
+    /// Change the level for `target`, or the default when None (or empty).
+    pub fn set(&mut self, target: Option<&str>, level: Level) {
+        match target.map(str::trim).filter(|t| !t.is_empty()) {
+            Some(t) => { self.targets.insert(t.to_string(), level); }
+            None => self.default = level,
+        }
+    }
+
+    /// Level for a record target: the most specific configured target that is the target itself
+    /// or one of its `::` parents, else the default.
+    pub fn level_for(&self, target: &str) -> Level {
+        self.targets.iter()
+            .filter(|(t, _)| target == t.as_str() || target.strip_prefix(t.as_str()).is_some_and(|rest| rest.starts_with("::")))
+            .max_by_key(|(t, _)| t.len())
+            .map_or(self.default, |(_, l)| *l)
+    }
+
+    pub fn enabled(&self, target: &str, level: Level) -> bool {
+        level != Level::Off && level <= self.level_for(target)
+    }
+
+    /// Most verbose level any target may log at (lets the logger skip formatting everything above it).
+    pub fn max_level(&self) -> Level {
+        self.targets.values().copied().fold(self.default, Level::max)
+    }
+
+    /// The filter in RUST_LOG syntax.
+    pub fn spec(&self) -> String {
+        std::iter::once(self.default.as_str().to_string())
+            .chain(self.targets.iter().map(|(t, l)| format!("{}={}", t, l.as_str())))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
@@ -0,0 +1,91 @@
+//! Process-wide download counters for GET /metrics.
+//!
+//! Rendered in the Prometheus text exposition format by hand (a handful of counters and gauges
+//! does not need a client library). Counters start at zero on every launch.
+
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::throughput::Throughput;
+
+/// How a download run ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+pub struct DownloadMetrics {
+    started: AtomicU64,
+    completed: AtomicU64,
+    failed: AtomicU64,
+    cancelled: AtomicU64,
+    bytes: AtomicU64,
+    throughput: Mutex<Throughput>,
+}
+
+impl Default for DownloadMetrics {
+    fn default() -> Self {
+        DownloadMetrics {
+            started: AtomicU64::new(0),
+            completed: AtomicU64::new(0),
+            failed: AtomicU64::new(0),
+            cancelled: AtomicU64::new(0),
+            bytes: AtomicU64::new(0),
+            throughput: Mutex::new(Throughput::new(Duration::from_secs(10))),
+        }
+    }
+}
+
+impl DownloadMetrics {
+    pub fn started(&self) {
+        self.started.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn finished(&self, outcome: Outcome) {
+        let counter = match outcome {
+            Outcome::Completed => &self.completed,
+            Outcome::Failed => &self.failed,
+            Outcome::Cancelled => &self.cancelled,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Count bytes fetched from the network (not files found up to date).
+    pub fn add_bytes(&self, n: u64) {
+        self.bytes.fetch_add(n, Ordering::Relaxed);
+    }
+
+    /// Downloads started and not yet finished.
+    pub fn active(&self) -> u64 {
+        let finished = self.completed.load(Ordering::Relaxed) + self.failed.load(Ordering::Relaxed) + self.cancelled.load(Ordering::Relaxed);
+        self.started.load(Ordering::Relaxed).saturating_sub(finished)
+    }
+
+    /// Record the byte total at `now` and return the aggregate rate across all downloads in bytes/second.
+    pub fn sample(&self, now: Instant) -> f64 {
+        let bytes = self.bytes.load(Ordering::Relaxed);
+        self.throughput.lock().unwrap_or_else(|p| p.into_inner()).record(now, bytes)
+    }
+
+    /// Prometheus text format; `active_jobs` counts running jobs of any kind (downloads, imports, creates).
+    pub fn render(&self, now: Instant, active_jobs: usize) -> String {
+        let bytes_per_sec = self.sample(now);
+        let mut out = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str, value: String| {
+            let _ = write!(out, "# HELP {name} {help}\n# TYPE {name} {kind}\n{name} {value}\n");
+        };
+        metric("egs_downloads_started_total", "counter", "Downloads started.", self.started.load(Ordering::Relaxed).to_string());
+        metric("egs_downloads_completed_total", "counter", "Downloads that completed.", self.completed.load(Ordering::Relaxed).to_string());
+        metric("egs_downloads_failed_total", "counter", "Downloads that ended with an error.", self.failed.load(Ordering::Relaxed).to_string());
+        metric("egs_downloads_cancelled_total", "counter", "Downloads cancelled via /cancel-job.", self.cancelled.load(Ordering::Relaxed).to_string());
+        metric("egs_downloaded_bytes_total", "counter", "Bytes fetched from the CDN.", self.bytes.load(Ordering::Relaxed).to_string());
+        metric("egs_downloads_active", "gauge", "Downloads in progress.", self.active().to_string());
+        metric("egs_jobs_active", "gauge", "Jobs (downloads, imports, project creation) still running.", active_jobs.to_string());
+        metric("egs_download_bytes_per_second", "gauge", "Current aggregate download throughput.", format!("{:.0}", bytes_per_sec));
+        out
+    }
+}
@@ -20,7 +20,7 @@
 //! - Fab asset types: https://docs.rs/egs-api/latest/egs_api/api/types/
 
 
-use std::collections::{HashMap, VecDeque};
+use std::collections::HashMap;
 use std::io;
 use egs_api::api::types::account::{AccountData, UserData};
 use egs_api::api::types::fab_library::FabLibrary;
@@ -31,11 +31,12 @@ use std::io::Read;
 use std::path::{Path, PathBuf};
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
-use std::sync::{Arc, OnceLock};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Instant;
 use actix::{Actor, ActorContext, AsyncContext, StreamHandler};
 use actix_web::{web, HttpResponse};
+use actix_web::http::StatusCode;
 use actix_web::web::Query;
 use actix_web_actors::ws;
 use dashmap::DashMap;
@@ -44,6 +45,64 @@ use tokio::sync::broadcast;
 use crate::api::{DEFAULT_CACHE_DIR_NAME, DEFAULT_DOWNLOADS_DIR_NAME};
 use crate::{models, utils};
 use crate::models::Phase;
+use disk_space::resolve_output_file_path;
+use verify_existing::ExistingFileCheck;
+
+pub mod asset_tags;
+pub mod batch;
+pub mod bind_retry;
+pub mod byte_progress;
+pub mod categories;
+pub mod chunk_assembly;
+pub mod chunk_check;
+pub mod config_reset;
+pub mod conflict;
+pub mod cpp_rename;
+pub mod create_defaults;
+pub mod default_dirs;
+pub mod disk_space;
+pub mod drain;
+pub mod editor_args;
+pub mod editor_log;
+pub mod engine_dupes;
+pub mod engine_list;
+pub mod engine_registry;
+pub mod event_buffer;
+pub mod exec_perm;
+pub mod fab_cache;
+pub mod file_hash;
+pub mod folder_progress;
+pub mod fs_scan;
+pub mod glob;
+pub mod library_filter;
+pub mod log_filter;
+pub mod metrics;
+pub mod parallel_copy;
+pub mod paths;
+pub mod pause;
+pub mod project_copy;
+pub mod project_trash;
+pub mod rate_limit;
+pub mod redact;
+pub mod retry;
+pub mod semver;
+pub mod staging;
+pub mod temp_users;
+pub mod throughput;
+pub mod timefmt;
+pub mod verify_cache;
+pub mod verify_existing;
+
+pub use paths::{resolve_within_root, PathGuardError};
+pub use conflict::{ConflictPolicy, CopyCounts};
+pub use project_copy::validate_ue_project_name;
+pub use exec_perm::describe_spawn_error;
+pub use fab_cache::{lock_fab_cache, write_fab_cache_atomic};
+pub use asset_tags::lock_asset_tags;
+pub use redact::redact_secrets;
+pub use semver::{compare_versions, parse_semver};
+pub use engine_list::{discover_engines, read_build_id};
+pub use fs_scan::{check_configured_dir, dir_size, dir_writable, find_content_dir_bfs, find_editor_binary, find_uplugin_for_content, find_uproject_bfs, is_download_complete, missing_editor_message, parse_version_from_name, read_build_version, uproject_candidates_in};
 
 pub const EPIC_LOGIN_URL: &str = "https://www.epicgames.com/id/login?redirectUrl=https%3A%2F%2Fwww.epicgames.com%2Fid%2Fapi%2Fredirect%3FclientId%3D34a02cf8f4414e29b15921876da36f9a%26responseType%3Dcode";
 
@@ -58,19 +117,84 @@ pub const EPIC_LOGIN_URL: &str = "https://www.epicgames.com/id/login?redirectUrl
 pub fn get_auth_code() -> String {
 
     if webbrowser::open(EPIC_LOGIN_URL).is_err() {
-        println!("Please go to {}", EPIC_LOGIN_URL)
+        log::info!("Please go to {}", EPIC_LOGIN_URL)
     }
-    println!("Please enter the 'authorizationCode' value from the JSON response");
+    log::info!("Please enter the 'authorizationCode' value from the JSON response");
 
     let mut auth_code = String::new();
     let stdin = io::stdin(); // We get `Stdin` here.
     stdin.read_line(&mut auth_code).unwrap();
     auth_code = auth_code.trim().to_string();
     auth_code = auth_code.replace(|c: char| c == '"', "");
-    println!("Using Auth Code: {}", auth_code);
+    // Never echo the code itself; it is a one-time credential
+    log::info!("Using Auth Code ({} chars)", auth_code.len());
     auth_code
 }
 
+// ===================== Runtime log levels =====================
+/// Log target for download progress details (chunk retries, per-file steps), so its verbosity can be
+/// changed on its own via POST /log-level.
+pub const DOWNLOAD_LOG_TARGET: &str = "download";
+
+static LOG_FILTER: OnceLock<std::sync::RwLock<log_filter::LogFilter>> = OnceLock::new();
+fn log_filter_lock() -> &'static std::sync::RwLock<log_filter::LogFilter> {
+    LOG_FILTER.get_or_init(|| std::sync::RwLock::new(log_filter::LogFilter::from_rust_log(std::env::var("RUST_LOG").ok().as_deref())))
+}
+
+fn to_level_filter(level: log_filter::Level) -> log::LevelFilter {
+    match level {
+        log_filter::Level::Off => log::LevelFilter::Off,
+        log_filter::Level::Error => log::LevelFilter::Error,
+        log_filter::Level::Warn => log::LevelFilter::Warn,
+        log_filter::Level::Info => log::LevelFilter::Info,
+        log_filter::Level::Debug => log::LevelFilter::Debug,
+        log_filter::Level::Trace => log::LevelFilter::Trace,
+    }
+}
+
+/// Whether a record for `target` at `level` passes the runtime filter (initialized from RUST_LOG).
+pub fn log_enabled(target: &str, level: log::Level) -> bool {
+    let filter = log_filter_lock().read().unwrap_or_else(|p| p.into_inner());
+    to_level_filter(filter.level_for(target)) >= level
+}
+
+/// Change the level for `target` (or the default when None) and return the resulting filter spec.
+pub fn set_log_level(target: Option<&str>, level: log_filter::Level) -> String {
+    let mut filter = log_filter_lock().write().unwrap_or_else(|p| p.into_inner());
+    filter.set(target, level);
+    log::set_max_level(to_level_filter(filter.max_level()));
+    filter.spec()
+}
+
+/// Current filter in RUST_LOG syntax.
+pub fn log_filter_spec() -> String {
+    log_filter_lock().read().unwrap_or_else(|p| p.into_inner()).spec()
+}
+
+tokio::task_local! {
+    /// Job the current task works for; log lines written inside with_log_job carry it as job_id.
+    static LOG_JOB_ID: String;
+}
+
+/// Run `fut` with `job_id` as the log context (no-op without a job id). Tasks spawned from inside
+/// do not inherit it and need their own with_log_job.
+pub async fn with_log_job<F: std::future::Future>(job_id: Option<String>, fut: F) -> F::Output {
+    match job_id {
+        Some(id) => LOG_JOB_ID.scope(id, fut).await,
+        None => fut.await,
+    }
+}
+
+/// Job id of the log context of the running task, if any.
+pub fn current_log_job() -> Option<String> {
+    LOG_JOB_ID.try_with(|j| j.clone()).ok()
+}
+
+/// Most verbose level currently enabled for any target, for log::set_max_level at startup.
+pub fn log_max_level() -> log::LevelFilter {
+    to_level_filter(log_filter_lock().read().unwrap_or_else(|p| p.into_inner()).max_level())
+}
+
 /// Constructs a new EpicGames client instance.
 ///
 /// The client is initially unauthenticated. Pair with try_cached_login or the
@@ -92,25 +216,21 @@ pub async fn get_account_details(epic_games_services: &mut EpicGames) -> Option<
 ///
 /// Current behavior:
 /// - In dev (debug builds), uses ./cache/.egs_client_tokens.json within the project directory.
-/// - In release, uses XDG config: $XDG_CONFIG_HOME/egs_client/tokens.json (fallback ~/.config/egs_client/tokens.json)
+/// - In release, uses XDG config: $XDG_CONFIG_HOME/egs_client/tokens.json (fallback ~/.config/egs_client/tokens.json),
+///   or %APPDATA%\egs_client\tokens.json on Windows.
 ///
 /// Future improvements (TODO):
 /// - Provide a "clear credentials" helper.
-fn token_cache_path() -> PathBuf {
+pub fn token_cache_path() -> PathBuf {
     // In debug builds, prefer a project-local cache file under ./cache
     if cfg!(debug_assertions) {
         return PathBuf::from("cache/.egs_client_tokens.json");
     }
-    // Production/default: XDG config: $XDG_CONFIG_HOME/egs_client/tokens.json (fallback ~/.config/egs_client/tokens.json)
-    let base = std::env::var("XDG_CONFIG_HOME")
-        .map(PathBuf::from)
-        .ok()
-        .filter(|p| !p.as_os_str().is_empty())
-        .or_else(|| std::env::var("HOME").ok().map(|h| PathBuf::from(h).join(".config")))
-        .unwrap_or_else(|| PathBuf::from(".config"));
+    // Production/default: $XDG_CONFIG_HOME/egs_client/tokens.json (fallback ~/.config; %APPDATA% on Windows)
+    let base = default_dirs::config_base(fs_scan::host_platform(), &host_env);
     let dir = base.join("egs_client");
     if let Err(e) = std::fs::create_dir_all(&dir) {
-        eprintln!("Warning: failed to create config dir {}: {}", dir.display(), e);
+        log::warn!("Failed to create config dir {}: {}", dir.display(), e);
     }
     dir.join("tokens.json")
 }
@@ -140,6 +260,25 @@ pub fn load_user_details() -> Option<UserData> {
     serde_json::from_slice::<UserData>(&data).ok()
 }
 
+/// Deletes the token cache file (sign out). A missing file is not an error.
+///
+/// Returns an error when removal fails or, on Unix, when the file is still present afterwards.
+pub fn clear_user_details() -> std::io::Result<()> {
+    let path = token_cache_path();
+    match fs::remove_file(&path) {
+        Ok(_) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(std::io::Error::new(e.kind(), format!("failed to remove {}: {}", path.display(), e))),
+    }
+    #[cfg(unix)]
+    {
+        if fs::symlink_metadata(&path).is_ok() {
+            return Err(std::io::Error::new(std::io::ErrorKind::Other, format!("token cache still present after removal: {}", path.display())));
+        }
+    }
+    Ok(())
+}
+
 /// Attempts to login using previously cached tokens.
 ///
 /// Returns true if login succeeds (including when tokens are refreshed), false otherwise.
@@ -157,6 +296,34 @@ pub async fn try_cached_login(epic: &mut EpicGames) -> bool {
     false
 }
 
+/// Refresh proactively when the access token has this many seconds or fewer left.
+pub const TOKEN_REFRESH_THRESHOLD_SECS: i64 = 5 * 60;
+
+/// Refresh the session when the cached access token expires within TOKEN_REFRESH_THRESHOLD_SECS, so a
+/// long download does not start with a token that lapses mid-transfer (chunk requests then fail with 403).
+///
+/// The refresh token is exchanged explicitly (auth_code with neither an exchange token nor an
+/// authorization code) and the new tokens are persisted via save_user_details. An unknown expiry is left
+/// to login()'s own checks. Err carries a message for the client when a needed refresh failed.
+pub async fn ensure_fresh_token(epic: &mut EpicGames) -> Result<(), String> {
+    let Ok(user) = serde_json::to_value(epic.user_details()) else { return Ok(()) };
+    let expires_at = ["expires_at", "expiresAt"].iter()
+        .find_map(|k| user.get(*k).and_then(|v| v.as_str()).and_then(timefmt::parse_rfc3339));
+    let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map(|d| d.as_secs() as i64).unwrap_or(0);
+    if !timefmt::expires_within(expires_at, now, TOKEN_REFRESH_THRESHOLD_SECS) {
+        return Ok(());
+    }
+    log::info!("Access token expires within {}s; refreshing", TOKEN_REFRESH_THRESHOLD_SECS);
+    if !epic.auth_code(None, None).await || !epic.login().await {
+        log::warn!("Proactive token refresh failed");
+        return Err("Epic session expired and could not be refreshed; log in again".to_string());
+    }
+    if let Err(e) = save_user_details(&epic.user_details()) {
+        log::warn!("Refreshed the Epic session but could not cache the new tokens: {}", e);
+    }
+    Ok(())
+}
+
 /// Retrieves the FabLibrary listing for the provided account.
 ///
 /// This is a convenience wrapper around EpicGames::fab_library_items.
@@ -168,42 +335,295 @@ pub async fn get_fab_library_items(epic_games_services: &mut EpicGames, info: Ac
 ///
 /// Layout:
 /// - Files are written under out_root/data/<relative_path>
-/// - Temporary chunk files are stored under sibling temp/ as <GUID>.chunk
+/// - Temporary chunk files are stored under sibling temp/ as <GUID>.chunk; temp/manifest.json records
+///   which chunks were fully written (size-checked), so after a restart only those are reused
 ///
 /// Behavior highlights:
-/// - Skips already present files by verifying SHA1 (when available) or total size.
-/// - Downloads signed chunk URLs with a simple one-retry policy.
+/// - Skips already present files by verifying their hash (SHA1/SHA256, when available) or total size.
+///   Passed checks are remembered in <out_root>/.verify.json and reused while size and mtime are unchanged.
+/// - Leaves out files matching EAM_SKIP_PATTERNS globs (e.g., "**/Win64/**;**/Mac/**") and records them in the completion marker.
+/// - Downloads signed chunk URLs, retrying network errors (also mid-body), truncated bodies, 408/429 and
+///   5xx with exponential backoff (EAM_CHUNK_RETRIES, default 3); other statuses such as 404 fail the
+///   chunk immediately.
 /// - Assembles each output file by slicing the chunk byte ranges defined in file_chunk_parts.
-/// - Optionally verifies file SHA1 after assembly (when file_hash is provided).
+/// - Verifies the file hash after assembly (SHA1 or SHA256, by digest length); a mismatch deletes the .part file,
+///   emits download:error and fails the download unless EAM_ALLOW_HASH_MISMATCH=1.
 /// - Performs atomic rename from .part to final file after successful assembly.
+/// - With EAM_STAGING_DOWNLOADS=1, downloads into downloads/.staging/<id>/ and renames the finished
+///   folder into out_root; the staging entry is removed on failure.
+///
+/// temp/ chunk folder after the download ends (non-staging mode):
+///
+/// | outcome   | EAM_KEEP_TEMP_ON_FAILURE unset/1 | EAM_KEEP_TEMP_ON_FAILURE=0 |
+/// |-----------|----------------------------------|----------------------------|
+/// | success   | removed                          | removed                    |
+/// | cancelled | removed                          | removed                    |
+/// | error     | kept (for resume)                | removed                    |
+///
+/// "removed" is skipped while another running download shares the same temp/ folder.
 ///
 /// Returns Ok on success (including when all files are already present), or an error
 /// when no files could be downloaded and none were up-to-date.
 pub type ProgressFn = std::sync::Arc<dyn Fn(u32, String) + Send + Sync + 'static>;
 
-pub async fn download_asset(dm: &DownloadManifest, _base_url: &str, download_directory_full_path: &Path, progress_callback: Option<ProgressFn>, job_id_opt: Option<&str>) -> Result<(), anyhow::Error> {
-    use egs_api::api::types::chunk::Chunk;
+pub async fn download_asset(dm: &DownloadManifest, base_url: &str, download_directory_full_path: &Path, progress_callback: Option<ProgressFn>, job_id_opt: Option<&str>, options: &models::DownloadOptions) -> Result<(), anyhow::Error> {
+    // Counted here so direct, staged and early-failing runs all reach /metrics
+    download_metrics().started();
+    let _active = active_downloads().acquire(download_directory_full_path);
+    let result = download_asset_run(dm, base_url, download_directory_full_path, progress_callback, job_id_opt, options).await;
+    download_metrics().finished(match &result {
+        Ok(()) => metrics::Outcome::Completed,
+        Err(e) if e.to_string() == staging::CANCELLED => metrics::Outcome::Cancelled,
+        Err(_) => metrics::Outcome::Failed,
+    });
+    result
+}
+
+static DOWNLOAD_METRICS: OnceLock<metrics::DownloadMetrics> = OnceLock::new();
+
+/// Process-wide download counters served by GET /metrics.
+pub fn download_metrics() -> &'static metrics::DownloadMetrics {
+    DOWNLOAD_METRICS.get_or_init(metrics::DownloadMetrics::default)
+}
+
+async fn download_asset_run(dm: &DownloadManifest, base_url: &str, download_directory_full_path: &Path, progress_callback: Option<ProgressFn>, job_id_opt: Option<&str>, options: &models::DownloadOptions) -> Result<(), anyhow::Error> {
+    if !staging_downloads_enabled() {
+        let result = download_asset_into(dm, base_url, download_directory_full_path, progress_callback, job_id_opt, options).await;
+        if let Err(e) = &result {
+            if staging::drop_chunks_after_failure(&e.to_string(), keep_temp_on_failure()) {
+                remove_chunk_temp_dir(download_directory_full_path);
+            }
+        }
+        return result;
+    }
+    let staging_root = staging_dir_for(download_directory_full_path);
+    let staged_asset = staging_root.join("asset");
+    // Seed staging with whatever is already in the final folder so verified files are reused.
+    // Different filesystem: start the staged copy from scratch and replace the old folder on promotion
+    let seeded = staging::seed(download_directory_full_path, &staged_asset);
+    log::info!(target: DOWNLOAD_LOG_TARGET, "Staging download in {}", staged_asset.display());
+    match download_asset_into(dm, base_url, &staged_asset, progress_callback, job_id_opt, options).await {
+        Ok(()) => {
+            promote_staged_download(&staged_asset, download_directory_full_path)?;
+            let _ = fs::remove_dir_all(&staging_root);
+            Ok(())
+        }
+        Err(e) => {
+            // Put a previous download back where it was instead of losing it with the staging entry
+            if let Err(rb) = staging::roll_back(&staged_asset, download_directory_full_path, seeded.as_ref()) {
+                log::warn!(target: DOWNLOAD_LOG_TARGET, "Failed to restore {} from staging: {}", download_directory_full_path.display(), rb);
+            }
+            // Same temp/ policy as unstaged downloads; kept chunks stay under the staging entry for a retry
+            if staging::drop_chunks_after_failure(&e.to_string(), keep_temp_on_failure()) {
+                remove_chunk_temp_dir(&staged_asset);
+                let _ = fs::remove_dir(&staging_root);
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Allowed range for per-download file concurrency.
+pub const FILE_CONCURRENCY_RANGE: std::ops::RangeInclusive<usize> = 1..=16;
+/// Allowed range for per-file chunk concurrency.
+pub const CHUNK_CONCURRENCY_RANGE: std::ops::RangeInclusive<usize> = 1..=64;
+
+/// Default cap on file tasks in flight across every download at once.
+pub const DEFAULT_GLOBAL_FILE_CONCURRENCY: usize = 8;
+
+static GLOBAL_FILE_SEMA: OnceLock<(Arc<tokio::sync::Semaphore>, usize)> = OnceLock::new();
+
+/// Process-wide file-task semaphore shared by all downloads, sized once from
+/// EAM_GLOBAL_FILE_CONCURRENCY (default 8, clamped to 1..=64). Returns it with its size.
+///
+/// A file task holds a permit from its own download's semaphore (per-call file concurrency,
+/// EAM_FILE_CONCURRENCY) and one from this one, so each download gets at most its own limit
+/// while all downloads together never exceed the global limit.
+pub fn global_file_semaphore() -> (Arc<tokio::sync::Semaphore>, usize) {
+    GLOBAL_FILE_SEMA.get_or_init(|| {
+        let n = std::env::var("EAM_GLOBAL_FILE_CONCURRENCY").ok()
+            .and_then(|v| v.trim().parse::<usize>().ok())
+            .filter(|&n| n > 0)
+            .unwrap_or(DEFAULT_GLOBAL_FILE_CONCURRENCY)
+            .clamp(1, 64);
+        (Arc::new(tokio::sync::Semaphore::new(n)), n)
+    }).clone()
+}
+
+/// Resolve (file, chunk) concurrency: request override > config.json > EAM_* env > defaults (2, 4).
+pub fn effective_download_concurrency(options: &models::DownloadOptions) -> (usize, usize) {
+    let cfg = load_paths_config();
+    let env = |k: &str| std::env::var(k).ok().and_then(|s| s.parse::<usize>().ok()).filter(|&n| n > 0);
+    let files = options.file_concurrency.or(cfg.file_concurrency).or_else(|| env("EAM_FILE_CONCURRENCY")).unwrap_or(2);
+    let chunks = options.chunk_concurrency.or(cfg.chunk_concurrency).or_else(|| env("EAM_CHUNK_CONCURRENCY")).unwrap_or(4);
+    (files.clamp(*FILE_CONCURRENCY_RANGE.start(), *FILE_CONCURRENCY_RANGE.end()),
+     chunks.clamp(*CHUNK_CONCURRENCY_RANGE.start(), *CHUNK_CONCURRENCY_RANGE.end()))
+}
+
+/// Resolve the download bandwidth cap: request override > EAM_MAX_BYTES_PER_SEC > unlimited (None).
+pub fn effective_download_rate_limit(options: &models::DownloadOptions) -> Option<u64> {
+    options.max_bytes_per_sec
+        .or_else(|| std::env::var("EAM_MAX_BYTES_PER_SEC").ok().and_then(|s| s.trim().parse::<u64>().ok()))
+        .filter(|&n| n > 0)
+}
+
+/// EAM_SKIP_SPACE_CHECK=1 disables the free-space preflight (e.g., on filesystems that misreport it).
+fn skip_space_check() -> bool {
+    std::env::var("EAM_SKIP_SPACE_CHECK").map(|v| matches!(v.trim(), "1" | "true" | "yes")).unwrap_or(false)
+}
+
+/// Fail early when the filesystem holding `target` cannot fit `needed` bytes plus the safety margin.
+/// Skipped when EAM_SKIP_SPACE_CHECK=1 or when free space cannot be determined.
+fn preflight_free_space(target: &Path, needed: u64) -> Result<(), String> {
+    if skip_space_check() {
+        return Ok(());
+    }
+    let Some(probe) = disk_space::nearest_existing_ancestor(target) else { return Ok(()) };
+    match fs2::available_space(&probe) {
+        Ok(available) => disk_space::check_free_space(needed, available, &probe),
+        Err(e) => {
+            log::warn!(target: DOWNLOAD_LOG_TARGET, "Could not determine free space at {}: {}", probe.display(), e);
+            Ok(())
+        }
+    }
+}
+
+/// EAM_KEEP_TEMP_ON_FAILURE=0 clears temp/ chunks when a download fails with an error (default: keep
+/// them so a retry resumes). Cancelled downloads always remove theirs.
+fn keep_temp_on_failure() -> bool {
+    std::env::var("EAM_KEEP_TEMP_ON_FAILURE").map(|v| !matches!(v.trim(), "0" | "false" | "no")).unwrap_or(true)
+}
+
+/// Retries per chunk after the first attempt (EAM_CHUNK_RETRIES, default 3, at most 10; 0 disables).
+fn chunk_retries() -> u32 {
+    retry::parse_retries(std::env::var("EAM_CHUNK_RETRIES").ok().as_deref())
+}
+
+/// Keep assembled files whose hash does not match the manifest (EAM_ALLOW_HASH_MISMATCH=1); by default
+/// a mismatch deletes the .part file and fails the download.
+fn allow_hash_mismatch() -> bool {
+    std::env::var("EAM_ALLOW_HASH_MISMATCH").map(|v| matches!(v.trim(), "1" | "true" | "yes")).unwrap_or(false)
+}
+
+/// Name of the hidden folder under downloads/ that holds in-progress staged downloads.
+pub const STAGING_DIR_NAME: &str = ".staging";
+
+/// Staging mode (EAM_STAGING_DOWNLOADS=1): download into downloads/.staging/<id>/ and only
+/// move the folder into place once its completion marker exists, so scans never see partial assets.
+pub fn staging_downloads_enabled() -> bool {
+    std::env::var("EAM_STAGING_DOWNLOADS").map(|v| matches!(v.trim(), "1" | "true" | "yes")).unwrap_or(false)
+}
+
+/// Staging folder for a final download path: downloads/.staging/<sha1 of the final path>.
+/// Deterministic so the same target always maps to the same staging entry.
+fn staging_dir_for(final_dir: &Path) -> PathBuf {
     use sha1::{Digest, Sha1};
-    use std::io::{self, Write};
+    let mut hasher = Sha1::new();
+    hasher.update(final_dir.to_string_lossy().as_bytes());
+    let id: String = hasher.finalize().iter().take(10).map(|b| format!("{:02x}", b)).collect();
+    get_default_downloads_dir_path().join(STAGING_DIR_NAME).join(id)
+}
+
+/// Move a completed staged download into its final location.
+///
+/// Uses a rename when staging and target share a filesystem. Otherwise it copies into a sibling
+/// temp folder next to the target and renames that, so the target still appears all at once.
+fn promote_staged_download(staged: &Path, final_dir: &Path) -> std::io::Result<()> {
+    if let Some(parent) = final_dir.parent() { fs::create_dir_all(parent)?; }
+    if final_dir.exists() {
+        // Keep top-level files only present in the old folder (e.g. .download_info.json written before staging)
+        if let Ok(entries) = fs::read_dir(final_dir) {
+            for e in entries.flatten() {
+                let target = staged.join(e.file_name());
+                if e.path().is_file() && !target.exists() { let _ = fs::copy(e.path(), &target); }
+            }
+        }
+        fs::remove_dir_all(final_dir)?;
+    }
+    if fs::rename(staged, final_dir).is_ok() {
+        return Ok(());
+    }
+    let mut tmp_name = final_dir.file_name().map(|n| n.to_os_string()).unwrap_or_default();
+    tmp_name.push(".promote-tmp");
+    let tmp = final_dir.with_file_name(tmp_name);
+    let _ = fs::remove_dir_all(&tmp);
+    parallel_copy::copy_tree_parallel(staged, &tmp, conflict::ConflictPolicy::Overwrite, 1, &|_, _| {}, &|| false)?;
+    fs::rename(&tmp, final_dir)?;
+    let _ = fs::remove_dir_all(staged);
+    Ok(())
+}
+
+async fn download_asset_into(dm: &DownloadManifest, _base_url: &str, download_directory_full_path: &Path, progress_callback: Option<ProgressFn>, job_id_opt: Option<&str>, options: &models::DownloadOptions) -> Result<(), anyhow::Error> {
+    use egs_api::api::types::chunk::Chunk;
     use tokio::sync::Semaphore;
     use tokio::task::JoinSet;
     use std::time::{Instant, Duration};
 
-    // Concurrency controls (sane defaults; can be tuned via env)
-    let max_files: usize = std::env::var("EAM_FILE_CONCURRENCY").ok().and_then(|s| s.parse().ok()).filter(|&n| n > 0).unwrap_or(2);
-    let max_chunks: usize = std::env::var("EAM_CHUNK_CONCURRENCY").ok().and_then(|s| s.parse().ok()).filter(|&n| n > 0).unwrap_or(4);
+    // Concurrency controls: request > config.json > env > defaults
+    let (max_files, max_chunks) = effective_download_concurrency(options);
+
+    let client = reqwest::Client::new();
+
+    // Optional files to leave out (e.g., platform-specific payloads), from EAM_SKIP_PATTERNS globs
+    let skip_set = match std::env::var("EAM_SKIP_PATTERNS") {
+        Ok(raw) => glob::PatternSet::new(&glob::parse_patterns(&raw)).unwrap_or_else(|e| {
+            log::warn!(target: DOWNLOAD_LOG_TARGET, "Ignoring EAM_SKIP_PATTERNS: {}", e);
+            glob::PatternSet::default()
+        }),
+        Err(_) => glob::PatternSet::default(),
+    };
+    let include_set = glob::PatternSet::new(&options.include_patterns).map_err(|e| anyhow::anyhow!(e))?;
+    let exclude_set = glob::PatternSet::new(&options.exclude_patterns).map_err(|e| anyhow::anyhow!(e))?;
+
+    // Get list of files to download
+    let manifest_files = dm.files();
+    let manifest_file_count = manifest_files.len();
+    let selection = glob::select_files(manifest_files.keys().cloned(), &include_set, &exclude_set, &skip_set);
+    let keep: std::collections::HashSet<&str> = selection.selected.iter().map(String::as_str).collect();
+    let files: Vec<_> = manifest_files.into_iter().filter(|(filename, _)| keep.contains(filename.as_str())).collect();
+    let total_files = files.len();
+    if total_files == 0 {
+        if selection.excluded_by_filter.len() == manifest_file_count && manifest_file_count > 0 {
+            return Err(anyhow::anyhow!("include/exclude filter matched none of the {} manifest files", manifest_file_count));
+        }
+        if manifest_file_count > 0 {
+            return Err(anyhow::anyhow!("all {} manifest files were excluded by EAM_SKIP_PATTERNS", manifest_file_count));
+        }
+        return Err(anyhow::anyhow!("download manifest contains no files"));
+    }
+    if !selection.skipped_by_pattern.is_empty() {
+        log::info!(target: DOWNLOAD_LOG_TARGET, "Skipping {} file(s) matching EAM_SKIP_PATTERNS {:?}", selection.skipped_by_pattern.len(), skip_set.patterns());
+    }
+
+    // Precompute total bytes across all files and a shared bytes_done counter for live speed
+    let total_bytes_all: u64 = files.iter()
+        .map(|(_, f)| f.file_chunk_parts.iter().map(|p| p.size as u64).sum::<u64>())
+        .sum();
+
+    // Refuse to start when the disk cannot hold the rest of the asset; files already at their
+    // final size from an earlier run do not need space again.
+    let expected_sizes: Vec<(String, u64)> = files.iter()
+        .map(|(name, f)| (name.clone(), f.file_chunk_parts.iter().map(|p| p.size as u64).sum()))
+        .collect();
+    let already_on_disk = disk_space::bytes_already_on_disk(download_directory_full_path, &expected_sizes);
+    if let Err(msg) = preflight_free_space(download_directory_full_path, total_bytes_all.saturating_sub(already_on_disk)) {
+        emit_event(job_id_opt, models::Phase::DownloadError, msg.clone(), None, None);
+        return Err(anyhow::anyhow!(msg));
+    }
 
     // Create asset folder
     std::fs::create_dir_all(download_directory_full_path)?;
     // Create temp folder under each asset for chunk downloads
-    let temp_dir = download_directory_full_path.parent().map(|p| p.join("temp")).unwrap_or_else(|| download_directory_full_path.join("temp"));
+    let temp_dir = chunk_temp_dir_for(download_directory_full_path);
+    // Held until this function returns so cleanup by a concurrent job sharing temp/ leaves it alone
+    let temp_lease = temp_dir_users().acquire(&temp_dir);
     std::fs::create_dir_all(&temp_dir)?;
 
     // Clear any stale completion marker when starting/resuming a download
-    let complete_marker = download_directory_full_path.join(".download_complete");
+    let complete_marker = download_directory_full_path.join(staging::COMPLETE_MARKER);
     match std::fs::remove_file(&complete_marker) {
         Ok(_) => {
-            println!("Clearing stale completion marker: {}", complete_marker.display());
+            log::info!(target: DOWNLOAD_LOG_TARGET, "Clearing stale completion marker: {}", complete_marker.display());
         }
         Err(e) => {
             if e.kind() != std::io::ErrorKind::NotFound {
@@ -212,49 +632,112 @@ pub async fn download_asset(dm: &DownloadManifest, _base_url: &str, download_dir
         }
     }
 
-    let client = reqwest::Client::new();
-
-    // Get list of files to download
-    let files: Vec<_> = dm.files().into_iter().collect();
-    let total_files = files.len();
-    if total_files == 0 {
-        return Err(anyhow::anyhow!("download manifest contains no files"));
-    }
-
-    // Precompute total bytes across all files and a shared bytes_done counter for live speed
-    let total_bytes_all: u64 = files.iter()
-        .map(|(_, f)| f.file_chunk_parts.iter().map(|p| p.size as u64).sum::<u64>())
-        .sum();
+    // Announce the full plan up front so the UI can size its progress bar before the first tick
+    emit_event(
+        job_id_opt,
+        models::Phase::DownloadStart,
+        format!("Downloading {} files ({:.2} MB)", total_files, total_bytes_all as f64 / (1024.0 * 1024.0)),
+        Some(0.0),
+        Some(serde_json::json!({
+            "total_files": total_files,
+            "total_bytes": total_bytes_all,
+            "target_path": download_directory_full_path.to_string_lossy(),
+            "skipped_by_pattern": selection.skipped_by_pattern.len(),
+            "manifest_files": manifest_file_count,
+            "excluded_by_filter": selection.excluded_by_filter.len(),
+            "max_bytes_per_sec": effective_download_rate_limit(options),
+        })),
+    );
 
-    let bytes_done = Arc::new(AtomicU64::new(0));
+    // Manifest bytes accounted for: the one progress value behind the callback and every event (see byte_progress)
+    let bytes_done = Arc::new(byte_progress::ByteProgress::new(total_bytes_all));
+    // Bytes actually fetched this run (bytes_done also counts files skipped as up to date), sampled
+    // over a rolling 3s window for bytes_per_sec/eta_seconds in progress events
+    let network_bytes = Arc::new(AtomicU64::new(0));
+    let throughput = Arc::new(Mutex::new(throughput::Throughput::new(Duration::from_secs(3))));
+    // Cached chunks found corrupt (e.g. CDN error pages) and fetched again
+    let recovered_chunks = Arc::new(AtomicU64::new(0));
+    // Chunks known to be fully written, persisted in temp/manifest.json so a restart reuses only those
+    let chunk_ledger = Arc::new(Mutex::new(chunk_check::ChunkLedger::load(&temp_dir)));
+    // One limiter for the whole download so the cap applies to the aggregate rate, not per chunk
+    let rate_limiter: Option<Arc<rate_limit::RateLimiter>> = effective_download_rate_limit(options)
+        .map(|bps| Arc::new(rate_limit::RateLimiter::new(bps)));
+    if let Some(rl) = rate_limiter.as_ref() {
+        log::info!(target: DOWNLOAD_LOG_TARGET, "Throttling download to {} bytes/s", rl.bytes_per_sec());
+    }
+    let max_chunk_retries = chunk_retries();
+
+    // Expected (file, size) set for the post-download reconciliation; zero-chunk files are never written
+    let expected_files: Vec<(String, u64)> = files.iter()
+        .filter(|(_, f)| !f.file_chunk_parts.is_empty())
+        .map(|(name, f)| (name.clone(), f.file_chunk_parts.iter().map(|p| p.size as u64).sum()))
+        .collect();
+    // Same set with hashes, recorded in the completion marker (see verify_managed_download)
+    let marker_files: Vec<serde_json::Value> = files.iter()
+        .filter(|(_, f)| !f.file_chunk_parts.is_empty())
+        .map(|(name, f)| serde_json::json!({
+            "name": name,
+            "size": f.file_chunk_parts.iter().map(|p| p.size as u64).sum::<u64>(),
+            "hash": f.file_hash,
+        }))
+        .collect();
 
     // Check if job has been requested to cancel
     if check_if_job_is_cancelled(job_id_opt) {
         cancel_this_job(job_id_opt);
-        return Err(anyhow::anyhow!("cancelled"));
-    }
-
-
-    // Setup file-level concurrency
+        return Err(anyhow::anyhow!(staging::CANCELLED));
+    }
+
+    // Verify already-present files up front, hashing several concurrently on the blocking pool.
+    let candidates: Vec<ExistingFileCheck> = files.iter()
+        .filter_map(|(filename, file)| {
+            let out_path = resolve_output_file_path(download_directory_full_path, filename);
+            if !out_path.exists() { return None; }
+            Some(ExistingFileCheck {
+                filename: filename.clone(),
+                path: out_path,
+                expected_hash: file.file_hash.clone(),
+                expected_size: file.file_chunk_parts.iter().map(|p| p.size as u64).sum(),
+            })
+        })
+        .collect();
+    let mut verify_cache = verify_cache::VerifyCache::load(download_directory_full_path);
+    let up_to_date_files = Arc::new(verify_existing_files(candidates, &mut verify_cache, job_id_opt).await);
+    if let Err(e) = verify_cache.save() {
+        log::warn!(target: DOWNLOAD_LOG_TARGET, "Failed to save {}: {}", verify_cache::VERIFY_CACHE_FILE, e);
+    }
+    // Files assembled below are recorded too, so the next run does not hash them again
+    let verify_cache = Arc::new(Mutex::new(verify_cache));
+
+
+    // Setup file-level concurrency: this download's own limit plus the process-wide cap
     let file_sema = Arc::new(Semaphore::new(max_files));
+    let (global_file_sema, _) = global_file_semaphore();
     let mut join = JoinSet::new();
 
     let totals = Arc::new(tokio::sync::Mutex::new(models::Totals::default()));
 
     // Track completed files across concurrent tasks to compute overall percent
     let completed = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    // Optional per-folder breakdown (detailed_progress); off by default to keep events small
+    let folder_progress: Option<Arc<folder_progress::FolderProgress>> = options.detailed_progress
+        .then(|| Arc::new(folder_progress::FolderProgress::new(files.iter().map(|(name, _)| name.as_str()))));
 
     // Capture job id for async blocks
     let job_id_owned = job_id_opt.map(|s| s.to_string());
 
     for (file_index, (filename, file)) in files.into_iter().enumerate() {
+        // Hold off starting new files while paused
+        wait_while_job_paused(job_id_opt).await;
         // Check if job has been requested to cancel
         if check_if_job_is_cancelled(job_id_opt) {
             cancel_this_job(job_id_opt);
-            return Err(anyhow::anyhow!("cancelled"));
+            return Err(anyhow::anyhow!(staging::CANCELLED));
         }
 
         let permit_owner = file_sema.clone().acquire_owned().await.expect("semaphore closed");
+        // Always taken after the per-download permit, so no task holds a global permit while queued locally
+        let global_permit = global_file_sema.clone().acquire_owned().await.expect("global semaphore closed");
 
         let client = client.clone();
         let temp_dir = temp_dir.clone();
@@ -264,48 +747,39 @@ pub async fn download_asset(dm: &DownloadManifest, _base_url: &str, download_dir
         let progress = progress_callback.clone();
         let job_id_owned = job_id_owned.clone();
         let bytes_done = bytes_done.clone();
+        let network_bytes = network_bytes.clone();
+        let throughput = throughput.clone();
+        let recovered_chunks = recovered_chunks.clone();
+        let chunk_ledger = chunk_ledger.clone();
         let _total_bytes_all = total_bytes_all;
+        let up_to_date_files = up_to_date_files.clone();
+        let verify_cache = verify_cache.clone();
+        let folder_progress = folder_progress.clone();
 
-        join.spawn(async move {
+        join.spawn(utils::with_log_job(job_id_owned.clone(), async move {
             let _permit = permit_owner; // hold until task end
+            let _global_permit = global_permit;
             let file_no = file_index + 1;
-            // println!("Downloading file {}/{}: {}", file_no, total_files, filename);
-            io::stdout().flush().ok();
+            log::debug!(target: DOWNLOAD_LOG_TARGET, "Downloading file {}/{}: {}", file_no, total_files, filename);
             // Total bytes for this file (sum of chunk parts)
             let file_total_bytes: u64 = file.file_chunk_parts.iter().map(|p| p.size as u64).sum();
 
             // Prepare final output path under .../data/<filename>
-            let mut out_path = out_directory.clone();
-            if out_path.file_name().map_or(false, |name| name == "data") == false { out_path = out_path.join("data"); }
-            let out_path = out_path.join(&filename);
+            let out_path = resolve_output_file_path(&out_directory, &filename);
             if let Some(parent) = out_path.parent() { let _ = std::fs::create_dir_all(parent); }
             let tmp_out_path = out_path.with_extension("part");
 
-            // Skip if final file already exists and matches expected hash/size
-            let mut skip_existing = false;
-            if out_path.exists() {
-                if !file.file_hash.is_empty() {
-                    if let Ok(mut _file) = std::fs::File::open(&out_path) {
-                        use std::io::Read;
-                        let mut hasher = Sha1::new();
-                        let mut buffer = [0u8; 1024 * 1024];
-                        loop { match _file.read(&mut buffer) { Ok(0) => break, Ok(n) => hasher.update(&buffer[..n]), Err(_) => break } }
-                        let got_hex = hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect::<String>();
-                        if got_hex == file.file_hash { println!("  skipping: existing file is up-to-date"); skip_existing = true; }
-                    }
-                } else {
-                    let expected_size: u64 = file.file_chunk_parts.iter().map(|p| p.size as u64).sum();
-                    if let Ok(meta) = std::fs::metadata(&out_path) { if meta.len() == expected_size { println!("  skipping: existing file size matches (no hash available)"); skip_existing = true; } }
-                }
-            }
+            // Skip if final file already exists and matched expected hash/size in the verification pass
+            let skip_existing = up_to_date_files.contains(&filename);
             if skip_existing {
                 // Count these bytes toward total progress
-                let cur = bytes_done.fetch_add(file_total_bytes, Ordering::SeqCst) + file_total_bytes;
+                let cur = bytes_done.add(file_total_bytes);
                 let mut totals_locked = totals.lock().await; totals_locked.up_to_date += 1;
 
-                // Count as completed for overall percent and notify progress
+                // Count as completed and notify progress
+                if let Some(fp) = folder_progress.as_ref() { fp.mark_done(&filename); }
                 let done = completed.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
-                if let Some(cb) = &progress { let pct = (((done as f64) / (total_files as f64)) * 100.0).floor() as u32; (cb)(pct.min(100), format!("{} / {}", done, total_files)); }
+                if let Some(cb) = &progress { (cb)(byte_progress::whole_percent(cur, _total_bytes_all), format!("{} / {}", done, total_files)); }
                 // Also emit a detailed progress event so UI can show bytes
                 // utils::emit_event(
                 //     job_id_owned.as_deref(),
@@ -325,23 +799,24 @@ pub async fn download_asset(dm: &DownloadManifest, _base_url: &str, download_dir
             // Ensure chunks
             let total_chunks = file.file_chunk_parts.len();
             if total_chunks == 0 {
-                eprintln!("Warning: zero chunk parts listed for file {}; skipping file", filename);
+                log::warn!(target: DOWNLOAD_LOG_TARGET, "Zero chunk parts listed for file {}; skipping file", filename);
                 let mut t = totals.lock().await; t.skipped_zero += 1;
-                // Treat as completed for overall progress and notify
+                // Treat as completed (it has no bytes to add) and notify
+                if let Some(fp) = folder_progress.as_ref() { fp.mark_done(&filename); }
                 let done = completed.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
-                if let Some(cb) = &progress { let pct = (((done as f64) / (total_files as f64)) * 100.0).floor() as u32; (cb)(pct.min(100), format!("{} / {}", done, total_files)); }
+                if let Some(cb) = &progress { (cb)(bytes_done.whole_percent(), format!("{} / {}", done, total_files)); }
                 // Emit a detailed progress event even for zero-chunk files
                 utils::emit_event(
                     job_id_owned.as_deref(),
                     models::Phase::DownloadProgress,
                     format!("download_asset#2:{} / {}", done, total_files),
-                    Some(((done as f64) / (total_files as f64) * 100.0) as f32),
-                    Some(serde_json::json!({
+                    Some(bytes_done.percent()),
+                    Some(with_folder_progress(serde_json::json!({
                         "downloaded_files": done,
                         "total_files": total_files,
-                        "bytes_done": bytes_done.load(std::sync::atomic::Ordering::SeqCst),
+                        "bytes_done": bytes_done.done(),
                         "total_bytes": _total_bytes_all,
-                    })),
+                    }), folder_progress.as_deref())),
                 );
                 return Ok(());
             }
@@ -351,6 +826,8 @@ pub async fn download_asset(dm: &DownloadManifest, _base_url: &str, download_dir
             let mut chunk_join = JoinSet::new();
 
             for (chunk_idx, part) in file.file_chunk_parts.iter().enumerate() {
+                // Chunks already in flight finish; new ones wait for a resume
+                utils::wait_while_job_paused(job_id_owned.as_deref()).await;
                 // Check if job has been requested to be cancelled
                 if utils::check_if_job_is_cancelled(job_id_owned.as_deref()) {
                     cancel_this_job(job_id_owned.as_deref());
@@ -358,94 +835,206 @@ pub async fn download_asset(dm: &DownloadManifest, _base_url: &str, download_dir
                 }
                 let guid = part.guid.clone();
                 let link = part.link.clone();
+                let part_size = part.size as u64;
                 let client = client.clone();
                 let temp_dir = temp_dir.clone();
                 let job_id_inner = job_id_owned.clone();
                 let chunk_permit_owner = chunk_sema.clone().acquire_owned().await.expect("chunk sema closed");
                 let completed = completed.clone();
                 let bytes_done = bytes_done.clone();
-                chunk_join.spawn(async move {
+                let network_bytes = network_bytes.clone();
+                let throughput = throughput.clone();
+                let recovered_chunks = recovered_chunks.clone();
+                let chunk_ledger = chunk_ledger.clone();
+                let rate_limiter = rate_limiter.clone();
+                let folder_progress = folder_progress.clone();
+                chunk_join.spawn(utils::with_log_job(job_id_inner.clone(), async move {
                     let _p = chunk_permit_owner; // hold permit until end
+                    // Paused after this chunk was queued? wait before fetching it. Once past the wait the
+                    // chunk counts as in flight, which a shutdown drain waits for (re-check closes the gap)
+                    let _in_flight = loop {
+                        utils::wait_while_job_paused(job_id_inner.as_deref()).await;
+                        let guard = in_flight_chunks().enter();
+                        if !job_id_inner.as_deref().is_some_and(|j| pause_map().is_paused(j)) {
+                            break guard;
+                        }
+                    };
                     // Cancelled? bail
                     if utils::check_if_job_is_cancelled(job_id_inner.as_deref()) {
                         cancel_this_job(job_id_inner.as_deref());
-                        return Err(anyhow::anyhow!("cancelled"));
+                        return Err(anyhow::anyhow!(staging::CANCELLED));
                     }
                     let chunk_path = temp_dir.join(format!("{}.chunk", guid));
                     if chunk_path.exists() {
-                        // print!("\r  chunks: {}/{} ({}%) - using cached chunk    ", chunk_idx + 1, total_chunks, ((chunk_idx + 1) * 100 / total_chunks).min(100));
-                        io::stdout().flush().ok();
-                        return Ok(());
+                        let on_disk = chunk_path.metadata().map(|m| m.len()).unwrap_or(0);
+                        let verified = chunk_ledger.lock().unwrap_or_else(|p| p.into_inner()).is_verified(&guid, on_disk);
+                        // Reuse only chunks recorded as fully written with this exact size that still look valid
+                        if verified && chunk_check::validate_cached_chunk(&chunk_path) {
+                            log::trace!(target: DOWNLOAD_LOG_TARGET, "Chunk {}/{}: using cached chunk", chunk_idx + 1, total_chunks);
+                            bytes_done.add(part_size);
+                            return Ok(());
+                        }
+                        // Partial (e.g. process killed mid-write) or not a real chunk (e.g. a cached HTML error page)
+                        let _ = std::fs::remove_file(&chunk_path);
+                        chunk_ledger.lock().unwrap_or_else(|p| p.into_inner()).forget(&guid);
+                        recovered_chunks.fetch_add(1, Ordering::SeqCst);
+                        log::info!(target: DOWNLOAD_LOG_TARGET, "Discarded unverified cached chunk {} ({} bytes); re-downloading", guid, on_disk);
                     }
 
-                    // print!("\r  chunks: {}/{} ({}%) - downloading...        ", chunk_idx + 1, total_chunks, ((chunk_idx + 1) * 100 / total_chunks).min(100));
-                    io::stdout().flush().ok();
+                    log::trace!(target: DOWNLOAD_LOG_TARGET, "Chunk {}/{}: downloading", chunk_idx + 1, total_chunks);
 
                     let link = link.as_ref().ok_or_else(|| anyhow::anyhow!("missing signed chunk link for {}", guid))?;
                     let url = link.to_string();
 
-                    // Check cancel right before sending
-                    if utils::check_if_job_is_cancelled(job_id_inner.as_deref()) {
-                        cancel_this_job(job_id_inner.as_deref());
-                        return Err(anyhow::anyhow!("cancelled"));
-                    }
-                    let mut resp = client.get(url.clone()).send().await;
-                    if resp.is_err() {
-                        resp = client.get(url.clone()).send().await;
-
-                    }
-                    let resp = resp.map_err(|e| anyhow::anyhow!("chunk request failed for {}: {}", guid, e))?;
-                    let resp = resp.error_for_status().map_err(|e| anyhow::anyhow!("chunk HTTP {} for {}", e.status().unwrap_or_default(), guid))?;
-
-                    // Check cancel before reading body
-                    if utils::check_if_job_is_cancelled(job_id_inner.as_deref()) {
-                        cancel_this_job(job_id_inner.as_deref());
-                        return Err(anyhow::anyhow!("cancelled"));
-                    }
-
-                    use futures_util::StreamExt;
-
                     if let Some(parent) = chunk_path.parent() {
                         let _ = std::fs::create_dir_all(parent);
                     }
 
-                    let mut _file = std::fs::File::create(&chunk_path)?;
+                    // One attempt requests the chunk and streams its body to disk. Failed requests, transient
+                    // statuses and bodies cut off mid-stream are all retried with backoff.
+                    let job = job_id_inner.as_deref();
+                    let (client, url, guid_ref, chunk_path_ref) = (&client, &url, &guid, &chunk_path);
+                    let (rate_limiter, network_bytes, completed, bytes_done_ref, throughput, folder_progress) =
+                        (&rate_limiter, &network_bytes, &completed, &bytes_done, &throughput, &folder_progress);
+                    let fetched_chunk = retry::with_retries(
+                        max_chunk_retries,
+                        move || async move {
+                            use futures_util::StreamExt;
+
+                            // Check cancel right before sending
+                            if utils::check_if_job_is_cancelled(job) {
+                                cancel_this_job(job);
+                                return Err(retry::AttemptError::Fatal(anyhow::anyhow!(staging::CANCELLED)));
+                            }
+                            let resp = match client.get(url.clone()).send().await {
+                                Ok(r) => r,
+                                Err(e) => return Err(retry::AttemptError::Transient(format!("request failed: {}", e))),
+                            };
+                            let status = resp.status().as_u16();
+                            if retry::is_retryable_status(status) {
+                                return Err(retry::AttemptError::Transient(format!("HTTP {}", status)));
+                            }
+                            let resp = match resp.error_for_status() {
+                                Ok(r) => r,
+                                Err(e) => {
+                                    return Err(retry::AttemptError::Fatal(anyhow::anyhow!("chunk HTTP {} for {}", e.status().unwrap_or_default(), guid_ref)));
+                                }
+                            };
 
-                    let mut stream = resp.bytes_stream();
-                    let mut last_emit = Instant::now();
-                    while let Some(next) = stream.next().await {
-                        if utils::check_if_job_is_cancelled(job_id_inner.as_deref()) {
-                            // Leave partial chunk; future runs may reuse/overwrite
-                            cancel_this_job(job_id_inner.as_deref());
-                            return Err(anyhow::anyhow!("cancelled"));
-                        }
+                            // Check cancel before reading body
+                            if utils::check_if_job_is_cancelled(job) {
+                                cancel_this_job(job);
+                                return Err(retry::AttemptError::Fatal(anyhow::anyhow!(staging::CANCELLED)));
+                            }
 
-                        let bytes = next.map_err(|e| anyhow::anyhow!("read chunk {}: {}", guid, e))?;
-                        std::io::Write::write_all(&mut _file, &bytes)?;
+                            let expected_len = resp.content_length();
+                            let mut file = match std::fs::File::create(chunk_path_ref) {
+                                Ok(f) => f,
+                                Err(e) => return Err(retry::AttemptError::Fatal(e.into())),
+                            };
+                            let mut written_len: u64 = 0;
+
+                            let mut stream = resp.bytes_stream();
+                            let mut last_emit = Instant::now();
+                            while let Some(next) = stream.next().await {
+                                if utils::check_if_job_is_cancelled(job) {
+                                    // Leave partial chunk; future runs may reuse/overwrite
+                                    cancel_this_job(job);
+                                    return Err(retry::AttemptError::Fatal(anyhow::anyhow!(staging::CANCELLED)));
+                                }
 
-                        // Update global bytes_done and emit throttled progress for live speed in UI
-                        let cur = bytes_done.fetch_add(bytes.len() as u64, Ordering::SeqCst) + (bytes.len() as u64);
-                        if last_emit.elapsed() >= Duration::from_millis(300) {
-                            let done_files = completed.load(std::sync::atomic::Ordering::SeqCst);
-                            let _percentage = if _total_bytes_all > 0 { ((cur as f64) / (_total_bytes_all as f64) * 100.0) as f32 } else { 0.0 };
+                                let bytes = match next {
+                                    Ok(b) => b,
+                                    Err(e) => return Err(retry::AttemptError::Transient(format!("body read failed after {} bytes: {}", written_len, e))),
+                                };
+                                if let Err(e) = std::io::Write::write_all(&mut file, &bytes) {
+                                    return Err(retry::AttemptError::Fatal(e.into()));
+                                }
+                                written_len += bytes.len() as u64;
+                                if let Some(rl) = rate_limiter.as_ref() {
+                                    let wait = rl.reserve(bytes.len() as u64);
+                                    if !wait.is_zero() {
+                                        tokio::time::sleep(wait).await;
+                                    }
+                                }
 
+                                // Count fetched bytes for live speed and emit throttled progress (bytes_done moves per finished chunk)
+                                let fetched = network_bytes.fetch_add(bytes.len() as u64, Ordering::SeqCst) + (bytes.len() as u64);
+                                download_metrics().add_bytes(bytes.len() as u64);
+                                if last_emit.elapsed() >= Duration::from_millis(300) {
+                                    let done_files = completed.load(std::sync::atomic::Ordering::SeqCst);
+                                    let cur = bytes_done_ref.done();
+                                    let _percentage = byte_progress::percent(cur, _total_bytes_all);
+                                    let bytes_per_sec = throughput.lock().unwrap_or_else(|p| p.into_inner()).record(Instant::now(), fetched);
+                                    download_metrics().sample(Instant::now());
+                                    let eta = throughput::eta_seconds(_total_bytes_all.saturating_sub(cur), bytes_per_sec);
+
+                                    utils::emit_event(
+                                        job,
+                                        models::Phase::DownloadProgress,
+                                        format!("download_asset#3:{} / {}", done_files, total_files),
+                                        Some(_percentage),
+                                        Some(with_folder_progress(serde_json::json!({
+                                            "downloaded_files": done_files,
+                                            "total_files": total_files,
+                                            "bytes_done": cur,
+                                            "total_bytes": _total_bytes_all,
+                                            "bytes_per_sec": bytes_per_sec.round() as u64,
+                                            "eta_seconds": eta,
+                                        }), folder_progress.as_deref())),
+                                    );
+                                    last_emit = Instant::now();
+                                }
+                            }
+                            if let Err(e) = std::io::Write::flush(&mut file) {
+                                return Err(retry::AttemptError::Fatal(e.into()));
+                            }
+                            drop(file);
+                            if let Some(expected) = expected_len {
+                                if written_len != expected {
+                                    let _ = std::fs::remove_file(chunk_path_ref);
+                                    return Err(retry::AttemptError::Transient(format!("truncated: got {} of {} bytes", written_len, expected)));
+                                }
+                            }
+                            Ok(written_len)
+                        },
+                        |retry_no, reason| {
+                            // Equal jitter; subsecond clock noise is enough to spread out tasks that failed together
+                            let jitter = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map(|d| d.subsec_nanos() as f64 / 1e9).unwrap_or(0.5);
+                            let delay = retry::backoff_delay(retry_no - 1, jitter);
+                            log::info!(target: DOWNLOAD_LOG_TARGET, "Chunk {} {}; retry {}/{} in {} ms", guid_ref, reason, retry_no, max_chunk_retries, delay.as_millis());
                             utils::emit_event(
-                                job_id_inner.as_deref(),
+                                job,
                                 models::Phase::DownloadProgress,
-                                format!("download_asset#3:{} / {}", done_files, total_files),
-                                Some(_percentage),
+                                format!("Retrying chunk {} ({}/{})", guid_ref, retry_no, max_chunk_retries),
+                                None,
                                 Some(serde_json::json!({
-                                    "downloaded_files": done_files,
-                                    "total_files": total_files,
-                                    "bytes_done": cur,
-                                    "total_bytes": _total_bytes_all,
+                                    "chunk_retry": {
+                                        "guid": guid_ref.to_string(),
+                                        "attempt": retry_no,
+                                        "max_retries": max_chunk_retries,
+                                        "reason": reason,
+                                        "delay_ms": delay.as_millis() as u64,
+                                    }
                                 })),
                             );
-                            last_emit = Instant::now();
-                        }
+                            delay
+                        },
+                    ).await;
+                    let written_len = match fetched_chunk {
+                        Ok(n) => n,
+                        Err(retry::AttemptError::Fatal(e)) => return Err(e),
+                        Err(retry::AttemptError::Transient(reason)) => return Err(anyhow::anyhow!("chunk {} failed: {}", guid, reason)),
+                    };
+                    if !chunk_check::validate_cached_chunk(&chunk_path) {
+                        return Err(anyhow::anyhow!("chunk {} is not valid chunk data (server returned an error page?)", guid));
                     }
+                    if let Err(e) = chunk_ledger.lock().unwrap_or_else(|p| p.into_inner()).record(&guid, written_len) {
+                        log::warn!(target: DOWNLOAD_LOG_TARGET, "Failed to update chunk manifest: {}", e);
+                    }
+                    bytes_done.add(part_size);
                     Ok(())
-                });
+                }));
             }
 
             // Wait all chunks; abort early on cancel
@@ -454,66 +1043,101 @@ pub async fn download_asset(dm: &DownloadManifest, _base_url: &str, download_dir
                 // If a task returned Err(cancelled), propagate
                 if utils::check_if_job_is_cancelled(job_id_owned.as_deref()) {
                     cancel_this_job(job_id_owned.as_deref());
-                    return Err(anyhow::anyhow!("cancelled"));
+                    return Err(anyhow::anyhow!(staging::CANCELLED));
                 }
             }
-            // println!("\r  chunks: {}/{} (100%) - done                    ", total_chunks, total_chunks);
+            log::debug!(target: DOWNLOAD_LOG_TARGET, "All {} chunks downloaded", total_chunks);
+            if let Err(e) = chunk_ledger.lock().unwrap_or_else(|p| p.into_inner()).save() {
+                log::warn!(target: DOWNLOAD_LOG_TARGET, "Failed to update chunk manifest: {}", e);
+            }
 
             // Cancel before assembling
             if utils::check_if_job_is_cancelled(job_id_owned.as_deref()) {
                 cancel_this_job(job_id_owned.as_deref());
-                return Err(anyhow::anyhow!("cancelled"));
+                return Err(anyhow::anyhow!(staging::CANCELLED));
             }
 
             // Assemble
             let mut out = std::fs::File::create(&tmp_out_path)?;
-            let mut hasher = Sha1::new();
             let total_bytes: u128 = file.file_chunk_parts.iter().map(|p| p.size as u128).sum();
             let mut written: u64 = 0;
             for (chunk_idx, part) in file.file_chunk_parts.iter().enumerate() {
                 if utils::check_if_job_is_cancelled(job_id_owned.as_deref()) {
                     cancel_this_job(job_id_owned.as_deref());
-                    return Err(anyhow::anyhow!("cancelled"));
+                    return Err(anyhow::anyhow!(staging::CANCELLED));
                 }
                 let guid = &part.guid;
                 let chunk_path = temp_dir.join(format!("{}.chunk", guid));
-                let chunk_bytes = std::fs::read(&chunk_path)?;
                 // Some distribution links (e.g., certain FAB endpoints) may return raw byte blobs rather than
-                // Epic chunk container files. Try to parse as a chunk first; if that fails, fall back to raw bytes.
-                let (data, data_len): (std::borrow::Cow<[u8]>, usize) = if let Some(chunk) = Chunk::from_vec(chunk_bytes.clone()) {
-                    let len = chunk.data.len();
-                    (std::borrow::Cow::Owned(chunk.data), len)
-                } else {
-                    let len = chunk_path.metadata().map(|m| m.len() as usize).unwrap_or(0);
-                    (std::borrow::Cow::Owned(chunk_bytes), len)
-                };
-                let start = part.offset as usize;
-                let end = (part.offset + part.size) as usize;
-                if end > data_len { return Err(anyhow::anyhow!("chunk/raw too small for {} [{}..{} > {}]", filename, start, end, data_len)); }
-                let slice = &data[start..end];
-                std::io::Write::write_all(&mut out, slice)?;
-                hasher.update(slice);
+                // Epic chunk container files. Only the header is read to tell them apart; raw blobs and
+                // uncompressed chunks are copied straight from disk without loading the whole file.
+                let start = part.offset as u64;
+                let end = (part.offset + part.size) as u64;
+                let mut chunk_file = std::fs::File::open(&chunk_path)?;
+                let layout = chunk_assembly::read_layout(&mut chunk_file)
+                    .map_err(|e| anyhow::anyhow!("chunk {} for {}: {}", guid, filename, e))?;
+                match layout.data_region() {
+                    Some((data_start, data_len)) => {
+                        if end > data_len { return Err(anyhow::anyhow!("chunk/raw too small for {} [{}..{} > {}]", filename, start, end, data_len)); }
+                        chunk_assembly::copy_range(&mut chunk_file, data_start + start, end - start, &mut out)?;
+                    }
+                    None => {
+                        drop(chunk_file);
+                        let chunk = Chunk::from_vec(std::fs::read(&chunk_path)?)
+                            .ok_or_else(|| anyhow::anyhow!("could not decode chunk {} for {}", guid, filename))?;
+                        let data_len = chunk.data.len() as u64;
+                        if end > data_len { return Err(anyhow::anyhow!("chunk/raw too small for {} [{}..{} > {}]", filename, start, end, data_len)); }
+                        std::io::Write::write_all(&mut out, &chunk.data[start as usize..end as usize])?;
+                    }
+                }
                 written += part.size as u64;
                 let total_chunks = file.file_chunk_parts.len();
                 let mb_done = (written as f64) / (1024.0 * 1024.0);
                 let mb_total = (total_bytes as f64) / (1024.0 * 1024.0);
-                // print!("\r  assembling: {}/{} ({}%)  [{:.2} / {:.2} MB]", chunk_idx + 1, total_chunks, ((chunk_idx + 1) * 100 / total_chunks).min(100), mb_done, mb_total);
-                io::stdout().flush().ok();
+                log::trace!(target: DOWNLOAD_LOG_TARGET, "Assembling {}: part {}/{} [{:.2} / {:.2} MB]", filename, chunk_idx + 1, total_chunks, mb_done, mb_total);
             }
-            // println!("\r  assembling: {}/{} (100%)  [{:.2} / {:.2} MB] - done", file.file_chunk_parts.len(), file.file_chunk_parts.len(), (total_bytes as f64)/(1024.0*1024.0), (total_bytes as f64)/(1024.0*1024.0));
+            log::debug!(target: DOWNLOAD_LOG_TARGET, "Assembled {} from {} chunk part(s) ({:.2} MB)", filename, file.file_chunk_parts.len(), (total_bytes as f64) / (1024.0 * 1024.0));
 
+            drop(out);
+            let mut hash_verified = false;
             if !file.file_hash.is_empty() {
-                let got = hasher.finalize();
-                let got_hex = got.iter().map(|b| format!("{:02x}", b)).collect::<String>();
-                if got_hex != file.file_hash { eprintln!("Warning: SHA1 mismatch for {} (expected {}, got {})", filename, file.file_hash, got_hex); }
+                match file_hash::verify_file_hash(&tmp_out_path, &file.file_hash) {
+                    Ok(()) => hash_verified = true,
+                    Err(file_hash::HashError::UnsupportedFormat(h)) => {
+                        log::warn!(target: DOWNLOAD_LOG_TARGET, "Cannot verify {}: unsupported hash format '{}'", filename, h);
+                    }
+                    Err(e) if allow_hash_mismatch() => {
+                        log::warn!(target: DOWNLOAD_LOG_TARGET, "{} for {}; keeping it because EAM_ALLOW_HASH_MISMATCH=1", e, filename);
+                    }
+                    Err(e) => {
+                        let _ = std::fs::remove_file(&tmp_out_path);
+                        let (expected, actual) = match &e {
+                            file_hash::HashError::Mismatch { expected, actual, .. } => (Some(expected.clone()), Some(actual.clone())),
+                            _ => (None, None),
+                        };
+                        utils::emit_event(
+                            job_id_owned.as_deref(),
+                            models::Phase::DownloadError,
+                            format!("Hash verification failed for {}: {}", filename, e),
+                            None,
+                            Some(serde_json::json!({ "file": filename, "expected_hash": expected, "actual_hash": actual })),
+                        );
+                        return Err(file_hash::FileRejected::HashMismatch { file: filename.to_string(), error: e }.into());
+                    }
+                }
             }
 
-            drop(out);
             std::fs::rename(&tmp_out_path, &out_path)?;
+            if hash_verified {
+                if let Some(stamp) = verify_cache::file_stamp(&out_path) {
+                    verify_cache.lock().unwrap_or_else(|p| p.into_inner()).record(&filename, &file.file_hash, stamp);
+                }
+            }
             let mut t = totals.lock().await; t.downloaded += 1;
-            // Count as completed for overall percent and notify
+            // Count as completed and notify; its chunk parts already added its bytes
+            if let Some(fp) = folder_progress.as_ref() { fp.mark_done(&filename); }
             let done = completed.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
-            if let Some(cb) = &progress { let pct = (((done as f64) / (total_files as f64)) * 100.0).floor() as u32; (cb)(pct.min(100), format!("{} / {}", done, total_files)); }
+            if let Some(cb) = &progress { (cb)(bytes_done.whole_percent(), format!("{} / {}", done, total_files)); }
             // Emit a detailed progress event on file completion as well
             // utils::emit_event(
             //     job_id_owned.as_deref(),
@@ -528,16 +1152,28 @@ pub async fn download_asset(dm: &DownloadManifest, _base_url: &str, download_dir
             //     })),
             // );
             Ok(())
-        });
+        }));
     }
 
     // Await all file tasks
     while let Some(res) = join.join_next().await {
-        if let Err(e) = res { return Err(e.into()); }
+        let res = match res { Ok(r) => r, Err(e) => return Err(e.into()) };
         if check_if_job_is_cancelled(job_id_opt) {
             cancel_this_job(job_id_opt.as_deref());
-            return Err(anyhow::anyhow!("cancelled"));
+            return Err(anyhow::anyhow!(staging::CANCELLED));
         }
+        // A corrupt file (hash mismatch) fails the whole download; the task already emitted download:error
+        if let Err(e) = res {
+            if let Some(file_hash::FileRejected::HashMismatch { .. }) = e.downcast_ref::<file_hash::FileRejected>() {
+                join.abort_all();
+                return Err(e);
+            }
+        }
+    }
+
+    let recovered = recovered_chunks.load(Ordering::SeqCst);
+    if recovered > 0 {
+        log::info!(target: DOWNLOAD_LOG_TARGET, "Recovered {} corrupt cached chunk(s) by re-downloading them", recovered);
     }
 
     let t = totals.lock().await;
@@ -547,38 +1183,199 @@ pub async fn download_asset(dm: &DownloadManifest, _base_url: &str, download_dir
 
     if downloaded_files == 0 {
         if up_to_date_files > 0 {
-            eprintln!("Note: all files already present ({} up-to-date, {} with zero chunks)", up_to_date_files, skipped_files);
+            log::info!(target: DOWNLOAD_LOG_TARGET, "All files already present ({} up-to-date, {} with zero chunks)", up_to_date_files, skipped_files);
         } else {
             return Err(anyhow::anyhow!(format!("no files could be downloaded: {} files listed, {} skipped (zero chunks)", total_files, skipped_files)));
         }
     } else if skipped_files > 0 {
-        eprintln!("Note: {} of {} files were skipped due to zero chunk parts", skipped_files, total_files);
+        log::info!(target: DOWNLOAD_LOG_TARGET, "{} of {} files were skipped due to zero chunk parts", skipped_files, total_files);
+    }
+
+    // Cross-check the files on disk against the manifest before declaring the download complete
+    let mismatches = reconcile_downloaded_files(download_directory_full_path, &expected_files);
+    if !mismatches.is_empty() {
+        let shown: Vec<&String> = mismatches.iter().take(50).collect();
+        emit_event(
+            job_id_opt,
+            models::Phase::DownloadError,
+            format!("Download incomplete: {} of {} files missing or wrong size", mismatches.len(), expected_files.len()),
+            None,
+            Some(serde_json::json!({ "mismatches": shown, "total_mismatches": mismatches.len() })),
+        );
+        return Err(anyhow::anyhow!("download incomplete: {} file(s) missing or wrong size (first: {})", mismatches.len(), mismatches[0]));
+    }
+    // Every file is on disk at its manifest size, so progress ends at exactly 100%
+    emit_event(
+        job_id_opt,
+        models::Phase::DownloadProgress,
+        format!("download_asset: {} / {}", total_files, total_files),
+        Some(100.0),
+        Some(serde_json::json!({
+            "downloaded_files": total_files,
+            "total_files": total_files,
+            "bytes_done": total_bytes_all,
+            "total_bytes": total_bytes_all,
+        })),
+    );
+
+    if let Err(e) = verify_cache.lock().unwrap_or_else(|p| p.into_inner()).save() {
+        log::warn!(target: DOWNLOAD_LOG_TARGET, "Failed to save {}: {}", verify_cache::VERIFY_CACHE_FILE, e);
     }
 
-    // Mark download as complete
-    let _ = std::fs::write(download_directory_full_path.join(".download_complete"), "ok");
+    // Mark download as complete, recording every file left out (filters and skip patterns) so a later
+    // full download knows it was partial. The per-file size/hash list lets verify_managed_download
+    // re-check the folder offline later.
+    let marker = selection.complete_marker(&include_set, &exclude_set, &skip_set, marker_files);
+    let marker_bytes = serde_json::to_vec_pretty(&marker).unwrap_or_else(|_| b"ok".to_vec());
+    let _ = std::fs::write(download_directory_full_path.join(staging::COMPLETE_MARKER), marker_bytes);
 
     // After a successful download, remove the temporary chunks folder under the asset
-    // The temp directory is created relative to the asset root (e.g., downloads/<Asset>/temp),
-    // so compute it the same way we did earlier.
-    let temp_dir_final = download_directory_full_path.parent().map(|p| p.join("temp")).unwrap_or_else(|| download_directory_full_path.join("temp"));
-    match std::fs::remove_dir_all(&temp_dir_final) {
+    drop(temp_lease);
+    remove_chunk_temp_dir(download_directory_full_path);
+
+    Ok(())
+}
+
+/// temp/ chunk folder of an asset download: a sibling of the asset folder (e.g., downloads/<Asset>/temp
+/// for downloads/<Asset>/<UE version>).
+fn chunk_temp_dir_for(download_directory_full_path: &Path) -> PathBuf {
+    download_directory_full_path.parent().map(|p| p.join("temp")).unwrap_or_else(|| download_directory_full_path.join("temp"))
+}
+
+static TEMP_DIR_USERS: OnceLock<temp_users::TempDirRegistry> = OnceLock::new();
+fn temp_dir_users() -> &'static temp_users::TempDirRegistry { TEMP_DIR_USERS.get_or_init(temp_users::TempDirRegistry::new) }
+
+/// True while a running download holds the temp/ chunk folder belonging to `download_directory_full_path`.
+pub fn chunk_temp_dir_in_use(download_directory_full_path: &Path) -> bool {
+    temp_dir_users().users(&chunk_temp_dir_for(download_directory_full_path)) > 0
+}
+
+// Final folders of running downloads; unversioned and staged downloads keep their chunks elsewhere
+static ACTIVE_DOWNLOADS: OnceLock<temp_users::TempDirRegistry> = OnceLock::new();
+fn active_downloads() -> &'static temp_users::TempDirRegistry { ACTIVE_DOWNLOADS.get_or_init(temp_users::TempDirRegistry::new) }
+
+/// True while a running download targets `dir` or a folder inside it, or writes chunks to a temp/
+/// folder inside it (e.g. an asset's title folder). Covers downloads whose chunks live in the shared
+/// downloads/temp or under downloads/.staging.
+pub fn download_running_under(dir: &Path) -> bool {
+    active_downloads().any_in_use_under(dir) || temp_dir_users().any_in_use_under(dir)
+}
+
+/// Remove the temp/ chunk folder belonging to an asset download, unless another running download
+/// shares it (an unversioned asset's temp/ is downloads/temp). False when it was kept for that reason.
+pub fn remove_chunk_temp_dir(download_directory_full_path: &Path) -> bool {
+    let temp_dir_final = chunk_temp_dir_for(download_directory_full_path);
+    let removed = temp_dir_users().remove_if_unused(&temp_dir_final, |dir| match std::fs::remove_dir_all(dir) {
         Ok(_) => {
-            println!("Cleaned up temp folder: {}", temp_dir_final.display());
+            log::debug!(target: DOWNLOAD_LOG_TARGET, "Cleaned up temp folder: {}", dir.display());
         }
         Err(e) => {
             // Ignore when it does not exist; warn on other errors
             if e.kind() != std::io::ErrorKind::NotFound {
-                eprintln!("Warning: failed to remove temp folder {}: {}", temp_dir_final.display(), e);
+                log::warn!(target: DOWNLOAD_LOG_TARGET, "Failed to remove temp folder {}: {}", dir.display(), e);
             }
         }
+    });
+    if !removed {
+        log::info!(target: DOWNLOAD_LOG_TARGET, "Keeping temp folder {}: still in use by another download", temp_dir_final.display());
     }
+    removed
+}
 
-    Ok(())
+/// Add a `folders: [{ name, files_done, files_total }]` entry to progress details when detailed progress is on.
+fn with_folder_progress(mut details: serde_json::Value, folders: Option<&folder_progress::FolderProgress>) -> serde_json::Value {
+    if let (Some(fp), Some(obj)) = (folders, details.as_object_mut()) {
+        let list: Vec<serde_json::Value> = fp.snapshot().into_iter()
+            .map(|(name, files_done, files_total)| serde_json::json!({ "name": name, "files_done": files_done, "files_total": files_total }))
+            .collect();
+        obj.insert("folders".to_string(), serde_json::Value::Array(list));
+    }
+    details
+}
+
+/// Compare the assembled files under out_root/data against the manifest's (file, size) list.
+///
+/// Returns one human-readable line per missing or size-mismatched file; empty when everything matches.
+fn reconcile_downloaded_files(out_root: &Path, expected: &[(String, u64)]) -> Vec<String> {
+    let mut problems = Vec::new();
+    for (filename, size) in expected {
+        let path = resolve_output_file_path(out_root, filename);
+        match fs::metadata(&path) {
+            Ok(m) if m.is_file() && m.len() == *size => {}
+            Ok(m) if m.is_file() => problems.push(format!("{}: size {} (expected {})", filename, m.len(), size)),
+            _ => problems.push(format!("{}: missing", filename)),
+        }
+    }
+    problems
+}
+
+/// Verify existing files concurrently and return the names of those that are up-to-date.
+///
+/// See `verify_existing` for the hashing rules (EAM_HASH_CONCURRENCY, EAM_TRUST_EXISTING, `cache`).
+/// Emits download:verifying progress while checking and stops early when the job is cancelled.
+async fn verify_existing_files(candidates: Vec<ExistingFileCheck>, cache: &mut verify_cache::VerifyCache, job_id_opt: Option<&str>) -> std::collections::HashSet<String> {
+    let total = candidates.len();
+    if total == 0 { return std::collections::HashSet::new(); }
+
+    emit_event(job_id_opt, models::Phase::DownloadVerifying, format!("Verifying {} existing files", total), Some(0.0), None);
+    let (up_to_date, remembered) = verify_existing::verify_files(
+        candidates,
+        cache,
+        verify_existing::hash_concurrency(),
+        verify_existing::trust_existing(),
+        |done, total| {
+            if check_if_job_is_cancelled(job_id_opt) { return false; }
+            emit_event(
+                job_id_opt,
+                models::Phase::DownloadVerifying,
+                format!("Verified {} / {}", done, total),
+                Some(((done as f64) / (total as f64) * 100.0) as f32),
+                None,
+            );
+            true
+        },
+    ).await;
+    log::info!(target: DOWNLOAD_LOG_TARGET, "Verification: {} of {} existing files are up-to-date ({} unchanged since last check)", up_to_date.len(), total, remembered);
+    up_to_date
+}
+
+/// Nearest ancestor of `path` holding a completion marker, i.e. the managed download it belongs to.
+pub fn managed_download_root(path: &Path) -> Option<PathBuf> {
+    path.ancestors().skip(1).find(|a| is_download_complete(a)).map(Path::to_path_buf)
+}
+
+/// Re-check a completed download against the file list recorded in its completion marker.
+///
+/// Reuses the pre-download verification (size + SHA1/SHA256, EAM_HASH_CONCURRENCY). Returns None when the
+/// marker has no file list (downloads made before it was recorded), otherwise the names of files that
+/// are missing, the wrong size or fail their hash (empty when the download is intact).
+pub async fn verify_managed_download(root: &Path, job_id_opt: Option<&str>) -> Option<Vec<String>> {
+    let marker = read_completion_marker(root)?;
+    let files = marker.get("files").and_then(|v| v.as_array())?;
+    let candidates: Vec<ExistingFileCheck> = files.iter()
+        .filter_map(|f| {
+            let filename = f.get("name")?.as_str()?.to_string();
+            Some(ExistingFileCheck {
+                path: resolve_output_file_path(root, &filename),
+                expected_hash: f.get("hash").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+                expected_size: f.get("size").and_then(|v| v.as_u64()).unwrap_or(0),
+                filename,
+            })
+        })
+        .collect();
+    let mut names: Vec<String> = candidates.iter().map(|c| c.filename.clone()).collect();
+    let mut cache = verify_cache::VerifyCache::load(root);
+    let ok = verify_existing_files(candidates, &mut cache, job_id_opt).await;
+    if let Err(e) = cache.save() {
+        log::warn!(target: DOWNLOAD_LOG_TARGET, "Failed to save {}: {}", verify_cache::VERIFY_CACHE_FILE, e);
+    }
+    names.retain(|n| !ok.contains(n));
+    names.sort();
+    Some(names)
 }
 
 fn cancel_this_job(job_id_opt: Option<&str>) {
-    println!("Cancelling job...");
+    log::info!(target: DOWNLOAD_LOG_TARGET, "Cancelling job...");
     emit_event(job_id_opt, models::Phase::Cancelled, "Job Cancelled", None, None);
     if let Some(ref j) = job_id_opt { acknowledge_cancel(j); }
 }
@@ -596,196 +1393,286 @@ pub fn sanitize_title_for_folder(s: &str) -> String {
 /// based on the presence of corresponding folders under downloads/.
 /// Returns (total_assets, marked_downloaded, changed).
 pub fn annotate_downloaded_flags(value: &mut serde_json::Value) -> (usize, usize, bool) {
-    let downloads_root = get_default_downloads_dir_path();
-    let mut total_assets = 0usize;
-    let mut marked_downloaded = 0usize;
-    let mut changed = false;
+    fs_scan::annotate_downloaded_flags_in(value, &get_default_downloads_dir_path(), sanitize_title_for_folder, &|| false)
+        .unwrap_or((0, 0, false))
+}
 
-    if let Some(results) = value.get_mut("results").and_then(|v| v.as_array_mut()) {
-        for asset in results.iter_mut() {
-            total_assets += 1;
-            let title: String = asset.get("title").and_then(|v| v.as_str()).unwrap_or("").to_string();
-            // let namespace: String = asset.get("assetNamespace").and_then(|v| v.as_str()).unwrap_or("").to_string();
-            // let asset_id: String = asset.get("assetId").and_then(|v| v.as_str()).unwrap_or("").to_string();
-
-            let mut asset_downloaded = false;
-            let mut used_title_folder = false;
-            let mut version_folders: Vec<String> = Vec::new();
-
-            if !title.is_empty() {
-                let folder = utils::sanitize_title_for_folder(&title);
-                let path = downloads_root.join(&folder);
-                if path.exists() {
-                    // Legacy: direct download into title folder
-                    if is_download_complete(&path) { asset_downloaded = true; used_title_folder = true; }
-                    // New: versioned subfolders under title
-                    if let Ok(entries) = fs::read_dir(&path) {
-                        for e in entries.flatten() {
-                            let p = e.path();
-                            if p.is_dir() {
-                                // folder name should be UE major.minor like 5.6 or 4.27
-                                if let Some(name) = p.file_name().and_then(|s| s.to_str()) {
-                                    let mm = name.trim();
-                                    if !mm.is_empty() && is_download_complete(&p) {
-                                        version_folders.push(mm.to_string());
-                                        asset_downloaded = true;
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+/// Same as annotate_downloaded_flags, but stops early (returning None) once the job is cancelled.
+pub fn annotate_downloaded_flags_cancellable(value: &mut serde_json::Value, job_id_opt: Option<&str>) -> Option<(usize, usize, bool)> {
+    fs_scan::annotate_downloaded_flags_in(value, &get_default_downloads_dir_path(), sanitize_title_for_folder, &|| check_if_job_is_cancelled(job_id_opt))
+}
 
-            // Annotate per-version flags based ONLY on versioned title subfolders to avoid over-marking.
-            if let Some(versions) = asset.get_mut("projectVersions").and_then(|v| v.as_array_mut()) {
-                for ver in versions.iter_mut() {
-                    let mut ver_downloaded = false;
-                    if !version_folders.is_empty() {
-                        if let Some(ev) = ver.get("engineVersions").and_then(|v| v.as_array()) {
-                            'outer: for mm in version_folders.iter() {
-                                let token = format!("UE_{}", mm);
-                                for e in ev.iter() {
-                                    if e.as_str().map_or(false, |s| s.trim() == token) {
-                                        ver_downloaded = true; break 'outer;
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    if let Some(obj) = ver.as_object_mut() {
-                        let prev = obj.get("downloaded").and_then(|v| v.as_bool());
-                        if prev != Some(ver_downloaded) {
-                            obj.insert("downloaded".into(), serde_json::Value::Bool(ver_downloaded));
-                            changed = true;
-                        }
-                    }
-                }
-            }
 
-            // Record the exact downloaded UE versions at the asset root for precise UI logic
+/// Narrow each asset's `downloaded` flag to a single UE version (major.minor, e.g. "5.6").
+///
+/// Expects `downloadedVersions` to have been filled by annotate_downloaded_flags. Meant for
+/// responses only; the cache keeps the "any version downloaded" semantics.
+pub fn apply_required_version(value: &mut serde_json::Value, mm: &str) {
+    if let Some(results) = value.get_mut("results").and_then(|v| v.as_array_mut()) {
+        for asset in results.iter_mut() {
+            let has = asset.get("downloadedVersions").and_then(|v| v.as_array())
+                .map_or(false, |a| a.iter().any(|v| v.as_str().map_or(false, |s| s.trim() == mm)));
             if let Some(obj) = asset.as_object_mut() {
-                // Set asset-level downloaded flag
-                if obj.get("downloaded").and_then(|v| v.as_bool()) != Some(asset_downloaded) {
-                    obj.insert("downloaded".into(), serde_json::Value::Bool(asset_downloaded));
-                    changed = true;
-                }
-                // Inject/update downloadedVersions array (sorted unique)
-                let mut versions_unique = version_folders.clone();
-                versions_unique.sort();
-                versions_unique.dedup();
-                let new_val = serde_json::Value::Array(versions_unique.into_iter().map(serde_json::Value::String).collect());
-                let prev = obj.get("downloadedVersions").cloned();
-                if prev.as_ref() != Some(&new_val) {
-                    obj.insert("downloadedVersions".into(), new_val);
-                    changed = true;
-                }
+                obj.insert("downloaded".into(), serde_json::Value::Bool(has));
             }
-
-            if asset_downloaded { marked_downloaded += 1; }
-
-            // No blanket marking of versions when using title folder; handled above via subfolders
         }
     }
+}
 
-    (total_assets, marked_downloaded, changed)
+/// Cache directory: the configured cache_dir, else builtin_cache_dir().
+pub fn default_cache_dir() -> PathBuf {
+    default_dirs::configured_dir(&config_file_path(), "cache_dir", builtin_cache_dir(), fs_scan::host_platform(), &host_env)
 }
 
+/// Downloads directory: the configured downloads_dir, else builtin_downloads_dir().
+pub fn get_default_downloads_dir_path() -> PathBuf {
+    default_dirs::configured_dir(&config_file_path(), "downloads_dir", builtin_downloads_dir(), fs_scan::host_platform(), &host_env)
+}
 
-pub fn default_cache_dir() -> PathBuf {
+/// Cache directory used when config.json does not set cache_dir.
+pub fn builtin_cache_dir() -> PathBuf {
     // Debug: project-local directory for easy inspection during development
     if cfg!(debug_assertions) {
         return PathBuf::from(DEFAULT_CACHE_DIR_NAME);
     }
-    // Release: XDG cache: $XDG_CACHE_HOME/egs_client (fallback ~/.cache/egs_client)
-    let base = std::env::var("XDG_CACHE_HOME")
-        .map(PathBuf::from)
-        .ok()
-        .filter(|p| !p.as_os_str().is_empty())
-        .or_else(|| std::env::var("HOME").ok().map(|h| PathBuf::from(h).join(".cache")))
-        .unwrap_or_else(|| PathBuf::from(".cache"));
+    // Release: $XDG_CACHE_HOME/egs_client (fallback ~/.cache/egs_client; %LOCALAPPDATA% on Windows)
+    let base = default_dirs::cache_base(fs_scan::host_platform(), &host_env);
     base.join("egs_client")
 }
 
-pub fn get_default_downloads_dir_path() -> PathBuf {
+/// Downloads directory used when config.json does not set downloads_dir.
+pub fn builtin_downloads_dir() -> PathBuf {
     // Debug: project-local directory for easy inspection during development
     if cfg!(debug_assertions) {
         return PathBuf::from(DEFAULT_DOWNLOADS_DIR_NAME);
     }
-    // Release: XDG data dir: $XDG_DATA_HOME/egs_client/downloads (fallback ~/.local/share/egs_client/downloads)
-    let base = std::env::var("XDG_DATA_HOME")
-        .map(PathBuf::from)
-        .ok()
-        .filter(|p| !p.as_os_str().is_empty())
-        .or_else(|| std::env::var("HOME").ok().map(|h| PathBuf::from(h).join(".local").join("share")))
-        .unwrap_or_else(|| PathBuf::from(".local/share"));
+    // Release: $XDG_DATA_HOME/egs_client/downloads (fallback ~/.local/share/egs_client/downloads; %LOCALAPPDATA% on Windows)
+    let base = default_dirs::data_base(fs_scan::host_platform(), &host_env);
     base.join("egs_client").join(DEFAULT_DOWNLOADS_DIR_NAME)
 }
 
-/// Checks whether a download directory contains a completion marker created after a successful download.
-pub fn is_download_complete(root: &Path) -> bool {
-    // Only trust the explicit completion marker to avoid false positives after cancellations.
-    root.join(".download_complete").is_file()
+/// Process environment lookup for default_dirs.
+fn host_env(key: &str) -> Option<String> {
+    std::env::var(key).ok()
 }
 
-pub fn get_fab_cache_file_path() -> PathBuf {
-    let dir = default_cache_dir();
-    let _ = std::fs::create_dir_all(&dir);
-    dir.join("fab_list.json")
+
+/// Summary of completed downloads under the downloads directory: (assets, versions, total_bytes).
+///
+/// An asset counts once if its title folder or any version subfolder is complete;
+/// versions counts each complete folder (legacy title folder or version subfolder).
+pub fn downloads_summary(downloads_root: &Path) -> (usize, usize, u64) {
+    let mut assets = 0usize;
+    let mut versions = 0usize;
+    let mut total_bytes = 0u64;
+    let Ok(entries) = fs::read_dir(downloads_root) else { return (0, 0, 0) };
+    for entry in entries.flatten() {
+        let title_dir = entry.path();
+        if !title_dir.is_dir() || entry.file_name() == STAGING_DIR_NAME { continue; }
+        let mut complete_here = 0usize;
+        if is_download_complete(&title_dir) { complete_here += 1; }
+        if let Ok(subs) = fs::read_dir(&title_dir) {
+            for sub in subs.flatten() {
+                if sub.path().is_dir() && is_download_complete(&sub.path()) { complete_here += 1; }
+            }
+        }
+        if complete_here > 0 {
+            assets += 1;
+            versions += complete_here;
+            total_bytes += dir_size(&title_dir);
+        }
+    }
+    (assets, versions, total_bytes)
 }
 
-pub fn read_build_version(engine_dir: &Path) -> Option<String> {
-    // Try Engine/Build/Build.version JSON to get Major/Minor/Patch
-    let build_file = engine_dir.join("Engine").join("Build").join("Build.version");
-    if let Ok(bytes) = fs::read(&build_file) {
-        if let Ok(v) = serde_json::from_slice::<serde_json::Value>(&bytes) {
-            let major = v.get("MajorVersion").and_then(|x| x.as_u64()).unwrap_or(0);
-            let minor = v.get("MinorVersion").and_then(|x| x.as_u64()).unwrap_or(0);
-            let patch = v.get("PatchVersion").and_then(|x| x.as_u64()).unwrap_or(0);
-            if major > 0 {
-                if patch > 0 {
-                    return Some(format!("{}.{}.{}", major, minor, patch));
-                } else {
-                    return Some(format!("{}.{}", major, minor));
-                }
+/// Read the completion marker's JSON details (skip patterns and skipped files).
+///
+/// Returns None when there is no marker; legacy plain-text "ok" markers yield a non-partial record.
+pub fn read_completion_marker(root: &Path) -> Option<serde_json::Value> {
+    let bytes = fs::read(root.join(staging::COMPLETE_MARKER)).ok()?;
+    Some(serde_json::from_slice::<serde_json::Value>(&bytes).unwrap_or_else(|_| serde_json::json!({
+        "status": "ok",
+        "partial": false,
+        "skip_patterns": [],
+        "skipped_files": [],
+    })))
+}
+
+/// Record the artifact identifiers for a download in <root>/.download_info.json.
+///
+/// Written before the download starts so an interrupted download can later be listed and resumed.
+pub fn write_download_info(root: &Path, info: &models::DownloadInfo) -> std::io::Result<()> {
+    fs::create_dir_all(root)?;
+    let data = serde_json::to_vec_pretty(info).map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+    fs::write(root.join(".download_info.json"), data)
+}
+
+/// Load the artifact identifiers recorded by write_download_info, if present and parseable.
+pub fn read_download_info(root: &Path) -> Option<models::DownloadInfo> {
+    let data = fs::read(root.join(".download_info.json")).ok()?;
+    serde_json::from_slice::<models::DownloadInfo>(&data).ok()
+}
+
+/// Returns true when `path` exists and resolves to a location strictly inside `root` (never `root` itself).
+///
+/// Thin wrapper over resolve_within_root for callers that only need a yes/no answer.
+pub fn is_within_root(root: &Path, path: &Path) -> bool {
+    match (fs::canonicalize(root), resolve_within_root(root, path)) {
+        (Ok(r), Ok(p)) => p != r && p.exists(),
+        _ => false,
+    }
+}
+
+/// Locate download folders (title folder or version subfolder) whose recorded info matches the given ids.
+pub fn find_download_dirs_by_ids(downloads_root: &Path, namespace: &str, asset_id: &str, artifact_id: &str, ue: Option<&str>) -> Vec<PathBuf> {
+    let mut out = Vec::new();
+    let matches = |info: &models::DownloadInfo| {
+        info.namespace == namespace
+            && info.asset_id == asset_id
+            && info.artifact_id == artifact_id
+            && ue.map_or(true, |v| info.ue.as_deref() == Some(v))
+    };
+    let Ok(entries) = fs::read_dir(downloads_root) else { return out };
+    for entry in entries.flatten() {
+        let title_dir = entry.path();
+        if !title_dir.is_dir() { continue; }
+        if read_download_info(&title_dir).map_or(false, |i| matches(&i)) { out.push(title_dir.clone()); }
+        if let Ok(subs) = fs::read_dir(&title_dir) {
+            for sub in subs.flatten() {
+                let p = sub.path();
+                if p.is_dir() && read_download_info(&p).map_or(false, |i| matches(&i)) { out.push(p); }
             }
         }
     }
-    None
+    out
 }
 
-pub fn find_editor_binary(engine_dir: &Path) -> Option<PathBuf> {
-    // Linux typical paths
-    let candidates = [
-        engine_dir.join("Engine/Binaries/Linux/UnrealEditor"),
-        engine_dir.join("Engine/Binaries/Linux/UE4Editor"),
-        engine_dir.join("Engine/Binaries/Linux/UnrealEditor.app/Contents/MacOS/UnrealEditor"), // in case of mac-like layout copied
-    ];
-    for c in candidates.iter() {
-        if c.exists() && c.is_file() {
-            return Some(c.clone());
+/// Scan the downloads directory for asset folders lacking a completion marker.
+///
+/// Considers both legacy title folders (downloads/<Title>/data) and versioned subfolders
+/// (downloads/<Title>/<UE major.minor>). The shared temp/ chunk folder and the staging area are ignored.
+pub fn find_incomplete_downloads(downloads_root: &Path) -> Vec<models::IncompleteDownload> {
+    let mut out = Vec::new();
+    let Ok(entries) = fs::read_dir(downloads_root) else { return out };
+    for entry in entries.flatten() {
+        let title_dir = entry.path();
+        if !title_dir.is_dir() || entry.file_name() == STAGING_DIR_NAME { continue; }
+        let asset_name = title_dir.file_name().and_then(|s| s.to_str()).unwrap_or("").to_string();
+        // Legacy: downloaded directly into the title folder
+        let legacy = title_dir.join("data").is_dir() || title_dir.join(".download_info.json").is_file();
+        if legacy && !is_download_complete(&title_dir) {
+            out.push(models::IncompleteDownload {
+                asset_name: asset_name.clone(),
+                version: None,
+                path: title_dir.to_string_lossy().to_string(),
+                info: read_download_info(&title_dir),
+            });
+        }
+        // Versioned subfolders
+        if let Ok(subs) = fs::read_dir(&title_dir) {
+            for sub in subs.flatten() {
+                let p = sub.path();
+                if !p.is_dir() { continue; }
+                let name = p.file_name().and_then(|s| s.to_str()).unwrap_or("").to_string();
+                if name == "temp" || name == "data" { continue; }
+                if !is_download_complete(&p) {
+                    out.push(models::IncompleteDownload {
+                        asset_name: asset_name.clone(),
+                        version: Some(name),
+                        path: p.to_string_lossy().to_string(),
+                        info: read_download_info(&p),
+                    });
+                }
+            }
         }
     }
-    None
+    out.sort_by(|a, b| a.asset_name.to_lowercase().cmp(&b.asset_name.to_lowercase()).then(a.version.cmp(&b.version)));
+    out
 }
 
-pub fn parse_version_from_name(name: &str) -> Option<String> {
-    // Extract first digit-sequence like 5, 5.2, 5.2.1
-    let mut version = String::new();
-    let mut seen_digit = false;
-    for ch in name.chars() {
-        if ch.is_ascii_digit() {
-            version.push(ch);
-            seen_digit = true;
-        } else if ch == '.' && seen_digit {
-            version.push(ch);
-        } else if seen_digit {
-            break;
+pub fn get_fab_cache_file_path() -> PathBuf {
+    let dir = default_cache_dir();
+    let _ = std::fs::create_dir_all(&dir);
+    dir.join("fab_list.json")
+}
+
+/// Recompute `downloaded` flags in fab_list.json from downloads/ and rewrite it when a flag changed.
+///
+/// Returns Ok(None) when there is no cached library yet, otherwise (total_assets, marked_downloaded, changed).
+/// Takes lock_fab_cache() itself, so callers must not hold it.
+pub fn reannotate_fab_cache() -> Result<Option<(usize, usize, bool)>, String> {
+    let path = get_fab_cache_file_path();
+    let _guard = lock_fab_cache();
+    let buf = match fs::read(&path) {
+        Ok(b) => b,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("Failed to read FAB cache: {}", e)),
+    };
+    let mut val: serde_json::Value = serde_json::from_slice(&buf).map_err(|e| format!("FAB cache is not valid JSON: {}", e))?;
+    let (total, marked, changed) = annotate_downloaded_flags(&mut val);
+    if changed {
+        let bytes = serde_json::to_vec_pretty(&val).map_err(|e| format!("Failed to serialize FAB cache: {}", e))?;
+        write_fab_cache_atomic(&path, &bytes).map_err(|e| format!("Failed to write FAB cache: {}", e))?;
+    }
+    Ok(Some((total, marked, changed)))
+}
+
+static LIBRARY_VERSIONS_CACHE: OnceLock<Mutex<Option<(std::time::SystemTime, u64, Vec<models::LibraryUeVersion>)>>> = OnceLock::new();
+
+/// Distinct UE major.minor versions across the cached Fab library, with asset counts, sorted ascending.
+///
+/// Computed from fab_list.json only (no network) and memoized until the cache file's
+/// modification time or size changes. Returns an empty list when there is no cache yet.
+pub fn library_ue_versions() -> Vec<models::LibraryUeVersion> {
+    let cache_path = get_fab_cache_file_path();
+    let meta = match fs::metadata(&cache_path) { Ok(m) => m, Err(_) => return Vec::new() };
+    let stamp = (meta.modified().unwrap_or(std::time::UNIX_EPOCH), meta.len());
+    let memo = LIBRARY_VERSIONS_CACHE.get_or_init(|| Mutex::new(None));
+    if let Some((mtime, len, versions)) = memo.lock().unwrap_or_else(|p| p.into_inner()).as_ref() {
+        if (*mtime, *len) == stamp { return versions.clone(); }
+    }
+
+    let value: serde_json::Value = {
+        let _guard = lock_fab_cache();
+        match fs::read(&cache_path).ok().and_then(|b| serde_json::from_slice(&b).ok()) {
+            Some(v) => v,
+            None => return Vec::new(),
+        }
+    };
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    if let Some(results) = value.get("results").and_then(|r| r.as_array()) {
+        for asset in results {
+            // Count each asset once per version, even if several projectVersions list it
+            let mut seen: std::collections::HashSet<String> = std::collections::HashSet::new();
+            if let Some(versions) = asset.get("projectVersions").and_then(|v| v.as_array()) {
+                for ver in versions {
+                    if let Some(ev) = ver.get("engineVersions").and_then(|v| v.as_array()) {
+                        for e in ev.iter().filter_map(|e| e.as_str()) {
+                            if let Some(mm) = normalize_engine_association(e) { seen.insert(mm); }
+                        }
+                    }
+                }
+            }
+            for mm in seen { *counts.entry(mm).or_insert(0) += 1; }
         }
     }
-    if !version.is_empty() { Some(version) } else { None }
+    let mut versions: Vec<models::LibraryUeVersion> = counts
+        .into_iter()
+        .map(|(version, asset_count)| models::LibraryUeVersion { version, asset_count })
+        .collect();
+    let key = |v: &str| -> (u32, u32) {
+        let mut it = v.split('.').map(|p| p.parse::<u32>().unwrap_or(0));
+        (it.next().unwrap_or(0), it.next().unwrap_or(0))
+    };
+    versions.sort_by(|a, b| key(&a.version).cmp(&key(&b.version)));
+
+    *memo.lock().unwrap_or_else(|p| p.into_inner()) = Some((stamp.0, stamp.1, versions.clone()));
+    versions
 }
 
+
+
+
 pub fn normalize_engine_association(assoc: &str) -> Option<String> {
     let mut s = assoc.trim();
     if s.is_empty() { return None; }
@@ -802,20 +1689,6 @@ pub fn normalize_engine_association(assoc: &str) -> Option<String> {
     None
 }
 
-/// Read BuildId from Engine/Build/Build.version if present
-pub fn read_build_id(engine_dir: &Path) -> Option<String> {
-    let build_file = engine_dir.join("Engine").join("Build").join("Build.version");
-    if let Ok(bytes) = fs::read(&build_file) {
-        if let Ok(v) = serde_json::from_slice::<serde_json::Value>(&bytes) {
-            if let Some(id) = v.get("BuildId").and_then(|x| x.as_str()) {
-                let s = id.trim();
-                if !s.is_empty() { return Some(s.to_string()); }
-            }
-        }
-    }
-    None
-}
-
 /// Convert version like "5.6.1" or "5.6" to major.minor form, e.g., "5.6"
 pub fn to_major_minor(ver: &str) -> String {
     let mut it = ver.split('.');
@@ -828,13 +1701,21 @@ pub fn to_major_minor(ver: &str) -> String {
     }
 }
 
-/// Resolve EngineAssociation to UE major.minor. Handles numeric strings and GUID BuildIds.
+/// Resolve EngineAssociation to UE major.minor. Handles numeric strings, identifiers of registered
+/// (source-built) engines and GUID BuildIds, with or without braces.
 pub fn resolve_engine_association_to_mm(assoc: &str) -> Option<String> {
     if let Some(mm) = normalize_engine_association(assoc) {
         return Some(mm);
     }
-    let s = assoc.trim();
+    let s = engine_registry::normalize_id(assoc);
     if s.is_empty() { return None; }
+    // Source builds register their identifier with the engine folder; read the version there
+    if let Some(dir) = engine_registry::lookup(&registered_engines(), s) {
+        match read_build_version(dir) {
+            Some(ver) => return Some(to_major_minor(&ver)),
+            None => log::warn!("Engine registered as {} at {} has no readable Build.version", s, dir.display()),
+        }
+    }
     // Detect GUID-like: 8-4-4-4-12 hex groups
     let is_guid_like = {
         let parts: Vec<&str> = s.split('-').collect();
@@ -851,7 +1732,7 @@ pub fn resolve_engine_association_to_mm(assoc: &str) -> Option<String> {
                 let build_file = dir.join("Engine").join("Build").join("Build.version");
                 if build_file.is_file() {
                     if let Some(build_id) = read_build_id(&dir) {
-                        if build_id.eq_ignore_ascii_case(s) {
+                        if engine_registry::normalize_id(&build_id).eq_ignore_ascii_case(s) {
                             if let Some(ver) = read_build_version(&dir) {
                                 return Some(to_major_minor(&ver));
                             }
@@ -864,6 +1745,23 @@ pub fn resolve_engine_association_to_mm(assoc: &str) -> Option<String> {
     None
 }
 
+/// Source-built engines registered on this machine as (identifier, engine dir): the registry on
+/// Windows, Install.ini elsewhere. Empty when nothing is registered or it can't be read.
+fn registered_engines() -> Vec<(String, PathBuf)> {
+    let platform = fs_scan::host_platform();
+    if platform == "Windows" {
+        return std::process::Command::new("reg")
+            .args(["query", engine_registry::WINDOWS_BUILDS_KEY])
+            .output()
+            .map(|o| engine_registry::parse_reg_query(&String::from_utf8_lossy(&o.stdout)))
+            .unwrap_or_default();
+    }
+    let Some(home) = std::env::var_os("HOME").map(PathBuf::from) else { return Vec::new() };
+    engine_registry::install_ini_path(platform, &home)
+        .and_then(|p| fs::read_to_string(p).ok())
+        .map(|text| engine_registry::parse_install_ini(&text))
+        .unwrap_or_default()
+}
 
 pub fn resolve_project_path(project_param: &str) -> Option<PathBuf> {
     let p = PathBuf::from(project_param);
@@ -884,13 +1782,22 @@ pub fn resolve_project_path(project_param: &str) -> Option<PathBuf> {
     None
 }
 
-pub fn pick_engine_for_version<'a>(engines: &'a [models::UnrealEngineInfo], requested: &str) -> Option<&'a models::UnrealEngineInfo> {
-    // Try exact version match first
-    if let Some(e) = engines.iter().find(|e| e.version == requested) { return Some(e); }
-    // Try prefix match (e.g., request 5.3 and engine 5.3.2)
-    if let Some(e) = engines.iter().find(|e| e.version.starts_with(requested)) { return Some(e); }
-    // Try name contains requested (e.g., UE_5.3)
-    engines.iter().find(|e| e.name.contains(requested))
+/// Pick the engine for `requested`: the newest matching patch ("5.4" picks 5.4.3 over 5.4.0), or with
+/// `exact` only an engine with exactly that version. Without `exact`, falls back to engines whose folder
+/// name contains `requested` (e.g., UE_5.3).
+pub fn pick_engine_for_version<'a>(engines: &'a [models::UnrealEngineInfo], requested: &str, exact: bool) -> Option<&'a models::UnrealEngineInfo> {
+    // Prefer launchable engines, then originals over flagged duplicates so selection is deterministic
+    let rank = |e: &models::UnrealEngineInfo| (!e.launchable as u8) * 2 + e.duplicate_of.is_some() as u8;
+    let tier = |r: u8| engines.iter().filter(move |e| rank(*e) == r);
+    for r in 0..4u8 {
+        if let Some(i) = semver::pick_version(tier(r).map(|e| e.version.as_str()), requested, exact) {
+            return tier(r).nth(i);
+        }
+    }
+    if exact {
+        return None;
+    }
+    (0..4u8).flat_map(tier).find(|e| e.name.contains(requested))
 }
 
 pub fn resolve_project_dir_from_param(param: &str) -> Option<PathBuf> {
@@ -898,36 +1805,16 @@ pub fn resolve_project_dir_from_param(param: &str) -> Option<PathBuf> {
     if let Some(p) = utils::resolve_project_path(param) {
         return p.parent().map(|p| p.to_path_buf());
     }
-    // If the param is a directory, check for a .uproject inside and return the dir
-    let p = PathBuf::from(param);
-    if p.is_dir() {
-        // Require that it looks like a UE project (contains a .uproject)
-        if let Ok(entries) = fs::read_dir(&p) {
-            for e in entries.flatten() {
-                let path = e.path();
-                if path.extension().map_or(false, |ext| ext == "uproject") {
-                    return Some(p);
-                }
-            }
-        }
-    }
-    // As a last resort, try treating it as a project name under default projects dir
-    let candidate = default_unreal_projects_dir().join(param);
-    if candidate.is_dir() {
-        if let Ok(entries) = fs::read_dir(&candidate) {
-            for e in entries.flatten() {
-                let path = e.path();
-                if path.extension().map_or(false, |ext| ext == "uproject") {
-                    return Some(candidate);
-                }
-            }
-        }
-    }
-    None
+    fs_scan::project_dir_in(param, &default_unreal_projects_dir())
 }
 
-pub fn copy_dir_recursive_with_progress(src: &Path, dst: &Path, overwrite: bool, job_id_opt: Option<&str>, phase: models::Phase) -> std::io::Result<(usize, usize)> {
-    // Returns (copied, skipped) while emitting percent progress (0..=100)
+/// Map a 0..=100 percentage into `span` (start, end) so several copies can share one progress bar.
+fn progress_in_span(span: (f32, f32), percent: f32) -> f32 {
+    span.0 + (span.1 - span.0) * (percent / 100.0)
+}
+
+pub fn copy_dir_recursive_with_progress(src: &Path, dst: &Path, policy: ConflictPolicy, job_id_opt: Option<&str>, phase: models::Phase, span: (f32, f32)) -> std::io::Result<CopyCounts> {
+    // Returns per-action counts while emitting percent progress mapped into span
     use walkdir::WalkDir;
     if !src.exists() {
         return Err(std::io::Error::new(std::io::ErrorKind::NotFound, format!("source not found: {}", src.display())));
@@ -938,10 +1825,9 @@ pub fn copy_dir_recursive_with_progress(src: &Path, dst: &Path, overwrite: bool,
         let entry = entry.map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
         if entry.file_type().is_file() { total_files += 1; }
     }
-    let mut copied = 0usize;
-    let mut skipped = 0usize;
+    let mut counts = CopyCounts::default();
     let mut last_percent: u32 = 0;
-    emit_event(job_id_opt, phase, "Starting...", Some(0.0), None);
+    emit_event(job_id_opt, phase, "Starting...", Some(span.0), None);
     for entry in WalkDir::new(src).follow_links(false) {
         if check_if_job_is_cancelled(job_id_opt) {
             cancel_this_job(job_id_opt.as_deref());
@@ -956,25 +1842,66 @@ pub fn copy_dir_recursive_with_progress(src: &Path, dst: &Path, overwrite: bool,
             continue;
         }
         if entry.file_type().is_file() {
-            if target.exists() && !overwrite {
-                skipped += 1;
-            } else {
-                if let Some(parent) = target.parent() { fs::create_dir_all(parent)?; }
-                fs::copy(path, &target)?;
-                copied += 1;
-            }
+            let action = conflict::decide(policy, path, &target);
+            conflict::apply(&action, path)?;
+            counts.record(&action);
             if total_files > 0 {
-                let mut percent = ((copied as f64 / total_files as f64) * 100.0).floor() as u32;
+                let mut percent = ((counts.copied as f64 / total_files as f64) * 100.0).floor() as u32;
                 if percent > 100 { percent = 100; }
                 if percent != last_percent {
                     last_percent = percent;
-                    emit_event(job_id_opt, phase, format!("{} / {}", copied, total_files), Some(percent as f32), None);
+                    emit_event(job_id_opt, phase, format!("{} / {}", counts.copied, total_files), Some(progress_in_span(span, percent as f32)), None);
                 }
             }
         }
     }
-    emit_event(job_id_opt, phase, "Done", Some(100.0), None);
-    Ok((copied, skipped))
+    emit_event(job_id_opt, phase, "Done", Some(span.1), None);
+    Ok(counts)
+}
+
+/// Number of worker threads to use when copying an asset into a project.
+///
+/// The request value wins; otherwise `EGS_IMPORT_COPY_WORKERS` is used. Defaults to 1 (serial),
+/// which is usually best on spinning disks and network shares.
+pub fn import_copy_workers(requested: Option<usize>) -> usize {
+    requested
+        .or_else(|| std::env::var("EGS_IMPORT_COPY_WORKERS").ok().and_then(|v| v.trim().parse::<usize>().ok()))
+        .unwrap_or(1)
+        .clamp(1, 64)
+}
+
+/// Like `copy_dir_recursive_with_progress`, but copies files on `workers` threads.
+/// Falls back to the serial copy when `workers <= 1`.
+pub fn copy_dir_parallel_with_progress(src: &Path, dst: &Path, policy: ConflictPolicy, job_id_opt: Option<&str>, phase: models::Phase, workers: usize, span: (f32, f32)) -> std::io::Result<CopyCounts> {
+    if workers <= 1 {
+        return copy_dir_recursive_with_progress(src, dst, policy, job_id_opt, phase, span);
+    }
+    use std::sync::atomic::{AtomicU32, Ordering};
+    let last_percent = AtomicU32::new(0);
+    emit_event(job_id_opt, phase, "Starting...", Some(span.0), None);
+    let result = parallel_copy::copy_tree_parallel(
+        src,
+        dst,
+        policy,
+        workers,
+        &|done, total| {
+            if total == 0 { return; }
+            let percent = (((done as f64 / total as f64) * 100.0).floor() as u32).min(100);
+            // Only the thread that advances the percentage emits, so events stay monotonic
+            if last_percent.fetch_max(percent, Ordering::SeqCst) < percent {
+                emit_event(job_id_opt, phase, format!("{} / {}", done, total), Some(progress_in_span(span, percent as f32)), None);
+            }
+        },
+        &|| check_if_job_is_cancelled(job_id_opt),
+    );
+    if let Err(e) = &result {
+        if e.kind() == std::io::ErrorKind::Interrupted {
+            cancel_this_job(job_id_opt);
+        }
+        return result;
+    }
+    emit_event(job_id_opt, phase, "Done", Some(span.1), None);
+    result
 }
 
 /// Ensure an asset with the given library title is available under downloads/.
@@ -982,11 +1909,7 @@ pub fn copy_dir_recursive_with_progress(src: &Path, dst: &Path, overwrite: bool,
 /// pick one of its project_versions (latest if possible), and download it.
 /// Returns the asset folder path under downloads/ on success.
 pub async fn ensure_asset_downloaded_by_name(title: &str, job_id_opt: Option<&str>, phase_for_progress: models::Phase) -> Result<PathBuf, String> {
-    // Resolve downloads base similar to other endpoints
-    let mut downloads_base = PathBuf::from("downloads");
-    if !downloads_base.exists() {
-        if let Ok(exe) = std::env::current_exe() { if let Some(exe_dir) = exe.parent() { let alt = exe_dir.join("downloads"); if alt.exists() { downloads_base = alt; } } }
-    }
+    let downloads_base = find_downloads_directory();
     // Check existing (exact/case-insensitive)
     let mut asset_dir = downloads_base.join(title);
     if !asset_dir.exists() {
@@ -1054,9 +1977,9 @@ pub async fn ensure_asset_downloaded_by_name(title: &str, job_id_opt: Option<&st
                     });
                     f
                 });
-                match utils::download_asset(&dm, url.as_str(), &out_root, progress_cb, job_id_opt).await {
+                match utils::download_asset(&dm, url.as_str(), &out_root, progress_cb, job_id_opt, &models::DownloadOptions::default()).await {
                     Ok(_) => { return Ok(out_root); },
-                    Err(e) => { eprintln!("Download failed from {}: {:?}", url, e); continue; }
+                    Err(e) => { log::warn!("Download failed from {}: {:?}", url, e); continue; }
                 }
             }
         }
@@ -1070,45 +1993,172 @@ pub async fn ensure_asset_downloaded_by_name(title: &str, job_id_opt: Option<&st
 
 // EVENTS - WEBSOCKETS
 
-static JOB_BUS: OnceLock<DashMap<String, broadcast::Sender<String>>> = OnceLock::new();
-static JOB_BUFFER: OnceLock<DashMap<String, VecDeque<String>>> = OnceLock::new();
+// Live channel plus replay buffer per job; publishing and subscribing both go through the map entry
+static JOB_CHANNELS: OnceLock<DashMap<String, event_buffer::JobChannel>> = OnceLock::new();
+// Last (phase, progress, unix millis) per job, so clients can rediscover jobs after a reload
+static JOB_STATE: OnceLock<DashMap<String, (String, Option<f32>, u64)>> = OnceLock::new();
+// Most recent event per job; unlike JOB_BUFFER it is never drained or evicted
+static JOB_LAST_EVENT: OnceLock<DashMap<String, models::ProgressEvent>> = OnceLock::new();
 
 // Cooperative job cancellation registry
 static CANCEL_MAP: OnceLock<DashMap<String, bool>> = OnceLock::new();
 fn cancel_map() -> &'static DashMap<String, bool> { CANCEL_MAP.get_or_init(|| DashMap::new()) }
-pub fn cancel_job(job_id: &str) { cancel_map().insert(job_id.to_string(), true); emit_event(Some(job_id), models::Phase::Cancel, "Cancellation requested", None, None); }
+pub fn cancel_job(job_id: &str) { cancel_map().insert(job_id.to_string(), true); let _ = pause_map().resume(job_id); emit_event(Some(job_id), models::Phase::Cancel, "Cancellation requested", None, None); }
 pub fn acknowledge_cancel(job_id: &str) { let _ = cancel_map().remove(job_id); }
 pub fn check_if_job_is_cancelled(job_id_opt: Option<&str>) -> bool { if let Some(j) = job_id_opt { cancel_map().get(j).is_some() } else { false } }
 
-pub fn bus() -> &'static DashMap<String, broadcast::Sender<String>> {
-    JOB_BUS.get_or_init(|| DashMap::new())
+// Cooperative job pause registry; download tasks wait here between chunks so temp/ is kept intact
+static PAUSE_MAP: OnceLock<pause::PauseRegistry> = OnceLock::new();
+fn pause_map() -> &'static pause::PauseRegistry { PAUSE_MAP.get_or_init(pause::PauseRegistry::new) }
+/// Pause a job; false when it was already paused.
+pub fn pause_job(job_id: &str) -> bool { pause_map().pause(job_id) }
+/// Resume a paused job; false when it was not paused.
+pub fn resume_job(job_id: &str) -> bool { pause_map().resume(job_id) }
+/// Whether `job_id` is currently paused.
+pub fn is_job_paused(job_id: &str) -> bool { pause_map().is_paused(job_id) }
+/// Wait while the job is paused (returns at once for unpaused jobs or no job id); a cancel ends the wait.
+pub async fn wait_while_job_paused(job_id_opt: Option<&str>) {
+    if let Some(j) = job_id_opt {
+        pause_map().wait_while_paused(j, &|| check_if_job_is_cancelled(Some(j))).await;
+    }
+}
+
+/// Generate a unique job id for server-initiated jobs (e.g., "resume-1718000000000-1").
+pub fn new_job_id(prefix: &str) -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let millis = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    let n = COUNTER.fetch_add(1, Ordering::SeqCst) + 1;
+    format!("{}-{}-{}", prefix, millis, n)
+}
+
+pub fn channel_map() -> &'static DashMap<String, event_buffer::JobChannel> {
+    JOB_CHANNELS.get_or_init(|| DashMap::new())
+}
+
+pub fn job_state_map() -> &'static DashMap<String, (String, Option<f32>, u64)> {
+    JOB_STATE.get_or_init(|| DashMap::new())
+}
+
+pub fn last_event_map() -> &'static DashMap<String, models::ProgressEvent> {
+    JOB_LAST_EVENT.get_or_init(|| DashMap::new())
+}
+
+/// Record the latest event for a job; a missing progress keeps the last known value.
+fn record_job_state(job_id: &str, phase: &Phase, progress: Option<f32>) {
+    let now_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+    let mut entry = job_state_map().entry(job_id.to_string()).or_insert_with(|| (String::new(), None, 0));
+    entry.0 = phase.to_string();
+    if progress.is_some() { entry.1 = progress; }
+    entry.2 = now_ms;
+}
+
+/// Known jobs (excluding the default channel), most recently active first.
+pub fn list_job_states() -> Vec<models::JobSummary> {
+    let mut jobs: Vec<models::JobSummary> = job_state_map()
+        .iter()
+        .filter(|e| e.key() != DEFAULT_JOB_ID)
+        .map(|e| {
+            let (phase, progress, last_event_ms) = e.value().clone();
+            let state = models::JobState::derive(&phase, cancel_map().contains_key(e.key()));
+            models::JobSummary { job_id: e.key().clone(), state, phase, progress, last_event_ms, last_event: None }
+        })
+        .collect();
+    jobs.sort_by(|a, b| b.last_event_ms.cmp(&a.last_event_ms).then_with(|| a.job_id.cmp(&b.job_id)));
+    jobs
+}
+
+/// Current state of one job, or None when it has never emitted an event and has no pending cancel.
+pub fn job_status(job_id: &str) -> Option<models::JobSummary> {
+    let cancel_pending = cancel_map().contains_key(job_id);
+    let (phase, progress, last_event_ms) = match job_state_map().get(job_id) {
+        Some(e) => e.value().clone(),
+        None if cancel_pending => (models::Phase::Cancel.to_string(), None, 0),
+        None => return None,
+    };
+    Some(models::JobSummary {
+        job_id: job_id.to_string(),
+        state: models::JobState::derive(&phase, cancel_pending),
+        phase,
+        progress,
+        last_event_ms,
+        last_event: last_event_map().get(job_id).map(|e| e.value().clone()),
+    })
+}
+
+
+/// How long a finished job's events stay replayable after its terminal event (EGS_WS_TERMINAL_GRACE_SECS, default 60).
+fn terminal_event_grace() -> std::time::Duration {
+    let secs = std::env::var("EGS_WS_TERMINAL_GRACE_SECS").ok().and_then(|v| v.trim().parse::<u64>().ok()).unwrap_or(60);
+    std::time::Duration::from_secs(secs)
+}
+
+/// Send an event to a job's subscribers and buffer it for late ones. Keeps up to 32 recent events;
+/// `sticky` (terminal) events are exempt from the cap, and the job's buffer is purged once their grace
+/// window ends.
+pub fn publish_event(job_id: &str, json: String, sticky: bool) {
+    if sticky { purge_finished_buffers(); }
+    job_channel(job_id).publish(json, if sticky { Some(terminal_event_grace()) } else { None });
 }
 
-pub fn buffer_map() -> &'static DashMap<String, VecDeque<String>> {
-    JOB_BUFFER.get_or_init(|| DashMap::new())
+/// Drop the buffers of jobs that finished (terminal event) more than the grace window ago, and their
+/// channels once nobody is subscribed.
+pub fn purge_finished_buffers() {
+    let now = std::time::Instant::now();
+    channel_map().retain(|_, ch| !ch.expire(now));
 }
 
-pub fn get_sender(job_id: &str) -> broadcast::Sender<String> {
-    if let Some(s) = bus().get(job_id) { return s.clone(); }
-    let (tx, _rx) = broadcast::channel::<String>(128);
-    bus().insert(job_id.to_string(), tx.clone());
-    tx
+/// Why a job's events cannot be followed (see subscribe_existing_job).
+pub enum SubscribeError {
+    /// The job never emitted an event and has no pending cancel.
+    UnknownJob,
+    /// The job finished and its replay was purged after the grace window.
+    Finished(models::JobState),
 }
 
-pub fn push_buffered(job_id: &str, json: String) {
-    let mut entry = buffer_map().entry(job_id.to_string()).or_insert_with(|| VecDeque::with_capacity(32));
-    // Keep up to 32 recent events
-    if entry.len() >= 32 { entry.pop_front(); }
-    entry.push_back(json);
+fn job_channel(job_id: &str) -> dashmap::mapref::one::RefMut<'static, String, event_buffer::JobChannel> {
+    channel_map().entry(job_id.to_string()).or_insert_with(|| event_buffer::JobChannel::new(32))
 }
 
-pub fn take_buffer(job_id: &str) -> Vec<String> {
-    if let Some(mut e) = buffer_map().get_mut(job_id) {
-        let mut out = Vec::new();
-        while let Some(v) = e.pop_front() { out.push(v); }
-        return out;
+/// Replay of a job's buffered events plus a receiver for everything emitted after it. Nothing is lost or
+/// delivered twice between the two. Works for jobs that have not started yet.
+pub fn subscribe_job(job_id: &str) -> (Vec<String>, broadcast::Receiver<String>) {
+    purge_finished_buffers();
+    job_channel(job_id).subscribe()
+}
+
+/// Like subscribe_job, but refuses jobs that never emitted an event or that finished and were purged,
+/// so the caller does not wait on a channel that will stay silent.
+pub fn subscribe_existing_job(job_id: &str) -> Result<(Vec<String>, broadcast::Receiver<String>), SubscribeError> {
+    if job_id == DEFAULT_JOB_ID {
+        return Ok(subscribe_job(job_id));
+    }
+    purge_finished_buffers();
+    let summary = job_status(job_id).ok_or(SubscribeError::UnknownJob)?;
+    let channel = job_channel(job_id);
+    if summary.state.is_finished() && channel.replay_is_empty() {
+        return Err(SubscribeError::Finished(summary.state));
+    }
+    Ok(channel.subscribe())
+}
+
+/// Job id used by /ws when the client does not pass one.
+pub const DEFAULT_JOB_ID: &str = "default";
+
+/// Whether events for every job are mirrored onto the DEFAULT_JOB_ID channel (the "firehose").
+///
+/// Enabled by default so a client connecting to /ws without a jobId still observes activity.
+/// Set EGS_WS_DEFAULT_FIREHOSE=0 to make "default" behave like any other job id.
+fn default_firehose_enabled() -> bool {
+    match std::env::var("EGS_WS_DEFAULT_FIREHOSE") {
+        Ok(v) => !matches!(v.trim().to_ascii_lowercase().as_str(), "0" | "false" | "no" | "off"),
+        Err(_) => true,
     }
-    Vec::new()
 }
 
 pub fn emit_event(job_id_opt: Option<&str>, phase: Phase, message: impl Into<String>, progress: Option<f32>, details: Option<serde_json::Value>) {
@@ -1116,13 +2166,21 @@ pub fn emit_event(job_id_opt: Option<&str>, phase: Phase, message: impl Into<Str
         let msg_str: String = message.into();
         // Debug: log every event emitted
         let pstr = match progress { Some(p) => format!("{:.1}%", p), None => "null".to_string() };
-        println!("[WS][emit] job_id={} phase={} progress={} msg={}", job_id, phase, pstr, msg_str);
+        log::debug!("[WS][emit] job_id={} phase={} progress={} msg={}", job_id, phase, pstr, msg_str);
+        record_job_state(job_id, &phase, progress);
+        // A finished job can no longer be paused; drop a leftover pause so the registry does not grow
+        if phase.is_terminal() { let _ = pause_map().resume(job_id); }
         let ev = models::ProgressEvent { job_id: job_id.to_string(), phase: phase.to_string(), message: msg_str, progress, details };
+        last_event_map().insert(job_id.to_string(), ev.clone());
         if let Ok(json) = serde_json::to_string(&ev) {
-            // Broadcast to current subscribers
-            let _ = get_sender(job_id).send(json.clone());
-            // Also buffer for late subscribers
-            push_buffered(job_id, json);
+            // Mirror onto the default channel so jobless /ws clients see all activity.
+            // The original job_id is kept in the payload so clients can tell jobs apart.
+            if job_id != DEFAULT_JOB_ID && default_firehose_enabled() {
+                publish_event(DEFAULT_JOB_ID, json.clone(), false);
+            }
+            // Broadcast to current subscribers and buffer for late ones; terminal events are kept
+            // through the grace window
+            publish_event(job_id, json, phase.is_terminal());
         }
     }
 }
@@ -1134,7 +2192,57 @@ pub fn set_shutdown_sender(tx: broadcast::Sender<()>) {
     let _ = SHUTDOWN_TX.set(tx);
 }
 
+// Graceful shutdown: running downloads are paused and in-flight chunks finish before the server stops
+static SHUTTING_DOWN: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+static IN_FLIGHT_CHUNKS: OnceLock<drain::InFlight> = OnceLock::new();
+fn in_flight_chunks() -> &'static drain::InFlight { IN_FLIGHT_CHUNKS.get_or_init(drain::InFlight::new) }
+
+pub fn is_shutting_down() -> bool {
+    SHUTTING_DOWN.load(Ordering::SeqCst)
+}
+
+/// How long shutdown waits for in-flight chunks (EGS_SHUTDOWN_TIMEOUT_SECS, default 30).
+pub fn shutdown_drain_timeout() -> std::time::Duration {
+    let secs = std::env::var("EGS_SHUTDOWN_TIMEOUT_SECS").ok().and_then(|s| s.trim().parse::<u64>().ok()).unwrap_or(30);
+    std::time::Duration::from_secs(secs)
+}
+
+/// Start shutting down: refuse new downloads and pause every running download job so no new chunk
+/// starts. Each paused job gets a download:paused event saying it resumes on the next launch.
+/// Returns the ids of the paused jobs.
+pub fn pause_jobs_for_shutdown() -> Vec<String> {
+    SHUTTING_DOWN.store(true, Ordering::SeqCst);
+    let running: Vec<String> = job_state_map().iter()
+        .filter(|e| e.value().0.starts_with("download") && models::JobState::derive(&e.value().0, cancel_map().contains_key(e.key())) == models::JobState::Running)
+        .map(|e| e.key().clone())
+        .collect();
+    for job_id in &running {
+        if pause_job(job_id) {
+            emit_event(
+                Some(job_id),
+                models::Phase::DownloadPaused,
+                "Paused for shutdown; the download can be resumed on the next launch",
+                None,
+                Some(serde_json::json!({ "reason": "shutdown", "resumable": true })),
+            );
+        }
+    }
+    running
+}
+
+/// Block until no chunk is being fetched or `timeout` passes; true when everything finished.
+pub fn wait_for_in_flight_chunks(timeout: std::time::Duration) -> bool {
+    in_flight_chunks().wait_idle(timeout)
+}
+
+/// Number of chunks currently being fetched.
+pub fn in_flight_chunk_count() -> usize {
+    in_flight_chunks().count()
+}
+
 pub struct WsSession {
+    /// Events buffered before the client subscribed, sent first.
+    pub replay: Vec<String>,
     pub rx: broadcast::Receiver<String>,
     pub job_id: String
 }
@@ -1144,7 +2252,7 @@ impl Actor for WsSession {
     fn stopped(&mut self, _ctx: &mut Self::Context) {
         // Do NOT shut down the backend on normal WS close.
         // Backend lifecycle is managed by process signals and (in BOTH mode) by the Flutter child watcher.
-        println!("[WS] session stopped for job {}", self.job_id);
+        log::debug!("[WS] session stopped for job {}", self.job_id);
         // Previously: if exit_on_ws_close_enabled() { request_shutdown(); }
     }
 }
@@ -1155,7 +2263,7 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for WsSession {
             Ok(ws::Message::Ping(msg)) => ctx.pong(&msg),
             Ok(ws::Message::Text(_)) => { /* ignore client messages */ },
             Ok(ws::Message::Close(_)) => {
-                println!("[WS] client closed WS for job {} (not treating as cancellation)", self.job_id);
+                log::debug!("[WS] client closed WS for job {} (not treating as cancellation)", self.job_id);
                 // Do not auto-cancel on WS close; user must hit Cancel or call /cancel-job explicitly.
                 ctx.stop();
             },
@@ -1164,16 +2272,15 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for WsSession {
     }
 
     fn started(&mut self, ctx: &mut Self::Context) {
-        println!("[WS] session started for job {}", self.job_id);
-        // First, flush any buffered events for late subscribers
-        for ev in take_buffer(&self.job_id) {
+        log::debug!("[WS] session started for job {}", self.job_id);
+        // First, replay buffered events for late subscribers (non-destructive, so other tabs get them too)
+        for ev in std::mem::take(&mut self.replay) {
             ctx.text(ev);
         }
         // Then forward new broadcast messages to the websocket
-        let mut rx = self.rx.resubscribe();
-        ctx.run_interval(std::time::Duration::from_millis(500), move |act, ctx| {
+        ctx.run_interval(std::time::Duration::from_millis(500), |act, ctx| {
             loop {
-                match rx.try_recv() {
+                match act.rx.try_recv() {
                     Ok(text) => ctx.text(text),
                     Err(broadcast::error::TryRecvError::Empty) => break,
                     Err(broadcast::error::TryRecvError::Closed) => { ctx.stop(); break; }
@@ -1192,13 +2299,8 @@ pub fn config_file_path() -> PathBuf {
         p.push("config.json");
         return p;
     }
-    // Production: XDG config: $XDG_CONFIG_HOME/egs_client/config.json (fallback ~/.config/egs_client/config.json)
-    let base = std::env::var("XDG_CONFIG_HOME")
-        .map(PathBuf::from)
-        .ok()
-        .filter(|p| !p.as_os_str().is_empty())
-        .or_else(|| std::env::var("HOME").ok().map(|h| PathBuf::from(h).join(".config")))
-        .unwrap_or_else(|| PathBuf::from(".config"));
+    // Production: $XDG_CONFIG_HOME/egs_client/config.json (fallback ~/.config; %APPDATA% on Windows)
+    let base = default_dirs::config_base(fs_scan::host_platform(), &host_env);
     let dir = base.join("egs_client");
     let _ = std::fs::create_dir_all(&dir);
     dir.join("config.json")
@@ -1214,13 +2316,64 @@ pub fn load_paths_config() -> models::PathsConfig {
             }
         }
     }
-    models::PathsConfig::default()
-}
-
-pub fn save_paths_config(cfg: &models::PathsConfig) -> std::io::Result<()> {
-    let path = utils::config_file_path();
-    let s = serde_json::to_string_pretty(cfg).unwrap_or_else(|_| "{}".to_string());
-    std::fs::write(path, s)
+    models::PathsConfig::default()
+}
+
+pub fn save_paths_config(cfg: &models::PathsConfig) -> std::io::Result<()> {
+    let path = utils::config_file_path();
+    let s = serde_json::to_string_pretty(cfg).unwrap_or_else(|_| "{}".to_string());
+    std::fs::write(path, s)
+}
+
+/// Path of the local asset tags/favorites store (next to config.json).
+pub fn asset_tags_file_path() -> PathBuf {
+    config_file_path().with_file_name("asset_tags.json")
+}
+
+pub fn load_asset_tags() -> models::AssetTagsStore {
+    asset_tags::load(&asset_tags_file_path())
+}
+
+/// Replace the tags store atomically; hold lock_asset_tags() across the read-modify-write.
+pub fn save_asset_tags(store: &models::AssetTagsStore) -> std::io::Result<()> {
+    asset_tags::save(&asset_tags_file_path(), store)
+}
+
+/// Path of the saved create-project defaults (next to config.json).
+pub fn create_defaults_file_path() -> PathBuf {
+    config_file_path().with_file_name("create_defaults.json")
+}
+
+pub fn load_create_defaults() -> models::CreateDefaults {
+    let path = create_defaults_file_path();
+    if let Ok(bytes) = fs::read(&path) {
+        if let Ok(defaults) = serde_json::from_slice::<models::CreateDefaults>(&bytes) {
+            return defaults;
+        }
+    }
+    models::CreateDefaults::default()
+}
+
+pub fn save_create_defaults(defaults: &models::CreateDefaults) -> std::io::Result<()> {
+    let path = create_defaults_file_path();
+    let s = serde_json::to_string_pretty(defaults).unwrap_or_else(|_| "{}".to_string());
+    std::fs::write(path, s)
+}
+
+/// Inject `userTags` and `favorite` into each asset of a FAB library JSON value from the local store.
+///
+/// Assets without an entry get an empty tag list and favorite=false so the UI can rely on the fields.
+pub fn merge_asset_tags(value: &mut serde_json::Value, store: &models::AssetTagsStore) {
+    if let Some(results) = value.get_mut("results").and_then(|v| v.as_array_mut()) {
+        for asset in results.iter_mut() {
+            let asset_id = asset.get("assetId").and_then(|v| v.as_str()).unwrap_or("").to_string();
+            let entry = store.get(&asset_id).cloned().unwrap_or_default();
+            if let Some(obj) = asset.as_object_mut() {
+                obj.insert("userTags".into(), serde_json::json!(entry.tags));
+                obj.insert("favorite".into(), serde_json::Value::Bool(entry.favorite));
+            }
+        }
+    }
 }
 
 pub fn default_unreal_projects_dir() -> PathBuf {
@@ -1236,16 +2389,8 @@ pub fn default_unreal_projects_dir() -> PathBuf {
             return PathBuf::from(val);
         }
     }
-    // 3) Default: $HOME/Documents/Unreal Projects
-    if let Ok(home) = std::env::var("HOME") {
-        let mut p = PathBuf::from(home);
-        p.push("Documents");
-        p.push("Unreal Projects");
-        p
-    } else {
-        // Fallback to current dir if HOME not set
-        PathBuf::from(".")
-    }
+    // 3) Default: $HOME/Documents/Unreal Projects (%USERPROFILE% on Windows; current dir if unset)
+    default_dirs::projects_dir(fs_scan::host_platform(), &host_env)
 }
 
 pub fn default_unreal_engines_dir() -> PathBuf {
@@ -1261,21 +2406,30 @@ pub fn default_unreal_engines_dir() -> PathBuf {
             return PathBuf::from(val);
         }
     }
-    // 3) Default: $HOME/UnrealEngines
-    if let Ok(home) = std::env::var("HOME") {
-        let mut p = PathBuf::from(home);
-        p.push("UnrealEngines");
-        p
-    } else {
-        PathBuf::from(".")
-    }
+    // 3) Default: $HOME/UnrealEngines (%ProgramFiles%\Epic Games on Windows)
+    default_dirs::engines_dir(fs_scan::host_platform(), &host_env)
 }
 
-/// Internal helper that refreshes the Fab library without initiating any downloads.
-///
-/// Returns a summary list (JSON) suitable for UI consumption. On auth failure or missing
-/// details, returns a 200 OK with a short message body describing the condition.
-pub async fn handle_refresh_fab_list() -> HttpResponse {
+/// Hex SHA1 of the library JSON as returned by Epic, before any local annotation.
+fn fab_library_hash(value: &serde_json::Value) -> String {
+    use sha1::{Digest, Sha1};
+    let mut hasher = Sha1::new();
+    hasher.update(serde_json::to_vec(value).unwrap_or_default());
+    hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Sidecar next to fab_list.json holding the fab_library_hash of the library it was built from.
+fn fab_library_hash_path(cache_path: &Path) -> PathBuf {
+    cache_path.with_extension("hash")
+}
+
+/// Library fetched by a refresh that was cancelled before it was cached; `resume=true` continues from it.
+fn fab_pending_refresh_path(cache_path: &Path) -> PathBuf {
+    cache_path.with_extension("pending.json")
+}
+
+/// Log in with cached tokens and fetch the Fab library from Epic as JSON. Err is the response to send.
+async fn fetch_fab_library() -> Result<serde_json::Value, HttpResponse> {
     // Try to use cached refresh token first (no browser, no copy-paste)
     let mut epic_games_services = utils::create_epic_games_services();
     if !utils::try_cached_login(&mut epic_games_services).await {
@@ -1286,65 +2440,129 @@ pub async fn handle_refresh_fab_list() -> HttpResponse {
             "auth_url": EPIC_LOGIN_URL,
             "message": "No cached credentials. Please log in via your browser and enter the authorization code in the app."
         });
-        return HttpResponse::Unauthorized().json(payload);
+        return Err(HttpResponse::Unauthorized().json(payload));
     } else {
-        println!("Logged in using cached credentials");
+        log::info!("Logged in using cached credentials");
     }
 
     // Fetch account details and additional account info (for diagnostics/UI display).
-    let details = utils::get_account_details(&mut epic_games_services).await;
+    let Some(info) = utils::get_account_details(&mut epic_games_services).await else {
+        log::info!("No details found");
+        return Err(crate::api::err(StatusCode::BAD_GATEWAY, "upstream_error", "Epic Games did not return account details; try again or log in again."));
+    };
 
     // Retrieve the Fab library based on the acquired account details.
-    match details {
-        None => {
-            println!("No details found");
-            HttpResponse::Ok().body("No details found")
-        }
-        Some(info) => {
-            let assets = utils::get_fab_library_items(&mut epic_games_services, info).await;
-            match assets {
-                None => {
-                    println!("No assets found");
-                    HttpResponse::Ok().body("No assets found")
-                }
-                Some(retrieved_assets) => {
-                    println!("Library items length: {:?}", retrieved_assets.results.len());
-
-                    // Convert to JSON value so we can enrich with local-only fields like 'downloaded'.
-                    let mut value = match serde_json::to_value(&retrieved_assets) {
-                        Ok(v) => v,
-                        Err(e) => {
-                            eprintln!("Warning: failed to convert FAB list to JSON value: {}", e);
-                            return HttpResponse::Ok().json(&retrieved_assets);
-                        }
-                    };
+    let Some(retrieved_assets) = utils::get_fab_library_items(&mut epic_games_services, info).await else {
+        log::info!("No assets found");
+        return Err(crate::api::err(StatusCode::BAD_GATEWAY, "upstream_error", "Epic Games did not return the Fab library; try refreshing again later."));
+    };
+    log::debug!("Library items length: {:?}", retrieved_assets.results.len());
 
-                    // Compute 'downloaded' flags (asset-level and per-version) using filesystem state.
-                    let (_total_assets, _marked, _changed) = annotate_downloaded_flags(&mut value);
+    // Convert to JSON value so we can enrich with local-only fields like 'downloaded'.
+    serde_json::to_value(&retrieved_assets).map_err(|e| {
+        log::warn!("Failed to convert FAB list to JSON value: {}", e);
+        HttpResponse::Ok().json(&retrieved_assets)
+    })
+}
 
-                    // Save enriched JSON to cache for faster subsequent loads and offline-friendly UI.
-                    if let Ok(json_bytes) = serde_json::to_vec_pretty(&value) {
-                        let cache_path = utils::get_fab_cache_file_path();
-                        if let Some(parent) = cache_path.parent() { let _ = fs::create_dir_all(parent); }
-                        if let Err(e) = fs::write(&cache_path, &json_bytes) {
-                            eprintln!("Warning: failed to write FAB cache: {}", e);
-                        }
-                    } else {
-                        eprintln!("Warning: failed to serialize enriched FAB library for cache");
-                    }
+/// Keep a cancelled refresh's fetched library for `resume=true`, acknowledge the cancel and answer
+/// 409 with code "cancelled".
+fn cancel_fab_refresh(job_id_opt: Option<&str>, pending_path: &Path, fetched: &serde_json::Value) -> HttpResponse {
+    let saved = serde_json::to_vec(fetched).ok().and_then(|bytes| write_fab_cache_atomic(pending_path, &bytes).ok()).is_some();
+    if !saved {
+        log::warn!("Could not keep the fetched Fab library at {}; a resume will have to fetch again", pending_path.display());
+    }
+    cancel_this_job(job_id_opt);
+    let hint = if saved { "; call /refresh-fab-list?resume=true to finish it without fetching again" } else { "" };
+    crate::api::err(StatusCode::CONFLICT, "cancelled", format!("Fab library refresh cancelled{}", hint))
+}
 
-                    // Return enriched library items so the UI can show download indicators.
-                    return HttpResponse::Ok().json(value);
+/// Fetch the Fab library from Epic and cache it, annotated with local download state.
+///
+/// When the fetched library hashes the same as the one the cache was built from (and `force` is false),
+/// the cached JSON is kept as-is and only re-annotated, so local fields survive and the file is only
+/// rewritten when a flag changed. The response carries `refreshed: true` only when the library changed.
+///
+/// With a job id, the refresh can be cancelled via /cancel-job: the network fetch runs to completion,
+/// but the annotation pass stops early, the cache is left untouched and 409 "cancelled" is returned.
+/// The fetched library is kept next to the cache, and `resume` finishes that refresh without logging
+/// in or fetching again (404 nothing_to_resume when there is none).
+pub async fn handle_refresh_fab_list(job_id_opt: Option<&str>, force: bool, resume: bool) -> HttpResponse {
+    let cache_path = utils::get_fab_cache_file_path();
+    let pending_path = fab_pending_refresh_path(&cache_path);
+    let fetched = if resume {
+        match fs::read(&pending_path).ok().and_then(|b| serde_json::from_slice::<serde_json::Value>(&b).ok()) {
+            Some(v) => {
+                log::info!("Resuming the cancelled Fab library refresh from {}", pending_path.display());
+                v
+            }
+            None => {
+                return crate::api::err(StatusCode::NOT_FOUND, "nothing_to_resume", "No cancelled Fab library refresh to resume; call /refresh-fab-list without resume");
+            }
+        }
+    } else {
+        match fetch_fab_library().await {
+            Ok(v) => v,
+            Err(resp) => return resp,
+        }
+    };
 
-                    // Reached only if json() above wasn't returned; keep OK fallback
-                    HttpResponse::Ok().finish()
-                }
+    if check_if_job_is_cancelled(job_id_opt) {
+        return cancel_fab_refresh(job_id_opt, &pending_path, &fetched);
+    }
+
+    let library_hash = fab_library_hash(&fetched);
+    let hash_path = fab_library_hash_path(&cache_path);
+    let guard = lock_fab_cache();
+    let unchanged_cache = if force || fs::read_to_string(&hash_path).ok().as_deref().map(str::trim) != Some(library_hash.as_str()) {
+        None
+    } else {
+        fs::read(&cache_path).ok().and_then(|b| serde_json::from_slice::<serde_json::Value>(&b).ok())
+    };
+    let refreshed = unchanged_cache.is_none();
+    let mut value = match unchanged_cache {
+        Some(cached) => {
+            log::info!("Fab library unchanged since the last refresh; re-annotating the cache only");
+            cached
+        }
+        None => fetched.clone(),
+    };
+
+    // Compute 'downloaded' flags (asset-level and per-version) using filesystem state.
+    let Some((_, _, annotations_changed)) = annotate_downloaded_flags_cancellable(&mut value, job_id_opt) else {
+        drop(guard);
+        return cancel_fab_refresh(job_id_opt, &pending_path, &fetched);
+    };
+
+    // Save enriched JSON to cache for faster subsequent loads and offline-friendly UI.
+    if refreshed || annotations_changed {
+        if let Ok(json_bytes) = serde_json::to_vec_pretty(&value) {
+            if let Err(e) = write_fab_cache_atomic(&cache_path, &json_bytes) {
+                log::warn!("Failed to write FAB cache: {}", e);
+            } else if let Err(e) = fs::write(&hash_path, &library_hash) {
+                log::warn!("Failed to write FAB library hash: {}", e);
             }
+        } else {
+            log::warn!("Failed to serialize enriched FAB library for cache");
         }
     }
+    drop(guard);
+    // A finished refresh supersedes any cancelled one
+    let _ = fs::remove_file(&pending_path);
+
+    // Layer local tags/favorites on the response only; they are not cached with the library.
+    merge_asset_tags(&mut value, &load_asset_tags());
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("refreshed".into(), serde_json::Value::Bool(refreshed));
+    }
+
+    // Return enriched library items so the UI can show download indicators.
+    HttpResponse::Ok().json(value)
 }
 
 pub fn update_fab_cache_json(namespace: String, asset_id: String, artifact_id: String, ue_major_minor_version: Option<String>, title_folder: Option<String>, cache_path: &PathBuf) {
+    // Serialize with other cache writers for the whole read-modify-write
+    let _guard = lock_fab_cache();
     if let Ok(mut f) = fs::File::open(&cache_path) {
         use std::io::Read as _;
         let mut buf = Vec::new();
@@ -1410,27 +2628,27 @@ pub fn update_fab_cache_json(namespace: String, asset_id: String, artifact_id: S
                     }
                 }
                 if !found_asset {
-                    eprintln!("Note: downloaded asset not found in cached FAB list (ns={}, id={}). Cache not updated.", namespace, asset_id);
+                    log::info!("Downloaded asset not found in cached FAB list (ns={}, id={}). Cache not updated.", namespace, asset_id);
                 } else if !found_version && title_folder.is_none() {
-                    eprintln!("Note: matching version (artifact {}) not found under asset {}. Only asset-level flag may be updated.", artifact_id, asset_id);
+                    log::info!("Matching version (artifact {}) not found under asset {}. Only asset-level flag may be updated.", artifact_id, asset_id);
                 }
                 if changed {
                     if let Ok(bytes) = serde_json::to_vec_pretty(&cache_val) {
-                        if let Err(e) = fs::write(&cache_path, &bytes) {
-                            eprintln!("Warning: failed to update FAB cache after download: {}", e);
+                        if let Err(e) = write_fab_cache_atomic(cache_path, &bytes) {
+                            log::warn!("Failed to update FAB cache after download: {}", e);
                         } else {
-                            println!("Updated FAB cache to mark asset {} / {} (artifact {}) as downloaded.", namespace, asset_id, artifact_id);
+                            log::info!("Updated FAB cache to mark asset {} / {} (artifact {}) as downloaded.", namespace, asset_id, artifact_id);
                         }
                     }
                 }
             } else {
-                eprintln!("Warning: failed to parse existing FAB cache for update");
+                log::warn!("Failed to parse existing FAB cache for update");
             }
         } else {
-            eprintln!("Warning: failed to read existing FAB cache for update");
+            log::warn!("Failed to read existing FAB cache for update");
         }
     } else {
-        eprintln!("Info: FAB cache file not found at {}. Skipping cache update.", cache_path.display());
+        log::info!("FAB cache file not found at {}. Skipping cache update.", cache_path.display());
     }
 }
 
@@ -1483,7 +2701,7 @@ pub async fn handle_fab_download(
         _ => return None,
     };
 
-    println!("¬ handle_fab_download");
+    log::debug!("¬ handle_fab_download");
 
     // Fast-path: if caller provided an asset_name and the files are already downloaded
     // for the requested UE version (if any), skip download entirely and proceed with creation.
@@ -1491,7 +2709,7 @@ pub async fn handle_fab_download(
         let downloads_base = find_downloads_directory();
         let asset_dir = find_asset_directory(&downloads_base, asset_name);
         if !needs_download(&asset_dir, &req.ue) {
-            println!(
+            log::debug!(
                 "handle_fab_download: Skipping download for '{}' (UE {:?}) — files already present.",
                 asset_name,
                 req.ue
@@ -1517,7 +2735,7 @@ pub async fn handle_fab_download(
         Err(err_response) => {
             // If the download failed (non-2xx), bubble up the error response
             if !err_response.status().is_success() {
-                println!("¬ handle_fab_download => ERR download_asset_handler: error response: {:?}", err_response);
+                log::debug!("¬ handle_fab_download => ERR download_asset_handler: error response: {:?}", err_response);
                 return Some(err_response);
             }
             // A successful (200 OK) response in this Err branch indicates a cancelled job
@@ -1530,7 +2748,7 @@ pub async fn handle_fab_download(
         Ok(response) => {
             // If the download endpoint returned a non-success status, propagate it
             if !response.status().is_success() {
-                println!("¬ handle_fab_download => Ok download_asset_handler: error response: {:?}", response);
+                log::debug!("¬ handle_fab_download => Ok download_asset_handler: error response: {:?}", response);
                 return Some(response);
             }
             // On success, do not return early; continue to project creation
@@ -1549,20 +2767,25 @@ pub fn validate_request(req: &models::CreateUnrealProjectRequest) -> Result<(),
         .unwrap_or(true);
 
     if template_empty && asset_empty {
-        return Err(HttpResponse::BadRequest().body(
-            "Provide either template_project (path/dir) or asset_name (under downloads/)"
+        return Err(crate::api::err(
+            StatusCode::BAD_REQUEST,
+            "invalid_parameter",
+            "Provide either template_project (path/dir) or asset_name (under downloads/)",
         ));
     }
     if req.output_dir.trim().is_empty() {
-        return Err(HttpResponse::BadRequest().body("output_dir is required"));
+        return Err(crate::api::err(StatusCode::BAD_REQUEST, "missing_parameter", "output_dir is required (or save a default with POST /config/create-defaults)"));
     }
     if req.project_name.trim().is_empty() {
-        return Err(HttpResponse::BadRequest().body("project_name is required"));
+        return Err(crate::api::err(StatusCode::BAD_REQUEST, "missing_parameter", "project_name is required"));
+    }
+    if let Err(msg) = validate_ue_project_name(&req.project_name) {
+        return Err(crate::api::err(StatusCode::BAD_REQUEST, "invalid_project_name", msg));
     }
 
     let project_type = req.project_type.as_deref().unwrap_or("bp").to_lowercase();
     if project_type != "bp" && project_type != "cpp" {
-        return Err(HttpResponse::BadRequest().body("project_type must be 'bp' or 'cpp'"));
+        return Err(crate::api::err(StatusCode::BAD_REQUEST, "invalid_parameter", "project_type must be 'bp' or 'cpp'"));
     }
 
     Ok(())
@@ -1579,10 +2802,12 @@ pub fn resolve_engine_path(req: &models::CreateUnrealProjectRequest) -> Result<P
     // If UE version specified, find matching engine
     if let Some(ue) = &req.ue {
         let engines = discover_engines(&base);
-        return match utils::pick_engine_for_version(&engines, ue) {
+        return match utils::pick_engine_for_version(&engines, ue, false) {
             Some(info) => Ok(PathBuf::from(info.path.clone())),
-            None => Err(HttpResponse::NotFound().body(
-                "Requested UE version not found among discovered engines"
+            None => Err(crate::api::err(
+                StatusCode::NOT_FOUND,
+                "engine_not_found",
+                "Requested UE version not found among discovered engines",
             )),
         };
     }
@@ -1591,70 +2816,37 @@ pub fn resolve_engine_path(req: &models::CreateUnrealProjectRequest) -> Result<P
     select_latest_engine(&base)
 }
 
-pub fn discover_engines(base: &Path) -> Vec<models::UnrealEngineInfo> {
-    let mut engines = Vec::new();
-    if !base.is_dir() {
-        return engines;
-    }
-
-    if let Ok(entries) = fs::read_dir(base) {
-        for entry in entries.flatten() {
-            let p = entry.path();
-            if !p.is_dir() || !p.join("Engine").join("Binaries").exists() {
-                continue;
-            }
-
-            let name = p.file_name()
-                .and_then(|s| s.to_str())
-                .unwrap_or("")
-                .to_string();
-            let version = utils::read_build_version(&p)
-                .or_else(|| utils::parse_version_from_name(&name))
-                .unwrap_or_else(|| "unknown".to_string());
-            let editor_path = utils::find_editor_binary(&p)
-                .map(|pp| pp.to_string_lossy().to_string());
-
-            engines.push(models::UnrealEngineInfo {
-                name,
-                version,
-                path: p.to_string_lossy().to_string(),
-                editor_path,
-            });
-        }
-    }
-    engines
-}
-
 pub fn select_latest_engine(base: &Path) -> Result<PathBuf, HttpResponse> {
     if !base.is_dir() {
-        return Err(HttpResponse::BadRequest().body(
-            "engine_path not provided and no engines found in default location"
+        return Err(crate::api::err(
+            StatusCode::BAD_REQUEST,
+            "engine_not_found",
+            "engine_path not provided and no engines found in default location",
         ));
     }
 
-    let mut engines: Vec<PathBuf> = Vec::new();
+    let mut engines: Vec<(PathBuf, String)> = Vec::new();
     if let Ok(entries) = fs::read_dir(base) {
         for entry in entries.flatten() {
             let p = entry.path();
             if p.is_dir() && p.join("Engine").exists() {
-                engines.push(p);
+                let name = p.file_name().and_then(|s| s.to_str()).unwrap_or("").to_string();
+                let (version, _) = fs_scan::detect_engine_version(&p, &name);
+                engines.push((p, version));
             }
         }
     }
 
-    if engines.is_empty() {
-        return Err(HttpResponse::BadRequest().body(
-            "engine_path not provided and no engines found in default location"
-        ));
-    }
-
-    engines.sort_by(|a, b| {
-        b.file_name()
-            .unwrap_or_default()
-            .cmp(a.file_name().unwrap_or_default())
-    });
-
-    Ok(engines[0].clone())
+    // Highest numeric version wins; engines without a parsable version rank lowest, then by folder name
+    engines
+        .into_iter()
+        .max_by_key(|(p, version)| (parse_semver(version), p.file_name().unwrap_or_default().to_os_string()))
+        .map(|(p, _)| p)
+        .ok_or_else(|| crate::api::err(
+            StatusCode::BAD_REQUEST,
+            "engine_not_found",
+            "engine_path not provided and no engines found in default location",
+        ))
 }
 
 pub async fn resolve_template_path(
@@ -1666,12 +2858,12 @@ pub async fn resolve_template_path(
     } else if let Some(name) = &req.asset_name {
         resolve_from_asset_name(name, req, job_id).await?
     } else {
-        return Err(HttpResponse::BadRequest().body("No template source provided"));
+        return Err(crate::api::err(StatusCode::BAD_REQUEST, "missing_parameter", "No template source provided"));
     };
 
     match template_path {
         Some(p) if p.extension().and_then(|s| s.to_str()) == Some("uproject") => {
-            println!("Using template .uproject: {}", p.to_string_lossy());
+            log::info!("Using template .uproject: {}", p.to_string_lossy());
 
             // Canonicalize to absolute path
             Ok(std::fs::canonicalize(&p).unwrap_or_else(|_| {
@@ -1680,11 +2872,13 @@ pub async fn resolve_template_path(
                     .unwrap_or(p)
             }))
         }
-        _ => Err(HttpResponse::BadRequest().body(
+        _ => Err(crate::api::err(
+            StatusCode::BAD_REQUEST,
+            "template_not_found",
             "Unable to resolve a .uproject from template_project/asset_name. \
              Tips: ensure there is a .uproject inside the selected folder; if using asset_name, \
              verify the asset exists under downloads/ (case-insensitive match is supported) \
-             and that the .uproject isn't packaged deep inside nested 'data' or 'Content' folders."
+             and that the .uproject isn't packaged deep inside nested 'data' or 'Content' folders.",
         )),
     }
 }
@@ -1696,11 +2890,21 @@ pub fn resolve_from_template_project(tp: &str) -> Result<Option<PathBuf>, HttpRe
     }
 
     let candidate = PathBuf::from(trim_quotes_and_expand_home(tp));
-    Ok(if candidate.is_dir() {
-        find_uproject_bfs(&candidate, 5)
-    } else {
-        Some(candidate)
-    })
+    if !candidate.is_dir() {
+        return Ok(Some(candidate));
+    }
+    find_uproject_bfs(&candidate, search_limits(5))
+        .map_err(|e| crate::api::err(StatusCode::BAD_REQUEST, "invalid_parameter", format!("{} (pass the .uproject file as template_project)", e)))
+}
+
+/// Depth/entry caps for directory searches. EGS_SEARCH_MAX_DEPTH overrides `default_depth`;
+/// EGS_SEARCH_MAX_ENTRIES caps how many directory entries a single search may examine (default 20000).
+pub fn search_limits(default_depth: usize) -> fs_scan::SearchLimits {
+    let read = |key: &str| std::env::var(key).ok().and_then(|v| v.trim().parse::<usize>().ok()).filter(|n| *n > 0);
+    fs_scan::SearchLimits {
+        max_depth: read("EGS_SEARCH_MAX_DEPTH").unwrap_or(default_depth),
+        max_entries: read("EGS_SEARCH_MAX_ENTRIES").unwrap_or(20_000),
+    }
 }
 
 pub async fn resolve_from_asset_name(
@@ -1710,6 +2914,12 @@ pub async fn resolve_from_asset_name(
 ) -> Result<Option<PathBuf>, HttpResponse> {
     let downloads_base = find_downloads_directory();
     let mut asset_dir = find_asset_directory(&downloads_base, name);
+    let guard_base = downloads_base.exists();
+    if guard_base {
+        if let Err(e) = resolve_within_root(&downloads_base, &asset_dir) {
+            return Err(crate::api::err(StatusCode::BAD_REQUEST, "invalid_parameter", format!("Invalid asset_name: {}", e)));
+        }
+    }
 
     // Determine search directory based on UE version
     let mut search_dir = asset_dir.clone();
@@ -1717,6 +2927,9 @@ pub async fn resolve_from_asset_name(
         let ue_trimmed = ue.trim();
         if !ue_trimmed.is_empty() {
             let candidate = asset_dir.join(ue_trimmed);
+            if guard_base && resolve_within_root(&downloads_base, &candidate).is_err() {
+                return Err(crate::api::err(StatusCode::BAD_REQUEST, "invalid_parameter", format!("Invalid ue version folder: {}", ue_trimmed)));
+            }
             if candidate.exists() {
                 search_dir = candidate;
             }
@@ -1729,12 +2942,15 @@ pub async fn resolve_from_asset_name(
         search_dir = determine_search_dir(&asset_dir, &req.ue);
     }
 
-    println!("Searching for .uproject under: {}", search_dir.to_string_lossy());
-    Ok(find_uproject_bfs(&search_dir, 8))
+    log::debug!("Searching for .uproject under: {}", search_dir.to_string_lossy());
+    find_uproject_bfs(&search_dir, search_limits(8))
+        .map_err(|e| crate::api::err(StatusCode::BAD_REQUEST, "invalid_parameter", format!("{} (pass the .uproject path as template_project)", e)))
 }
 
+/// Downloads root to look assets up in: get_default_downloads_dir_path(), or a `downloads` folder next
+/// to the executable when that does not exist.
 pub fn find_downloads_directory() -> PathBuf {
-    let mut downloads_base = PathBuf::from("downloads");
+    let mut downloads_base = get_default_downloads_dir_path();
     if !downloads_base.exists() {
         if let Ok(exe) = std::env::current_exe() {
             if let Some(exe_dir) = exe.parent() {
@@ -1831,16 +3047,16 @@ pub async fn download_template_asset(
     // Find asset by title (case-insensitive)
     let account = match get_account_details(&mut epic_services).await {
         Some(a) => a,
-        None => return Err(HttpResponse::BadRequest().body("Unable to get account details")),
+        None => return Err(crate::api::err(StatusCode::BAD_REQUEST, "upstream_error", "Unable to get account details")),
     };
     let library = match get_fab_library_items(&mut epic_services, account).await {
         Some(l) => l,
-        None => return Err(HttpResponse::BadRequest().body("Unable to fetch Fab library items")),
+        None => return Err(crate::api::err(StatusCode::BAD_REQUEST, "upstream_error", "Unable to fetch Fab library items")),
     };
     let asset_opt = library.results.iter().find(|a| a.title.eq_ignore_ascii_case(name));
     let asset = match asset_opt {
         Some(a) => a,
-        None => return Err(HttpResponse::NotFound().body(format!("Asset '{}' not found in your Fab library", name))),
+        None => return Err(crate::api::err(StatusCode::NOT_FOUND, "asset_not_found", format!("Asset '{}' not found in your Fab library", name))),
     };
 
     // Select artifact based on requested UE major.minor when provided; otherwise pick highest supported.
@@ -1883,7 +3099,7 @@ pub async fn download_template_asset(
     }
     let artifact_id = match selected_artifact {
         Some(a) => a,
-        None => return Err(HttpResponse::BadRequest().body("Selected asset has no compatible project versions")),
+        None => return Err(crate::api::err(StatusCode::BAD_REQUEST, "incompatible_asset", "Selected asset has no compatible project versions")),
     };
     // Namespace and asset_id for cache update
     let namespace = asset.asset_namespace.clone();
@@ -1895,7 +3111,7 @@ pub async fn download_template_asset(
         Ok(m) => m,
         Err(e) => {
             emit_event(job_id, models::Phase::CreateError, format!("Failed to fetch manifest: {:?}", e), None, None);
-            return Err(HttpResponse::BadRequest().body(format!("Failed to fetch manifest: {:?}", e)));
+            return Err(crate::api::err(StatusCode::BAD_REQUEST, "upstream_error", format!("Failed to fetch manifest: {:?}", e)));
         }
     };
 
@@ -1923,6 +3139,17 @@ pub async fn download_template_asset(
         f
     });
 
+    // Record artifact info so an interrupted download can be listed and resumed later
+    let info = models::DownloadInfo {
+        namespace: namespace.clone(),
+        asset_id: asset_id.clone(),
+        artifact_id: artifact_id.clone(),
+        ue: version_to_use.clone(),
+    };
+    if let Err(e) = write_download_info(&out_root, &info) {
+        log::warn!("Failed to write download info marker: {}", e);
+    }
+
     for manifest in manifests.iter() {
         for url in manifest.distribution_point_base_urls.iter() {
             if check_if_job_is_cancelled(job_id) {
@@ -1939,7 +3166,7 @@ pub async fn download_template_asset(
                     map.insert("SourceURL".to_string(), url.clone());
                     dm.custom_fields = Some(map);
                 }
-                match download_asset(&dm, url.as_str(), &out_root, progress_callback.clone(), job_id).await {
+                match download_asset(&dm, url.as_str(), &out_root, progress_callback.clone(), job_id, &models::DownloadOptions::default()).await {
                     Ok(_) => {
                         // On success, update FAB cache to mark this version as downloaded
                         let fab_cache_file_path = get_fab_cache_file_path();
@@ -1950,7 +3177,7 @@ pub async fn download_template_asset(
                         return Ok(out_root);
                     }
                     Err(e) => {
-                        eprintln!("Download failed from {}: {:?}", url, e);
+                        log::warn!("Download failed from {}: {:?}", url, e);
                         continue;
                     }
                 }
@@ -1959,7 +3186,7 @@ pub async fn download_template_asset(
     }
 
     emit_event(job_id, models::Phase::DownloadError, "Unable to download asset from any distribution point", None, None);
-    Err(HttpResponse::InternalServerError().body("Unable to download asset from any distribution point"))
+    Err(crate::api::err(StatusCode::INTERNAL_SERVER_ERROR, "download_failed", "Unable to download asset from any distribution point"))
 }
 
 pub fn determine_search_dir(asset_dir: &Path, ue_version: &Option<String>) -> PathBuf {
@@ -1975,6 +3202,23 @@ pub fn determine_search_dir(asset_dir: &Path, ue_version: &Option<String>) -> Pa
     asset_dir.to_path_buf()
 }
 
+/// Describe a conflict between the cache and downloads directories a config would produce, if any.
+///
+/// Unset entries fall back to their defaults. The two must be distinct and not nested in each other:
+/// otherwise fab_list.json ends up inside the downloads tree (or downloads inside the cache), where
+/// scans misread it and download cleanup could delete it.
+pub fn cache_downloads_conflict(cfg: &models::PathsConfig) -> Option<String> {
+    let resolve = |v: &Option<String>, default: PathBuf| default_dirs::configured_or(v.as_deref(), default, fs_scan::host_platform(), &host_env);
+    let cache = resolve(&cfg.cache_dir, builtin_cache_dir());
+    let downloads = resolve(&cfg.downloads_dir, builtin_downloads_dir());
+    if !paths::overlap(&cache, &downloads) {
+        return None;
+    }
+    let (c, d) = (paths::normalize(&cache), paths::normalize(&downloads));
+    let relation = if c == d { "is the same directory as" } else if c.starts_with(&d) { "is inside" } else { "contains" };
+    Some(format!("cache_dir ({}) {} downloads_dir ({}); choose separate, non-nested directories", c.display(), relation, d.display()))
+}
+
 pub fn trim_quotes_and_expand_home(s: &str) -> String {
     let mut t = s.trim().to_string();
 
@@ -1984,81 +3228,19 @@ pub fn trim_quotes_and_expand_home(s: &str) -> String {
     }
 
     // Expand home directory
-    if let Ok(home) = std::env::var("HOME") {
-        if t.starts_with("~/") {
-            t = t.replacen("~", &home, 1);
-        }
-        if t.contains("$HOME") {
-            t = t.replace("$HOME", &home);
-        }
-    }
-    t
+    default_dirs::expand_home(&t, fs_scan::host_platform(), &host_env)
 }
 
-pub fn find_uproject_bfs(start: &Path, max_depth: usize) -> Option<PathBuf> {
-    use std::collections::VecDeque;
-
-    if max_depth == 0 {
-        return None;
-    }
-
-    let mut queue: VecDeque<(PathBuf, usize)> = VecDeque::new();
-    queue.push_back((start.to_path_buf(), 0));
-
-    while let Some((dir, depth)) = queue.pop_front() {
-        // If it's a file, check if it's a .uproject
-        if dir.is_file() {
-            if dir.extension().and_then(|s| s.to_str()) == Some("uproject") {
-                return Some(dir);
-            }
-            continue;
-        }
-
-        if !dir.is_dir() {
-            continue;
-        }
-
-        // Check current directory for .uproject files
-        if let Ok(entries) = fs::read_dir(&dir) {
-            for entry in entries.flatten() {
-                let p = entry.path();
-                if p.is_file() && p.extension().and_then(|s| s.to_str()) == Some("uproject") {
-                    return Some(p);
-                }
-            }
-        }
-
-        if depth >= max_depth {
-            continue;
-        }
-
-        // Enqueue subdirectories (excluding common non-project dirs)
-        if let Ok(entries) = fs::read_dir(&dir) {
-            for entry in entries.flatten() {
-                let p = entry.path();
-                if p.is_dir() {
-                    if let Some(name) = p.file_name().and_then(|s| s.to_str()) {
-                        let lname = name.to_ascii_lowercase();
-                        if lname == "content" || lname == ".git" || lname == ".svn" {
-                            continue;
-                        }
-                    }
-                    queue.push_back((p, depth + 1));
-                }
-            }
-        }
-    }
-
-    None
-}
 
-pub fn setup_output_directory(req: &models::CreateUnrealProjectRequest) -> Result<(PathBuf, PathBuf), HttpResponse> {
+pub fn setup_output_directory(req: &models::CreateUnrealProjectRequest) -> Result<(PathBuf, project_copy::ProjectDir), HttpResponse> {
     let out_dir = PathBuf::from(trim_quotes_and_expand_home(&req.output_dir));
 
     if !out_dir.exists() {
         if let Err(e) = fs::create_dir_all(&out_dir) {
-            return Err(HttpResponse::InternalServerError().body(
-                format!("Failed to create output_dir: {}", e)
+            return Err(crate::api::err(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "io_error",
+                format!("Failed to create output_dir: {}", e),
             ));
         }
     }
@@ -2069,12 +3251,16 @@ pub fn setup_output_directory(req: &models::CreateUnrealProjectRequest) -> Resul
             .unwrap_or(out_dir)
     });
 
-    let new_project_dir = out_dir.join(&req.project_name);
-    if let Err(e) = fs::create_dir_all(&new_project_dir) {
-        return Err(HttpResponse::InternalServerError().body(
-            format!("Failed to create new project directory: {}", e)
-        ));
-    }
+    let new_project_dir = match project_copy::ProjectDir::prepare(out_dir.join(&req.project_name)) {
+        Ok(d) => d,
+        Err(e) => {
+            return Err(crate::api::err(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "io_error",
+                format!("Failed to create new project directory: {}", e),
+            ));
+        }
+    };
 
     Ok((out_dir, new_project_dir))
 }
@@ -2087,7 +3273,7 @@ pub fn handle_dry_run(
     target_uproject: &Path,
 ) -> HttpResponse {
     let exclude_names = ["Binaries", "DerivedDataCache", "Intermediate", "Saved", ".git", ".svn", ".vs"];
-    let project_type = req.project_type.as_deref().unwrap_or("bp");
+    let editor_args = build_editor_args(target_uproject, req);
 
     let mut actions = vec![
         format!(
@@ -2096,12 +3282,7 @@ pub fn handle_dry_run(
             new_project_dir.to_string_lossy(),
             exclude_names
         ),
-        format!(
-            "Open with: {} {}{}",
-            editor_path.to_string_lossy(),
-            target_uproject.to_string_lossy(),
-            if project_type == "bp" { " -NoCompile" } else { "" }
-        ),
+        format!("Open with: {}", format_command(editor_path, &editor_args)),
     ];
 
     let resp = models::CreateUnrealProjectResponse {
@@ -2115,26 +3296,32 @@ pub fn handle_dry_run(
             }
         ),
         command: actions.join(" | "),
+        args: editor_args,
         project_path: Some(new_project_dir.to_string_lossy().to_string()),
+        failed_files: Vec::new(),
     };
 
     HttpResponse::Ok().json(resp)
 }
 
+/// Copy the template into the new project, checking for cancellation before each file.
+///
+/// Returns CopyOutcome::Cancelled (without rolling back) when the job was cancelled mid-copy.
+/// Files that failed to copy are returned alongside the outcome; a failed .uproject is an error.
 pub fn copy_project_files(
     template_dir: &Path,
-    new_project_dir: &Path,
+    project_dir: &project_copy::ProjectDir,
     project_name: &str,
-    template_path: &Path,
     job_id: &Option<String>,
-) -> Result<(usize, usize), HttpResponse> {
-    println!("¬ copy_project_files");
+) -> Result<(project_copy::CopyOutcome, Vec<models::FailedFile>), HttpResponse> {
+    log::debug!("¬ copy_project_files");
+    let new_project_dir = project_dir.path();
     let exclude_names = ["Binaries", "DerivedDataCache", "Intermediate", "Saved", ".git", ".svn", ".vs"];
 
     // Count total files to copy
     let total_files = count_files_to_copy(template_dir, &exclude_names);
 
-    println!(
+    log::info!(
         "[copy-start] {} -> {} ({} files, excluding {:?})",
         template_dir.to_string_lossy(),
         new_project_dir.to_string_lossy(),
@@ -2150,24 +3337,81 @@ pub fn copy_project_files(
         None,
     );
 
-    let (copied, skipped) = perform_copy(
+    let mut last_logged_percent = 0u32;
+    let mut last_log_instant = Instant::now();
+    let mut on_copied = |copied: usize| {
+        // Log progress
+        if total_files > 0 {
+            let percent = ((copied as f64 / total_files as f64) * 100.0).floor() as u32;
+            if percent >= last_logged_percent + 5 || last_log_instant.elapsed().as_secs() >= 2 {
+                last_logged_percent = percent;
+                last_log_instant = Instant::now();
+                utils::emit_event(
+                    job_id.as_deref(),
+                    models::Phase::CreateCopying,
+                    format!("perform_copy: {} / {}", copied, total_files),
+                    Some(percent as f32),
+                    None,
+                );
+            }
+        }
+    };
+    // A file that fails to copy is reported as it happens and the copy carries on. The report stays on
+    // create:copying: clients treat any *error phase as the end of the job.
+    let mut failed_files: Vec<models::FailedFile> = Vec::new();
+    let mut on_failed = |failure: &project_copy::CopyFailure| {
+        let path = failure.path.to_string_lossy().to_string();
+        log::warn!("[copy-error] {}", failure.error);
+        utils::emit_event(
+            job_id.as_deref(),
+            models::Phase::CreateCopying,
+            format!("Failed to copy {}", path),
+            None,
+            Some(serde_json::json!({ "path": path, "error": failure.error, "failed": true })),
+        );
+        failed_files.push(models::FailedFile { path, error: failure.error.clone() });
+    };
+    let outcome = project_copy::copy_template(
         template_dir,
         new_project_dir,
         project_name,
-        template_path,
         &exclude_names,
-        total_files,
-        job_id,
-    )?;
-
-    println!(
-        "[copy-finish] Copied {} files ({} skipped) to {}",
-        copied,
-        skipped,
-        new_project_dir.to_string_lossy()
+        &|| check_if_job_is_cancelled(job_id.as_deref()),
+        &mut on_copied,
+        &mut on_failed,
     );
+    let outcome = match outcome {
+        Ok(outcome) => outcome,
+        // Without its .uproject the copy is not a project; remove what this run created
+        Err(msg) => {
+            rollback_project_dir(project_dir);
+            utils::emit_event(job_id.as_deref(), models::Phase::CreateError, msg.clone(), None, None);
+            return Err(HttpResponse::InternalServerError().json(models::CreateUnrealProjectResponse {
+                ok: false,
+                message: msg,
+                command: String::new(),
+                args: Vec::new(),
+                project_path: Some(new_project_dir.to_string_lossy().to_string()),
+                failed_files,
+            }));
+        }
+    };
 
-    Ok((copied, skipped))
+    match &outcome {
+        project_copy::CopyOutcome::Completed { copied, skipped, failed } => log::info!(
+            "[copy-finish] Copied {} files ({} skipped, {} failed) to {}",
+            copied,
+            skipped,
+            failed,
+            new_project_dir.to_string_lossy()
+        ),
+        project_copy::CopyOutcome::Cancelled { copied } => log::info!(
+            "[copy-cancelled] Stopped after {} files in {}",
+            copied,
+            new_project_dir.to_string_lossy()
+        ),
+    }
+    Ok((outcome, failed_files))
 }
 
 fn count_files_to_copy(template_dir: &Path, exclude_names: &[&str]) -> usize {
@@ -2176,7 +3420,7 @@ fn count_files_to_copy(template_dir: &Path, exclude_names: &[&str]) -> usize {
         let src_path = entry.path();
         let Ok(rel) = src_path.strip_prefix(template_dir) else { continue };
 
-        if rel.as_os_str().is_empty() || should_exclude(rel, exclude_names) {
+        if rel.as_os_str().is_empty() || project_copy::should_exclude(rel, exclude_names) {
             continue;
         }
 
@@ -2187,96 +3431,24 @@ fn count_files_to_copy(template_dir: &Path, exclude_names: &[&str]) -> usize {
     count
 }
 
-fn should_exclude(rel_path: &Path, exclude_names: &[&str]) -> bool {
-    use std::path::Component;
-
-    if let Some(Component::Normal(os)) = rel_path.components().next() {
-        let name = os.to_string_lossy().to_string();
-        return exclude_names.iter().any(|ex| name.eq_ignore_ascii_case(ex));
+/// Stop a create job: remove the project directory if this run created it, emit Cancelled and
+/// return the same 200 "cancelled" response the download path uses.
+pub fn cancel_create(job_id: Option<&str>, project_dir: Option<&project_copy::ProjectDir>) -> HttpResponse {
+    if let Some(dir) = project_dir {
+        rollback_project_dir(dir);
     }
-    false
+    cancel_this_job(job_id);
+    HttpResponse::Ok().body("cancelled")
 }
 
-fn perform_copy(
-    template_dir: &Path,
-    new_project_dir: &Path,
-    project_name: &str,
-    template_path: &Path,
-    exclude_names: &[&str],
-    total_files: usize,
-    job_id: &Option<String>,
-) -> Result<(usize, usize), HttpResponse> {
-    let mut copied = 0usize;
-    let mut skipped = 0usize;
-    let mut last_logged_percent = 0u32;
-    let mut last_log_instant = Instant::now();
-
-    for entry in walkdir::WalkDir::new(template_dir).into_iter().filter_map(|e| e.ok()) {
-        let src_path = entry.path();
-        let Ok(rel) = src_path.strip_prefix(template_dir) else { continue };
-
-        if rel.as_os_str().is_empty() {
-            continue;
-        }
-
-        if should_exclude(rel, exclude_names) {
-            skipped += 1;
-            continue;
-        }
-
-        let dst_path = new_project_dir.join(rel);
-
-        if entry.file_type().is_dir() {
-            if let Err(e) = fs::create_dir_all(&dst_path) {
-                return Err(HttpResponse::InternalServerError().body(
-                    format!("Failed to create dir {}: {}", dst_path.to_string_lossy(), e)
-                ));
-            }
-        } else if entry.file_type().is_file() {
-            let final_dst = if src_path.extension().and_then(|s| s.to_str()) == Some("uproject") {
-                new_project_dir.join(format!("{}.uproject", project_name))
-            } else {
-                dst_path
-            };
-
-            if let Some(parent) = final_dst.parent() {
-                if let Err(e) = fs::create_dir_all(parent) {
-                    return Err(HttpResponse::InternalServerError().body(
-                        format!("Failed to create parent dir {}: {}", parent.to_string_lossy(), e)
-                    ));
-                }
-            }
-
-            if let Err(e) = fs::copy(src_path, &final_dst) {
-                return Err(HttpResponse::InternalServerError().body(
-                    format!("Failed to copy {} -> {}: {}", src_path.to_string_lossy(), final_dst.to_string_lossy(), e)
-                ));
-            }
-
-            copied += 1;
-
-            // Log progress
-            if total_files > 0 {
-                let percent = ((copied as f64 / total_files as f64) * 100.0).floor() as u32;
-                if percent >= last_logged_percent + 5 || last_log_instant.elapsed().as_secs() >= 2 {
-                    // println!("[copy-progress] {}/{} ({}%) - {}", copied, total_files, percent, rel.to_string_lossy());
-                    last_logged_percent = percent;
-                    last_log_instant = Instant::now();
-                    utils::emit_event(
-                        job_id.as_deref(),
-                        models::Phase::CreateCopying,
-                        format!("perform_copy: {} / {}", copied, total_files),
-                        Some(percent as f32),
-                        None,
-                    );
-                }
-            }
-        } else if entry.file_type().is_symlink() {
-            skipped += 1;
-        }
+/// Remove a project directory this run created; a pre-existing one is left alone.
+fn rollback_project_dir(dir: &project_copy::ProjectDir) {
+    match dir.rollback() {
+        Ok(true) => log::info!("Rolled back partially created project {}", dir.path().to_string_lossy()),
+        Ok(false) if !dir.created_now() => log::info!("Keeping pre-existing directory {}", dir.path().to_string_lossy()),
+        Ok(false) => {}
+        Err(e) => log::warn!("Failed to roll back {}: {}", dir.path().to_string_lossy(), e),
     }
-
-    Ok((copied, skipped))
 }
 
 pub fn finalize_uproject(
@@ -2303,18 +3475,52 @@ pub fn finalize_uproject(
     target_uproject
 }
 
+/// For cpp projects, rename the template's primary module (Source folders, rules, macros, .uproject
+/// and config references) to the project name so the copy compiles.
+///
+/// Err carries a message when the rename failed; the copy is then left half-renamed and will not compile.
+pub fn rename_cpp_modules(new_project_dir: &Path, req: &models::CreateUnrealProjectRequest, template_path: &Path) -> Result<(), String> {
+    if !req.project_type.as_deref().unwrap_or("bp").eq_ignore_ascii_case("cpp") {
+        return Ok(());
+    }
+    let stem = template_path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+    let Some(old) = fs::read_to_string(template_path).ok().and_then(|t| cpp_rename::primary_module_name(&t, stem)) else {
+        log::info!("Template {} lists no C++ modules; nothing to rename", template_path.display());
+        return Ok(());
+    };
+    match cpp_rename::rename_modules(new_project_dir, &old, &req.project_name) {
+        Ok(report) => {
+            log::info!(
+                "Renamed module '{}' to '{}': {} path(s) renamed, {} file(s) rewritten",
+                old, req.project_name, report.renamed.len(), report.rewritten.len()
+            );
+            Ok(())
+        }
+        Err(e) => Err(format!("Failed to rename C++ module '{}' to '{}': {}", old, req.project_name, e)),
+    }
+}
+
+/// Fail a create after the template was copied: remove the project this run created, emit
+/// create:error and answer 500 with `msg`.
+pub fn fail_create(job_id: Option<&str>, project_dir: &project_copy::ProjectDir, msg: String) -> HttpResponse {
+    rollback_project_dir(project_dir);
+    emit_event(job_id, models::Phase::CreateError, msg.clone(), None, None);
+    HttpResponse::InternalServerError().json(models::CreateUnrealProjectResponse {
+        ok: false,
+        message: msg,
+        command: String::new(),
+        args: Vec::new(),
+        project_path: Some(project_dir.path().to_string_lossy().to_string()),
+        failed_files: Vec::new(),
+    })
+}
+
 fn update_project_metadata(uproject_path: &Path, req: &models::CreateUnrealProjectRequest) {
     let Ok(json_text) = fs::read_to_string(uproject_path) else { return };
 
     // Update display/friendly name
-    if json_text.contains("\"FileVersion\"") || json_text.contains("\"EngineAssociation\"") {
-        let updated = json_text
-            .replace("\"DisplayName\":\"", &format!("\"DisplayName\":\"{}",req.project_name))
-            .replace("\"FriendlyName\":\"", &format!("\"FriendlyName\":\"{}",  req.project_name));
-
-        if updated != json_text {
-            let _ = fs::write(uproject_path, &updated);
-        }
+    if let Some(updated) = project_copy::set_display_names(&json_text, &req.project_name) {
+        let _ = fs::write(uproject_path, updated);
     }
 
     // Set EngineAssociation if UE version specified
@@ -2347,64 +3553,142 @@ fn set_engine_association(uproject_path: &Path, ue_version: &str) {
     }
 }
 
-pub fn build_editor_command(
-    editor_path: &Path,
-    uproject_path: &Path,
-    project_type: &Option<String>,
-) -> String {
-    let ptype = project_type.as_deref().unwrap_or("bp");
-    format!(
-        "{} {}{}",
-        editor_path.to_string_lossy(),
-        uproject_path.to_string_lossy(),
-        if ptype == "bp" { " -NoCompile" } else { "" }
-    )
+/// With fix_permissions=true, restore the execute bit on an editor binary that lost it before launching.
+pub fn fix_editor_permissions_if_requested(query: &HashMap<String, String>, editor_path: &Path) {
+    if !query.get("fix_permissions").map_or(false, |v| v == "1" || v.eq_ignore_ascii_case("true")) {
+        return;
+    }
+    match exec_perm::ensure_executable(editor_path) {
+        Ok(true) => log::info!("Made editor executable: {}", editor_path.display()),
+        Ok(false) => {}
+        Err(e) => log::warn!("Failed to make {} executable: {}", editor_path.display(), e),
+    }
+}
+
+/// Spawn Unreal Editor and, when a job id is given, watch it for launch failures.
+///
+/// Without a job id this is a plain fire-and-forget spawn. With one, stdout/stderr go to the job's
+/// `editor_log` file (never a pipe, so the editor outlives the backend safely) and a watcher thread emits
+/// `editor:error` with the last 200 logged lines if the editor exits non-zero or within
+/// EGS_EDITOR_EARLY_EXIT_SECS (default 30) of launch, otherwise `editor:exited` on a clean exit.
+/// Older logs are pruned first (see `editor_log::prune_logs`). The caller's response is never blocked.
+pub fn spawn_editor_watched(mut cmd: std::process::Command, job_id_opt: Option<&str>) -> std::io::Result<()> {
+    use std::process::Stdio;
+    use std::time::Duration;
+
+    let Some(job_id) = job_id_opt.map(|s| s.to_string()) else {
+        cmd.spawn()?;
+        return Ok(());
+    };
+
+    let log_path = editor_log::log_path(&default_cache_dir(), &job_id);
+    if let Some(dir) = log_path.parent() {
+        let removed = editor_log::prune_logs(dir, editor_log::KEEP_LOGS, editor_log::MAX_LOG_AGE);
+        if removed > 0 { log::debug!("Removed {} old editor logs from {}", removed, dir.display()); }
+    }
+    match editor_log::open_log(&log_path) {
+        Ok((out, err)) => { cmd.stdout(out).stderr(err); }
+        Err(e) => {
+            log::warn!("Cannot create editor log {}: {}; editor output will not be captured", log_path.display(), e);
+            cmd.stdout(Stdio::null()).stderr(Stdio::null());
+        }
+    }
+    let mut child = cmd.spawn()?;
+    let started = Instant::now();
+
+    let early_exit_secs: u64 = std::env::var("EGS_EDITOR_EARLY_EXIT_SECS").ok().and_then(|s| s.parse().ok()).unwrap_or(30);
+    std::thread::spawn(move || {
+        // Same polling approach as the Flutter child watcher in main.rs
+        let status = loop {
+            match child.try_wait() {
+                Ok(Some(status)) => break Ok(status),
+                Ok(None) => std::thread::sleep(Duration::from_millis(500)),
+                Err(e) => break Err(e),
+            }
+        };
+        let lines = editor_log::tail_lines(&log_path, editor_log::TAIL_LINES);
+        let log_file = log_path.to_string_lossy().to_string();
+        let elapsed = started.elapsed();
+        match status {
+            Ok(status) => {
+                let early = elapsed < Duration::from_secs(early_exit_secs);
+                let details = serde_json::json!({
+                    "exit_code": status.code(),
+                    "elapsed_secs": elapsed.as_secs(),
+                    "early_exit": early,
+                    "output_tail": lines,
+                    "log_path": log_file,
+                });
+                if !status.success() || early {
+                    emit_event(Some(&job_id), models::Phase::EditorError, format!("Unreal Editor exited after {}s with status {}", elapsed.as_secs(), status), None, Some(details));
+                } else {
+                    emit_event(Some(&job_id), models::Phase::EditorExited, "Unreal Editor exited normally", None, Some(details));
+                }
+            }
+            Err(e) => {
+                emit_event(Some(&job_id), models::Phase::EditorError, format!("Error monitoring Unreal Editor process: {}", e), None, Some(serde_json::json!({ "output_tail": lines, "log_path": log_file })));
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Arguments for opening a newly created project: `-NoCompile` for Blueprint projects unless the request
+/// overrides it with `no_compile`, then the request's `extra_args`.
+pub fn build_editor_args(uproject_path: &Path, req: &models::CreateUnrealProjectRequest) -> Vec<String> {
+    let no_compile = editor_args::default_no_compile(req.project_type.as_deref(), req.no_compile);
+    editor_args::editor_args(&uproject_path.to_string_lossy(), no_compile, req.extra_args.as_deref().unwrap_or_default())
+}
+
+/// Human-readable command line for responses and logs; the editor itself is spawned with separate arguments.
+pub fn format_command(editor_path: &Path, args: &[String]) -> String {
+    std::iter::once(editor_path.to_string_lossy().to_string()).chain(args.iter().cloned()).collect::<Vec<_>>().join(" ")
 }
 
 pub fn execute_project_open(
     req: &models::CreateUnrealProjectRequest,
     copied: usize,
     skipped: usize,
-    command: String,
+    failed_files: Vec<models::FailedFile>,
+    editor_path: &Path,
+    args: Vec<String>,
     project_dir: &Path,
 ) -> HttpResponse {
-    let project_type = req.project_type.as_deref().unwrap_or("bp");
+    let command = format_command(editor_path, &args);
     let open_after = req.open_after_create.unwrap_or(false);
+    let failed_note = if failed_files.is_empty() { String::new() } else { format!(", {} failed to copy", failed_files.len()) };
 
     if !open_after {
         let resp = models::CreateUnrealProjectResponse {
             ok: true,
             message: format!(
-                "Project created ({} files, {} skipped). Not opening (open_after_create=false).",
-                copied, skipped
+                "Project created ({} files, {} skipped{}). Not opening (open_after_create=false).",
+                copied, skipped, failed_note
             ),
             command,
+            args,
             project_path: Some(project_dir.to_string_lossy().to_string()),
+            failed_files,
         };
         return HttpResponse::Ok().json(resp);
     }
 
-    // Parse command and spawn process
-    let parts: Vec<&str> = command.split_whitespace().collect();
-    if parts.is_empty() {
-        return HttpResponse::InternalServerError().body("Invalid command");
-    }
-
-    let mut cmd = std::process::Command::new(parts[0]);
-    for arg in &parts[1..] {
-        cmd.arg(arg);
-    }
+    // One argument per arg() call, no shell: paths with spaces and caller-supplied args stay intact
+    let mut cmd = std::process::Command::new(editor_path);
+    cmd.args(&args);
 
     match cmd.spawn() {
         Ok(_) => {
             let resp = models::CreateUnrealProjectResponse {
                 ok: true,
                 message: format!(
-                    "Project created ({} files, {} skipped). Unreal Editor is launching...",
-                    copied, skipped
+                    "Project created ({} files, {} skipped{}). Unreal Editor is launching...",
+                    copied, skipped, failed_note
                 ),
                 command,
+                args,
                 project_path: Some(project_dir.to_string_lossy().to_string()),
+                failed_files,
             };
             HttpResponse::Ok().json(resp)
         }
@@ -2412,11 +3696,13 @@ pub fn execute_project_open(
             let resp = models::CreateUnrealProjectResponse {
                 ok: true,
                 message: format!(
-                    "Project created ({} files, {} skipped). Failed to launch UnrealEditor: {}",
-                    copied, skipped, e
+                    "Project created ({} files, {} skipped{}). Failed to launch UnrealEditor: {}",
+                    copied, skipped, failed_note, e
                 ),
                 command,
+                args,
                 project_path: Some(project_dir.to_string_lossy().to_string()),
+                failed_files,
             };
             HttpResponse::Ok().json(resp)
         }
@@ -2424,10 +3710,39 @@ pub fn execute_project_open(
 }
 
 pub async fn download_asset_handler(path: web::Path<(String, String, String)>, query: Query<HashMap<String, String>>) -> Result<HttpResponse, HttpResponse> {
+    if is_shutting_down() {
+        return Err(crate::api::err(StatusCode::SERVICE_UNAVAILABLE, "shutting_down", "Server is shutting down; start the download after the next launch"));
+    }
     let (namespace, asset_id, artifact_id) = path.into_inner();
     let job_id = query.get("jobId").cloned().or_else(|| query.get("job_id").cloned());
     let ue_major_minor_version = query.get("ue").cloned();
-    println!("¬ download_asset_handler");
+    let mut options = models::DownloadOptions::default();
+    for (key, range, slot) in [
+        ("file_concurrency", FILE_CONCURRENCY_RANGE, &mut options.file_concurrency),
+        ("chunk_concurrency", CHUNK_CONCURRENCY_RANGE, &mut options.chunk_concurrency),
+    ] {
+        if let Some(raw) = query.get(key) {
+            match raw.trim().parse::<usize>() {
+                Ok(n) if range.contains(&n) => *slot = Some(n),
+                _ => return Err(crate::api::err(StatusCode::BAD_REQUEST, "invalid_parameter", format!("{} must be an integer in {}..={}", key, range.start(), range.end()))),
+            }
+        }
+    }
+    options.detailed_progress = query.get("detailed_progress").map_or(false, |v| v == "1" || v.eq_ignore_ascii_case("true"));
+    options.include_patterns = query.get("include").map(|s| glob::parse_patterns(s)).unwrap_or_default();
+    options.exclude_patterns = query.get("exclude").map(|s| glob::parse_patterns(s)).unwrap_or_default();
+    for patterns in [&options.include_patterns, &options.exclude_patterns] {
+        if let Err(e) = glob::PatternSet::new(patterns) {
+            return Err(crate::api::err(StatusCode::BAD_REQUEST, "invalid_parameter", e));
+        }
+    }
+    if let Some(raw) = query.get("download_rate_limit") {
+        match raw.trim().parse::<u64>() {
+            Ok(n) if n > 0 => options.max_bytes_per_sec = Some(n),
+            _ => return Err(crate::api::err(StatusCode::BAD_REQUEST, "invalid_parameter", "download_rate_limit must be a positive integer (bytes per second)")),
+        }
+    }
+    log::debug!("¬ download_asset_handler");
     // If already cancelled before we start, exit early
     if check_if_job_is_cancelled(job_id.as_deref()) {
         cancel_this_job(job_id.as_deref());
@@ -2440,6 +3755,10 @@ pub async fn download_asset_handler(path: web::Path<(String, String, String)>, q
     if !try_cached_login(&mut epic_services).await {
         epic_authenticate(&mut epic_services).await;
     }
+    if let Err(msg) = ensure_fresh_token(&mut epic_services).await {
+        emit_event(job_id.as_deref(), models::Phase::DownloadError, msg.clone(), None, None);
+        return Err(crate::api::err(StatusCode::UNAUTHORIZED, "auth_failed", msg));
+    }
 
     // Emit start event with a user-friendly asset title if available.
     let asset_name = utils::get_friendly_asset_name(&namespace, &asset_id, &artifact_id, &mut epic_services).await;
@@ -2448,7 +3767,15 @@ pub async fn download_asset_handler(path: web::Path<(String, String, String)>, q
         models::Phase::DownloadStart,
         format!("download_asset_handler: Starting to download asset: {}", asset_name),
         Some(0.0),
-        None);
+        {
+            let (file_concurrency, chunk_concurrency) = effective_download_concurrency(&options);
+            Some(serde_json::json!({
+                "file_concurrency": file_concurrency,
+                "chunk_concurrency": chunk_concurrency,
+                "global_file_concurrency": global_file_semaphore().1,
+                "max_bytes_per_sec": effective_download_rate_limit(&options),
+            }))
+        });
 
     // Fetch manifest for the specified asset/artifact
     let manifest_res = epic_services.fab_asset_manifest(&artifact_id, &namespace, &asset_id, None).await;
@@ -2456,11 +3783,16 @@ pub async fn download_asset_handler(path: web::Path<(String, String, String)>, q
         Ok(m) => m,
         Err(e) => {
             emit_event(job_id.as_deref(), models::Phase::DownloadError, format!("Failed to fetch manifest: {:?}", e), None, None);
-            return Err(HttpResponse::BadRequest().body(format!("Failed to fetch manifest: {:?}", e)));
+            return Err(crate::api::err(StatusCode::BAD_REQUEST, "upstream_error", format!("Failed to fetch manifest: {:?}", e)));
         }
     };
 
     for manifest in manifests.iter() {
+        // A previous distribution point may have taken long enough for the token to near expiry
+        if let Err(msg) = ensure_fresh_token(&mut epic_services).await {
+            emit_event(job_id.as_deref(), models::Phase::DownloadError, msg.clone(), None, None);
+            return Err(crate::api::err(StatusCode::UNAUTHORIZED, "auth_failed", msg));
+        }
         // Get a download URL
         for url in manifest.distribution_point_base_urls.iter() {
             // Check if job has been requested to cancel
@@ -2480,6 +3812,24 @@ pub async fn download_asset_handler(path: web::Path<(String, String, String)>, q
                     download_manifest.custom_fields = Some(map);
                 }
 
+                // An include/exclude filter that selects nothing is a request error, not a failed download
+                if !options.include_patterns.is_empty() || !options.exclude_patterns.is_empty() {
+                    let manifest_files = download_manifest.files();
+                    let include = glob::PatternSet::new(&options.include_patterns).unwrap_or_default();
+                    let exclude = glob::PatternSet::new(&options.exclude_patterns).unwrap_or_default();
+                    let selected = manifest_files.keys()
+                        .filter(|name| glob::is_selected(&include, &exclude, name))
+                        .count();
+                    if selected == 0 {
+                        let msg = format!(
+                            "include/exclude filter matched none of the {} manifest files (include: {:?}, exclude: {:?})",
+                            manifest_files.len(), options.include_patterns, options.exclude_patterns
+                        );
+                        emit_event(job_id.as_deref(), models::Phase::DownloadError, msg.clone(), None, None);
+                        return Err(crate::api::err(StatusCode::BAD_REQUEST, "invalid_parameter", msg));
+                    }
+                }
+
                 let friendly_folder_name = get_friendly_folder_name(asset_name.clone());
                 let folder_name = friendly_folder_name.clone().unwrap_or_else(|| format!("{}-{}-{}", namespace, asset_id, artifact_id));
 
@@ -2525,10 +3875,21 @@ pub async fn download_asset_handler(path: web::Path<(String, String, String)>, q
                 }
                 if let Some(ref mm) = version_to_use {
                     // Create folder called specific version of asset
-                    println!("Creating folder with specific version asset: {}", mm);
+                    log::info!("Creating folder with specific version asset: {}", mm);
                     download_directory_full_path = download_directory_full_path.join(mm);
                 }
 
+                // Record artifact info so an interrupted download can be listed and resumed later
+                let info = models::DownloadInfo {
+                    namespace: namespace.clone(),
+                    asset_id: asset_id.clone(),
+                    artifact_id: artifact_id.clone(),
+                    ue: version_to_use.clone(),
+                };
+                if let Err(e) = write_download_info(&download_directory_full_path, &info) {
+                    log::warn!("Failed to write download info marker: {}", e);
+                }
+
                 // Progress callback: forward file completion percentage over WS
                 let progress_callback: Option<ProgressFn> = job_id.as_deref().map(|jid| {
                     let jid = jid.to_string();
@@ -2538,15 +3899,16 @@ pub async fn download_asset_handler(path: web::Path<(String, String, String)>, q
                     f
                 });
 
-                match download_asset(&download_manifest, url.as_str(), &download_directory_full_path, progress_callback, job_id.as_deref()).await {
+                match download_asset(&download_manifest, url.as_str(), &download_directory_full_path, progress_callback, job_id.as_deref(), &options).await {
                     Ok(_) => {
-                        println!("Download complete");
+                        log::info!("Download complete");
 
                         if utils::check_if_job_is_cancelled(job_id.as_deref()) {
                             // Remove the incomplete asset folder so partial files are not left behind
                             if let Err(err) = fs::remove_dir_all(&download_directory_full_path) {
-                                eprintln!("Cleanup warning: failed to remove incomplete asset folder {}: {:?}", download_directory_full_path.display(), err);
+                                log::warn!("Failed to remove incomplete asset folder {}: {:?}", download_directory_full_path.display(), err);
                             }
+                            remove_chunk_temp_dir(&download_directory_full_path);
                             cancel_this_job(job_id.as_deref());
                             return Err(HttpResponse::Ok().body("cancelled"));
                         }
@@ -2568,12 +3930,13 @@ pub async fn download_asset_handler(path: web::Path<(String, String, String)>, q
                         if utils::check_if_job_is_cancelled(job_id.as_deref()) {
                             // Remove the incomplete asset folder so partial files are not left behind
                             if let Err(err) = fs::remove_dir_all(&download_directory_full_path) {
-                                eprintln!("Cleanup warning: failed to remove incomplete asset folder {}: {:?}", download_directory_full_path.display(), err);
+                                log::warn!("Failed to remove incomplete asset folder {}: {:?}", download_directory_full_path.display(), err);
                             }
+                            remove_chunk_temp_dir(&download_directory_full_path);
                             if let Some(ref j) = job_id { utils::acknowledge_cancel(j); }
                             return Err(HttpResponse::Ok().body("cancelled"));
                         }
-                        eprintln!("Download failed from {}: {:?}", url, e);
+                        log::warn!("Download failed from {}: {:?}", url, e);
                         continue;
                     }
                 }
@@ -2582,5 +3945,5 @@ pub async fn download_asset_handler(path: web::Path<(String, String, String)>, q
     }
 
     utils::emit_event(job_id.as_deref(), models::Phase::DownloadError, "Unable to download asset from any distribution point", None, None);
-    Ok(HttpResponse::InternalServerError().body("Unable to download asset from any distribution point"))
+    Ok(crate::api::err(StatusCode::INTERNAL_SERVER_ERROR, "download_failed", "Unable to download asset from any distribution point"))
 }
\ No newline at end of file
@@ -0,0 +1,113 @@
+//! Multi-threaded directory copy used by imports of very large packs.
+//!
+//! Semantics match the serial copy: directories are recreated, existing files are handled by the
+//! conflict policy, and the result is the per-action `CopyCounts`.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use super::conflict::{self, ConflictPolicy, CopyCounts};
+
+/// Copy `src` into `dst` using `workers` threads.
+///
+/// `on_progress(processed, total)` is called after each file (from worker threads), and
+/// `is_cancelled()` is polled between files; cancellation returns `ErrorKind::Interrupted`.
+pub fn copy_tree_parallel(
+    src: &Path,
+    dst: &Path,
+    policy: ConflictPolicy,
+    workers: usize,
+    on_progress: &(dyn Fn(usize, usize) + Sync),
+    is_cancelled: &(dyn Fn() -> bool + Sync),
+) -> io::Result<CopyCounts> {
+    if !src.exists() {
+        return Err(io::Error::new(io::ErrorKind::NotFound, format!("source not found: {}", src.display())));
+    }
+
+    // Recreate the directory structure up front, then fan out file copies
+    let mut files: Vec<(PathBuf, PathBuf)> = Vec::new();
+    for entry in walkdir::WalkDir::new(src).follow_links(false) {
+        let entry = entry.map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        let rel = entry.path().strip_prefix(src).map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        let target = dst.join(rel);
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&target)?;
+        } else if entry.file_type().is_file() {
+            files.push((entry.path().to_path_buf(), target));
+        }
+    }
+
+    let total = files.len();
+    let next = AtomicUsize::new(0);
+    let processed = AtomicUsize::new(0);
+    let counts: Mutex<CopyCounts> = Mutex::new(CopyCounts::default());
+    let rename_lock = Mutex::new(());
+    let stop = AtomicBool::new(false);
+    let first_error: Mutex<Option<io::Error>> = Mutex::new(None);
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers.max(1) {
+            scope.spawn(|| loop {
+                if stop.load(Ordering::SeqCst) {
+                    break;
+                }
+                if is_cancelled() {
+                    stop.store(true, Ordering::SeqCst);
+                    let mut e = first_error.lock().unwrap_or_else(|p| p.into_inner());
+                    if e.is_none() {
+                        *e = Some(io::Error::new(io::ErrorKind::Interrupted, "cancelled by user"));
+                    }
+                    break;
+                }
+                let i = next.fetch_add(1, Ordering::SeqCst);
+                if i >= total {
+                    break;
+                }
+                let (from, to) = &files[i];
+                let res = {
+                    // Renames pick and write their free name under a lock so two workers can't claim the same one
+                    let _rename = (policy == ConflictPolicy::RenameNew && to.exists()).then(|| rename_lock.lock().unwrap_or_else(|p| p.into_inner()));
+                    let action = conflict::decide(policy, from, to);
+                    conflict::apply(&action, from).map(|_| counts.lock().unwrap_or_else(|p| p.into_inner()).record(&action))
+                };
+                if let Err(err) = res {
+                    stop.store(true, Ordering::SeqCst);
+                    let mut e = first_error.lock().unwrap_or_else(|p| p.into_inner());
+                    if e.is_none() {
+                        *e = Some(err);
+                    }
+                    break;
+                }
+                let done = processed.fetch_add(1, Ordering::SeqCst) + 1;
+                on_progress(done, total);
+            });
+        }
+    });
+
+    if let Some(err) = first_error.into_inner().unwrap_or_else(|p| p.into_inner()) {
+        return Err(err);
+    }
+    Ok(counts.into_inner().unwrap_or_else(|p| p.into_inner()))
+}
+
+/// Count what copying `src` into `dst` would do, without touching either tree.
+///
+/// Returns the counts the copy itself would report under `policy`.
+pub fn plan_copy(src: &Path, dst: &Path, policy: ConflictPolicy) -> io::Result<CopyCounts> {
+    if !src.exists() {
+        return Err(io::Error::new(io::ErrorKind::NotFound, format!("source not found: {}", src.display())));
+    }
+    let mut counts = CopyCounts::default();
+    for entry in walkdir::WalkDir::new(src).follow_links(false) {
+        let entry = entry.map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let rel = entry.path().strip_prefix(src).map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        counts.record(&conflict::decide(policy, entry.path(), &dst.join(rel)));
+    }
+    Ok(counts)
+}
@@ -0,0 +1,90 @@
+//! Path comparisons for configured directories.
+
+use std::path::{Component, Path, PathBuf};
+
+/// Absolute, `.`/`..`-free form of `p` without touching the filesystem beyond `current_dir`.
+/// Existing paths are canonicalized so symlinked spellings compare equal.
+pub fn normalize(p: &Path) -> PathBuf {
+    if let Ok(c) = std::fs::canonicalize(p) {
+        return c;
+    }
+    let abs = if p.is_absolute() {
+        p.to_path_buf()
+    } else {
+        std::env::current_dir().map(|d| d.join(p)).unwrap_or_else(|_| p.to_path_buf())
+    };
+    let mut out = PathBuf::new();
+    for comp in abs.components() {
+        match comp {
+            Component::CurDir => {}
+            Component::ParentDir => { out.pop(); }
+            other => out.push(other.as_os_str()),
+        }
+    }
+    out
+}
+
+/// True when `a` and `b` are the same directory or one is nested inside the other.
+pub fn overlap(a: &Path, b: &Path) -> bool {
+    let (a, b) = (normalize(a), normalize(b));
+    a.starts_with(&b) || b.starts_with(&a)
+}
+
+/// Why resolve_within_root rejected a path.
+#[derive(Debug)]
+pub enum PathGuardError {
+    /// The root itself could not be resolved (missing or unreadable).
+    RootUnavailable(std::io::Error),
+    /// The path resolves (via `..`, an absolute path or a symlink) to somewhere outside the root.
+    OutsideRoot(PathBuf),
+}
+
+impl std::fmt::Display for PathGuardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathGuardError::RootUnavailable(e) => write!(f, "root directory is unavailable: {}", e),
+            PathGuardError::OutsideRoot(p) => write!(f, "path escapes its root directory: {}", p.display()),
+        }
+    }
+}
+
+impl std::error::Error for PathGuardError {}
+
+/// Resolve `user_path` (relative to `root`, or absolute) and verify it stays inside `root`.
+///
+/// A `user_path` that was already built as `root.join(..)` is taken as-is rather than joined twice.
+///
+/// The deepest existing ancestor is canonicalized, so symlinks cannot escape; components that do not
+/// exist yet (e.g. a destination folder about to be created) are appended as-is but may not contain
+/// `..`. Returns the resolved path, which may be `root` itself.
+pub fn resolve_within_root(root: &Path, user_path: &Path) -> Result<PathBuf, PathGuardError> {
+    let joined = if user_path.starts_with(root) { user_path.to_path_buf() } else { root.join(user_path) };
+    let root = std::fs::canonicalize(root).map_err(PathGuardError::RootUnavailable)?;
+    let mut existing = joined.as_path();
+    let mut rest: Vec<&std::ffi::OsStr> = Vec::new();
+    let base = loop {
+        match std::fs::canonicalize(existing) {
+            Ok(c) => break c,
+            Err(_) => {
+                let (Some(parent), Some(name)) = (existing.parent(), existing.file_name()) else {
+                    return Err(PathGuardError::OutsideRoot(joined.clone()));
+                };
+                rest.push(name);
+                existing = parent;
+            }
+        }
+    };
+    // A `..` in the missing tail has no file_name() and is rejected above; this is belt and braces
+    let mut resolved = base;
+    for name in rest.into_iter().rev() {
+        if name == ".." || name == "." {
+            return Err(PathGuardError::OutsideRoot(joined.clone()));
+        }
+        resolved.push(name);
+    }
+    if resolved.starts_with(&root) {
+        Ok(resolved)
+    } else {
+        Err(PathGuardError::OutsideRoot(joined))
+    }
+}
@@ -0,0 +1,69 @@
+//! Cooperative pause/resume registry for download jobs.
+//!
+//! Pausing only marks the job; download tasks call `wait_while_paused` between chunks, so a chunk
+//! already in flight finishes and nothing in temp/ is discarded. Resuming wakes every waiter.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
+
+/// How often a paused waiter re-checks cancellation while no resume has arrived.
+const CANCEL_POLL: Duration = Duration::from_millis(500);
+
+#[derive(Default)]
+pub struct PauseRegistry {
+    paused: Mutex<HashMap<String, Arc<Notify>>>,
+}
+
+impl PauseRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Arc<Notify>>> {
+        self.paused.lock().unwrap_or_else(|p| p.into_inner())
+    }
+
+    /// Mark `job_id` paused; false when it already was.
+    pub fn pause(&self, job_id: &str) -> bool {
+        let mut map = self.lock();
+        if map.contains_key(job_id) {
+            return false;
+        }
+        map.insert(job_id.to_string(), Arc::new(Notify::new()));
+        true
+    }
+
+    /// Clear the pause and wake every waiter; false when the job was not paused.
+    pub fn resume(&self, job_id: &str) -> bool {
+        match self.lock().remove(job_id) {
+            Some(notify) => {
+                notify.notify_waiters();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn is_paused(&self, job_id: &str) -> bool {
+        self.lock().contains_key(job_id)
+    }
+
+    /// Block until `job_id` is resumed or `is_cancelled()` returns true.
+    /// Returns whether it actually had to wait.
+    pub async fn wait_while_paused(&self, job_id: &str, is_cancelled: &(dyn Fn() -> bool + Sync)) -> bool {
+        let mut waited = false;
+        loop {
+            let Some(notify) = self.lock().get(job_id).cloned() else { return waited };
+            let mut notified = std::pin::pin!(notify.notified());
+            // Register before re-checking so a resume between the check and the await is not lost
+            notified.as_mut().enable();
+            if !self.is_paused(job_id) || is_cancelled() {
+                return waited;
+            }
+            waited = true;
+            let _ = tokio::time::timeout(CANCEL_POLL, notified).await;
+        }
+    }
+}
@@ -0,0 +1,200 @@
+//! Template copy for create_unreal_project, with cooperative cancellation and rollback.
+//!
+//! The copy checks `is_cancelled` before every entry. A project directory is only ever removed on
+//! rollback when this run created it, so cancelling never deletes a folder the user already had.
+
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+/// Result of copy_template: either every file was processed (`failed` of them could not be copied),
+/// or a cancel stopped it part-way.
+#[derive(Debug, PartialEq, Eq)]
+pub enum CopyOutcome {
+    Completed { copied: usize, skipped: usize, failed: usize },
+    Cancelled { copied: usize },
+}
+
+/// A template entry that could not be copied; `path` is relative to the template folder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopyFailure {
+    pub path: PathBuf,
+    pub error: String,
+}
+
+/// A new project directory, remembering whether this run created it.
+#[derive(Debug)]
+pub struct ProjectDir {
+    path: PathBuf,
+    created_now: bool,
+}
+
+impl ProjectDir {
+    /// Create `path` (and parents) if needed.
+    pub fn prepare(path: PathBuf) -> io::Result<ProjectDir> {
+        let created_now = !path.exists();
+        fs::create_dir_all(&path)?;
+        Ok(ProjectDir { path, created_now })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn created_now(&self) -> bool {
+        self.created_now
+    }
+
+    /// Remove the directory if this run created it; returns whether anything was removed.
+    pub fn rollback(&self) -> io::Result<bool> {
+        if !self.created_now || !self.path.exists() {
+            return Ok(false);
+        }
+        fs::remove_dir_all(&self.path)?;
+        Ok(true)
+    }
+}
+
+/// Longest project name Unreal's New Project dialog accepts.
+pub const MAX_PROJECT_NAME_LEN: usize = 20;
+
+/// Check `name` against Unreal's project naming rules: `[A-Za-z_][A-Za-z0-9_]*`, at most
+/// MAX_PROJECT_NAME_LEN characters. The error says what is wrong with it.
+pub fn validate_ue_project_name(name: &str) -> Result<(), String> {
+    let Some(first) = name.chars().next() else {
+        return Err("project_name is required".to_string());
+    };
+    if name.chars().count() > MAX_PROJECT_NAME_LEN {
+        return Err(format!("project_name '{}' is longer than {} characters", name, MAX_PROJECT_NAME_LEN));
+    }
+    if first.is_ascii_digit() {
+        return Err(format!("project_name '{}' must not start with a digit", name));
+    }
+    if name.contains(char::is_whitespace) {
+        return Err(format!("project_name '{}' must not contain spaces", name));
+    }
+    if let Some(bad) = name.chars().find(|c| !(c.is_ascii_alphanumeric() || *c == '_')) {
+        return Err(format!("project_name '{}' contains '{}'; use only letters, digits and underscores", name, bad));
+    }
+    Ok(())
+}
+
+/// True when the first component of `rel_path` is one of `exclude_names` (case-insensitive).
+pub fn should_exclude(rel_path: &Path, exclude_names: &[&str]) -> bool {
+    if let Some(Component::Normal(os)) = rel_path.components().next() {
+        let name = os.to_string_lossy().to_string();
+        return exclude_names.iter().any(|ex| name.eq_ignore_ascii_case(ex));
+    }
+    false
+}
+
+/// Copy `template_dir` into `new_project_dir`, renaming any .uproject to `<project_name>.uproject`.
+///
+/// Top-level folders in `exclude_names` and symlinks are skipped. `on_copied` receives the running
+/// copied-file count. A file or folder that fails to copy is passed to `on_failed` and the copy carries
+/// on; only a failure to copy the .uproject itself makes the whole copy an error (a human-readable
+/// message), since the result would not be a project.
+pub fn copy_template(
+    template_dir: &Path,
+    new_project_dir: &Path,
+    project_name: &str,
+    exclude_names: &[&str],
+    is_cancelled: &dyn Fn() -> bool,
+    on_copied: &mut dyn FnMut(usize),
+    on_failed: &mut dyn FnMut(&CopyFailure),
+) -> Result<CopyOutcome, String> {
+    let mut copied = 0usize;
+    let mut skipped = 0usize;
+    let mut failed = 0usize;
+    let mut uproject_error: Option<String> = None;
+
+    for entry in walkdir::WalkDir::new(template_dir).into_iter().filter_map(|e| e.ok()) {
+        if is_cancelled() {
+            return Ok(CopyOutcome::Cancelled { copied });
+        }
+        let src_path = entry.path();
+        let Ok(rel) = src_path.strip_prefix(template_dir) else { continue };
+
+        if rel.as_os_str().is_empty() {
+            continue;
+        }
+
+        if should_exclude(rel, exclude_names) {
+            skipped += 1;
+            continue;
+        }
+
+        let dst_path = new_project_dir.join(rel);
+
+        if entry.file_type().is_dir() {
+            if let Err(e) = fs::create_dir_all(&dst_path) {
+                failed += 1;
+                on_failed(&CopyFailure { path: rel.to_path_buf(), error: format!("Failed to create dir {}: {}", dst_path.to_string_lossy(), e) });
+            }
+        } else if entry.file_type().is_file() {
+            let is_uproject = src_path.extension().and_then(|s| s.to_str()) == Some("uproject");
+            let final_dst = if is_uproject {
+                new_project_dir.join(format!("{}.uproject", project_name))
+            } else {
+                dst_path
+            };
+
+            let result = match final_dst.parent() {
+                Some(parent) => fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create parent dir {}: {}", parent.to_string_lossy(), e)),
+                None => Ok(()),
+            }
+            .and_then(|_| {
+                fs::copy(src_path, &final_dst).map_err(|e| {
+                    format!("Failed to copy {} -> {}: {}", src_path.to_string_lossy(), final_dst.to_string_lossy(), e)
+                })
+            });
+
+            match result {
+                Ok(_) => {
+                    copied += 1;
+                    on_copied(copied);
+                }
+                Err(error) => {
+                    failed += 1;
+                    if is_uproject {
+                        uproject_error = Some(error.clone());
+                    }
+                    on_failed(&CopyFailure { path: rel.to_path_buf(), error });
+                }
+            }
+        } else if entry.file_type().is_symlink() {
+            skipped += 1;
+        }
+    }
+
+    if let Some(error) = uproject_error {
+        return Err(format!("{} ({} file(s) failed in total)", error, failed));
+    }
+    Ok(CopyOutcome::Completed { copied, skipped, failed })
+}
+
+/// Set the `DisplayName`/`FriendlyName` fields a .uproject already has to `project_name`.
+///
+/// Returns the updated pretty-printed JSON, or None when the text is not a .uproject object or
+/// nothing changed.
+pub fn set_display_names(uproject_text: &str, project_name: &str) -> Option<String> {
+    let mut json: serde_json::Value = serde_json::from_str(uproject_text).ok()?;
+    let obj = json.as_object_mut()?;
+    if !obj.contains_key("FileVersion") && !obj.contains_key("EngineAssociation") {
+        return None;
+    }
+    let mut changed = false;
+    for key in ["DisplayName", "FriendlyName"] {
+        if let Some(value) = obj.get_mut(key) {
+            if value.as_str() != Some(project_name) {
+                *value = serde_json::Value::String(project_name.to_string());
+                changed = true;
+            }
+        }
+    }
+    if !changed {
+        return None;
+    }
+    serde_json::to_string_pretty(&json).ok()
+}
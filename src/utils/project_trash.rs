@@ -0,0 +1,82 @@
+//! Safety checks and archiving for deleting Unreal projects.
+//!
+//! Only folders with a .uproject directly inside, strictly under the projects directory, are ever
+//! deleted or archived. Archived projects are moved to `<projects dir>/.trash/<name>-<unix seconds>`; the project listing only looks one
+//! level deep, so they drop out of it.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Folder under the projects directory that archived projects are moved into.
+pub const TRASH_DIR_NAME: &str = ".trash";
+
+/// True when `dir` has a .uproject file directly inside it.
+pub fn contains_uproject(dir: &Path) -> bool {
+    fs::read_dir(dir).is_ok_and(|entries| {
+        entries.flatten().any(|e| {
+            let p = e.path();
+            p.is_file() && p.extension().and_then(|x| x.to_str()).is_some_and(|x| x.eq_ignore_ascii_case("uproject"))
+        })
+    })
+}
+
+/// Why a folder may not be deleted.
+#[derive(Debug, PartialEq, Eq)]
+pub enum DeleteRefusal {
+    /// The folder is an allowed root itself, or one of its ancestors.
+    ContainsRoot(PathBuf),
+    /// The folder is not inside any allowed root.
+    OutsideRoots,
+}
+
+/// Canonical path of `dir` when it may be deleted: strictly inside one of `allowed_roots`, and
+/// neither a root itself nor an ancestor of one (e.g. when the projects dir is set to a subfolder of
+/// the folder being deleted). Roots that do not exist are ignored.
+pub fn check_delete_target(dir: &Path, allowed_roots: &[PathBuf]) -> Result<PathBuf, DeleteRefusal> {
+    let dir = fs::canonicalize(dir).map_err(|_| DeleteRefusal::OutsideRoots)?;
+    let roots: Vec<PathBuf> = allowed_roots.iter().filter_map(|r| fs::canonicalize(r).ok()).collect();
+    if let Some(root) = roots.iter().find(|root| root.starts_with(&dir)) {
+        return Err(DeleteRefusal::ContainsRoot(root.clone()));
+    }
+    if roots.iter().any(|root| dir.starts_with(root)) {
+        Ok(dir)
+    } else {
+        Err(DeleteRefusal::OutsideRoots)
+    }
+}
+
+/// Free destination for archiving project folder `name` at `unix_secs`: `<name>-<secs>`, then `-2`, `-3`, ...
+pub fn archive_destination(trash_root: &Path, name: &str, unix_secs: u64) -> PathBuf {
+    let base = format!("{}-{}", name, unix_secs);
+    let first = trash_root.join(&base);
+    if !first.exists() {
+        return first;
+    }
+    (2..)
+        .map(|n| trash_root.join(format!("{}-{}", base, n)))
+        .find(|p| !p.exists())
+        .unwrap_or(first)
+}
+
+/// Move `from` to `to` (whose parent is created). Falls back to copy-then-delete when a rename is not
+/// possible, e.g. when the trash is on another volume.
+pub fn move_dir(from: &Path, to: &Path) -> io::Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    for entry in walkdir::WalkDir::new(from).follow_links(false) {
+        let entry = entry.map_err(io::Error::other)?;
+        let rel = entry.path().strip_prefix(from).map_err(io::Error::other)?;
+        let target = to.join(rel);
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&target)?;
+        } else if entry.file_type().is_file() {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+    fs::remove_dir_all(from)
+}
@@ -0,0 +1,45 @@
+//! Shared token-bucket bandwidth limiter for downloads.
+//!
+//! One limiter is shared by every chunk task of a download, so the configured rate caps the
+//! aggregate throughput rather than each connection. Callers report bytes as they arrive and
+//! sleep for the returned duration; the bucket holds at most one second worth of tokens.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+pub struct RateLimiter {
+    bytes_per_sec: u64,
+    state: Mutex<(f64, Instant)>,
+}
+
+impl RateLimiter {
+    pub fn new(bytes_per_sec: u64) -> Self {
+        let rate = bytes_per_sec.max(1);
+        RateLimiter { bytes_per_sec: rate, state: Mutex::new((rate as f64, Instant::now())) }
+    }
+
+    pub fn bytes_per_sec(&self) -> u64 {
+        self.bytes_per_sec
+    }
+
+    /// Take `n` bytes worth of tokens and return how long the caller must wait before continuing.
+    pub fn reserve(&self, n: u64) -> Duration {
+        self.reserve_at(n, Instant::now())
+    }
+
+    /// Same as reserve(), with an explicit clock for deterministic callers.
+    /// The balance may go negative; the debt is what later callers wait out.
+    pub fn reserve_at(&self, n: u64, now: Instant) -> Duration {
+        let rate = self.bytes_per_sec as f64;
+        let mut st = self.state.lock().unwrap_or_else(|p| p.into_inner());
+        let elapsed = now.saturating_duration_since(st.1).as_secs_f64();
+        st.0 = (st.0 + elapsed * rate).min(rate);
+        st.1 = st.1.max(now);
+        st.0 -= n as f64;
+        if st.0 >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-st.0 / rate)
+        }
+    }
+}
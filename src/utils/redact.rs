@@ -0,0 +1,53 @@
+//! Masking secrets (tokens, auth codes) before a message reaches a log line.
+
+/// Replacement for a secret value.
+pub const MASK: &str = "***";
+
+/// Keys whose values are masked, and whether JSON `"key":"value"` pairs count too. A bare `code` is only
+/// masked in `code=...` (OAuth redirect URLs): as a JSON key it is the ErrorResponse error code.
+const KEYS: [(&str, bool); 6] = [
+    ("access_token", true),
+    ("refresh_token", true),
+    ("authorizationCode", true),
+    ("auth_code", true),
+    ("code", false),
+    ("token", true),
+];
+
+/// Mask secret values in a log message.
+///
+/// Handles JSON-style `"key":"value"` / `"key": "value"` and query-style `key=value` occurrences
+/// of well-known secret keys; the value is replaced with `***`.
+pub fn redact_secrets(msg: &str) -> String {
+    let mut out = msg.to_string();
+    for (key, in_json) in KEYS {
+        let mut patterns = vec![format!("{}=", key)];
+        if in_json {
+            patterns.push(format!("\"{}\":\"", key));
+            patterns.push(format!("\"{}\": \"", key));
+        }
+        for pattern in patterns {
+            let terminators: &[char] = if pattern.ends_with('"') { &['"'] } else { &['&', ' ', '"', ','] };
+            let mut search_from = 0;
+            while let Some(pos) = out[search_from..].find(&pattern) {
+                let key_start = search_from + pos;
+                let start = key_start + pattern.len();
+                // Only match whole keys (avoid e.g. "zipcode=")
+                let prev = out[..key_start].chars().last();
+                if !pattern.starts_with('"') && prev.is_some_and(|c| c.is_ascii_alphanumeric() || c == '_') {
+                    search_from = start;
+                    continue;
+                }
+                let end = out[start..].find(terminators).map_or(out.len(), |i| start + i);
+                // Continue after the value as it now reads; every index stays on a char boundary
+                search_from = if end > start && &out[start..end] != MASK {
+                    out.replace_range(start..end, MASK);
+                    start + MASK.len()
+                } else {
+                    end
+                };
+            }
+        }
+    }
+    out
+}
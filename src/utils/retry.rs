@@ -0,0 +1,73 @@
+//! Retry policy for chunk downloads: which failures are worth retrying and how long to wait.
+//!
+//! The delay grows exponentially per attempt up to a cap, and half of it is randomized ("equal
+//! jitter") so parallel chunk tasks that failed together do not hammer the CDN in lockstep.
+//!
+//! A retry covers the whole attempt: the request and streaming its body, so a connection dropped
+//! mid-body is retried like a failed request.
+
+use std::future::Future;
+use std::time::Duration;
+
+/// Retries per chunk when EAM_CHUNK_RETRIES is unset.
+pub const DEFAULT_CHUNK_RETRIES: u32 = 3;
+/// Upper bound accepted for EAM_CHUNK_RETRIES.
+pub const MAX_CHUNK_RETRIES: u32 = 10;
+/// Delay before the first retry; doubled for each further attempt.
+pub const BASE_DELAY_MS: u64 = 500;
+/// Cap on a single retry delay.
+pub const MAX_DELAY_MS: u64 = 15_000;
+
+/// Parse an EAM_CHUNK_RETRIES value: a non-negative integer clamped to MAX_CHUNK_RETRIES,
+/// or DEFAULT_CHUNK_RETRIES when missing or malformed.
+pub fn parse_retries(raw: Option<&str>) -> u32 {
+    raw.and_then(|s| s.trim().parse::<u32>().ok())
+        .map_or(DEFAULT_CHUNK_RETRIES, |n| n.min(MAX_CHUNK_RETRIES))
+}
+
+/// Transient HTTP statuses: 408 (request timeout), 429 (rate limited) and every 5xx.
+/// Anything else, 404 included, will not get better by asking again.
+pub fn is_retryable_status(status: u16) -> bool {
+    status == 408 || status == 429 || (500..=599).contains(&status)
+}
+
+/// Delay before retry number `attempt` (0-based). `jitter` in [0, 1) picks a point in the upper half
+/// of the exponential delay, so the result is within [delay / 2, delay].
+pub fn backoff_delay(attempt: u32, jitter: f64) -> Duration {
+    let exp = BASE_DELAY_MS.saturating_mul(1u64 << attempt.min(20)).min(MAX_DELAY_MS);
+    let half = exp / 2;
+    Duration::from_millis(half + (half as f64 * jitter.clamp(0.0, 1.0)) as u64)
+}
+
+/// How one attempt failed.
+#[derive(Debug)]
+pub enum AttemptError {
+    /// Worth another try after a backoff: the request failed, a transient status, or the body was cut off.
+    Transient(String),
+    /// Retrying will not help (e.g. 404, a disk error, or the job was cancelled).
+    Fatal(anyhow::Error),
+}
+
+/// Run `attempt` until it succeeds or fails for good. Transient failures are retried up to `max_retries`
+/// times; `on_retry(retry_number, reason)` (1-based) reports the retry and returns how long to wait
+/// before it. After the last retry the final Transient error is returned.
+pub async fn with_retries<T, Fut>(
+    max_retries: u32,
+    mut attempt: impl FnMut() -> Fut,
+    mut on_retry: impl FnMut(u32, &str) -> Duration,
+) -> Result<T, AttemptError>
+where
+    Fut: Future<Output = Result<T, AttemptError>>,
+{
+    let mut retries = 0;
+    loop {
+        match attempt().await {
+            Err(AttemptError::Transient(reason)) if retries < max_retries => {
+                retries += 1;
+                let delay = on_retry(retries, &reason);
+                tokio::time::sleep(delay).await;
+            }
+            other => return other,
+        }
+    }
+}
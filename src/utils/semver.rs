@@ -0,0 +1,63 @@
+//! Numeric engine version ordering.
+//!
+//! Engine versions ("5.4", "5.4.2") compared as strings put "5.10" before "5.9"; these helpers
+//! compare them as (major, minor, patch) numbers instead.
+
+use std::cmp::Ordering;
+
+/// Parse "5", "5.4" or "5.4.2" into (major, minor, patch); missing parts are 0.
+/// Returns None for anything else (e.g., "unknown", "5.x", "5.4.2.1").
+pub fn parse_semver(v: &str) -> Option<(u32, u32, u32)> {
+    let mut parts = [0u32; 3];
+    let mut count = 0;
+    for part in v.trim().split('.') {
+        if count == parts.len() {
+            return None;
+        }
+        parts[count] = part.parse().ok()?;
+        count += 1;
+    }
+    Some((parts[0], parts[1], parts[2]))
+}
+
+/// Order versions numerically. Parsable versions sort before unparsable ones ("unknown"),
+/// which fall back to string order among themselves.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    match (parse_semver(a), parse_semver(b)) {
+        (Some(x), Some(y)) => x.cmp(&y),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => a.cmp(b),
+    }
+}
+
+/// Whether installed version `installed` satisfies `requested`.
+///
+/// A partial request ("5.4") accepts any patch of 5.4 and a full one ("5.4.2") only that version.
+/// With `exact` the versions must be equal, so "5.4" then only matches 5.4 / 5.4.0. Unparsable
+/// versions fall back to string equality, or a prefix match when not exact.
+pub fn satisfies(installed: &str, requested: &str, exact: bool) -> bool {
+    let requested = requested.trim();
+    let given = requested.split('.').count();
+    match (parse_semver(installed), parse_semver(requested)) {
+        (Some(i), Some(r)) if exact || given >= 3 => i == r,
+        (Some(i), Some(r)) => i.0 == r.0 && (given < 2 || i.1 == r.1),
+        _ if exact => installed.trim() == requested,
+        _ => installed.starts_with(requested),
+    }
+}
+
+/// Index of the newest version in `installed` that satisfies `requested` (so "5.4" picks 5.4.3
+/// over 5.4.0); the first one wins ties.
+pub fn pick_version<'a>(installed: impl IntoIterator<Item = &'a str>, requested: &str, exact: bool) -> Option<usize> {
+    let mut best: Option<(usize, &str)> = None;
+    for (i, v) in installed.into_iter().enumerate() {
+        if !satisfies(v, requested, exact) {
+            continue;
+        }
+        if best.map_or(true, |(_, b)| compare_versions(v, b) == Ordering::Greater) {
+            best = Some((i, v));
+        }
+    }
+    best.map(|(i, _)| i)
+}
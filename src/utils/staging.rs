@@ -0,0 +1,63 @@
+//! Seeding and rolling back staged downloads (EAM_STAGING_DOWNLOADS=1).
+//!
+//! A staged download may start from the asset's existing folder, moved into the staging entry so
+//! verified files are reused. If the run then fails or is cancelled, that folder has to go back where
+//! it came from: it is the user's previous, complete download.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Completion marker written into a finished download folder.
+pub const COMPLETE_MARKER: &str = ".download_complete";
+
+/// Error message of a download run that stopped because its job was cancelled.
+pub const CANCELLED: &str = "cancelled";
+
+/// What seeding took from the final folder, so a failed run can put it back.
+pub struct Seeded {
+    marker: Option<Vec<u8>>,
+}
+
+/// Move an existing `final_dir` to `staged`. None when there is nothing to move, a staged folder is
+/// already there, or the rename fails (e.g. another filesystem); `final_dir` then stays in place.
+pub fn seed(final_dir: &Path, staged: &Path) -> Option<Seeded> {
+    if !final_dir.exists() || staged.exists() {
+        return None;
+    }
+    if let Some(parent) = staged.parent() {
+        fs::create_dir_all(parent).ok()?;
+    }
+    let marker = fs::read(final_dir.join(COMPLETE_MARKER)).ok();
+    fs::rename(final_dir, staged).ok()?;
+    Some(Seeded { marker })
+}
+
+/// Undo a failed staged run. A seeded folder is moved back to `final_dir` with the completion marker
+/// the run cleared (files it replaced had already passed their hash check); a folder staged from
+/// nothing is deleted. Chunk folders next to `staged` are left to the caller's temp/ policy.
+pub fn roll_back(staged: &Path, final_dir: &Path, seeded: Option<&Seeded>) -> io::Result<()> {
+    let Some(seeded) = seeded else {
+        return match fs::remove_dir_all(staged) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        };
+    };
+    if final_dir.exists() {
+        // Nothing should have recreated it meanwhile; never overwrite whatever is there now
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("{} exists; staged copy left at {}", final_dir.display(), staged.display())));
+    }
+    fs::rename(staged, final_dir)?;
+    if let Some(marker) = &seeded.marker {
+        fs::write(final_dir.join(COMPLETE_MARKER), marker)?;
+    }
+    Ok(())
+}
+
+/// Whether a failed run's temp/ chunks are deleted. A cancelled download is discarded, so its chunks go
+/// too; other failures keep them for a retry unless `keep_temp_on_failure` is off.
+///
+/// Decided from the run's error: by the time it returns, the cancel request itself was already acknowledged.
+pub fn drop_chunks_after_failure(error: &str, keep_temp_on_failure: bool) -> bool {
+    error == CANCELLED || !keep_temp_on_failure
+}
@@ -0,0 +1,71 @@
+//! Tracks which download jobs are using a temp/ chunk folder.
+//!
+//! The chunk folder lives next to the asset folder (downloads/<Asset>/temp, or downloads/temp for an
+//! unversioned asset), so two concurrent jobs can share one. Cleanup after a cancel or failure must
+//! not pull it out from under a job that is still writing chunks there.
+//!
+//! The same registry records the final folder of every running download, so deleting an asset can
+//! refuse while it is being written.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+#[derive(Default)]
+pub struct TempDirRegistry {
+    users: Mutex<HashMap<PathBuf, usize>>,
+}
+
+/// Marks a temp folder as in use until dropped.
+pub struct TempDirLease<'a> {
+    registry: &'a TempDirRegistry,
+    dir: PathBuf,
+}
+
+impl TempDirRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<PathBuf, usize>> {
+        self.users.lock().unwrap_or_else(|p| p.into_inner())
+    }
+
+    /// Register one more user of `dir`. Waits for a removal of the same folder in progress to finish.
+    pub fn acquire(&self, dir: &Path) -> TempDirLease<'_> {
+        *self.lock().entry(dir.to_path_buf()).or_insert(0) += 1;
+        TempDirLease { registry: self, dir: dir.to_path_buf() }
+    }
+
+    pub fn users(&self, dir: &Path) -> usize {
+        self.lock().get(dir).copied().unwrap_or(0)
+    }
+
+    /// True when a job holds a lease on `root` or a folder inside it.
+    pub fn any_in_use_under(&self, root: &Path) -> bool {
+        self.lock().iter().any(|(dir, &n)| n > 0 && dir.starts_with(root))
+    }
+
+    /// Run `remove(dir)` unless a job holds a lease on it; false when skipped.
+    /// The registry stays locked meanwhile, so no job can start using the folder mid-removal.
+    pub fn remove_if_unused(&self, dir: &Path, remove: impl FnOnce(&Path)) -> bool {
+        let map = self.lock();
+        if map.get(dir).is_some_and(|&n| n > 0) {
+            return false;
+        }
+        remove(dir);
+        true
+    }
+}
+
+impl Drop for TempDirLease<'_> {
+    fn drop(&mut self) {
+        let mut map = self.registry.lock();
+        if let Some(n) = map.get_mut(&self.dir) {
+            *n = n.saturating_sub(1);
+            if *n == 0 {
+                map.remove(&self.dir);
+            }
+        }
+    }
+}
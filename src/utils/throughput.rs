@@ -0,0 +1,46 @@
+//! Rolling download throughput for live speed and ETA in download:progress events.
+//!
+//! Samples are cumulative byte counts taken at the existing 300ms progress throttle; the rate is
+//! measured over the last few seconds so short stalls and bursts average out.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+pub struct Throughput {
+    window: Duration,
+    samples: VecDeque<(Instant, u64)>,
+}
+
+impl Throughput {
+    pub fn new(window: Duration) -> Self {
+        Throughput { window, samples: VecDeque::new() }
+    }
+
+    /// Record the cumulative byte count at `now` and return bytes/second over the window.
+    /// Returns 0.0 until there are two samples at different instants.
+    pub fn record(&mut self, now: Instant, total_bytes: u64) -> f64 {
+        self.samples.push_back((now, total_bytes));
+        // Drop samples older than the window, keeping one as the baseline for the rate
+        while self.samples.len() > 2 && now.duration_since(self.samples[1].0) >= self.window {
+            self.samples.pop_front();
+        }
+        let (t0, b0) = self.samples[0];
+        let secs = now.duration_since(t0).as_secs_f64();
+        if secs <= 0.0 {
+            return 0.0;
+        }
+        total_bytes.saturating_sub(b0) as f64 / secs
+    }
+}
+
+/// Seconds left for `remaining` bytes at `bytes_per_sec`; None while the speed is zero (or not
+/// a finite positive number) so callers emit null instead of infinity.
+pub fn eta_seconds(remaining: u64, bytes_per_sec: f64) -> Option<u64> {
+    if remaining == 0 {
+        return Some(0);
+    }
+    if !bytes_per_sec.is_finite() || bytes_per_sec <= 0.0 {
+        return None;
+    }
+    Some((remaining as f64 / bytes_per_sec).ceil() as u64)
+}
@@ -0,0 +1,94 @@
+//! Remembered hash checks for files already on disk (<asset root>/.verify.json).
+//!
+//! Re-downloading an asset the user already has used to re-hash every file. Each successful check
+//! is recorded with the file's size and modification time; as long as neither changed, the file is
+//! trusted without reading it again. Any change (or a different expected hash) forces a full hash.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const VERIFY_CACHE_FILE: &str = ".verify.json";
+
+/// Size and modification time (ns since the epoch) of a file, or None when it cannot be read.
+pub fn file_stamp(path: &Path) -> Option<(u64, u64)> {
+    let meta = fs::metadata(path).ok()?;
+    let mtime = meta.modified().ok()?.duration_since(std::time::UNIX_EPOCH).ok()?;
+    Some((meta.len(), mtime.as_nanos() as u64))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    hash: String,
+    size: u64,
+    mtime: u64,
+}
+
+/// Verified files of one asset folder, keyed by manifest file name.
+pub struct VerifyCache {
+    path: PathBuf,
+    entries: BTreeMap<String, Entry>,
+    dirty: bool,
+}
+
+impl VerifyCache {
+    /// Load the cache stored in `root` (empty when missing or unreadable).
+    pub fn load(root: &Path) -> Self {
+        let path = root.join(VERIFY_CACHE_FILE);
+        let entries = read_entries(&path);
+        VerifyCache { path, entries, dirty: false }
+    }
+
+    /// True when `name` was verified against `expected_hash` and still has the recorded size and mtime.
+    pub fn is_fresh(&self, name: &str, expected_hash: &str, stamp: (u64, u64)) -> bool {
+        self.entries.get(name).is_some_and(|e| {
+            e.hash.eq_ignore_ascii_case(expected_hash.trim()) && (e.size, e.mtime) == stamp
+        })
+    }
+
+    /// Remember that `name` matched `hash` with the given (size, mtime) stamp.
+    pub fn record(&mut self, name: &str, hash: &str, stamp: (u64, u64)) {
+        let entry = Entry { hash: hash.trim().to_ascii_lowercase(), size: stamp.0, mtime: stamp.1 };
+        if self.entries.get(name) != Some(&entry) {
+            self.entries.insert(name.to_string(), entry);
+            self.dirty = true;
+        }
+    }
+
+    /// Drop the entry for `name`, e.g. after its hash check failed.
+    pub fn forget(&mut self, name: &str) {
+        self.dirty |= self.entries.remove(name).is_some();
+    }
+
+    /// Write the cache (tmp + rename) if anything changed since it was loaded.
+    pub fn save(&mut self) -> std::io::Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+        let files: serde_json::Map<String, serde_json::Value> = self.entries.iter()
+            .map(|(k, e)| (k.clone(), serde_json::json!({ "hash": e.hash, "size": e.size, "mtime": e.mtime })))
+            .collect();
+        let data = serde_json::to_vec_pretty(&serde_json::json!({ "files": files }))
+            .map_err(std::io::Error::other)?;
+        let tmp = self.path.with_extension("json.tmp");
+        fs::write(&tmp, data)?;
+        fs::rename(&tmp, &self.path)?;
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+fn read_entries(path: &Path) -> BTreeMap<String, Entry> {
+    fs::read(path)
+        .ok()
+        .and_then(|b| serde_json::from_slice::<serde_json::Value>(&b).ok())
+        .and_then(|v| v.get("files").and_then(|m| m.as_object()).map(|m| {
+            m.iter().filter_map(|(k, v)| {
+                let hash = v.get("hash")?.as_str()?.to_string();
+                let size = v.get("size")?.as_u64()?;
+                let mtime = v.get("mtime")?.as_u64()?;
+                Some((k.clone(), Entry { hash, size, mtime }))
+            }).collect()
+        }))
+        .unwrap_or_default()
+}
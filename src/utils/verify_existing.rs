@@ -0,0 +1,113 @@
+//! Concurrent verification of files already on disk (skip-existing pass and /verify of a managed download).
+//!
+//! Each file must have the manifest size; when an expected SHA1/SHA256 digest is known it is also hashed
+//! on Tokio's blocking pool, at most `max_hashes` at a time. Hashes remembered in the `VerifyCache`
+//! for an unchanged file are trusted without reading it again.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use super::file_hash;
+use super::verify_cache::{self, VerifyCache};
+
+#[derive(Clone)]
+pub struct ExistingFileCheck {
+    pub filename: String,
+    pub path: PathBuf,
+    pub expected_hash: String,
+    pub expected_size: u64,
+}
+
+/// Parallel hash checks from EAM_HASH_CONCURRENCY (default 4).
+pub fn hash_concurrency() -> usize {
+    std::env::var("EAM_HASH_CONCURRENCY").ok().and_then(|s| s.parse().ok()).filter(|&n| n > 0).unwrap_or(4)
+}
+
+/// EAM_TRUST_EXISTING=1: files whose size matches the manifest are trusted without hashing.
+pub fn trust_existing() -> bool {
+    std::env::var("EAM_TRUST_EXISTING").map(|v| v == "1" || v.eq_ignore_ascii_case("true")).unwrap_or(false)
+}
+
+/// Expected hash and `file_stamp` of a file that passed its hash check, for the `VerifyCache`.
+pub type VerifiedHash = (String, (u64, u64));
+
+/// Whether one file is up to date, plus what to remember when it passed a hash check.
+pub fn check_file(check: &ExistingFileCheck, trust_existing: bool) -> (bool, Option<VerifiedHash>) {
+    // Stamp taken before hashing, so a write during the check makes the entry stale
+    let stamp = verify_cache::file_stamp(&check.path);
+    let size_ok = stamp.is_some_and(|(size, _)| size == check.expected_size);
+    if check.expected_hash.is_empty() || trust_existing {
+        // No hash available (or user opted to trust existing files): size must match
+        return (size_ok, None);
+    }
+    if !size_ok {
+        return (false, None);
+    }
+    // Unknown hash formats fall back to the size check
+    match file_hash::verify_file_hash(&check.path, &check.expected_hash) {
+        Ok(()) => (true, stamp.map(|st| (check.expected_hash.clone(), st))),
+        Err(file_hash::HashError::UnsupportedFormat(_)) => (true, None),
+        Err(_) => (false, None),
+    }
+}
+
+/// Verify `candidates` concurrently and return the names of those that are up to date, plus how many
+/// were trusted from `cache` without hashing.
+///
+/// Successful hash checks are recorded in `cache` and failed ones forgotten. `on_progress(done, total)`
+/// runs after each finished check; returning false stops the pass (e.g. on cancellation).
+pub async fn verify_files(
+    candidates: Vec<ExistingFileCheck>,
+    cache: &mut VerifyCache,
+    max_hashes: usize,
+    trust_existing: bool,
+    mut on_progress: impl FnMut(usize, usize) -> bool,
+) -> (HashSet<String>, usize) {
+    let mut up_to_date = HashSet::new();
+    let total = candidates.len();
+    let sema = Arc::new(Semaphore::new(max_hashes.max(1)));
+    let mut join = JoinSet::new();
+
+    let mut remembered = 0usize;
+    for check in candidates {
+        if !check.expected_hash.is_empty() && !trust_existing {
+            let fresh = verify_cache::file_stamp(&check.path)
+                .is_some_and(|stamp| stamp.0 == check.expected_size && cache.is_fresh(&check.filename, &check.expected_hash, stamp));
+            if fresh {
+                remembered += 1;
+                up_to_date.insert(check.filename);
+                continue;
+            }
+        }
+        let sema = sema.clone();
+        join.spawn(async move {
+            let _permit = sema.acquire_owned().await.expect("hash sema closed");
+            tokio::task::spawn_blocking(move || {
+                let (ok, hashed) = check_file(&check, trust_existing);
+                (check.filename, ok, hashed)
+            }).await
+        });
+    }
+
+    let mut done = remembered;
+    while let Some(res) = join.join_next().await {
+        done += 1;
+        if let Ok(Ok((filename, ok, hashed))) = res {
+            match hashed {
+                Some((hash, stamp)) => cache.record(&filename, &hash, stamp),
+                None if !ok => cache.forget(&filename),
+                None => {}
+            }
+            if ok { up_to_date.insert(filename); }
+        }
+        if !on_progress(done, total) {
+            join.abort_all();
+            break;
+        }
+    }
+    (up_to_date, remembered)
+}
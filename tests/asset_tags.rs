@@ -0,0 +1,51 @@
+// Locked, atomic saves of the asset tags store (src/utils/asset_tags.rs).
+
+#[path = "../src/utils/asset_tags.rs"]
+mod asset_tags;
+#[allow(dead_code)]
+#[path = "../src/models/mod.rs"]
+mod models;
+
+use std::fs;
+use std::sync::Arc;
+
+#[test]
+fn save_replaces_the_store_without_leaving_a_temp_file() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("asset_tags.json");
+    assert!(asset_tags::load(&path).is_empty());
+
+    let mut store = models::AssetTagsStore::new();
+    store.insert("a1".to_string(), models::AssetTagEntry { tags: vec!["props".to_string()], favorite: true });
+    asset_tags::save(&path, &store).unwrap();
+    let loaded = asset_tags::load(&path);
+    assert_eq!(loaded["a1"].tags, vec!["props"]);
+    assert!(loaded["a1"].favorite);
+    assert_eq!(fs::read_dir(tmp.path()).unwrap().count(), 1, "temp file should be renamed away");
+
+    fs::write(&path, "{ truncated").unwrap();
+    assert!(asset_tags::load(&path).is_empty());
+}
+
+#[test]
+fn concurrent_locked_edits_are_all_kept() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = Arc::new(tmp.path().join("asset_tags.json"));
+    let handles: Vec<_> = (0..8)
+        .map(|t| {
+            let path = path.clone();
+            std::thread::spawn(move || {
+                for i in 0..10 {
+                    let _guard = asset_tags::lock_asset_tags();
+                    let mut store = asset_tags::load(&path);
+                    store.insert(format!("asset-{}-{}", t, i), models::AssetTagEntry { tags: Vec::new(), favorite: true });
+                    asset_tags::save(&path, &store).unwrap();
+                }
+            })
+        })
+        .collect();
+    for h in handles {
+        h.join().unwrap();
+    }
+    assert_eq!(asset_tags::load(&path).len(), 80);
+}
@@ -0,0 +1,93 @@
+// Bounded bind retries and auto-port fallback (src/utils/bind_retry.rs).
+
+#[path = "../src/utils/bind_retry.rs"]
+mod bind_retry;
+
+use bind_retry::{BindSettings, DEFAULT_MAX_RETRIES};
+use std::io;
+use std::time::Duration;
+
+fn settings(max_retries: u32, auto_port: bool) -> BindSettings {
+    BindSettings { max_retries, retry_delay: Duration::from_millis(250), auto_port }
+}
+
+fn in_use() -> io::Error {
+    io::Error::from(io::ErrorKind::AddrInUse)
+}
+
+#[test]
+fn reads_env_with_defaults() {
+    let none = |_: &str| None;
+    let s = BindSettings::from_env(&none, false);
+    assert_eq!(s.max_retries, DEFAULT_MAX_RETRIES);
+    assert_eq!(s.retry_delay, Duration::from_millis(2000));
+    assert!(!s.auto_port);
+    assert!(BindSettings::from_env(&none, true).auto_port);
+
+    let env = |k: &str| match k {
+        "EGS_BIND_MAX_RETRIES" => Some("0".to_string()),
+        "EGS_BIND_RETRY_DELAY_MS" => Some(" 50 ".to_string()),
+        "EGS_AUTO_PORT" => Some("0".to_string()),
+        _ => None,
+    };
+    assert_eq!(BindSettings::from_env(&env, true), BindSettings { max_retries: 0, retry_delay: Duration::from_millis(50), auto_port: false });
+}
+
+#[test]
+fn retries_a_taken_port_then_gives_up() {
+    let mut attempts = 0;
+    let mut slept = Vec::new();
+    let res: Result<((), String), String> = bind_retry::bind_with_retry("127.0.0.1:8080", &settings(3, false), |_| {
+        attempts += 1;
+        Err(in_use())
+    }, |d| slept.push(d));
+    let msg = res.unwrap_err();
+    assert_eq!(attempts, 4);
+    assert_eq!(slept, vec![Duration::from_millis(250); 3]);
+    assert!(msg.contains("127.0.0.1:8080") && msg.contains("after 3 retries") && msg.contains("EGS_AUTO_PORT"), "{}", msg);
+}
+
+#[test]
+fn succeeds_once_the_port_frees_up() {
+    let mut attempts = 0;
+    let (_, addr) = bind_retry::bind_with_retry("127.0.0.1:8080", &settings(5, false), |_| {
+        attempts += 1;
+        if attempts < 3 { Err(in_use()) } else { Ok(()) }
+    }, |_| {}).unwrap();
+    assert_eq!(addr, "127.0.0.1:8080");
+    assert_eq!(attempts, 3);
+}
+
+#[test]
+fn other_errors_are_not_retried() {
+    let mut attempts = 0;
+    let res: Result<((), String), String> = bind_retry::bind_with_retry("127.0.0.1:80", &settings(5, true), |_| {
+        attempts += 1;
+        Err(io::Error::from(io::ErrorKind::PermissionDenied))
+    }, |_| panic!("must not sleep"));
+    assert!(res.is_err());
+    assert_eq!(attempts, 1);
+}
+
+#[test]
+fn auto_port_moves_to_the_next_free_port() {
+    let taken = ["127.0.0.1:8080", "127.0.0.1:8081"];
+    let (_, addr) = bind_retry::bind_with_retry("127.0.0.1:8080", &settings(1, true), |a| {
+        if taken.contains(&a) { Err(in_use()) } else { Ok(()) }
+    }, |_| {}).unwrap();
+    assert_eq!(addr, "127.0.0.1:8082");
+
+    let candidates = bind_retry::auto_port_candidates("0.0.0.0:65534");
+    assert_eq!(candidates, vec!["0.0.0.0:65535", "0.0.0.0:0"]);
+    assert!(bind_retry::auto_port_candidates("localhost:8080").is_empty());
+}
+
+#[test]
+fn client_address_replaces_wildcard_hosts() {
+    assert_eq!(bind_retry::client_reachable_addr("0.0.0.0:9000"), "127.0.0.1:9000");
+    assert_eq!(bind_retry::client_reachable_addr("[::]:9000"), "127.0.0.1:9000");
+    assert_eq!(bind_retry::client_reachable_addr("127.0.0.1:8081"), "127.0.0.1:8081");
+    assert_eq!(bind_retry::client_reachable_addr("192.168.1.20:8080"), "192.168.1.20:8080");
+    assert_eq!(bind_retry::client_reachable_addr("[::1]:8080"), "[::1]:8080");
+    assert_eq!(bind_retry::client_reachable_addr(" localhost:8080 "), "localhost:8080");
+}
@@ -0,0 +1,57 @@
+// Byte-based download progress shared by the callback and progress events (src/utils/byte_progress.rs).
+
+#[path = "../src/utils/byte_progress.rs"]
+mod byte_progress;
+
+use byte_progress::{percent, whole_percent, ByteProgress};
+
+#[test]
+fn mixed_downloaded_and_skipped_files_progress_by_bytes() {
+    // A: 600 bytes up to date; B: three 100-byte chunk parts (one reused from temp/); C: zero chunks; D: 100 bytes
+    let files: [&[u64]; 4] = [&[600], &[100, 100, 100], &[], &[100]];
+    let total: u64 = files.iter().flat_map(|f| f.iter()).sum();
+    let progress = ByteProgress::new(total);
+    assert_eq!(progress.whole_percent(), 0);
+
+    let mut seen = vec![progress.percent()];
+    for parts in files {
+        for &size in parts {
+            progress.add(size);
+            seen.push(progress.percent());
+        }
+        // A file finishing (or having nothing to fetch) never moves progress by itself
+        seen.push(progress.percent());
+    }
+    assert!(seen.windows(2).all(|w| w[0] <= w[1]), "progress went backwards: {:?}", seen);
+    // The big up-to-date file counts for its size, not as one file in four
+    assert_eq!(seen[1], 60.0);
+    assert_eq!(progress.done(), total);
+    assert_eq!(progress.percent(), 100.0);
+    assert_eq!(progress.whole_percent(), 100);
+}
+
+#[test]
+fn percentages_are_capped_and_round_down() {
+    assert_eq!(whole_percent(999, 1000), 99, "100 only once every byte is in");
+    assert_eq!(whole_percent(1000, 1000), 100);
+    assert_eq!(percent(1500, 1000), 100.0);
+    assert_eq!(whole_percent(u64::MAX, u64::MAX), 100);
+    assert_eq!(percent(0, 0), 100.0);
+
+    let progress = ByteProgress::new(10);
+    assert_eq!(progress.add(25), 10, "never reports more than the total");
+}
+
+#[test]
+fn concurrent_adds_are_all_counted() {
+    let progress = std::sync::Arc::new(ByteProgress::new(8 * 1000));
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let p = progress.clone();
+            std::thread::spawn(move || (0..1000).for_each(|_| { p.add(1); }))
+        })
+        .collect();
+    handles.into_iter().for_each(|h| h.join().unwrap());
+    assert_eq!(progress.done(), 8000);
+    assert_eq!(progress.whole_percent(), 100);
+}
@@ -0,0 +1,37 @@
+// Category extraction and filtering (src/utils/categories.rs) over mixed library shapes.
+
+#[path = "../src/utils/categories.rs"]
+mod categories;
+
+use serde_json::json;
+
+fn library() -> serde_json::Value {
+    json!({
+        "results": [
+            { "title": "A", "categories": [ { "id": "c1", "name": "Environments" }, "Props" ] },
+            { "title": "B", "categories": [ { "path": "props" } ] },
+            { "title": "C", "category": "Characters" },
+            { "title": "D" },
+            { "title": "E", "categories": "not-an-array" }
+        ]
+    })
+}
+
+#[test]
+fn counts_categories_and_tolerates_missing_fields() {
+    let counts = categories::category_counts(&library());
+    assert_eq!(counts.get("Environments"), Some(&1));
+    // "Props" and "props" are one category, spelled as first seen
+    assert_eq!(counts.get("Props"), Some(&2));
+    assert_eq!(counts.get("Characters"), Some(&1));
+    assert_eq!(counts.len(), 3);
+    assert!(categories::category_counts(&json!({})).is_empty());
+}
+
+#[test]
+fn retain_category_filters_case_insensitively() {
+    let mut v = library();
+    categories::retain_category(&mut v, "PROPS");
+    let titles: Vec<&str> = v["results"].as_array().unwrap().iter().map(|a| a["title"].as_str().unwrap()).collect();
+    assert_eq!(titles, vec!["A", "B"]);
+}
@@ -0,0 +1,96 @@
+// Streaming chunk slices during assembly (src/utils/chunk_assembly.rs).
+
+#[path = "../src/utils/chunk_check.rs"]
+#[allow(dead_code)]
+mod chunk_check;
+#[path = "../src/utils/chunk_assembly.rs"]
+mod chunk_assembly;
+
+use chunk_assembly::ChunkLayout;
+use std::io::{self, Cursor, Read, Seek, SeekFrom};
+
+/// Reader that counts every byte handed out, to prove only the requested slice is read.
+struct CountingReader<R> {
+    inner: R,
+    bytes_read: u64,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.bytes_read += n as u64;
+        Ok(n)
+    }
+}
+
+impl<R: Seek> Seek for CountingReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+fn chunk_header(header_size: u32, data_size: u32, stored_as: u8) -> Vec<u8> {
+    let mut h = Vec::new();
+    h.extend_from_slice(&chunk_check::CHUNK_MAGIC.to_le_bytes());
+    h.extend_from_slice(&3u32.to_le_bytes()); // version
+    h.extend_from_slice(&header_size.to_le_bytes());
+    h.extend_from_slice(&data_size.to_le_bytes());
+    h.extend_from_slice(&[0u8; 16]); // guid
+    h.extend_from_slice(&0u64.to_le_bytes()); // rolling hash
+    h.push(stored_as);
+    h.resize(header_size as usize, 0);
+    h
+}
+
+fn pattern(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i % 251) as u8).collect()
+}
+
+#[test]
+fn large_raw_blob_reads_only_the_slice() {
+    let data = pattern(32 * 1024 * 1024);
+    let mut src = CountingReader { inner: Cursor::new(data.clone()), bytes_read: 0 };
+    let layout = chunk_assembly::read_layout(&mut src).unwrap();
+    assert_eq!(layout, ChunkLayout::Raw { len: data.len() as u64 });
+
+    let header_bytes = src.bytes_read;
+    let (start, len) = (20 * 1024 * 1024 + 7, 4096u64);
+    let mut out = Vec::new();
+    chunk_assembly::copy_range(&mut src, start, len, &mut out).unwrap();
+    assert_eq!(out, &data[start as usize..(start + len) as usize]);
+    assert_eq!(src.bytes_read - header_bytes, len, "only the requested slice is read");
+    assert!(src.bytes_read < 8192);
+}
+
+#[test]
+fn stored_chunk_slices_after_the_header() {
+    let payload = pattern(1 << 20);
+    let mut file = chunk_header(66, payload.len() as u32, 0);
+    file.extend_from_slice(&payload);
+    let mut src = Cursor::new(file);
+    let layout = chunk_assembly::read_layout(&mut src).unwrap();
+    assert_eq!(layout, ChunkLayout::Stored { data_start: 66, data_len: payload.len() as u64 });
+
+    let (data_start, _) = layout.data_region().unwrap();
+    let mut out = Vec::new();
+    chunk_assembly::copy_range(&mut src, data_start + 100, 500, &mut out).unwrap();
+    assert_eq!(out, &payload[100..600]);
+}
+
+#[test]
+fn compressed_or_broken_chunks_are_not_streamed() {
+    let mut compressed = chunk_header(66, 10, 0x01);
+    compressed.extend_from_slice(&[0u8; 10]);
+    let layout = chunk_assembly::read_layout(&mut Cursor::new(compressed)).unwrap();
+    assert_eq!(layout, ChunkLayout::Encoded);
+    assert_eq!(layout.data_region(), None);
+
+    // Header claims more data than the file holds
+    let mut short = chunk_header(66, 1000, 0);
+    short.extend_from_slice(&[0u8; 10]);
+    assert!(chunk_assembly::read_layout(&mut Cursor::new(short)).is_err());
+
+    // Copying past the end is an error, not a silently short file
+    let mut out = Vec::new();
+    assert!(chunk_assembly::copy_range(&mut Cursor::new(vec![1u8; 10]), 5, 10, &mut out).is_err());
+}
@@ -0,0 +1,95 @@
+// Corrupt chunk cache recovery (src/utils/chunk_check.rs): an HTML error page cached as a chunk
+// must be discarded so it is downloaded again, while real chunks are reused.
+
+#[path = "../src/utils/chunk_check.rs"]
+mod chunk_check;
+
+use std::fs;
+
+fn real_chunk_bytes() -> Vec<u8> {
+    let mut bytes = chunk_check::CHUNK_MAGIC.to_le_bytes().to_vec();
+    bytes.resize(256, 0xAB);
+    bytes
+}
+
+#[test]
+fn html_error_page_in_chunk_cache_is_discarded() {
+    let tmp = tempfile::tempdir().unwrap();
+    let bad = tmp.path().join("0123456789ABCDEF0123456789ABCDEF.chunk");
+    fs::write(&bad, b"<!DOCTYPE html><html><body>503 Service Unavailable</body></html>").unwrap();
+
+    assert!(!chunk_check::validate_cached_chunk(&bad));
+    assert!(!bad.exists(), "implausible chunk must be removed so it gets re-downloaded");
+}
+
+#[test]
+fn valid_and_truncated_chunks() {
+    let tmp = tempfile::tempdir().unwrap();
+    let good = tmp.path().join("good.chunk");
+    fs::write(&good, real_chunk_bytes()).unwrap();
+    assert!(chunk_check::validate_cached_chunk(&good));
+    assert!(good.exists());
+
+    // Right magic but shorter than a header
+    let short = tmp.path().join("short.chunk");
+    fs::write(&short, &real_chunk_bytes()[..8]).unwrap();
+    assert!(!chunk_check::validate_cached_chunk(&short));
+
+    assert!(!chunk_check::validate_cached_chunk(&tmp.path().join("missing.chunk")));
+}
+
+#[test]
+fn data_without_chunk_magic_is_rejected() {
+    assert!(!chunk_check::is_plausible_chunk(b"\x00\x01binary-payload", 4096));
+    assert!(!chunk_check::is_plausible_chunk(b"  \n<html>", 4096));
+    assert!(!chunk_check::is_plausible_chunk(b"", 0));
+    assert!(chunk_check::is_plausible_chunk(&real_chunk_bytes(), 256));
+}
+
+#[test]
+fn ledger_survives_restart_and_rejects_short_chunks() {
+    let tmp = tempfile::tempdir().unwrap();
+    {
+        let mut ledger = chunk_check::ChunkLedger::load(tmp.path());
+        ledger.record("AAA", 1024).unwrap();
+        ledger.record("BBB", 2048).unwrap();
+        ledger.save().unwrap();
+    }
+    // "Restart": a fresh load sees what was recorded
+    let mut ledger = chunk_check::ChunkLedger::load(tmp.path());
+    assert!(ledger.is_verified("AAA", 1024));
+    // A truncated file on disk no longer matches the recorded size
+    assert!(!ledger.is_verified("BBB", 1000));
+    // Never recorded (e.g. killed mid-write)
+    assert!(!ledger.contains("CCC"));
+    ledger.forget("BBB");
+    assert!(!ledger.contains("BBB"));
+
+    // Concurrent writers merge rather than clobber each other
+    let mut other = chunk_check::ChunkLedger::load(tmp.path());
+    other.record("DDD", 1).unwrap();
+    other.save().unwrap();
+    ledger.record("EEE", 2).unwrap();
+    ledger.save().unwrap();
+    let reloaded = chunk_check::ChunkLedger::load(tmp.path());
+    assert!(reloaded.is_verified("DDD", 1) && reloaded.is_verified("EEE", 2));
+}
+
+#[test]
+fn ledger_batches_writes() {
+    let tmp = tempfile::tempdir().unwrap();
+    let manifest = tmp.path().join("manifest.json");
+    let mut ledger = chunk_check::ChunkLedger::load(tmp.path());
+    ledger.record("AAA", 1).unwrap();
+    assert!(!manifest.exists(), "a single chunk must not rewrite the ledger");
+
+    for i in 1..chunk_check::LEDGER_SAVE_EVERY {
+        ledger.record(&format!("G{}", i), 1).unwrap();
+    }
+    assert!(chunk_check::ChunkLedger::load(tmp.path()).is_verified("AAA", 1));
+
+    // Entries still pending are saved when the ledger is dropped
+    ledger.record("ZZZ", 3).unwrap();
+    drop(ledger);
+    assert!(chunk_check::ChunkLedger::load(tmp.path()).is_verified("ZZZ", 3));
+}
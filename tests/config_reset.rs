@@ -0,0 +1,74 @@
+// POST /config/reset clearing the paths config back to the built-in defaults (src/utils/config_reset.rs).
+
+#[path = "../src/utils/config_reset.rs"]
+mod config_reset;
+#[allow(dead_code)]
+#[path = "../src/utils/default_dirs.rs"]
+mod default_dirs;
+#[allow(dead_code)]
+#[path = "../src/models/mod.rs"]
+mod models;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn no_env(_: &str) -> Option<String> {
+    None
+}
+
+/// A directory the way utils resolves it: the config entry, else `builtin`.
+fn resolved(config_path: &Path, field: &str, builtin: &str) -> PathBuf {
+    default_dirs::configured_dir(config_path, field, PathBuf::from(builtin), "Linux", &no_env)
+}
+
+fn write_config(path: &Path) {
+    let cfg: models::PathsConfig = serde_json::from_value(serde_json::json!({
+        "projects_dir": "/custom/projects",
+        "engines_dir": "/custom/engines",
+        "cache_dir": "/custom/cache",
+        "downloads_dir": "/custom/downloads",
+        "file_concurrency": 8,
+    }))
+    .unwrap();
+    fs::write(path, serde_json::to_string_pretty(&cfg).unwrap()).unwrap();
+}
+
+#[test]
+fn full_reset_deletes_the_config_and_restores_builtin_dirs() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("config.json");
+    write_config(&path);
+    assert_eq!(resolved(&path, "downloads_dir", "/builtin/downloads"), PathBuf::from("/custom/downloads"));
+
+    config_reset::reset_file(&path).unwrap();
+    assert!(!path.exists());
+    for (field, builtin) in [("projects_dir", "/builtin/projects"), ("engines_dir", "/builtin/engines"), ("cache_dir", "/builtin/cache"), ("downloads_dir", "/builtin/downloads")] {
+        assert_eq!(resolved(&path, field, builtin), PathBuf::from(builtin), "{}", field);
+    }
+    // Resetting again is not an error
+    config_reset::reset_file(&path).unwrap();
+}
+
+#[test]
+fn clearing_fields_keeps_the_rest() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("config.json");
+    write_config(&path);
+
+    let mut cfg: models::PathsConfig = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+    config_reset::clear_fields(&mut cfg, &["downloads_dir".to_string(), " file_concurrency ".to_string()]).unwrap();
+    fs::write(&path, serde_json::to_string_pretty(&cfg).unwrap()).unwrap();
+
+    assert_eq!(resolved(&path, "downloads_dir", "/builtin/downloads"), PathBuf::from("/builtin/downloads"));
+    assert_eq!(resolved(&path, "cache_dir", "/builtin/cache"), PathBuf::from("/custom/cache"));
+    assert_eq!(cfg.projects_dir.as_deref(), Some("/custom/projects"));
+    assert!(cfg.file_concurrency.is_none());
+}
+
+#[test]
+fn unknown_field_changes_nothing() {
+    let mut cfg = models::PathsConfig { downloads_dir: Some("/custom/downloads".to_string()), ..Default::default() };
+    let e = config_reset::clear_fields(&mut cfg, &["downloads_dir".to_string(), "theme".to_string()]).unwrap_err();
+    assert!(e.contains("theme"), "{}", e);
+    assert_eq!(cfg.downloads_dir.as_deref(), Some("/custom/downloads"));
+}
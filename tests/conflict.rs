@@ -0,0 +1,106 @@
+// Import conflict policies (src/utils/conflict.rs) applied through the parallel copy.
+
+#[path = "../src/utils/conflict.rs"]
+mod conflict;
+#[path = "../src/utils/parallel_copy.rs"]
+mod parallel_copy;
+
+use conflict::{ConflictPolicy, CopyCounts};
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+fn set_mtime(path: &Path, t: SystemTime) {
+    fs::File::options().write(true).open(path).unwrap().set_modified(t).unwrap();
+}
+
+/// src has a.uasset (new), b.uasset and c.uasset; dst already has b.uasset (older than src) and
+/// c.uasset (newer than src).
+fn tree(root: &Path) -> (std::path::PathBuf, std::path::PathBuf) {
+    let src = root.join("src");
+    let dst = root.join("dst");
+    fs::create_dir_all(src.join("Maps")).unwrap();
+    fs::create_dir_all(dst.join("Maps")).unwrap();
+    fs::write(src.join("a.uasset"), "src-a").unwrap();
+    fs::write(src.join("Maps/b.uasset"), "src-b").unwrap();
+    fs::write(src.join("c.uasset"), "src-c").unwrap();
+    fs::write(dst.join("Maps/b.uasset"), "dst-b").unwrap();
+    fs::write(dst.join("c.uasset"), "dst-c").unwrap();
+    let now = SystemTime::now();
+    let hour = Duration::from_secs(3600);
+    set_mtime(&src.join("Maps/b.uasset"), now);
+    set_mtime(&dst.join("Maps/b.uasset"), now - hour);
+    set_mtime(&src.join("c.uasset"), now - hour);
+    set_mtime(&dst.join("c.uasset"), now);
+    (src, dst)
+}
+
+fn run(policy: ConflictPolicy, workers: usize) -> (tempfile::TempDir, std::path::PathBuf, CopyCounts) {
+    let tmp = tempfile::tempdir().unwrap();
+    let (src, dst) = tree(tmp.path());
+    let planned = parallel_copy::plan_copy(&src, &dst, policy).unwrap();
+    let counts = parallel_copy::copy_tree_parallel(&src, &dst, policy, workers, &|_, _| {}, &|| false).unwrap();
+    assert_eq!(planned, counts, "dry-run plan must match the copy for {:?}", policy);
+    (tmp, dst, counts)
+}
+
+fn read(p: &Path) -> String {
+    fs::read_to_string(p).unwrap()
+}
+
+#[test]
+fn skip_keeps_existing_files() {
+    let (_tmp, dst, c) = run(ConflictPolicy::Skip, 1);
+    assert_eq!(c, CopyCounts { copied: 1, skipped: 2, overwritten: 0, renamed: 0 });
+    assert_eq!(read(&dst.join("a.uasset")), "src-a");
+    assert_eq!(read(&dst.join("Maps/b.uasset")), "dst-b");
+    assert_eq!(read(&dst.join("c.uasset")), "dst-c");
+}
+
+#[test]
+fn overwrite_replaces_existing_files() {
+    let (_tmp, dst, c) = run(ConflictPolicy::Overwrite, 4);
+    assert_eq!(c, CopyCounts { copied: 3, skipped: 0, overwritten: 2, renamed: 0 });
+    assert_eq!(read(&dst.join("Maps/b.uasset")), "src-b");
+    assert_eq!(read(&dst.join("c.uasset")), "src-c");
+}
+
+#[test]
+fn rename_new_writes_next_to_existing_files() {
+    let (_tmp, dst, c) = run(ConflictPolicy::RenameNew, 4);
+    assert_eq!(c, CopyCounts { copied: 3, skipped: 0, overwritten: 0, renamed: 2 });
+    assert_eq!(read(&dst.join("Maps/b.uasset")), "dst-b");
+    assert_eq!(read(&dst.join("Maps/b_1.uasset")), "src-b");
+    assert_eq!(read(&dst.join("c.uasset")), "dst-c");
+    assert_eq!(read(&dst.join("c_1.uasset")), "src-c");
+}
+
+#[test]
+fn rename_new_picks_the_next_free_suffix() {
+    let tmp = tempfile::tempdir().unwrap();
+    let target = tmp.path().join("c.uasset");
+    fs::write(&target, "").unwrap();
+    fs::write(tmp.path().join("c_1.uasset"), "").unwrap();
+    assert_eq!(conflict::free_name(&target), tmp.path().join("c_2.uasset"));
+    assert_eq!(conflict::free_name(&tmp.path().join("README")), tmp.path().join("README_1"));
+}
+
+#[test]
+fn newer_overwrites_only_older_targets() {
+    let (_tmp, dst, c) = run(ConflictPolicy::Newer, 1);
+    assert_eq!(c, CopyCounts { copied: 2, skipped: 1, overwritten: 1, renamed: 0 });
+    assert_eq!(read(&dst.join("Maps/b.uasset")), "src-b");
+    assert_eq!(read(&dst.join("c.uasset")), "dst-c");
+}
+
+#[test]
+fn policy_from_request() {
+    assert_eq!(ConflictPolicy::from_request(None, false), Ok(ConflictPolicy::Skip));
+    assert_eq!(ConflictPolicy::from_request(None, true), Ok(ConflictPolicy::Overwrite));
+    assert_eq!(ConflictPolicy::from_request(Some(""), true), Ok(ConflictPolicy::Overwrite));
+    assert_eq!(ConflictPolicy::from_request(Some(" Newer "), true), Ok(ConflictPolicy::Newer));
+    assert_eq!(ConflictPolicy::from_request(Some("rename_new"), false), Ok(ConflictPolicy::RenameNew));
+    assert_eq!(ConflictPolicy::from_request(Some("skip"), true), Ok(ConflictPolicy::Skip));
+    assert!(ConflictPolicy::from_request(Some("merge"), false).is_err());
+    assert_eq!(ConflictPolicy::RenameNew.as_str(), "rename_new");
+}
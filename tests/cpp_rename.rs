@@ -0,0 +1,124 @@
+// Renaming a cpp template's modules to the new project name (src/utils/cpp_rename.rs).
+
+#[path = "../src/utils/cpp_rename.rs"]
+mod cpp_rename;
+
+use std::fs;
+use std::path::Path;
+
+const UPROJECT: &str = r#"{
+	"FileVersion": 3,
+	"EngineAssociation": "5.4",
+	"Modules": [
+		{ "Name": "TP_Shooter", "Type": "Runtime", "LoadingPhase": "Default" }
+	]
+}"#;
+
+/// A minimal cpp template already copied to `root` under the new project's .uproject name.
+fn fake_cpp_project(root: &Path) {
+    for (rel, body) in [
+        ("MyShooter.uproject", UPROJECT),
+        (
+            "Source/TP_Shooter.Target.cs",
+            "public class TP_ShooterTarget : TargetRules\n{\n\tpublic TP_ShooterTarget(TargetInfo Target) : base(Target)\n\t{\n\t\tExtraModuleNames.Add(\"TP_Shooter\");\n\t}\n}\n",
+        ),
+        (
+            "Source/TP_ShooterEditor.Target.cs",
+            "public class TP_ShooterEditorTarget : TargetRules\n{\n\tpublic TP_ShooterEditorTarget(TargetInfo Target) : base(Target)\n\t{\n\t\tExtraModuleNames.Add(\"TP_Shooter\");\n\t}\n}\n",
+        ),
+        (
+            "Source/TP_Shooter/TP_Shooter.Build.cs",
+            "public class TP_Shooter : ModuleRules\n{\n\tpublic TP_Shooter(ReadOnlyTargetRules Target) : base(Target) { }\n}\n",
+        ),
+        ("Source/TP_Shooter/TP_Shooter.h", "#pragma once\n#include \"CoreMinimal.h\"\n"),
+        (
+            "Source/TP_Shooter/TP_Shooter.cpp",
+            "#include \"TP_Shooter.h\"\n#include \"Modules/ModuleManager.h\"\nIMPLEMENT_PRIMARY_GAME_MODULE( FDefaultGameModuleImpl, TP_Shooter, \"TP_Shooter\" );\n",
+        ),
+        (
+            "Source/TP_Shooter/TP_ShooterCharacter.h",
+            "#include \"TP_Shooter.h\"\nUCLASS()\nclass TP_SHOOTER_API ATP_ShooterCharacter : public ACharacter {};\n",
+        ),
+        (
+            "Config/DefaultEngine.ini",
+            "[/Script/EngineSettings.GameMapsSettings]\nGlobalDefaultGameMode=/Script/TP_Shooter.TP_ShooterGameMode\nOther=/Script/TP_ShooterExtras.Thing\n",
+        ),
+        ("Content/Maps/Main.umap", "binary"),
+    ] {
+        let p = root.join(rel);
+        fs::create_dir_all(p.parent().unwrap()).unwrap();
+        fs::write(p, body).unwrap();
+    }
+}
+
+fn read(root: &Path, rel: &str) -> String {
+    fs::read_to_string(root.join(rel)).unwrap_or_else(|e| panic!("{}: {}", rel, e))
+}
+
+#[test]
+fn renames_module_folders_rules_and_references() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    fake_cpp_project(root);
+
+    let old = cpp_rename::primary_module_name(UPROJECT, "TP_Shooter").unwrap();
+    assert_eq!(old, "TP_Shooter");
+    let report = cpp_rename::rename_modules(root, &old, "MyShooter").unwrap();
+
+    assert!(!root.join("Source/TP_Shooter").exists());
+    assert!(!root.join("Source/TP_Shooter.Target.cs").exists());
+    let target = read(root, "Source/MyShooter.Target.cs");
+    assert!(target.contains("public class MyShooterTarget : TargetRules"));
+    assert!(target.contains("public MyShooterTarget(TargetInfo Target)"));
+    assert!(target.contains("ExtraModuleNames.Add(\"MyShooter\")"));
+    assert!(read(root, "Source/MyShooterEditor.Target.cs").contains("public class MyShooterEditorTarget"));
+
+    let build = read(root, "Source/MyShooter/MyShooter.Build.cs");
+    assert!(build.contains("public class MyShooter : ModuleRules"));
+    assert!(build.contains("public MyShooter(ReadOnlyTargetRules Target)"));
+    let module_cpp = read(root, "Source/MyShooter/MyShooter.cpp");
+    assert!(module_cpp.contains("#include \"MyShooter.h\""));
+    assert!(module_cpp.contains("IMPLEMENT_PRIMARY_GAME_MODULE( FDefaultGameModuleImpl, MyShooter, \"MyShooter\" );"));
+    assert!(root.join("Source/MyShooter/MyShooter.h").is_file());
+
+    // Class names stay; the export macro and includes follow the module
+    let character = read(root, "Source/MyShooter/TP_ShooterCharacter.h");
+    assert!(character.contains("class MYSHOOTER_API ATP_ShooterCharacter"));
+    assert!(character.contains("#include \"MyShooter.h\""));
+
+    let uproject: serde_json::Value = serde_json::from_str(&read(root, "MyShooter.uproject")).unwrap();
+    assert_eq!(uproject["Modules"][0]["Name"], "MyShooter");
+    assert_eq!(uproject["EngineAssociation"], "5.4");
+
+    let ini = read(root, "Config/DefaultEngine.ini");
+    assert!(ini.contains("GlobalDefaultGameMode=/Script/MyShooter.TP_ShooterGameMode"));
+    assert!(ini.contains("Other=/Script/TP_ShooterExtras.Thing"));
+    assert!(ini.contains("+ActiveGameNameRedirects=(OldGameName=\"/Script/TP_Shooter\",NewGameName=\"/Script/MyShooter\")"));
+    assert_eq!(read(root, "Content/Maps/Main.umap"), "binary");
+
+    assert!(report.renamed.iter().any(|(from, to)| from == Path::new("Source/TP_Shooter") && to == Path::new("Source/MyShooter")));
+    assert!(report.rewritten.contains(&Path::new("MyShooter.uproject").to_path_buf()));
+
+    // Running again changes nothing and doesn't duplicate the redirects
+    let again = cpp_rename::rename_modules(root, &old, "MyShooter").unwrap();
+    assert_eq!(again, cpp_rename::RenameReport::default());
+    assert_eq!(read(root, "Config/DefaultEngine.ini"), ini);
+}
+
+#[test]
+fn identifiers_are_replaced_whole() {
+    let map = cpp_rename::identifier_map("Game", "Arena");
+    assert_eq!(
+        cpp_rename::replace_identifiers("Game GameTarget GameMode AGame GAME_API x.Game", &map),
+        "Arena ArenaTarget GameMode AGame ARENA_API x.Arena"
+    );
+    assert_eq!(cpp_rename::replace_script_paths("/Script/Game.X /Script/Games /Script/Game", "Game", "Arena"), "/Script/Arena.X /Script/Games /Script/Arena");
+}
+
+#[test]
+fn primary_module_prefers_the_project_file_name() {
+    let two = r#"{"Modules":[{"Name":"Tools"},{"Name":"Shooter"}]}"#;
+    assert_eq!(cpp_rename::primary_module_name(two, "Shooter").as_deref(), Some("Shooter"));
+    assert_eq!(cpp_rename::primary_module_name(two, "Other").as_deref(), Some("Tools"));
+    assert_eq!(cpp_rename::primary_module_name("{}", "Shooter"), None);
+}
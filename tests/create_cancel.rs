@@ -0,0 +1,144 @@
+// Cancelling create_unreal_project at each phase boundary leaves no partial project behind
+// (src/utils/project_copy.rs).
+
+#[path = "../src/utils/project_copy.rs"]
+mod project_copy;
+
+use project_copy::{copy_template, CopyFailure, CopyOutcome, ProjectDir};
+use std::cell::Cell;
+use std::fs;
+use std::path::Path;
+
+const EXCLUDE: &[&str] = &["Binaries", "Saved"];
+
+fn make_template(root: &Path) {
+    for (rel, body) in [
+        ("Sample.uproject", "{}"),
+        ("Config/DefaultGame.ini", "x"),
+        ("Content/A.uasset", "a"),
+        ("Content/B.uasset", "b"),
+        ("Content/C.uasset", "c"),
+        ("Saved/Logs/log.txt", "skip"),
+    ] {
+        let p = root.join(rel);
+        fs::create_dir_all(p.parent().unwrap()).unwrap();
+        fs::write(p, body).unwrap();
+    }
+}
+
+#[test]
+fn completed_copy_renames_uproject_and_skips_excluded() {
+    let tmp = tempfile::tempdir().unwrap();
+    let template = tmp.path().join("Template");
+    make_template(&template);
+    let dir = ProjectDir::prepare(tmp.path().join("out/MyGame")).unwrap();
+    assert!(dir.created_now());
+
+    let outcome = copy_template(&template, dir.path(), "MyGame", EXCLUDE, &|| false, &mut |_| {}, &mut |_| {}).unwrap();
+    assert!(matches!(outcome, CopyOutcome::Completed { copied: 5, .. }));
+    assert!(dir.path().join("MyGame.uproject").is_file());
+    assert!(!dir.path().join("Saved").exists());
+}
+
+#[test]
+fn cancel_before_copy_rolls_back_new_dir() {
+    let tmp = tempfile::tempdir().unwrap();
+    let template = tmp.path().join("Template");
+    make_template(&template);
+    let dir = ProjectDir::prepare(tmp.path().join("MyGame")).unwrap();
+
+    let outcome = copy_template(&template, dir.path(), "MyGame", EXCLUDE, &|| true, &mut |_| {}, &mut |_| {}).unwrap();
+    assert_eq!(outcome, CopyOutcome::Cancelled { copied: 0 });
+    assert!(dir.rollback().unwrap());
+    assert!(!tmp.path().join("MyGame").exists());
+}
+
+#[test]
+fn cancel_mid_copy_rolls_back_partial_project() {
+    let tmp = tempfile::tempdir().unwrap();
+    let template = tmp.path().join("Template");
+    make_template(&template);
+    let dir = ProjectDir::prepare(tmp.path().join("MyGame")).unwrap();
+
+    // Cancel is requested once two files have been copied
+    let copied_so_far = Cell::new(0usize);
+    let outcome = copy_template(
+        &template,
+        dir.path(),
+        "MyGame",
+        EXCLUDE,
+        &|| copied_so_far.get() >= 2,
+        &mut |n| copied_so_far.set(n),
+        &mut |_| {},
+    )
+    .unwrap();
+    assert_eq!(outcome, CopyOutcome::Cancelled { copied: 2 });
+    assert!(dir.path().exists(), "partial files are present until rollback");
+    assert!(dir.rollback().unwrap());
+    assert!(!tmp.path().join("MyGame").exists());
+}
+
+#[test]
+fn cancel_after_copy_rolls_back_finished_copy() {
+    let tmp = tempfile::tempdir().unwrap();
+    let template = tmp.path().join("Template");
+    make_template(&template);
+    let dir = ProjectDir::prepare(tmp.path().join("MyGame")).unwrap();
+    copy_template(&template, dir.path(), "MyGame", EXCLUDE, &|| false, &mut |_| {}, &mut |_| {}).unwrap();
+
+    // Cancel arrives between copy and launch
+    assert!(dir.rollback().unwrap());
+    assert!(!tmp.path().join("MyGame").exists());
+}
+
+#[test]
+fn rollback_never_removes_a_pre_existing_directory() {
+    let tmp = tempfile::tempdir().unwrap();
+    let template = tmp.path().join("Template");
+    make_template(&template);
+    let existing = tmp.path().join("MyGame");
+    fs::create_dir_all(&existing).unwrap();
+    fs::write(existing.join("notes.txt"), "keep me").unwrap();
+
+    let dir = ProjectDir::prepare(existing.clone()).unwrap();
+    assert!(!dir.created_now());
+    copy_template(&template, dir.path(), "MyGame", EXCLUDE, &|| true, &mut |_| {}, &mut |_| {}).unwrap();
+    assert!(!dir.rollback().unwrap());
+    assert!(existing.join("notes.txt").is_file());
+}
+
+#[test]
+fn failed_files_are_reported_and_the_rest_still_copied() {
+    let tmp = tempfile::tempdir().unwrap();
+    let template = tmp.path().join("Template");
+    make_template(&template);
+    let dir = ProjectDir::prepare(tmp.path().join("MyGame")).unwrap();
+    // A folder squatting on a file's destination makes that one copy fail
+    fs::create_dir_all(dir.path().join("Content/B.uasset")).unwrap();
+
+    let mut failures: Vec<CopyFailure> = Vec::new();
+    let outcome = copy_template(&template, dir.path(), "MyGame", EXCLUDE, &|| false, &mut |_| {}, &mut |f| failures.push(f.clone())).unwrap();
+    assert!(matches!(outcome, CopyOutcome::Completed { copied: 4, failed: 1, .. }));
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].path, Path::new("Content/B.uasset"));
+    assert!(dir.path().join("Content/A.uasset").is_file());
+    assert!(dir.path().join("Content/C.uasset").is_file());
+    assert!(dir.path().join("MyGame.uproject").is_file());
+}
+
+#[test]
+fn failing_to_copy_the_uproject_fails_the_copy() {
+    let tmp = tempfile::tempdir().unwrap();
+    let template = tmp.path().join("Template");
+    make_template(&template);
+    let dir = ProjectDir::prepare(tmp.path().join("MyGame")).unwrap();
+    fs::create_dir_all(dir.path().join("MyGame.uproject")).unwrap();
+
+    let mut failures: Vec<CopyFailure> = Vec::new();
+    let err = copy_template(&template, dir.path(), "MyGame", EXCLUDE, &|| false, &mut |_| {}, &mut |f| failures.push(f.clone())).unwrap_err();
+    assert!(err.contains("MyGame.uproject"), "{}", err);
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].path, Path::new("Sample.uproject"));
+    // Everything else was still copied
+    assert!(dir.path().join("Content/C.uasset").is_file());
+}
@@ -0,0 +1,65 @@
+// Saved create-project defaults applied to /create-unreal-project requests (src/utils/create_defaults.rs).
+
+#[path = "../src/utils/create_defaults.rs"]
+mod create_defaults;
+#[allow(dead_code)]
+#[path = "../src/models/mod.rs"]
+mod models;
+
+fn saved() -> models::CreateDefaults {
+    serde_json::from_value(serde_json::json!({
+        "engine_path": "/engines/UE_5.4",
+        "output_dir": "/projects",
+        "project_type": "cpp",
+        "open_after_create": true,
+    }))
+    .unwrap()
+}
+
+fn request(body: serde_json::Value) -> models::CreateUnrealProjectRequest {
+    serde_json::from_value(body).unwrap()
+}
+
+#[test]
+fn omitted_fields_come_from_the_saved_defaults() {
+    let mut req = request(serde_json::json!({ "project_name": "MyGame", "template_project": "/t/T.uproject" }));
+    create_defaults::apply(&mut req, &saved());
+    assert_eq!(req.engine_path.as_deref(), Some("/engines/UE_5.4"));
+    assert_eq!(req.output_dir, "/projects");
+    assert_eq!(req.project_type.as_deref(), Some("cpp"));
+    assert_eq!(req.open_after_create, Some(true));
+}
+
+#[test]
+fn explicit_values_override_the_saved_defaults() {
+    let mut req = request(serde_json::json!({
+        "project_name": "MyGame",
+        "engine_path": "/engines/UE_5.5",
+        "output_dir": "/elsewhere",
+        "project_type": "bp",
+        "open_after_create": false,
+    }));
+    create_defaults::apply(&mut req, &saved());
+    assert_eq!(req.engine_path.as_deref(), Some("/engines/UE_5.5"));
+    assert_eq!(req.output_dir, "/elsewhere");
+    assert_eq!(req.project_type.as_deref(), Some("bp"));
+    assert_eq!(req.open_after_create, Some(false));
+}
+
+#[test]
+fn requested_ue_version_keeps_the_default_engine_out() {
+    let mut req = request(serde_json::json!({ "project_name": "MyGame", "ue": "5.6", "output_dir": " ", "project_type": "" }));
+    create_defaults::apply(&mut req, &saved());
+    assert!(req.engine_path.is_none());
+    // Blank values count as omitted
+    assert_eq!(req.output_dir, "/projects");
+    assert_eq!(req.project_type.as_deref(), Some("cpp"));
+}
+
+#[test]
+fn nothing_saved_leaves_the_request_alone() {
+    let mut req = request(serde_json::json!({ "project_name": "MyGame" }));
+    create_defaults::apply(&mut req, &models::CreateDefaults::default());
+    assert!(req.engine_path.is_none() && req.project_type.is_none() && req.open_after_create.is_none());
+    assert!(req.output_dir.is_empty());
+}
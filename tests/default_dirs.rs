@@ -0,0 +1,112 @@
+// Per-platform default directories resolved from environment variables (src/utils/default_dirs.rs).
+
+#[path = "../src/utils/default_dirs.rs"]
+mod default_dirs;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+fn env_of(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+    let map: HashMap<String, String> = pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+    move |k| map.get(k).cloned()
+}
+
+#[test]
+fn windows_uses_profile_and_appdata() {
+    let env = env_of(&[
+        ("USERPROFILE", r"C:\Users\alex"),
+        ("APPDATA", r"C:\Users\alex\AppData\Roaming"),
+        ("LOCALAPPDATA", r"C:\Users\alex\AppData\Local"),
+        ("ProgramFiles", r"C:\Program Files"),
+        ("HOME", "/should/not/win"),
+    ]);
+    let p = "Windows";
+    assert_eq!(default_dirs::home_dir(p, &env), Some(PathBuf::from(r"C:\Users\alex")));
+    assert_eq!(default_dirs::config_base(p, &env), PathBuf::from(r"C:\Users\alex\AppData\Roaming"));
+    assert_eq!(default_dirs::cache_base(p, &env), PathBuf::from(r"C:\Users\alex\AppData\Local"));
+    assert_eq!(default_dirs::data_base(p, &env), PathBuf::from(r"C:\Users\alex\AppData\Local"));
+    assert_eq!(default_dirs::projects_dir(p, &env), PathBuf::from(r"C:\Users\alex").join("Documents").join("Unreal Projects"));
+    assert_eq!(default_dirs::engines_dir(p, &env), PathBuf::from(r"C:\Program Files").join("Epic Games"));
+}
+
+#[test]
+fn windows_falls_back_to_homedrive_and_profile_subfolders() {
+    let env = env_of(&[("HOMEDRIVE", "D:"), ("HOMEPATH", r"\Users\sam"), ("APPDATA", "")]);
+    let p = "Windows";
+    let home = PathBuf::from(r"D:\Users\sam");
+    assert_eq!(default_dirs::home_dir(p, &env), Some(home.clone()));
+    assert_eq!(default_dirs::config_base(p, &env), home.join("AppData").join("Roaming"));
+    assert_eq!(default_dirs::cache_base(p, &env), home.join("AppData").join("Local"));
+}
+
+#[test]
+fn unix_follows_xdg_then_home() {
+    for p in ["Linux", "macOS"] {
+        let env = env_of(&[("HOME", "/home/alex"), ("XDG_CONFIG_HOME", "/cfg"), ("XDG_DATA_HOME", ""), ("USERPROFILE", r"C:\ignored")]);
+        assert_eq!(default_dirs::config_base(p, &env), PathBuf::from("/cfg"));
+        assert_eq!(default_dirs::cache_base(p, &env), PathBuf::from("/home/alex/.cache"));
+        // Empty XDG values are ignored
+        assert_eq!(default_dirs::data_base(p, &env), PathBuf::from("/home/alex/.local/share"));
+        assert_eq!(default_dirs::projects_dir(p, &env), PathBuf::from("/home/alex/Documents/Unreal Projects"));
+        assert_eq!(default_dirs::engines_dir(p, &env), PathBuf::from("/home/alex/UnrealEngines"));
+    }
+}
+
+#[test]
+fn missing_home_falls_back_to_relative_paths() {
+    let env = env_of(&[]);
+    assert_eq!(default_dirs::home_dir("Linux", &env), None);
+    assert_eq!(default_dirs::config_base("Linux", &env), PathBuf::from(".config"));
+    assert_eq!(default_dirs::projects_dir("Linux", &env), PathBuf::from("."));
+    assert_eq!(default_dirs::engines_dir("Windows", &env), PathBuf::from(r"C:\Program Files\Epic Games"));
+}
+
+#[test]
+fn configured_dir_overrides_the_default() {
+    let env = env_of(&[("HOME", "/home/alex")]);
+    let fallback = PathBuf::from("/home/alex/.local/share/egs_client/downloads");
+    let resolve = |v: Option<&str>| default_dirs::configured_or(v, fallback.clone(), "Linux", &env);
+    assert_eq!(resolve(Some("/mnt/fab")), PathBuf::from("/mnt/fab"));
+    assert_eq!(resolve(Some("  \"/mnt/fab assets\" ")), PathBuf::from("/mnt/fab assets"));
+    assert_eq!(resolve(Some("~/Fab")), PathBuf::from("/home/alex/Fab"));
+    assert_eq!(resolve(Some("$HOME/Fab")), PathBuf::from("/home/alex/Fab"));
+    // Unset or blank entries fall back
+    assert_eq!(resolve(None), fallback);
+    assert_eq!(resolve(Some("   ")), fallback);
+    assert_eq!(resolve(Some("''")), fallback);
+}
+
+#[test]
+fn home_expansion_needs_a_home() {
+    let none = env_of(&[]);
+    assert_eq!(default_dirs::expand_home("~/Fab", "Linux", &none), "~/Fab");
+    let win = env_of(&[("USERPROFILE", r"C:\Users\alex")]);
+    assert_eq!(default_dirs::expand_home("~/Fab", "Windows", &win), r"C:\Users\alex/Fab");
+    assert_eq!(default_dirs::expand_home("/opt/~/x", "Windows", &win), "/opt/~/x");
+}
+
+#[test]
+fn saved_paths_config_drives_cache_and_downloads_dirs() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = tmp.path().join("config.json");
+    let env = env_of(&[("HOME", "/home/alex")]);
+    let cache_default = PathBuf::from("/home/alex/.cache/egs_client");
+    let downloads_default = PathBuf::from("/home/alex/.local/share/egs_client/downloads");
+    let cache = || default_dirs::configured_dir(&config, "cache_dir", cache_default.clone(), "Linux", &env);
+    let downloads = || default_dirs::configured_dir(&config, "downloads_dir", downloads_default.clone(), "Linux", &env);
+
+    // No config yet: built-in defaults
+    assert_eq!((cache(), downloads()), (cache_default.clone(), downloads_default.clone()));
+
+    // Same shape POST /config/paths saves
+    std::fs::write(&config, r#"{"projects_dir":null,"engines_dir":null,"cache_dir":"/mnt/egs/cache","downloads_dir":"~/Fab"}"#).unwrap();
+    assert_eq!(cache(), PathBuf::from("/mnt/egs/cache"));
+    assert_eq!(downloads(), PathBuf::from("/home/alex/Fab"));
+
+    // Clearing one field only affects that directory
+    std::fs::write(&config, r#"{"cache_dir":null,"downloads_dir":"/mnt/fab"}"#).unwrap();
+    assert_eq!((cache(), downloads()), (cache_default.clone(), PathBuf::from("/mnt/fab")));
+
+    std::fs::write(&config, "not json").unwrap();
+    assert_eq!((cache(), downloads()), (cache_default, downloads_default));
+}
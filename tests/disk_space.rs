@@ -0,0 +1,61 @@
+// Free-space preflight arithmetic and messages (src/utils/disk_space.rs).
+
+#[path = "../src/utils/disk_space.rs"]
+mod disk_space;
+
+use std::path::Path;
+
+const GB: u64 = 1024 * 1024 * 1024;
+
+#[test]
+fn margin_is_ten_percent_and_saturates() {
+    assert_eq!(disk_space::SAFETY_MARGIN_PERCENT, 10);
+    assert_eq!(disk_space::required_with_margin(0), 0);
+    assert_eq!(disk_space::required_with_margin(10 * GB), 11 * GB);
+    assert_eq!(disk_space::required_with_margin(u64::MAX), u64::MAX);
+}
+
+#[test]
+fn insufficient_space_reports_need_and_free() {
+    let at = Path::new("/mnt/assets");
+    assert!(disk_space::check_free_space(10 * GB, 11 * GB, at).is_ok());
+
+    // Enough for the raw bytes but not for the margin
+    let msg = disk_space::check_free_space(10 * GB, 10 * GB + 1, at).unwrap_err();
+    assert!(msg.starts_with("Need 11.00 GB"), "{}", msg);
+    assert!(msg.contains("only 10.00 GB free at /mnt/assets"), "{}", msg);
+}
+
+#[test]
+fn probes_nearest_existing_ancestor() {
+    let tmp = tempfile::tempdir().unwrap();
+    let target = tmp.path().join("downloads/Pack/5.4");
+    assert_eq!(disk_space::nearest_existing_ancestor(&target).unwrap(), tmp.path());
+    assert_eq!(disk_space::nearest_existing_ancestor(Path::new("no-such-dir/x")).unwrap(), Path::new("."));
+}
+
+#[test]
+fn resumed_download_counts_files_already_under_data() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path().join("Pack").join("5.6");
+    let files = vec![
+        ("Content/Rock.uasset".to_string(), 1000),
+        ("Content/Tree.uasset".to_string(), 500),
+        ("Config/Game.ini".to_string(), 200),
+    ];
+    assert_eq!(disk_space::bytes_already_on_disk(&root, &files), 0);
+
+    // An interrupted run left one file complete and one half written, both under data/
+    let rock = disk_space::resolve_output_file_path(&root, "Content/Rock.uasset");
+    assert_eq!(rock, root.join("data").join("Content/Rock.uasset"));
+    std::fs::create_dir_all(rock.parent().unwrap()).unwrap();
+    std::fs::write(&rock, vec![0u8; 1000]).unwrap();
+    std::fs::write(disk_space::resolve_output_file_path(&root, "Content/Tree.uasset"), vec![0u8; 250]).unwrap();
+    // A stray file at the asset root is not where the writer puts it and does not count
+    std::fs::create_dir_all(root.join("Config")).unwrap();
+    std::fs::write(root.join("Config/Game.ini"), vec![0u8; 200]).unwrap();
+
+    assert_eq!(disk_space::bytes_already_on_disk(&root, &files), 1250);
+    // Passing the data/ folder itself resolves to the same files
+    assert_eq!(disk_space::bytes_already_on_disk(&root.join("data"), &files), 1250);
+}
@@ -0,0 +1,46 @@
+// Waiting for in-flight chunk transfers during shutdown (src/utils/drain.rs).
+
+#[path = "../src/utils/drain.rs"]
+mod drain;
+
+use drain::InFlight;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+#[test]
+fn idle_returns_immediately() {
+    let f = InFlight::new();
+    let start = Instant::now();
+    assert!(f.wait_idle(Duration::from_secs(5)));
+    assert!(start.elapsed() < Duration::from_secs(1));
+}
+
+#[test]
+fn waits_for_running_work_to_finish() {
+    let f = Arc::new(InFlight::new());
+    let (tx, rx) = std::sync::mpsc::channel();
+    let worker = {
+        let f = f.clone();
+        std::thread::spawn(move || {
+            let _a = f.enter();
+            let _b = f.enter();
+            tx.send(()).unwrap();
+            std::thread::sleep(Duration::from_millis(150));
+        })
+    };
+    rx.recv().unwrap();
+    assert_eq!(f.count(), 2);
+    assert!(f.wait_idle(Duration::from_secs(5)));
+    assert_eq!(f.count(), 0);
+    worker.join().unwrap();
+}
+
+#[test]
+fn gives_up_after_timeout() {
+    let f = InFlight::new();
+    let _stuck = f.enter();
+    let start = Instant::now();
+    assert!(!f.wait_idle(Duration::from_millis(100)));
+    assert!(start.elapsed() >= Duration::from_millis(100));
+    assert_eq!(f.count(), 1);
+}
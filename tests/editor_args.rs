@@ -0,0 +1,42 @@
+// Editor launch arguments for open/create (src/utils/editor_args.rs).
+
+#[path = "../src/utils/editor_args.rs"]
+mod editor_args;
+
+use std::collections::HashMap;
+
+fn query(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+}
+
+#[test]
+fn extra_args_accept_spaces_or_json() {
+    assert_eq!(editor_args::parse_extra_args(" -log  -game ").unwrap(), vec!["-log", "-game"]);
+    assert_eq!(
+        editor_args::parse_extra_args(r#"["-ExecCmds=stat fps; r.VSync 0", "-ResX=1280"]"#).unwrap(),
+        vec!["-ExecCmds=stat fps; r.VSync 0", "-ResX=1280"]
+    );
+    assert!(editor_args::parse_extra_args("[-log").is_err());
+    assert!(editor_args::parse_extra_args("").unwrap().is_empty());
+}
+
+#[test]
+fn toggles_come_before_extra_args() {
+    let q = query(&[("vulkan", "true"), ("log", "1"), ("fullscreen", "false"), ("extra_args", "-game")]);
+    assert_eq!(editor_args::from_query(&q).unwrap(), vec!["-vulkan", "-log", "-game"]);
+    assert!(editor_args::from_query(&query(&[("extra_args", "[1]")])).is_err());
+}
+
+#[test]
+fn arg_vector_keeps_paths_whole_and_drops_repeats() {
+    let extra = vec!["-nocompile".to_string(), "-log".to_string(), "-log".to_string(), "; rm -rf /".to_string()];
+    assert_eq!(
+        editor_args::editor_args("/home/me/Unreal Projects/My Game.uproject", true, &extra),
+        vec!["/home/me/Unreal Projects/My Game.uproject", "-NoCompile", "-log", "; rm -rf /"]
+    );
+    assert!(editor_args::default_no_compile(None, None));
+    assert!(editor_args::default_no_compile(Some("BP"), None));
+    assert!(!editor_args::default_no_compile(Some("cpp"), None));
+    assert!(!editor_args::default_no_compile(Some("bp"), Some(false)));
+    assert!(editor_args::default_no_compile(Some("cpp"), Some(true)));
+}
@@ -0,0 +1,92 @@
+// Per-job editor output logs (src/utils/editor_log.rs).
+
+#[path = "../src/utils/editor_log.rs"]
+mod editor_log;
+
+use std::fs;
+use std::io::Write;
+use std::time::Duration;
+
+#[test]
+fn log_path_is_per_job_under_the_cache_dir() {
+    let cache = std::path::Path::new("/cache");
+    assert_eq!(editor_log::log_path(cache, "job-1_a"), cache.join(editor_log::EDITOR_LOG_DIR).join("job-1_a.log"));
+    assert_eq!(editor_log::log_path(cache, "../x/y"), cache.join("editor-logs").join("___x_y.log"));
+}
+
+#[test]
+fn stdout_and_stderr_handles_share_one_file() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = editor_log::log_path(tmp.path(), "job");
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, "stale output from an earlier launch\n").unwrap();
+
+    let (mut out, mut err) = editor_log::open_log(&path).unwrap();
+    writeln!(out, "out line").unwrap();
+    writeln!(err, "err line").unwrap();
+    drop((out, err));
+
+    assert_eq!(editor_log::tail_lines(&path, 10), vec!["out line", "err line"]);
+}
+
+#[cfg(unix)]
+#[test]
+fn child_output_lands_in_the_log() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = editor_log::log_path(tmp.path(), "child");
+    let (out, err) = editor_log::open_log(&path).unwrap();
+    let status = std::process::Command::new("sh")
+        .args(["-c", "echo to-stdout; echo to-stderr >&2; exit 3"])
+        .stdout(out)
+        .stderr(err)
+        .status()
+        .unwrap();
+    assert_eq!(status.code(), Some(3));
+    assert_eq!(editor_log::tail_lines(&path, editor_log::TAIL_LINES), vec!["to-stdout", "to-stderr"]);
+}
+
+#[test]
+fn tail_keeps_the_last_lines_of_a_large_log() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("big.log");
+    let text: String = (0..50_000).map(|i| format!("line {}\n", i)).collect();
+    fs::write(&path, text).unwrap();
+
+    let tail = editor_log::tail_lines(&path, 3);
+    assert_eq!(tail, vec!["line 49997", "line 49998", "line 49999"]);
+    let many = editor_log::tail_lines(&path, 100_000);
+    assert!(many.len() < 50_000);
+    assert!(many[0].starts_with("line ") && many[0].len() > "line ".len(), "cut-off first line is dropped");
+    assert!(editor_log::tail_lines(&tmp.path().join("missing.log"), 3).is_empty());
+}
+
+#[test]
+fn prune_keeps_only_the_newest_logs() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path().join(editor_log::EDITOR_LOG_DIR);
+    fs::create_dir_all(&dir).unwrap();
+    let now = std::time::SystemTime::now();
+    for i in 0..5u64 {
+        let f = fs::File::create(dir.join(format!("job{}.log", i))).unwrap();
+        f.set_modified(now - Duration::from_secs(60 * (5 - i))).unwrap();
+    }
+    fs::write(dir.join("notes.txt"), "not a log").unwrap();
+
+    assert_eq!(editor_log::prune_logs(&dir, 3, editor_log::MAX_LOG_AGE), 2);
+    let mut left: Vec<String> = fs::read_dir(&dir).unwrap().map(|e| e.unwrap().file_name().to_string_lossy().to_string()).collect();
+    left.sort();
+    assert_eq!(left, vec!["job2.log", "job3.log", "job4.log", "notes.txt"]);
+}
+
+#[test]
+fn prune_removes_logs_past_the_max_age() {
+    let tmp = tempfile::tempdir().unwrap();
+    let old = fs::File::create(tmp.path().join("old.log")).unwrap();
+    old.set_modified(std::time::SystemTime::now() - editor_log::MAX_LOG_AGE - Duration::from_secs(60)).unwrap();
+    fs::write(tmp.path().join("recent.log"), "").unwrap();
+
+    assert_eq!(editor_log::prune_logs(tmp.path(), editor_log::KEEP_LOGS, editor_log::MAX_LOG_AGE), 1);
+    assert!(!tmp.path().join("old.log").exists());
+    assert!(tmp.path().join("recent.log").exists());
+    assert_eq!(editor_log::prune_logs(&tmp.path().join("missing"), 0, editor_log::MAX_LOG_AGE), 0);
+}
@@ -0,0 +1,29 @@
+// Duplicate engine detection (src/utils/engine_dupes.rs).
+
+#[path = "../src/utils/engine_dupes.rs"]
+mod engine_dupes;
+
+#[test]
+fn two_same_version_dirs_are_flagged() {
+    // Entries given in reverse path order to make sure the original is chosen by path, not input order
+    let dupes = engine_dupes::find_duplicates(&[("5.4", Some("abc"), "/e/UE_5.4_copy"), ("5.4", Some("ABC"), "/e/UE_5.4")]);
+    assert_eq!(dupes, vec![Some("/e/UE_5.4".to_string()), None]);
+}
+
+#[test]
+fn differing_build_ids_or_versions_are_not_duplicates() {
+    let dupes = engine_dupes::find_duplicates(&[
+        ("5.4", Some("abc"), "/e/a"),
+        ("5.4", Some("def"), "/e/b"),
+        ("5.3", None, "/e/c"),
+        ("unknown", None, "/e/d"),
+        ("unknown", None, "/e/e"),
+    ]);
+    assert_eq!(dupes, vec![None, None, None, None, None]);
+}
+
+#[test]
+fn missing_build_id_still_matches_on_version() {
+    let dupes = engine_dupes::find_duplicates(&[("5.6", None, "/e/a"), ("5.6", Some("x"), "/e/b")]);
+    assert_eq!(dupes, vec![None, Some("/e/a".to_string())]);
+}
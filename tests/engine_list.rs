@@ -0,0 +1,85 @@
+// Engine installs as /list-unreal-engines reports them (src/utils/engine_list.rs).
+
+#[path = "../src/utils/engine_dupes.rs"]
+mod engine_dupes;
+#[path = "../src/utils/engine_list.rs"]
+mod engine_list;
+#[allow(dead_code)]
+#[path = "../src/utils/fs_scan.rs"]
+mod fs_scan;
+#[allow(dead_code)]
+#[path = "../src/models/mod.rs"]
+mod models;
+#[allow(dead_code)]
+#[path = "../src/utils/paths.rs"]
+mod paths;
+
+use std::fs;
+use std::path::Path;
+
+fn touch(path: &Path, contents: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, contents).unwrap();
+}
+
+#[test]
+fn engine_without_an_editor_binary_is_reported_unavailable() {
+    let tmp = tempfile::tempdir().unwrap();
+    let base = tmp.path();
+    // A game-only build: Engine/Binaries exists, the editor does not
+    fs::create_dir_all(base.join("UE_5.4/Engine/Binaries/Linux")).unwrap();
+    touch(&base.join("UE_5.4/Engine/Binaries/Linux/UnrealGame"), "");
+    let editor_rel = fs_scan::editor_candidates_for(fs_scan::host_platform())[0];
+    touch(&base.join("UE_5.5").join(editor_rel), "");
+
+    let engines = engine_list::discover_engines(base);
+    assert_eq!(engines.len(), 2);
+
+    let game_only = &engines[0];
+    assert_eq!(game_only.name, "UE_5.4");
+    assert!(!game_only.editor_available);
+    assert!(game_only.editor_path.is_none());
+    let reason = game_only.reason.as_deref().unwrap();
+    assert!(reason.contains("No editor binary found"), "{}", reason);
+    assert!(reason.contains(editor_rel), "{}", reason);
+    assert!(!game_only.launchable);
+
+    let with_editor = &engines[1];
+    assert_eq!(with_editor.name, "UE_5.5");
+    assert!(with_editor.editor_available && with_editor.launchable);
+    assert!(with_editor.reason.is_none());
+    assert!(with_editor.editor_path.as_ref().unwrap().ends_with(editor_rel));
+
+    // The JSON shape the UI reads
+    let json = serde_json::to_value(&engines).unwrap();
+    assert_eq!(json[0]["editor_available"], false);
+    assert_eq!(json[0]["reason"].as_str(), Some(reason));
+    assert_eq!(json[1]["editor_available"], true);
+    assert!(json[1].get("reason").is_none());
+}
+
+#[test]
+fn copied_engine_install_is_flagged_as_duplicate() {
+    let tmp = tempfile::tempdir().unwrap();
+    let base = tmp.path();
+    let build_version = r#"{"MajorVersion": 5, "MinorVersion": 4, "PatchVersion": 2, "BuildId": "abc-123"}"#;
+    let editor_rel = fs_scan::editor_candidates_for(fs_scan::host_platform())[0];
+    // Same Build.version in both folders; a third install of another version is left alone
+    let other_version = r#"{"MajorVersion": 5, "MinorVersion": 5, "PatchVersion": 0, "BuildId": "abc-123"}"#;
+    for (name, version) in [("UE_5.4", build_version), ("UE_5.4_copy", build_version), ("UE_5.5", other_version)] {
+        touch(&fs_scan::build_version_path(&base.join(name)), version);
+        touch(&base.join(name).join(editor_rel), "");
+    }
+
+    let engines = engine_list::discover_engines(base);
+    let names: Vec<&str> = engines.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["UE_5.4", "UE_5.4_copy", "UE_5.5"]);
+    assert_eq!(engines[0].version, "5.4.2");
+    assert_eq!(engines[1].version, "5.4.2");
+    assert!(engines[0].duplicate_of.is_none());
+    assert_eq!(engines[1].duplicate_of.as_deref(), Some(engines[0].path.as_str()));
+    assert!(engines[2].duplicate_of.is_none());
+
+    let json = serde_json::to_value(&engines).unwrap();
+    assert_eq!(json[1]["duplicate_of"].as_str(), Some(engines[0].path.as_str()));
+}
@@ -0,0 +1,63 @@
+// Registered source-built engines from Install.ini and `reg query` output (src/utils/engine_registry.rs).
+
+#[path = "../src/utils/engine_registry.rs"]
+mod engine_registry;
+
+use engine_registry::{install_ini_path, lookup, normalize_id, parse_install_ini, parse_reg_query};
+use std::path::{Path, PathBuf};
+
+#[test]
+fn install_ini_installations_are_parsed() {
+    let ini = "\
+[Installations]
+{1E3F5A2B-0C4D-4E6F-8A9B-0C1D2E3F4A5B}=/home/alex/src/UnrealEngine
+; a comment
+MyFork = \"/opt/UE Fork\"
+
+[Other]
+{DEADBEEF-0000-0000-0000-000000000000}=/not/an/installation
+";
+    assert_eq!(
+        parse_install_ini(ini),
+        vec![
+            ("{1E3F5A2B-0C4D-4E6F-8A9B-0C1D2E3F4A5B}".to_string(), PathBuf::from("/home/alex/src/UnrealEngine")),
+            ("MyFork".to_string(), PathBuf::from("/opt/UE Fork")),
+        ]
+    );
+    assert!(parse_install_ini("").is_empty());
+}
+
+#[test]
+fn reg_query_values_are_parsed() {
+    assert!(engine_registry::WINDOWS_BUILDS_KEY.ends_with(r"\Unreal Engine\Builds"));
+    let out = "\r\nHKEY_CURRENT_USER\\Software\\Epic Games\\Unreal Engine\\Builds\r\n    {1E3F5A2B-0C4D-4E6F-8A9B-0C1D2E3F4A5B}    REG_SZ    D:/Source/Unreal Engine\r\n\r\n";
+    assert_eq!(
+        parse_reg_query(out),
+        vec![("{1E3F5A2B-0C4D-4E6F-8A9B-0C1D2E3F4A5B}".to_string(), PathBuf::from("D:/Source/Unreal Engine"))]
+    );
+}
+
+#[test]
+fn lookup_ignores_braces_and_case() {
+    let registered = vec![
+        ("{1E3F5A2B-0C4D-4E6F-8A9B-0C1D2E3F4A5B}".to_string(), PathBuf::from("/src/UE")),
+        ("MyFork".to_string(), PathBuf::from("/opt/fork")),
+    ];
+    assert_eq!(lookup(&registered, "{1e3f5a2b-0c4d-4e6f-8a9b-0c1d2e3f4a5b}"), Some(Path::new("/src/UE")));
+    assert_eq!(lookup(&registered, "1E3F5A2B-0C4D-4E6F-8A9B-0C1D2E3F4A5B"), Some(Path::new("/src/UE")));
+    assert_eq!(lookup(&registered, " myfork "), Some(Path::new("/opt/fork")));
+    assert_eq!(lookup(&registered, "5.3"), None);
+    assert_eq!(lookup(&registered, "{}"), None);
+    assert_eq!(normalize_id(" {ABC} "), "ABC");
+}
+
+#[test]
+fn install_ini_location_per_platform() {
+    let home = Path::new("/home/alex");
+    assert_eq!(install_ini_path("Linux", home), Some(PathBuf::from("/home/alex/.config/Epic/UnrealEngine/Install.ini")));
+    assert_eq!(
+        install_ini_path("macOS", home),
+        Some(PathBuf::from("/home/alex/Library/Application Support/Epic/UnrealEngine/Install.ini"))
+    );
+    assert_eq!(install_ini_path("Windows", home), None);
+}
@@ -0,0 +1,37 @@
+// Editor executable-bit diagnostics (src/utils/exec_perm.rs) against a fake editor binary.
+
+#![cfg(unix)]
+
+#[path = "../src/utils/exec_perm.rs"]
+mod exec_perm;
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::process::Command;
+
+#[test]
+fn non_executable_editor_gets_diagnostic_and_optional_fix() {
+    let tmp = tempfile::tempdir().unwrap();
+    let editor = tmp.path().join("UnrealEditor");
+    fs::write(&editor, "#!/bin/sh\nexit 0\n").unwrap();
+    fs::set_permissions(&editor, fs::Permissions::from_mode(0o644)).unwrap();
+    assert!(!exec_perm::is_executable(&editor));
+
+    let err = Command::new(&editor).status().unwrap_err();
+    let msg = exec_perm::describe_spawn_error(&editor, &err);
+    assert!(msg.contains("is not executable"), "{}", msg);
+    assert!(msg.contains("chmod +x"), "{}", msg);
+
+    assert!(exec_perm::ensure_executable(&editor).unwrap());
+    assert!(!exec_perm::ensure_executable(&editor).unwrap());
+    assert_eq!(fs::metadata(&editor).unwrap().permissions().mode() & 0o777, 0o755);
+    assert!(Command::new(&editor).status().unwrap().success());
+}
+
+#[test]
+fn other_spawn_errors_are_relayed() {
+    let tmp = tempfile::tempdir().unwrap();
+    let missing = tmp.path().join("UnrealEditor");
+    let err = Command::new(&missing).status().unwrap_err();
+    assert_eq!(exec_perm::describe_spawn_error(&missing, &err), err.to_string());
+}
@@ -0,0 +1,90 @@
+// Stress test for fab_list.json access (src/utils/fab_cache.rs): many concurrent
+// /get-fab-list-style passes (read, re-annotate from downloads/, write back) interleaved
+// with download-completion updates and lock-free readers, all on a temp cache.
+
+#[path = "../src/utils/fab_cache.rs"]
+mod fab_cache;
+#[path = "../src/utils/fs_scan.rs"]
+mod fs_scan;
+#[path = "../src/utils/paths.rs"]
+mod paths;
+
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+/// What serving /get-fab-list does with the cache: re-annotate under the lock, write back on change.
+fn serve_pass(cache: &Path, downloads: &Path) {
+    let _g = fab_cache::lock_fab_cache();
+    let mut v: serde_json::Value = serde_json::from_slice(&fs::read(cache).unwrap()).expect("cache must parse under the lock");
+    let (_, _, changed) = fs_scan::annotate_downloaded_flags_in(&mut v, downloads, |t| t.to_string(), &|| false).unwrap();
+    if changed {
+        fab_cache::write_fab_cache_atomic(cache, &serde_json::to_vec_pretty(&v).unwrap()).unwrap();
+    }
+}
+
+/// What a finished download does: record one extra field on its asset.
+fn update_pass(cache: &Path, i: usize) {
+    let _g = fab_cache::lock_fab_cache();
+    let mut v: serde_json::Value = serde_json::from_slice(&fs::read(cache).unwrap()).unwrap();
+    v["results"][i]["lastDownloadedBy"] = serde_json::json!(format!("job-{}", i));
+    fab_cache::write_fab_cache_atomic(cache, &serde_json::to_vec_pretty(&v).unwrap()).unwrap();
+}
+
+#[test]
+fn concurrent_serve_and_update_keep_cache_valid() {
+    let tmp = tempfile::tempdir().unwrap();
+    let cache = tmp.path().join("cache").join("fab_list.json");
+    let downloads = tmp.path().join("downloads");
+
+    let assets: Vec<serde_json::Value> = (0..32)
+        .map(|i| serde_json::json!({ "title": format!("Asset {}", i), "projectVersions": [ { "engineVersions": ["UE_5.4"] } ] }))
+        .collect();
+    fab_cache::write_fab_cache_atomic(&cache, &serde_json::to_vec_pretty(&serde_json::json!({ "results": assets })).unwrap()).unwrap();
+
+    // Readers never take the lock, like /categories after an atomic rename; they must never see a torn file
+    let done = Arc::new(AtomicBool::new(false));
+    let readers: Vec<_> = (0..4).map(|_| {
+        let (cache, done) = (cache.clone(), done.clone());
+        thread::spawn(move || {
+            while !done.load(Ordering::Relaxed) {
+                let bytes = fs::read(&cache).unwrap();
+                serde_json::from_slice::<serde_json::Value>(&bytes).expect("lock-free reader saw a partial cache");
+            }
+        })
+    }).collect();
+
+    let writers: Vec<_> = (0..32).map(|i| {
+        let (cache, downloads) = (cache.clone(), downloads.clone());
+        thread::spawn(move || {
+            // Half the assets finish downloading while serve passes run
+            if i % 2 == 0 {
+                let dir = downloads.join(format!("Asset {}", i)).join("5.4");
+                fs::create_dir_all(&dir).unwrap();
+                fs::write(dir.join(".download_complete"), "ok").unwrap();
+            }
+            serve_pass(&cache, &downloads);
+            update_pass(&cache, i);
+            serve_pass(&cache, &downloads);
+        })
+    }).collect();
+    for w in writers {
+        w.join().unwrap();
+    }
+    serve_pass(&cache, &downloads);
+    done.store(true, Ordering::Relaxed);
+    for r in readers {
+        r.join().unwrap();
+    }
+
+    let v: serde_json::Value = serde_json::from_slice(&fs::read(&cache).unwrap()).expect("cache must stay valid JSON");
+    let results = v["results"].as_array().unwrap();
+    assert_eq!(results.len(), 32);
+    for (i, a) in results.iter().enumerate() {
+        assert_eq!(a["lastDownloadedBy"], format!("job-{}", i), "update {} was lost", i);
+        assert_eq!(a["downloaded"], i % 2 == 0, "asset {} has a stale downloaded flag", i);
+    }
+    assert!(!cache.with_extension("json.tmp").exists());
+}
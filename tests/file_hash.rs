@@ -0,0 +1,50 @@
+// Hash verification shared by the skip-existing check and post-assembly check (src/utils/file_hash.rs).
+
+#[path = "../src/utils/file_hash.rs"]
+mod file_hash;
+
+use file_hash::{verify_file_hash, HashAlgo, HashError};
+
+// Digests of b"hello world"
+const SHA1_HELLO: &str = "2aae6c35c94fcfb415dbe95f408b9ce91ee846ed";
+const SHA256_HELLO: &str = "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+
+#[test]
+fn detects_algorithm_from_digest_length() {
+    assert_eq!(HashAlgo::detect(SHA1_HELLO), Some(HashAlgo::Sha1));
+    assert_eq!(HashAlgo::detect(SHA256_HELLO), Some(HashAlgo::Sha256));
+    assert_eq!(HashAlgo::detect(&SHA1_HELLO.to_uppercase()), Some(HashAlgo::Sha1));
+    assert_eq!(HashAlgo::detect("abc"), None);
+    assert_eq!(HashAlgo::detect(&"z".repeat(40)), None);
+}
+
+#[test]
+fn verifies_sha1_and_sha256_files() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("hello.txt");
+    std::fs::write(&path, b"hello world").unwrap();
+
+    assert!(verify_file_hash(&path, SHA1_HELLO).is_ok());
+    assert!(verify_file_hash(&path, SHA256_HELLO).is_ok());
+    assert!(verify_file_hash(&path, &SHA256_HELLO.to_uppercase()).is_ok());
+
+    match verify_file_hash(&path, &"0".repeat(64)) {
+        Err(HashError::Mismatch { algo, actual, .. }) => {
+            assert_eq!(algo, HashAlgo::Sha256);
+            assert_eq!(actual, SHA256_HELLO);
+        }
+        other => panic!("expected mismatch, got {:?}", other),
+    }
+    assert!(matches!(verify_file_hash(&path, "not-a-hash"), Err(HashError::UnsupportedFormat(_))));
+    assert!(matches!(verify_file_hash(&tmp.path().join("missing"), SHA1_HELLO), Err(HashError::Io(_))));
+}
+
+#[test]
+fn rejected_file_survives_anyhow_wrapping() {
+    let mismatch = HashError::Mismatch { algo: HashAlgo::Sha1, expected: SHA1_HELLO.to_string(), actual: "0".repeat(40) };
+    let err: anyhow::Error = file_hash::FileRejected::HashMismatch { file: "Content/a.uasset".to_string(), error: mismatch }.into();
+    // The download loop matches on the variant, not on the message text
+    assert!(matches!(err.downcast_ref::<file_hash::FileRejected>(), Some(file_hash::FileRejected::HashMismatch { file, .. }) if file == "Content/a.uasset"));
+    assert!(err.to_string().starts_with("hash mismatch for Content/a.uasset: SHA1 mismatch"));
+    assert!(anyhow::anyhow!("chunk download failed").downcast_ref::<file_hash::FileRejected>().is_none());
+}
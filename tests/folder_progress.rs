@@ -0,0 +1,36 @@
+// Per-folder download progress summary (src/utils/folder_progress.rs).
+
+#[path = "../src/utils/folder_progress.rs"]
+mod folder_progress;
+
+use folder_progress::{top_level_folder, FolderProgress};
+
+#[test]
+fn groups_by_first_path_component() {
+    assert_eq!(top_level_folder("Content/Props/Crate.uasset"), "Content");
+    assert_eq!(top_level_folder("Config\\DefaultGame.ini"), "Config");
+    assert_eq!(top_level_folder("/Content/Maps/Demo.umap"), "Content");
+    assert_eq!(top_level_folder("Sample.uproject"), ".");
+}
+
+#[test]
+fn counts_done_and_total_per_folder() {
+    let files = ["Content/A.uasset", "Content/Sub/B.uasset", "Config/DefaultGame.ini", "Sample.uproject"];
+    let fp = FolderProgress::new(files.iter().copied());
+    assert_eq!(fp.snapshot(), vec![
+        (".".to_string(), 0, 1),
+        ("Config".to_string(), 0, 1),
+        ("Content".to_string(), 0, 2),
+    ]);
+
+    fp.mark_done("Content/Sub/B.uasset");
+    fp.mark_done("Sample.uproject");
+    // Unknown folders are ignored and a folder never exceeds its total
+    fp.mark_done("Plugins/X.uplugin");
+    fp.mark_done("Sample.uproject");
+    assert_eq!(fp.snapshot(), vec![
+        (".".to_string(), 1, 1),
+        ("Config".to_string(), 0, 1),
+        ("Content".to_string(), 1, 2),
+    ]);
+}
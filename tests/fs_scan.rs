@@ -0,0 +1,346 @@
+// Filesystem helpers (src/utils/fs_scan.rs) exercised against isolated temp trees,
+// without touching $HOME or the real downloads/projects/engines directories.
+
+#[path = "../src/utils/fs_scan.rs"]
+mod fs_scan;
+#[path = "../src/utils/paths.rs"]
+mod paths;
+
+use std::fs;
+use std::path::Path;
+
+fn touch(path: &Path, contents: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, contents).unwrap();
+}
+
+#[test]
+fn engine_discovery_reads_build_version_and_falls_back_to_name() {
+    let tmp = tempfile::tempdir().unwrap();
+    let base = tmp.path();
+    // Build.version wins over the folder name
+    touch(&base.join("Custom/Engine/Build/Build.version"), r#"{"MajorVersion":5,"MinorVersion":4,"PatchVersion":2}"#);
+    touch(&base.join("Custom/Engine/Binaries/Linux/UnrealEditor"), "");
+    // No Build.version: parse from folder name; no editor binary
+    fs::create_dir_all(base.join("UE_5.6/Engine/Binaries")).unwrap();
+    // Not an engine (missing Engine/Binaries)
+    fs::create_dir_all(base.join("Random/Stuff")).unwrap();
+
+    let mut engines = fs_scan::scan_engine_dirs(base);
+    engines.sort_by(|a, b| a.name.cmp(&b.name));
+    assert_eq!(engines.len(), 2);
+    assert_eq!(engines[0].name, "Custom");
+    assert_eq!(engines[0].version, "5.4.2");
+    assert_eq!(engines[0].version_source, "build.version");
+    assert!(engines[0].editor_path.as_ref().unwrap().ends_with("Engine/Binaries/Linux/UnrealEditor"));
+    assert_eq!(engines[1].name, "UE_5.6");
+    assert_eq!(engines[1].version, "5.6");
+    assert_eq!(engines[1].version_source, "folder-name");
+    assert!(engines[1].editor_path.is_none());
+    assert!(engines[1].missing_editor.as_ref().unwrap().contains("Engine/Binaries/Linux/UnrealEditor"));
+
+    assert!(fs_scan::scan_engine_dirs(&base.join("missing")).is_empty());
+}
+
+#[test]
+fn malformed_build_version_falls_back_to_folder_name() {
+    let tmp = tempfile::tempdir().unwrap();
+    let base = tmp.path();
+    // Truncated JSON
+    touch(&base.join("UE_5.3/Engine/Build/Build.version"), r#"{"MajorVersion":5,"Minor"#);
+    fs::create_dir_all(base.join("UE_5.3/Engine/Binaries")).unwrap();
+    // Valid JSON without a usable MajorVersion, and no version in the folder name
+    touch(&base.join("Custom/Engine/Build/Build.version"), r#"{"MajorVersion":"five"}"#);
+    fs::create_dir_all(base.join("Custom/Engine/Binaries")).unwrap();
+
+    assert!(fs_scan::parse_build_version_file(&base.join("UE_5.3")).unwrap_err().contains("invalid JSON"));
+    assert!(fs_scan::parse_build_version_file(&base.join("Custom")).unwrap_err().contains("MajorVersion"));
+    assert_eq!(fs_scan::parse_build_version_file(&base.join("missing")), Ok(None));
+    assert_eq!(fs_scan::read_build_version(&base.join("UE_5.3")), None);
+
+    let mut engines = fs_scan::scan_engine_dirs(base);
+    engines.sort_by(|a, b| a.name.cmp(&b.name));
+    assert_eq!((engines[0].version.as_str(), engines[0].version_source), ("unknown", "unknown"));
+    assert_eq!((engines[1].version.as_str(), engines[1].version_source), ("5.3", "folder-name"));
+}
+
+#[test]
+fn editor_discovery_covers_each_platform_layout() {
+    let tmp = tempfile::tempdir().unwrap();
+    let layouts = [
+        ("Linux", "Engine/Binaries/Linux/UnrealEditor"),
+        ("Linux", "Engine/Binaries/Linux/UE4Editor"),
+        ("Windows", "Engine/Binaries/Win64/UnrealEditor.exe"),
+        ("Windows", "Engine/Binaries/Win64/UE4Editor.exe"),
+        ("macOS", "Engine/Binaries/Mac/UnrealEditor.app/Contents/MacOS/UnrealEditor"),
+        ("macOS", "Engine/Binaries/Mac/UE4Editor.app/Contents/MacOS/UE4Editor"),
+    ];
+    for (i, (platform, rel)) in layouts.iter().enumerate() {
+        let engine = tmp.path().join(format!("Engine{}", i));
+        touch(&engine.join(rel), "");
+        // Found natively on its own platform and through the fallback everywhere else
+        for host in ["Linux", "Windows", "macOS"] {
+            let found = fs_scan::find_editor_binary_for(&engine, host).unwrap_or_else(|| panic!("{} on {}", rel, host));
+            assert!(found.ends_with(rel), "{} on {}: {}", platform, host, found.display());
+        }
+    }
+}
+
+#[test]
+fn editor_discovery_prefers_host_platform() {
+    let tmp = tempfile::tempdir().unwrap();
+    let engine = tmp.path().join("UE_5.5");
+    touch(&engine.join("Engine/Binaries/Linux/UnrealEditor"), "");
+    touch(&engine.join("Engine/Binaries/Win64/UnrealEditor.exe"), "");
+    touch(&engine.join("Engine/Binaries/Mac/UnrealEditor.app/Contents/MacOS/UnrealEditor"), "");
+    assert!(fs_scan::find_editor_binary_for(&engine, "Linux").unwrap().ends_with("Linux/UnrealEditor"));
+    assert!(fs_scan::find_editor_binary_for(&engine, "Windows").unwrap().ends_with("Win64/UnrealEditor.exe"));
+    assert!(fs_scan::find_editor_binary_for(&engine, "macOS").unwrap().ends_with("MacOS/UnrealEditor"));
+    assert_eq!(fs_scan::editor_candidates_for("Windows")[0], "Engine/Binaries/Win64/UnrealEditor.exe");
+}
+
+#[test]
+fn missing_editor_message_lists_every_layout() {
+    let tmp = tempfile::tempdir().unwrap();
+    let engine = tmp.path().join("UE_5.5");
+    // The .app bundle alone is not an editor binary
+    fs::create_dir_all(engine.join("Engine/Binaries/Mac/UnrealEditor.app")).unwrap();
+    assert!(fs_scan::find_editor_binary(&engine).is_none());
+    let msg = fs_scan::missing_editor_message(&engine);
+    for rel in ["Linux/UnrealEditor", "Win64/UE4Editor.exe", "Mac/UE4Editor.app/Contents/MacOS/UE4Editor"] {
+        assert!(msg.contains(rel), "{}", msg);
+    }
+}
+
+#[test]
+fn uproject_bfs_respects_depth_and_skips_content() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    touch(&root.join("a/b/c/Deep.uproject"), "{}");
+    touch(&root.join("Content/Hidden.uproject"), "{}");
+
+    let depth = |max_depth| fs_scan::SearchLimits { max_depth, max_entries: 1000 };
+    assert!(fs_scan::find_uproject_bfs(root, depth(0)).unwrap().is_none());
+    assert!(fs_scan::find_uproject_bfs(root, depth(2)).unwrap().is_none(), "Deep.uproject sits at depth 3");
+    let found = fs_scan::find_uproject_bfs(root, depth(3)).unwrap().unwrap();
+    assert!(found.ends_with("a/b/c/Deep.uproject"));
+
+    // A direct file path is returned as-is
+    let direct = root.join("a/b/c/Deep.uproject");
+    assert_eq!(fs_scan::find_uproject_bfs(&direct, depth(1)).unwrap().unwrap(), direct);
+}
+
+#[test]
+fn searches_abort_when_entry_cap_is_exceeded() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    // 200 sibling folders, each with a 6-deep chain, and a project only at the very bottom of the last one
+    for i in 0..200 {
+        fs::create_dir_all(root.join(format!("wide{:03}/d1/d2/d3/d4/d5/d6", i))).unwrap();
+    }
+    touch(&root.join("wide199/d1/d2/d3/d4/d5/d6/Deep.uproject"), "{}");
+    touch(&root.join("wide199/d1/d2/d3/d4/d5/d6/Content/Mesh.uasset"), "");
+
+    let tight = fs_scan::SearchLimits { max_depth: 10, max_entries: 500 };
+    let err = fs_scan::find_uproject_bfs(root, tight).unwrap_err();
+    assert_eq!(err.limit, 500);
+    assert!(err.to_string().contains("search scope exceeded"), "{}", err);
+    assert!(err.to_string().contains("specify the path explicitly"), "{}", err);
+    assert!(fs_scan::find_content_dir_bfs(root, tight).is_err());
+
+    let roomy = fs_scan::SearchLimits { max_depth: 10, max_entries: 5000 };
+    assert!(fs_scan::find_uproject_bfs(root, roomy).unwrap().unwrap().ends_with("Deep.uproject"));
+    let content = fs_scan::find_content_dir_bfs(root, roomy).unwrap().unwrap();
+    assert!(content.ends_with("d6/Content"));
+    assert_eq!(err.start, root);
+}
+
+#[test]
+fn content_search_prefers_marketplace_plugin_folder() {
+    let tmp = tempfile::tempdir().unwrap();
+    let data = tmp.path();
+    fs::create_dir_all(data.join("Samples/Content")).unwrap();
+    fs::create_dir_all(data.join("Engine/Plugins/Marketplace/Pack/content")).unwrap();
+    let limits = fs_scan::SearchLimits { max_depth: 10, max_entries: 1000 };
+    let found = fs_scan::find_content_dir_bfs(data, limits).unwrap().unwrap();
+    assert!(found.ends_with("Marketplace/Pack/content"), "{}", found.display());
+    assert!(fs_scan::find_content_dir_bfs(&data.join("Samples/Content"), limits).unwrap().is_none());
+}
+
+#[test]
+fn plugin_descriptor_is_found_next_to_the_plugin_content() {
+    let tmp = tempfile::tempdir().unwrap();
+    let data = tmp.path();
+    let plugin = data.join("Engine/Plugins/Marketplace/Pack");
+    fs::create_dir_all(plugin.join("Content")).unwrap();
+    touch(&plugin.join("Pack.uplugin"), "{}");
+    touch(&plugin.join("Content/Nested.uplugin"), "{}");
+    let limits = fs_scan::SearchLimits { max_depth: 10, max_entries: 1000 };
+    let content = fs_scan::find_content_dir_bfs(data, limits).unwrap().unwrap();
+    assert_eq!(fs_scan::find_uplugin_for_content(&content), Some(plugin.join("Pack.uplugin")));
+
+    // Plain content packs have no descriptor
+    fs::create_dir_all(data.join("Other/Content")).unwrap();
+    assert_eq!(fs_scan::find_uplugin_for_content(&data.join("Other/Content")), None);
+}
+
+#[test]
+fn annotation_matches_version_folders_to_engine_versions() {
+    let tmp = tempfile::tempdir().unwrap();
+    let downloads = tmp.path();
+    touch(&downloads.join("Pack/5.4/.download_complete"), "ok");
+    // Incomplete version folder must not count
+    fs::create_dir_all(downloads.join("Pack/5.6/data")).unwrap();
+
+    let mut value = serde_json::json!({
+        "results": [
+            {
+                "title": "Pack",
+                "projectVersions": [
+                    { "engineVersions": ["UE_5.3", "UE_5.4"] },
+                    { "engineVersions": ["UE_5.6"] }
+                ]
+            },
+            { "title": "Other", "projectVersions": [ { "engineVersions": ["UE_5.4"] } ] }
+        ]
+    });
+    let (total, marked, changed) = fs_scan::annotate_downloaded_flags_in(&mut value, downloads, |t| t.to_string(), &|| false).unwrap();
+    assert_eq!((total, marked, changed), (2, 1, true));
+
+    let pack = &value["results"][0];
+    assert_eq!(pack["downloaded"], true);
+    assert_eq!(pack["downloadedVersions"], serde_json::json!(["5.4"]));
+    assert_eq!(pack["projectVersions"][0]["downloaded"], true);
+    assert_eq!(pack["projectVersions"][1]["downloaded"], false);
+    assert_eq!(value["results"][1]["downloaded"], false);
+
+    // Second pass over the same tree changes nothing
+    let (_, _, changed_again) = fs_scan::annotate_downloaded_flags_in(&mut value, downloads, |t| t.to_string(), &|| false).unwrap();
+    assert!(!changed_again);
+
+    // Cancellation stops before annotating anything
+    assert!(fs_scan::annotate_downloaded_flags_in(&mut value, downloads, |t| t.to_string(), &|| true).is_none());
+}
+
+#[test]
+fn annotation_reports_downloaded_bytes_from_a_cached_size() {
+    let tmp = tempfile::tempdir().unwrap();
+    let downloads = tmp.path();
+    touch(&downloads.join("Pack/5.4/.download_complete"), "ok");
+    touch(&downloads.join("Pack/5.4/data/Content/a.uasset"), "0123456789");
+    let mut value = serde_json::json!({ "results": [ { "title": "Pack" }, { "title": "Missing" } ] });
+
+    fs_scan::annotate_downloaded_flags_in(&mut value, downloads, |t| t.to_string(), &|| false).unwrap();
+    assert_eq!(value["results"][0]["downloadedBytes"], 12);
+    assert_eq!(value["results"][1]["downloadedBytes"], 0);
+    let sidecar: serde_json::Value = serde_json::from_slice(&fs::read(downloads.join(fs_scan::SIZE_CACHE_FILE)).unwrap()).unwrap();
+    assert_eq!(sidecar["Pack"]["bytes"], 12);
+
+    // An unchanged folder reuses the cached size instead of walking the tree again
+    let mut forged = sidecar.clone();
+    forged["Pack"]["bytes"] = serde_json::json!(999);
+    fs::write(downloads.join(fs_scan::SIZE_CACHE_FILE), serde_json::to_vec(&forged).unwrap()).unwrap();
+    fs_scan::annotate_downloaded_flags_in(&mut value, downloads, |t| t.to_string(), &|| false).unwrap();
+    assert_eq!(value["results"][0]["downloadedBytes"], 999);
+
+    // A stale stamp forces a recount
+    forged["Pack"]["stamp"] = serde_json::json!(1);
+    fs::write(downloads.join(fs_scan::SIZE_CACHE_FILE), serde_json::to_vec(&forged).unwrap()).unwrap();
+    fs_scan::annotate_downloaded_flags_in(&mut value, downloads, |t| t.to_string(), &|| false).unwrap();
+    assert_eq!(value["results"][0]["downloadedBytes"], 12);
+    assert_eq!(fs_scan::dir_size(&downloads.join("Pack")), 12);
+}
+
+#[test]
+fn project_resolution_by_directory_or_name() {
+    let tmp = tempfile::tempdir().unwrap();
+    let projects = tmp.path().join("Projects");
+    touch(&projects.join("MyGame/MyGame.uproject"), "{}");
+    fs::create_dir_all(projects.join("NotAProject")).unwrap();
+
+    let by_dir = projects.join("MyGame");
+    assert_eq!(fs_scan::project_dir_in(by_dir.to_str().unwrap(), &projects).unwrap(), by_dir);
+    assert_eq!(fs_scan::project_dir_in("MyGame", &projects).unwrap(), by_dir);
+    assert!(fs_scan::project_dir_in("NotAProject", &projects).is_none());
+    assert!(fs_scan::project_dir_in("Missing", &projects).is_none());
+}
+
+#[test]
+fn duplicate_project_names_are_reported_as_candidates() {
+    let tmp = tempfile::tempdir().unwrap();
+    // Candidates are canonical paths
+    let projects = &fs::canonicalize(tmp.path()).unwrap();
+    touch(&projects.join("MyGame/MyGame.uproject"), "{}");
+    touch(&projects.join("MyGame_Backup/mygame.uproject"), "{}");
+    touch(&projects.join("Other/Other.uproject"), "{}");
+    touch(&projects.join("Two/A.uproject"), "{}");
+    touch(&projects.join("Two/B.uproject"), "{}");
+
+    let dupes = fs_scan::uproject_candidates_in("MyGame", projects);
+    assert_eq!(dupes, vec![projects.join("MyGame/MyGame.uproject"), projects.join("MyGame_Backup/mygame.uproject")]);
+
+    // A unique name resolves to exactly one project
+    assert_eq!(fs_scan::uproject_candidates_in("Other", projects), vec![projects.join("Other/Other.uproject")]);
+    // Several .uproject files in one folder are ambiguous too
+    assert_eq!(fs_scan::uproject_candidates_in("Two", projects).len(), 2);
+    assert!(fs_scan::uproject_candidates_in("Missing", projects).is_empty());
+    assert!(fs_scan::uproject_candidates_in("../escape", projects).is_empty());
+}
+
+#[test]
+fn project_candidates_reached_twice_count_once() {
+    let tmp = tempfile::tempdir().unwrap();
+    let projects = &fs::canonicalize(tmp.path()).unwrap();
+    touch(&projects.join("MyGame/MyGame.uproject"), "{}");
+    let expected = vec![projects.join("MyGame/MyGame.uproject")];
+
+    // `..` spellings resolve to the same file
+    fs::create_dir_all(projects.join("Sub")).unwrap();
+    assert_eq!(fs_scan::uproject_candidates_in("Sub/../MyGame", projects), expected);
+    // A root given with `..` still yields canonical candidates
+    assert_eq!(fs_scan::uproject_candidates_in("MyGame", &projects.join("Sub/..")), expected);
+
+    #[cfg(unix)]
+    {
+        // A symlinked folder exposing the same project is not a second candidate
+        std::os::unix::fs::symlink(projects.join("MyGame"), projects.join("MyGame_Link")).unwrap();
+        assert_eq!(fs_scan::uproject_candidates_in("MyGame", projects), expected);
+        // ...and one pointing outside the projects root is not a candidate at all
+        let outside = tempfile::tempdir().unwrap();
+        touch(&outside.path().join("MyGame.uproject"), "{}");
+        std::os::unix::fs::symlink(outside.path(), projects.join("Elsewhere")).unwrap();
+        assert_eq!(fs_scan::uproject_candidates_in("MyGame", projects), expected);
+    }
+}
+
+#[test]
+fn write_probe_creates_the_dir_and_cleans_up() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path().join("cache/nested");
+    assert!(fs_scan::dir_writable(&dir));
+    assert!(dir.is_dir());
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), 0, "probe file must be removed");
+
+    // A regular file where the directory should be is not writable
+    let file = tmp.path().join("not_a_dir");
+    touch(&file, "x");
+    assert!(!fs_scan::dir_writable(&file));
+    assert!(!fs_scan::dir_writable(&file.join("child")));
+}
+
+#[test]
+fn configured_dir_checks_do_not_create_anything() {
+    let tmp = tempfile::tempdir().unwrap();
+    assert_eq!(fs_scan::check_configured_dir(tmp.path(), true), Ok(String::new()));
+
+    let missing = tmp.path().join("a/b");
+    let note = fs_scan::check_configured_dir(&missing, true).unwrap();
+    assert!(note.contains("will be created"), "{}", note);
+    assert!(!tmp.path().join("a").exists(), "checking must not create the directory");
+
+    let file = tmp.path().join("file");
+    touch(&file, "x");
+    assert!(fs_scan::check_configured_dir(&file, false).unwrap_err().contains("not a directory"));
+    assert!(fs_scan::check_configured_dir(&file.join("child"), false).unwrap_err().contains("not a directory"));
+    assert!(fs_scan::check_configured_dir(Path::new("relative/dir"), false).unwrap_err().contains("absolute"));
+}
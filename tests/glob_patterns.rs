@@ -0,0 +1,102 @@
+// Tests for the manifest skip-pattern and include/exclude globs (src/utils/glob.rs).
+
+#[path = "../src/utils/glob.rs"]
+mod glob;
+
+use glob::{is_selected, parse_patterns, select_files, PatternSet};
+
+fn set(raw: &str) -> PatternSet {
+    PatternSet::new(&parse_patterns(raw)).unwrap()
+}
+
+fn glob_match(pattern: &str, path: &str) -> bool {
+    set(pattern).first_match(path).is_some()
+}
+
+#[test]
+fn star_stays_within_a_segment() {
+    assert!(glob_match("*.pdb", "Game.pdb"));
+    assert!(!glob_match("*.pdb", "Binaries/Win64/Game.pdb"));
+    assert!(glob_match("Binaries/*/Game.pdb", "Binaries/Win64/Game.pdb"));
+}
+
+#[test]
+fn double_star_spans_directories() {
+    assert!(glob_match("**/*.pdb", "Binaries/Win64/Game.pdb"));
+    assert!(glob_match("**/*.pdb", "Game.pdb"));
+    assert!(glob_match("**/Win64/**", "Plugin/Binaries/Win64/x.dll"));
+    assert!(!glob_match("**/Win64/**", "Plugin/Binaries/Linux/x.so"));
+}
+
+#[test]
+fn question_mark_and_case_and_separators() {
+    assert!(glob_match("Content/Map?.umap", "content/MAP1.umap"));
+    assert!(!glob_match("Content/Map?.umap", "Content/Map12.umap"));
+    assert!(glob_match("**/mac/**", "Binaries\\Mac\\lib.dylib"));
+}
+
+#[test]
+fn pattern_lists() {
+    let pats = parse_patterns(" **/Win64/** ; **/Mac/**, ,");
+    assert_eq!(pats, vec!["**/Win64/**".to_string(), "**/Mac/**".to_string()]);
+    let compiled = PatternSet::new(&pats).unwrap();
+    assert_eq!(compiled.first_match("A/Mac/b"), Some("**/Mac/**"));
+    assert_eq!(compiled.first_match("A/Win64/Mac/b"), Some("**/Win64/**"));
+    assert_eq!(compiled.first_match("A/Linux/b"), None);
+}
+
+#[test]
+fn include_exclude_filter() {
+    let include = set("**/Content/**");
+    let exclude = set("**/*.umap");
+    let none = PatternSet::default();
+    assert!(is_selected(&include, &exclude, "Pack/Content/Meshes/Rock.uasset"));
+    assert!(!is_selected(&include, &exclude, "Pack/Content/Maps/Demo.umap"));
+    assert!(!is_selected(&include, &exclude, "Pack/Config/DefaultGame.ini"));
+    assert!(is_selected(&none, &exclude, "Pack/Config/DefaultGame.ini"));
+    assert!(is_selected(&none, &none, "anything"));
+}
+
+#[test]
+fn globset_syntax_and_invalid_patterns() {
+    assert!(glob_match("**/*.{umap,uasset}", "Pack/Content/Demo.UMAP"));
+    assert_eq!(parse_patterns("**/*.{umap,uasset}, *.pdb"), vec!["**/*.{umap,uasset}", "*.pdb"]);
+    assert!(glob_match("Binaries/Win[0-9][0-9]/*", "Binaries/Win64/Game.dll"));
+    let err = PatternSet::new(&["Content/[".to_string()]).err().unwrap();
+    assert!(err.contains("Content/["), "{}", err);
+}
+
+fn manifest() -> Vec<String> {
+    ["Pack/Content/Rock.uasset", "Pack/Content/Demo.umap", "Pack/Config/DefaultGame.ini", "Pack/Binaries/Win64/x.pdb"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+
+#[test]
+fn include_filtered_download_is_marked_partial() {
+    let (include, exclude, skip) = (set("**/Content/**"), PatternSet::default(), PatternSet::default());
+    let sel = select_files(manifest(), &include, &exclude, &skip);
+    assert_eq!(sel.selected, vec!["Pack/Content/Demo.umap", "Pack/Content/Rock.uasset"]);
+    assert_eq!(sel.excluded_by_filter, vec!["Pack/Binaries/Win64/x.pdb", "Pack/Config/DefaultGame.ini"]);
+    assert!(sel.skipped_by_pattern.is_empty());
+
+    let marker = sel.complete_marker(&include, &exclude, &skip, Vec::new());
+    assert_eq!(marker["partial"], true);
+    assert_eq!(marker["include_patterns"], serde_json::json!(["**/Content/**"]));
+    assert_eq!(marker["excluded_files"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn skip_patterns_and_full_downloads() {
+    let none = PatternSet::default();
+    let skip = set("**/*.pdb");
+    let sel = select_files(manifest(), &none, &set("**/*.umap"), &skip);
+    assert_eq!(sel.excluded_by_filter, vec!["Pack/Content/Demo.umap"]);
+    assert_eq!(sel.skipped_by_pattern, vec!["Pack/Binaries/Win64/x.pdb"]);
+    assert!(sel.complete_marker(&none, &none, &skip, Vec::new())["partial"].as_bool().unwrap());
+
+    let full = select_files(manifest(), &none, &none, &none);
+    assert_eq!(full.selected.len(), 4);
+    assert_eq!(full.complete_marker(&none, &none, &none, Vec::new())["partial"], false);
+}
@@ -0,0 +1,62 @@
+// Running /import-assets batches in order (src/utils/batch.rs): continue_on_error, partial failure and cancel.
+
+#[path = "../src/utils/batch.rs"]
+mod batch;
+
+use batch::{run_in_order, ItemOutcome};
+
+/// Runs a batch where each item is its planned outcome; results read "ran:<item>" or "skipped:<why>".
+async fn run(items: &[&'static str], continue_on_error: bool) -> Vec<String> {
+    run_in_order(
+        items.to_vec(),
+        continue_on_error,
+        |_, item| async move {
+            let result = format!("ran:{}", item);
+            match item {
+                "fail" => ItemOutcome::Failed(result),
+                "cancel" => ItemOutcome::Cancelled(result),
+                _ => ItemOutcome::Done(result),
+            }
+        },
+        |_, why| format!("skipped:{:?}", why),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn every_item_runs_when_all_succeed() {
+    assert_eq!(run(&["a", "b", "c"], false).await, vec!["ran:a", "ran:b", "ran:c"]);
+}
+
+#[tokio::test]
+async fn a_failure_stops_the_batch_by_default() {
+    assert_eq!(run(&["a", "fail", "b", "c"], false).await, vec!["ran:a", "ran:fail", "skipped:EarlierFailure", "skipped:EarlierFailure"]);
+}
+
+#[tokio::test]
+async fn continue_on_error_runs_past_failures() {
+    assert_eq!(run(&["fail", "a", "fail", "b"], true).await, vec!["ran:fail", "ran:a", "ran:fail", "ran:b"]);
+}
+
+#[tokio::test]
+async fn a_cancel_stops_the_batch_even_with_continue_on_error() {
+    let results = run(&["a", "cancel", "b", "fail"], true).await;
+    assert_eq!(results, vec!["ran:a", "ran:cancel", "skipped:Cancelled", "skipped:Cancelled"]);
+}
+
+#[tokio::test]
+async fn items_run_one_at_a_time_with_their_index() {
+    let mut seen = Vec::new();
+    let results = run_in_order(
+        vec!["x", "y"],
+        false,
+        |i, item| {
+            seen.push(i);
+            std::future::ready(ItemOutcome::Done(item))
+        },
+        |item, _| item,
+    )
+    .await;
+    assert_eq!(results, vec!["x", "y"]);
+    assert_eq!(seen, vec![0, 1]);
+}
@@ -0,0 +1,99 @@
+// Server-side Fab library search (src/utils/library_filter.rs) over cached library JSON.
+
+#[path = "../src/utils/library_filter.rs"]
+mod library_filter;
+
+use library_filter::{paginate, search_results, LibraryFilter, ListPage, SortKey};
+use serde_json::json;
+
+fn library() -> serde_json::Value {
+    json!({
+        "results": [
+            { "title": "Industry Props Pack", "downloaded": true,
+              "projectVersions": [ { "engineVersions": ["UE_5.3", "UE_5.4"] } ] },
+            { "title": "Forest Environment", "downloaded": false,
+              "projectVersions": [ { "engineVersions": ["UE_5.5"] }, { "engineVersions": ["UE_5.6"] } ] },
+            { "title": "Props Starter" },
+            { "projectVersions": "not-an-array" }
+        ]
+    })
+}
+
+fn titles(found: &[serde_json::Value]) -> Vec<&str> {
+    found.iter().map(|a| a["title"].as_str().unwrap_or("")).collect()
+}
+
+#[test]
+fn empty_filter_matches_everything() {
+    assert_eq!(search_results(&library(), &LibraryFilter::default()).len(), 4);
+    assert!(search_results(&json!({}), &LibraryFilter::default()).is_empty());
+}
+
+#[test]
+fn filters_by_title_downloaded_and_engine() {
+    let lib = library();
+    let by_text = LibraryFilter { text: Some("PROPS".into()), ..Default::default() };
+    assert_eq!(titles(&search_results(&lib, &by_text)), vec!["Industry Props Pack", "Props Starter"]);
+
+    // A missing downloaded flag counts as not downloaded
+    let not_downloaded = LibraryFilter { downloaded: Some(false), ..Default::default() };
+    assert_eq!(titles(&search_results(&lib, &not_downloaded)), vec!["Forest Environment", "Props Starter", ""]);
+
+    let engine = LibraryFilter { engine: Some("5.6".into()), ..Default::default() };
+    assert_eq!(titles(&search_results(&lib, &engine)), vec!["Forest Environment"]);
+    let prefixed = LibraryFilter { engine: Some("UE_5.4".into()), ..Default::default() };
+    assert_eq!(titles(&search_results(&lib, &prefixed)), vec!["Industry Props Pack"]);
+
+    let combined = LibraryFilter { text: Some("props".into()), downloaded: Some(true), engine: Some("5.3".into()) };
+    assert_eq!(titles(&search_results(&lib, &combined)), vec!["Industry Props Pack"]);
+    let none = LibraryFilter { text: Some("props".into()), engine: Some("5.5".into()), ..Default::default() };
+    assert!(search_results(&lib, &none).is_empty());
+}
+
+fn sized_library() -> serde_json::Value {
+    json!({
+        "results": [
+            { "title": "beta", "downloaded": true, "downloadedBytes": 300 },
+            { "title": "Alpha", "downloaded": false },
+            { "title": "gamma", "downloaded": true, "downloadedBytes": 300 },
+            { "title": "Delta", "downloaded": false, "downloadedBytes": 0 },
+            { "title": "epsilon", "downloaded": true, "downloadedBytes": 50 }
+        ]
+    })
+}
+
+fn paged_titles(page: &serde_json::Value) -> Vec<&str> {
+    page["results"].as_array().unwrap().iter().map(|a| a["title"].as_str().unwrap()).collect()
+}
+
+#[test]
+fn sorts_by_title_and_size_with_stable_ties() {
+    let lib = sized_library();
+    let by_title = paginate(&lib, &ListPage { sort: SortKey::parse("TITLE"), ..Default::default() });
+    assert_eq!(paged_titles(&by_title), vec!["Alpha", "beta", "Delta", "epsilon", "gamma"]);
+
+    // Missing sizes count as 0; equal sizes keep library order in both directions
+    let asc = paginate(&lib, &ListPage { sort: Some(SortKey::Size), ..Default::default() });
+    assert_eq!(paged_titles(&asc), vec!["Alpha", "Delta", "epsilon", "beta", "gamma"]);
+    let desc = paginate(&lib, &ListPage { sort: Some(SortKey::Size), descending: true, ..Default::default() });
+    assert_eq!(paged_titles(&desc), vec!["beta", "gamma", "epsilon", "Alpha", "Delta"]);
+
+    let downloaded_first = paginate(&lib, &ListPage { sort: Some(SortKey::Downloaded), descending: true, ..Default::default() });
+    assert_eq!(paged_titles(&downloaded_first), vec!["beta", "gamma", "epsilon", "Alpha", "Delta"]);
+    assert_eq!(SortKey::parse("rating"), None);
+}
+
+#[test]
+fn pages_report_total_before_slicing() {
+    let lib = sized_library();
+    let page = paginate(&lib, &ListPage { sort: Some(SortKey::Title), offset: 1, limit: Some(2), ..Default::default() });
+    assert_eq!(page["total"], 5);
+    assert_eq!(page["offset"], 1);
+    assert_eq!(page["limit"], 2);
+    assert_eq!(paged_titles(&page), vec!["beta", "Delta"]);
+
+    let past_end = paginate(&lib, &ListPage { offset: 10, ..Default::default() });
+    assert_eq!(past_end["total"], 5);
+    assert!(past_end["limit"].is_null());
+    assert!(paged_titles(&past_end).is_empty());
+}
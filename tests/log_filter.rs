@@ -0,0 +1,58 @@
+// Runtime log filter in RUST_LOG syntax (src/utils/log_filter.rs).
+
+#[path = "../src/utils/log_filter.rs"]
+mod log_filter;
+
+use log_filter::{Level, LogFilter};
+
+#[test]
+fn parses_rust_log_directives() {
+    let f = LogFilter::parse("info, download=debug ,egs_client::utils=warn,actix_web,bogus=loud");
+    assert_eq!(f.level_for("egs_client::api"), Level::Info);
+    assert_eq!(f.level_for("download"), Level::Debug);
+    assert_eq!(f.level_for("egs_client::utils"), Level::Warn);
+    // Bare target means everything for it; an unknown level is ignored
+    assert_eq!(f.level_for("actix_web::server"), Level::Trace);
+    assert_eq!(f.level_for("bogus"), Level::Info);
+    assert_eq!(f.max_level(), Level::Trace);
+
+    assert_eq!(LogFilter::parse(""), LogFilter::default());
+    assert_eq!(LogFilter::default().level_for("anything"), Level::Error);
+}
+
+#[test]
+fn unset_rust_log_means_info() {
+    for unset in [None, Some(""), Some("  ")] {
+        let f = LogFilter::from_rust_log(unset);
+        assert_eq!(f.level_for("egs_client"), Level::Info);
+        assert!(!f.enabled("download", Level::Debug));
+    }
+    assert_eq!(LogFilter::from_rust_log(Some("warn,download=debug")), LogFilter::parse("warn,download=debug"));
+}
+
+#[test]
+fn most_specific_target_wins_on_module_boundaries() {
+    let mut f = LogFilter::parse("warn,egs_client=info");
+    f.set(Some("egs_client::utils"), Level::Trace);
+    assert_eq!(f.level_for("egs_client::utils::fs_scan"), Level::Trace);
+    assert_eq!(f.level_for("egs_client::api"), Level::Info);
+    // "egs_client_extra" is not a child of "egs_client"
+    assert_eq!(f.level_for("egs_client_extra"), Level::Warn);
+
+    assert!(f.enabled("egs_client::utils", Level::Debug));
+    assert!(!f.enabled("egs_client::api", Level::Debug));
+    assert!(!f.enabled("egs_client::api", Level::Off));
+}
+
+#[test]
+fn runtime_changes_round_trip_through_spec() {
+    let mut f = LogFilter::parse("info");
+    f.set(None, Level::Debug);
+    f.set(Some("download"), Level::Off);
+    f.set(Some("  "), Level::Warn); // blank target changes the default
+    assert_eq!(f.spec(), "warn,download=off");
+    assert_eq!(LogFilter::parse(&f.spec()), f);
+    assert!(!f.enabled("download", Level::Error));
+    assert_eq!(Level::parse("DEBUG"), Some(Level::Debug));
+    assert_eq!(Level::parse("verbose"), None);
+}
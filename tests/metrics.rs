@@ -0,0 +1,51 @@
+// Download counters rendered for GET /metrics (src/utils/metrics.rs).
+
+#[path = "../src/utils/throughput.rs"]
+mod throughput;
+#[path = "../src/utils/metrics.rs"]
+mod metrics;
+
+use metrics::{DownloadMetrics, Outcome};
+use std::time::{Duration, Instant};
+
+fn value(text: &str, name: &str) -> String {
+    text.lines()
+        .find_map(|l| l.strip_prefix(name).and_then(|rest| rest.strip_prefix(' ')))
+        .unwrap_or_else(|| panic!("{} missing from\n{}", name, text))
+        .to_string()
+}
+
+#[test]
+fn counts_outcomes_and_active_downloads() {
+    let m = DownloadMetrics::default();
+    for _ in 0..4 {
+        m.started();
+    }
+    m.finished(Outcome::Completed);
+    m.finished(Outcome::Failed);
+    m.finished(Outcome::Cancelled);
+    m.add_bytes(1500);
+    assert_eq!(m.active(), 1);
+
+    let text = m.render(Instant::now(), 2);
+    assert_eq!(value(&text, "egs_downloads_started_total"), "4");
+    assert_eq!(value(&text, "egs_downloads_completed_total"), "1");
+    assert_eq!(value(&text, "egs_downloads_failed_total"), "1");
+    assert_eq!(value(&text, "egs_downloads_cancelled_total"), "1");
+    assert_eq!(value(&text, "egs_downloaded_bytes_total"), "1500");
+    assert_eq!(value(&text, "egs_downloads_active"), "1");
+    assert_eq!(value(&text, "egs_jobs_active"), "2");
+    assert!(text.contains("# TYPE egs_downloads_started_total counter\n"));
+    assert!(text.contains("# TYPE egs_download_bytes_per_second gauge\n"));
+    assert!(text.ends_with('\n'));
+}
+
+#[test]
+fn throughput_is_measured_between_samples() {
+    let m = DownloadMetrics::default();
+    let t0 = Instant::now();
+    assert_eq!(m.sample(t0), 0.0);
+    m.add_bytes(4000);
+    let text = m.render(t0 + Duration::from_secs(2), 0);
+    assert_eq!(value(&text, "egs_download_bytes_per_second"), "2000");
+}
@@ -0,0 +1,96 @@
+// Parallel import copy (src/utils/parallel_copy.rs) over a large synthetic tree.
+
+#[path = "../src/utils/conflict.rs"]
+mod conflict;
+#[path = "../src/utils/parallel_copy.rs"]
+mod parallel_copy;
+
+use conflict::ConflictPolicy;
+use std::fs;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[test]
+fn copies_large_tree_with_correct_counts_and_content() {
+    let tmp = tempfile::tempdir().unwrap();
+    let src = tmp.path().join("src");
+    let dst = tmp.path().join("dst");
+    for d in 0..20 {
+        for f in 0..100 {
+            let dir = src.join(format!("dir{}", d)).join("nested");
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join(format!("f{}.bin", f)), format!("{}-{}", d, f)).unwrap();
+        }
+    }
+    // Pre-existing file must be skipped (not overwritten) without overwrite
+    fs::create_dir_all(dst.join("dir0").join("nested")).unwrap();
+    fs::write(dst.join("dir0").join("nested").join("f0.bin"), "keep").unwrap();
+
+    let max_seen = AtomicUsize::new(0);
+    let counts = parallel_copy::copy_tree_parallel(
+        &src,
+        &dst,
+        ConflictPolicy::Skip,
+        8,
+        &|done, total| {
+            assert!(done <= total);
+            max_seen.fetch_max(done, Ordering::SeqCst);
+        },
+        &|| false,
+    )
+    .unwrap();
+
+    assert_eq!(counts.copied, 1999);
+    assert_eq!(counts.skipped, 1);
+    assert_eq!(max_seen.load(Ordering::SeqCst), 2000);
+    assert_eq!(fs::read_to_string(dst.join("dir0/nested/f0.bin")).unwrap(), "keep");
+    for d in 0..20 {
+        for f in 0..100 {
+            if d == 0 && f == 0 { continue; }
+            let p = dst.join(format!("dir{}/nested/f{}.bin", d, f));
+            assert_eq!(fs::read_to_string(p).unwrap(), format!("{}-{}", d, f));
+        }
+    }
+}
+
+#[test]
+fn overwrite_replaces_existing_and_cancel_interrupts() {
+    let tmp = tempfile::tempdir().unwrap();
+    let src = tmp.path().join("src");
+    let dst = tmp.path().join("dst");
+    fs::create_dir_all(&src).unwrap();
+    fs::create_dir_all(&dst).unwrap();
+    fs::write(src.join("a.txt"), "new").unwrap();
+    fs::write(dst.join("a.txt"), "old").unwrap();
+
+    let counts = parallel_copy::copy_tree_parallel(&src, &dst, ConflictPolicy::Overwrite, 4, &|_, _| {}, &|| false).unwrap();
+    assert_eq!((counts.copied, counts.skipped, counts.overwritten), (1, 0, 1));
+    assert_eq!(fs::read_to_string(dst.join("a.txt")).unwrap(), "new");
+
+    let err = parallel_copy::copy_tree_parallel(&src, &dst, ConflictPolicy::Overwrite, 4, &|_, _| {}, &|| true).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::Interrupted);
+}
+
+#[test]
+fn plan_counts_like_the_copy_without_writing() {
+    let tmp = tempfile::tempdir().unwrap();
+    let src = tmp.path().join("src");
+    let dst = tmp.path().join("dst");
+    fs::create_dir_all(src.join("Maps")).unwrap();
+    fs::create_dir_all(dst.join("Maps")).unwrap();
+    fs::write(src.join("a.uasset"), "a").unwrap();
+    fs::write(src.join("Maps/m.umap"), "m").unwrap();
+    fs::write(src.join("Maps/n.umap"), "n").unwrap();
+    fs::write(dst.join("Maps/m.umap"), "old").unwrap();
+
+    let plan = |dst: &std::path::Path, policy| {
+        let c = parallel_copy::plan_copy(&src, dst, policy).unwrap();
+        (c.copied, c.skipped)
+    };
+    assert_eq!(plan(&dst, ConflictPolicy::Skip), (2, 1));
+    assert_eq!(plan(&dst, ConflictPolicy::Overwrite), (3, 0));
+    // A destination that doesn't exist yet gets everything
+    assert_eq!(plan(&tmp.path().join("missing"), ConflictPolicy::Skip), (3, 0));
+    assert!(!dst.join("a.uasset").exists());
+    assert!(!tmp.path().join("missing").exists());
+    assert_eq!(fs::read_to_string(dst.join("Maps/m.umap")).unwrap(), "old");
+}
@@ -0,0 +1,106 @@
+// Concurrent verification of files already on disk (src/utils/verify_existing.rs).
+
+#[path = "../src/utils/file_hash.rs"]
+mod file_hash;
+#[path = "../src/utils/verify_cache.rs"]
+mod verify_cache;
+#[path = "../src/utils/verify_existing.rs"]
+mod verify_existing;
+
+use sha1::{Digest, Sha1};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use verify_cache::VerifyCache;
+use verify_existing::{verify_files, ExistingFileCheck};
+
+fn sha1_hex(data: &[u8]) -> String {
+    Sha1::digest(data).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// 16 files of varying sizes (one spanning several read blocks) with checks carrying their real SHA1.
+fn write_files(dir: &Path) -> Vec<ExistingFileCheck> {
+    (0..16usize)
+        .map(|i| {
+            let len = (i * 7919 + 1) * if i == 15 { 400 } else { 1 };
+            let data: Vec<u8> = (0..len).map(|b| (b % 251) as u8 ^ i as u8).collect();
+            let path = dir.join(format!("file{}.bin", i));
+            fs::write(&path, &data).unwrap();
+            ExistingFileCheck { filename: format!("file{}.bin", i), path, expected_hash: sha1_hex(&data), expected_size: len as u64 }
+        })
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn concurrent_pass_accepts_intact_files_and_rejects_bad_ones() {
+    let tmp = tempfile::tempdir().unwrap();
+    let mut checks = write_files(tmp.path());
+    checks[3].expected_hash = "0".repeat(40);
+    checks[7].expected_size += 1;
+    checks[9].expected_hash = "not-a-digest".to_string();
+    let all: HashSet<String> = checks.iter().map(|c| c.filename.clone()).collect();
+
+    let mut cache = VerifyCache::load(tmp.path());
+    let mut calls = 0;
+    // EAM_HASH_CONCURRENCY / EAM_TRUST_EXISTING are unset here: 4 hashes at a time, no trusting
+    assert!(!verify_existing::trust_existing());
+    let (ok, remembered) = verify_files(checks, &mut cache, verify_existing::hash_concurrency(), false, |done, total| {
+        calls += 1;
+        assert!(done <= total && total == 16);
+        true
+    })
+    .await;
+
+    let expected: HashSet<String> = all.into_iter().filter(|n| n != "file3.bin" && n != "file7.bin").collect();
+    assert_eq!(ok, expected);
+    assert_eq!(remembered, 0);
+    assert_eq!(calls, 16);
+    // Passed hash checks are remembered; unsupported formats only passed the size check
+    let stamp = verify_cache::file_stamp(&tmp.path().join("file0.bin")).unwrap();
+    let file0 = fs::read(tmp.path().join("file0.bin")).unwrap();
+    assert!(cache.is_fresh("file0.bin", &sha1_hex(&file0), stamp));
+    let stamp9 = verify_cache::file_stamp(&tmp.path().join("file9.bin")).unwrap();
+    assert!(!cache.is_fresh("file9.bin", "not-a-digest", stamp9));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn remembered_files_are_not_hashed_again() {
+    let tmp = tempfile::tempdir().unwrap();
+    let checks = write_files(tmp.path());
+    let mut cache = VerifyCache::load(tmp.path());
+    let (first, remembered) = verify_files(checks.clone(), &mut cache, 2, false, |_, _| true).await;
+    assert_eq!((first.len(), remembered), (16, 0));
+    cache.save().unwrap();
+
+    let mut cache = VerifyCache::load(tmp.path());
+    let (second, remembered) = verify_files(checks, &mut cache, 2, false, |_, _| true).await;
+    assert_eq!(second, first);
+    assert_eq!(remembered, 16, "unchanged files should be trusted from the cache");
+}
+
+#[tokio::test]
+async fn trust_existing_only_checks_sizes() {
+    let tmp = tempfile::tempdir().unwrap();
+    let mut checks = write_files(tmp.path());
+    checks[0].expected_hash = "0".repeat(40);
+    checks[1].expected_size += 1;
+    let mut cache = VerifyCache::load(tmp.path());
+    let (ok, _) = verify_files(checks, &mut cache, 4, true, |_, _| true).await;
+    assert!(ok.contains("file0.bin"));
+    assert!(!ok.contains("file1.bin"));
+    assert_eq!(ok.len(), 15);
+}
+
+#[tokio::test]
+async fn stopping_from_progress_ends_the_pass() {
+    let tmp = tempfile::tempdir().unwrap();
+    let mut cache = VerifyCache::load(tmp.path());
+    let mut calls = 0;
+    let (ok, _) = verify_files(write_files(tmp.path()), &mut cache, 1, false, |_, _| {
+        calls += 1;
+        false
+    })
+    .await;
+    assert_eq!(calls, 1);
+    assert!(ok.len() <= 1);
+}
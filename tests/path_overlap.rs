@@ -0,0 +1,81 @@
+// Overlap detection for cache/downloads directories (src/utils/paths.rs).
+
+#[path = "../src/utils/paths.rs"]
+mod paths;
+
+use std::path::Path;
+
+#[test]
+fn detects_equal_and_nested_directories() {
+    assert!(paths::overlap(Path::new("/data/egs"), Path::new("/data/egs")));
+    assert!(paths::overlap(Path::new("/data/egs"), Path::new("/data/egs/downloads")));
+    assert!(paths::overlap(Path::new("/data/egs/cache/../downloads"), Path::new("/data/egs/downloads/./x")));
+    assert!(!paths::overlap(Path::new("/data/egs/cache"), Path::new("/data/egs/downloads")));
+    // Component-wise, not string prefix
+    assert!(!paths::overlap(Path::new("/data/egs"), Path::new("/data/egs2")));
+}
+
+#[test]
+fn symlinked_spelling_is_detected() {
+    let tmp = tempfile::tempdir().unwrap();
+    let real = tmp.path().join("real");
+    std::fs::create_dir_all(&real).unwrap();
+    #[cfg(unix)]
+    {
+        let link = tmp.path().join("link");
+        std::os::unix::fs::symlink(&real, &link).unwrap();
+        assert!(paths::overlap(&link, &real.join("sub")));
+    }
+}
+
+#[test]
+fn resolve_within_root_accepts_nested_paths() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path().join("downloads");
+    std::fs::create_dir_all(root.join("Asset/5.6")).unwrap();
+    let canon = std::fs::canonicalize(&root).unwrap();
+    assert_eq!(paths::resolve_within_root(&root, Path::new("Asset/5.6")).unwrap(), canon.join("Asset/5.6"));
+    // Not-yet-existing destinations are fine as long as they stay inside
+    assert_eq!(paths::resolve_within_root(&root, Path::new("Asset/new/dir")).unwrap(), canon.join("Asset/new/dir"));
+    // `..` that stays inside the root is allowed; the root itself resolves too
+    assert_eq!(paths::resolve_within_root(&root, Path::new("Asset/../Asset")).unwrap(), canon.join("Asset"));
+    assert_eq!(paths::resolve_within_root(&root, Path::new("")).unwrap(), canon);
+    // Absolute paths inside the root are accepted, as are paths already joined onto the root
+    assert!(paths::resolve_within_root(&root, &canon.join("Asset")).is_ok());
+    assert_eq!(paths::resolve_within_root(&root, &root.join("Asset/5.6")).unwrap(), canon.join("Asset/5.6"));
+    assert!(paths::resolve_within_root(&root, &root.join("../secret")).is_err());
+}
+
+#[test]
+fn resolve_within_root_rejects_traversal() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path().join("downloads");
+    std::fs::create_dir_all(root.join("Asset")).unwrap();
+    std::fs::create_dir_all(tmp.path().join("secret")).unwrap();
+    for bad in ["..", "../secret", "Asset/../../secret", "missing/../../secret", "Asset/missing/../../.."] {
+        assert!(paths::resolve_within_root(&root, Path::new(bad)).is_err(), "{} should be rejected", bad);
+    }
+    assert!(paths::resolve_within_root(&root, &tmp.path().join("secret")).is_err());
+    assert!(paths::resolve_within_root(&root, Path::new("/etc")).is_err());
+    // A missing root is an error rather than a pass
+    assert!(paths::resolve_within_root(&tmp.path().join("nope"), Path::new("x")).is_err());
+}
+
+#[test]
+fn resolve_within_root_rejects_symlink_escapes() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path().join("projects");
+    let outside = tmp.path().join("outside");
+    std::fs::create_dir_all(&root).unwrap();
+    std::fs::create_dir_all(&outside).unwrap();
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(&outside, root.join("escape")).unwrap();
+        assert!(paths::resolve_within_root(&root, Path::new("escape")).is_err());
+        assert!(paths::resolve_within_root(&root, Path::new("escape/new")).is_err());
+        // A symlink that points back inside the root is fine
+        std::fs::create_dir_all(root.join("real")).unwrap();
+        std::os::unix::fs::symlink(root.join("real"), root.join("alias")).unwrap();
+        assert!(paths::resolve_within_root(&root, Path::new("alias")).is_ok());
+    }
+}
@@ -0,0 +1,70 @@
+// Pause/resume registry (src/utils/pause.rs): paused waiters block until resume or cancel, and only
+// jobs that are still running can be paused (src/models/mod.rs).
+
+#[allow(dead_code)]
+#[path = "../src/models/mod.rs"]
+mod models;
+#[path = "../src/utils/pause.rs"]
+mod pause;
+
+use pause::PauseRegistry;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[tokio::test]
+async fn unpaused_jobs_do_not_wait() {
+    let reg = PauseRegistry::new();
+    assert!(!reg.is_paused("job"));
+    assert!(!reg.wait_while_paused("job", &|| false).await);
+    assert!(!reg.resume("job"), "resuming a job that is not paused is a no-op");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn waiter_blocks_until_resume() {
+    let reg = Arc::new(PauseRegistry::new());
+    assert!(reg.pause("job"));
+    assert!(!reg.pause("job"), "second pause reports already paused");
+
+    let waiter = {
+        let reg = reg.clone();
+        tokio::spawn(async move { reg.wait_while_paused("job", &|| false).await })
+    };
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(!waiter.is_finished());
+
+    assert!(reg.resume("job"));
+    let waited = tokio::time::timeout(Duration::from_secs(2), waiter).await.unwrap().unwrap();
+    assert!(waited);
+    assert!(!reg.is_paused("job"));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn cancel_ends_the_wait() {
+    let reg = Arc::new(PauseRegistry::new());
+    reg.pause("job");
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let waiter = {
+        let reg = reg.clone();
+        let cancelled = cancelled.clone();
+        tokio::spawn(async move { reg.wait_while_paused("job", &|| cancelled.load(Ordering::SeqCst)).await })
+    };
+    cancelled.store(true, Ordering::SeqCst);
+    // No resume: the periodic cancel check must release the waiter
+    assert!(tokio::time::timeout(Duration::from_secs(2), waiter).await.unwrap().is_ok());
+    assert!(reg.is_paused("job"));
+}
+
+#[test]
+fn finished_jobs_cannot_be_paused() {
+    use models::{JobState, Phase};
+    for phase in [Phase::DownloadComplete, Phase::DownloadError, Phase::Cancelled, Phase::EditorExited] {
+        assert!(phase.is_terminal());
+        assert!(JobState::derive(phase.as_str(), false).is_finished(), "{} ends the job", phase.as_str());
+    }
+    for phase in [Phase::DownloadProgress, Phase::DownloadPaused, Phase::DownloadStart] {
+        assert!(!JobState::derive(phase.as_str(), false).is_finished(), "{} is still running", phase.as_str());
+    }
+    // A cancel that has not been acknowledged yet still counts as running
+    assert!(!JobState::derive(Phase::Cancelled.as_str(), true).is_finished());
+}
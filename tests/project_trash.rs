@@ -0,0 +1,78 @@
+// Deleting and archiving Unreal projects (src/utils/project_trash.rs).
+
+#[path = "../src/utils/project_trash.rs"]
+mod project_trash;
+
+use std::fs;
+
+#[test]
+fn only_folders_with_a_uproject_count_as_projects() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = tmp.path().join("MyGame");
+    fs::create_dir_all(project.join("Content")).unwrap();
+    assert!(!project_trash::contains_uproject(&project));
+    // A .uproject deeper down does not make the parent a project
+    fs::write(project.join("Content/Nested.uproject"), "{}").unwrap();
+    assert!(!project_trash::contains_uproject(&project));
+    assert!(!project_trash::contains_uproject(tmp.path()));
+    fs::write(project.join("MyGame.uproject"), "{}").unwrap();
+    assert!(project_trash::contains_uproject(&project));
+    assert!(!project_trash::contains_uproject(&tmp.path().join("missing")));
+}
+
+#[test]
+fn archive_destinations_do_not_collide() {
+    let tmp = tempfile::tempdir().unwrap();
+    let trash = tmp.path().join(project_trash::TRASH_DIR_NAME);
+    let first = project_trash::archive_destination(&trash, "MyGame", 1700000000);
+    assert_eq!(first, trash.join("MyGame-1700000000"));
+    fs::create_dir_all(&first).unwrap();
+    assert_eq!(project_trash::archive_destination(&trash, "MyGame", 1700000000), trash.join("MyGame-1700000000-2"));
+}
+
+#[test]
+fn move_dir_keeps_the_tree() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = tmp.path().join("MyGame");
+    fs::create_dir_all(project.join("Content/Maps")).unwrap();
+    fs::write(project.join("MyGame.uproject"), "{}").unwrap();
+    fs::write(project.join("Content/Maps/Main.umap"), "map").unwrap();
+
+    let dest = tmp.path().join(".trash/MyGame-1");
+    project_trash::move_dir(&project, &dest).unwrap();
+    assert!(!project.exists());
+    assert_eq!(fs::read_to_string(dest.join("Content/Maps/Main.umap")).unwrap(), "map");
+    assert!(project_trash::contains_uproject(&dest));
+}
+
+#[test]
+fn only_folders_inside_the_projects_dir_may_be_deleted() {
+    let tmp = tempfile::tempdir().unwrap();
+    let home = fs::canonicalize(tmp.path()).unwrap();
+    let projects = home.join("Unreal Projects");
+    let project = projects.join("MyGame");
+    fs::create_dir_all(&project).unwrap();
+    let elsewhere = home.join("Elsewhere/Other");
+    fs::create_dir_all(&elsewhere).unwrap();
+    let roots = vec![projects.clone()];
+
+    assert_eq!(project_trash::check_delete_target(&project, &roots), Ok(project.clone()));
+    // `..` spellings are resolved before checking
+    assert_eq!(project_trash::check_delete_target(&projects.join("MyGame/../MyGame"), &roots), Ok(project.clone()));
+    // The projects dir itself and anything above it are never deletable
+    assert_eq!(project_trash::check_delete_target(&projects, &roots), Err(project_trash::DeleteRefusal::ContainsRoot(projects.clone())));
+    assert_eq!(project_trash::check_delete_target(&home, &roots), Err(project_trash::DeleteRefusal::ContainsRoot(projects.clone())));
+    assert_eq!(project_trash::check_delete_target(&project.join(".."), &roots), Err(project_trash::DeleteRefusal::ContainsRoot(projects.clone())));
+    // Outside every allowed root
+    assert_eq!(project_trash::check_delete_target(&elsewhere, &roots), Err(project_trash::DeleteRefusal::OutsideRoots));
+    assert_eq!(project_trash::check_delete_target(&home.join("missing"), &roots), Err(project_trash::DeleteRefusal::OutsideRoots));
+    assert_eq!(project_trash::check_delete_target(&project, &[home.join("no-such-root")]), Err(project_trash::DeleteRefusal::OutsideRoots));
+
+    #[cfg(unix)]
+    {
+        // A symlink inside the projects dir does not make its target deletable
+        let link = projects.join("Linked");
+        std::os::unix::fs::symlink(&elsewhere, &link).unwrap();
+        assert_eq!(project_trash::check_delete_target(&link, &roots), Err(project_trash::DeleteRefusal::OutsideRoots));
+    }
+}
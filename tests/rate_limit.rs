@@ -0,0 +1,43 @@
+// Aggregate bandwidth limiter used by download_asset (src/utils/rate_limit.rs).
+
+#[path = "../src/utils/rate_limit.rs"]
+mod rate_limit;
+
+use rate_limit::RateLimiter;
+use std::time::{Duration, Instant};
+
+#[test]
+fn burst_up_to_one_second_is_free() {
+    let rl = RateLimiter::new(1000);
+    assert_eq!(rl.bytes_per_sec(), 1000);
+    let t0 = Instant::now();
+    assert_eq!(rl.reserve_at(600, t0), Duration::ZERO);
+    assert_eq!(rl.reserve_at(400, t0), Duration::ZERO);
+    // Bucket is empty now: the next 500 bytes cost half a second
+    let wait = rl.reserve_at(500, t0);
+    assert!((wait.as_secs_f64() - 0.5).abs() < 1e-6, "wait was {:?}", wait);
+}
+
+#[test]
+fn debt_is_shared_across_callers() {
+    // Two "chunk tasks" drawing from one limiter queue behind each other
+    let rl = RateLimiter::new(1000);
+    let t0 = Instant::now();
+    rl.reserve_at(1000, t0);
+    let a = rl.reserve_at(1000, t0);
+    let b = rl.reserve_at(1000, t0);
+    assert!((a.as_secs_f64() - 1.0).abs() < 1e-6);
+    assert!((b.as_secs_f64() - 2.0).abs() < 1e-6);
+}
+
+#[test]
+fn refills_over_time_but_never_beyond_one_second() {
+    let rl = RateLimiter::new(1000);
+    let t0 = Instant::now();
+    rl.reserve_at(1000, t0);
+    assert_eq!(rl.reserve_at(500, t0 + Duration::from_millis(500)), Duration::ZERO);
+    // A long idle period refills to the cap only
+    let later = t0 + Duration::from_secs(60);
+    assert_eq!(rl.reserve_at(1000, later), Duration::ZERO);
+    assert!(rl.reserve_at(1, later) > Duration::ZERO);
+}
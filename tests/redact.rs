@@ -0,0 +1,40 @@
+// Secret masking applied to every log line (src/utils/redact.rs).
+
+#[path = "../src/utils/redact.rs"]
+mod redact;
+
+use redact::redact_secrets;
+
+#[test]
+fn tokens_are_masked_in_json_and_query_form() {
+    assert_eq!(
+        redact_secrets(r#"{"access_token":"abc.def","refresh_token": "xyz","expires_in":7200}"#),
+        r#"{"access_token":"***","refresh_token": "***","expires_in":7200}"#
+    );
+    assert_eq!(redact_secrets("GET /cb?code=s3cr3t&state=1"), "GET /cb?code=***&state=1");
+    assert_eq!(redact_secrets("token=abc token=def"), "token=*** token=***");
+    assert_eq!(redact_secrets(r#"{"authorizationCode":"xyz"}"#), r#"{"authorizationCode":"***"}"#);
+}
+
+#[test]
+fn whole_keys_only() {
+    assert_eq!(redact_secrets("zipcode=12345 my_token=1"), "zipcode=12345 my_token=1");
+    assert_eq!(redact_secrets("already token=***"), "already token=***");
+}
+
+#[test]
+fn multibyte_values_do_not_panic() {
+    assert_eq!(redact_secrets("token=\"€"), "token=\"€");
+    assert_eq!(redact_secrets("token=€€€ next"), "token=*** next");
+    assert_eq!(redact_secrets(r#"{"token":"€"} token=ü"#), r#"{"token":"***"} token=***"#);
+    assert_eq!(redact_secrets("€ code=日本 ✓"), "€ code=*** ✓");
+    assert_eq!(redact_secrets("token="), "token=");
+}
+
+#[test]
+fn error_response_codes_are_kept() {
+    let body = r#"{"ok":false,"code":"project_not_found","message":"No project named Foo"}"#;
+    assert_eq!(redact_secrets(body), body);
+    let spaced = r#"{"ok": false, "code": "auth_failed", "message": "token=abc rejected"}"#;
+    assert_eq!(redact_secrets(spaced), r#"{"ok": false, "code": "auth_failed", "message": "token=*** rejected"}"#);
+}
@@ -0,0 +1,88 @@
+// Chunk retry policy (src/utils/retry.rs): retryable statuses, backoff growth, env parsing and the
+// retry loop around a whole attempt.
+
+#[path = "../src/utils/retry.rs"]
+mod retry;
+
+use retry::{with_retries, AttemptError};
+use std::cell::Cell;
+use std::time::Duration;
+
+#[test]
+fn only_transient_statuses_are_retried() {
+    for code in [408, 429, 500, 502, 503, 504, 599] {
+        assert!(retry::is_retryable_status(code), "{}", code);
+    }
+    for code in [200, 206, 400, 401, 403, 404, 410] {
+        assert!(!retry::is_retryable_status(code), "{}", code);
+    }
+}
+
+#[test]
+fn backoff_doubles_with_jitter_and_caps() {
+    assert_eq!(retry::backoff_delay(0, 0.0), Duration::from_millis(250));
+    assert_eq!(retry::backoff_delay(0, 1.0), Duration::from_millis(500));
+    assert_eq!(retry::backoff_delay(1, 0.0), Duration::from_millis(500));
+    assert_eq!(retry::backoff_delay(2, 0.5), Duration::from_millis(1500));
+    // Never above the cap, even for absurd attempt numbers
+    assert_eq!(retry::backoff_delay(10, 1.0), Duration::from_millis(retry::MAX_DELAY_MS));
+    assert_eq!(retry::backoff_delay(u32::MAX, 1.0), Duration::from_millis(retry::MAX_DELAY_MS));
+    for attempt in 0..8 {
+        assert!(retry::backoff_delay(attempt, 0.99) <= retry::backoff_delay(attempt + 1, 0.99));
+    }
+}
+
+#[test]
+fn retries_from_env_value() {
+    assert_eq!(retry::parse_retries(None), retry::DEFAULT_CHUNK_RETRIES);
+    assert_eq!(retry::parse_retries(Some(" 5 ")), 5);
+    assert_eq!(retry::parse_retries(Some("0")), 0);
+    assert_eq!(retry::parse_retries(Some("100")), retry::MAX_CHUNK_RETRIES);
+    assert_eq!(retry::parse_retries(Some("-1")), retry::DEFAULT_CHUNK_RETRIES);
+}
+
+#[tokio::test]
+async fn body_cut_off_mid_stream_is_retried() {
+    let attempts = Cell::new(0);
+    let mut reasons = Vec::new();
+    let result = with_retries(
+        3,
+        || {
+            attempts.set(attempts.get() + 1);
+            let n = attempts.get();
+            async move {
+                // The request succeeded both times; the first body was cut off
+                if n == 1 { Err(AttemptError::Transient("body read failed after 512 bytes: connection reset".to_string())) } else { Ok(4096u64) }
+            }
+        },
+        |retry_no, reason| {
+            reasons.push((retry_no, reason.to_string()));
+            Duration::ZERO
+        },
+    )
+    .await;
+    assert_eq!(result.unwrap(), 4096);
+    assert_eq!(attempts.get(), 2);
+    assert_eq!(reasons, vec![(1, "body read failed after 512 bytes: connection reset".to_string())]);
+}
+
+#[tokio::test]
+async fn gives_up_after_max_retries_and_never_retries_fatal_errors() {
+    let attempts = Cell::new(0);
+    let result: Result<(), _> = with_retries(2, || {
+        attempts.set(attempts.get() + 1);
+        async { Err(AttemptError::Transient("HTTP 503".to_string())) }
+    }, |_, _| Duration::ZERO)
+    .await;
+    assert!(matches!(result, Err(AttemptError::Transient(ref r)) if r == "HTTP 503"));
+    assert_eq!(attempts.get(), 3, "first attempt plus two retries");
+
+    attempts.set(0);
+    let result: Result<(), _> = with_retries(5, || {
+        attempts.set(attempts.get() + 1);
+        async { Err(AttemptError::Fatal(anyhow::anyhow!("cancelled"))) }
+    }, |_, _| Duration::ZERO)
+    .await;
+    assert!(matches!(result, Err(AttemptError::Fatal(ref e)) if e.to_string() == "cancelled"));
+    assert_eq!(attempts.get(), 1);
+}
@@ -0,0 +1,57 @@
+// Numeric engine version parsing and ordering (src/utils/semver.rs).
+
+#[path = "../src/utils/semver.rs"]
+mod semver;
+
+use semver::{compare_versions, parse_semver};
+use std::cmp::Ordering;
+
+#[test]
+fn parses_one_to_three_numeric_parts() {
+    assert_eq!(parse_semver("5"), Some((5, 0, 0)));
+    assert_eq!(parse_semver("5.10"), Some((5, 10, 0)));
+    assert_eq!(parse_semver(" 4.27.2 "), Some((4, 27, 2)));
+    assert_eq!(parse_semver("unknown"), None);
+    assert_eq!(parse_semver("5.x"), None);
+    assert_eq!(parse_semver("5.4.2.1"), None);
+    assert_eq!(parse_semver(""), None);
+}
+
+#[test]
+fn compares_numerically_not_as_strings() {
+    assert_eq!(compare_versions("5.9", "5.10"), Ordering::Less);
+    assert_eq!(compare_versions("4.27", "5.0"), Ordering::Less);
+    assert_eq!(compare_versions("5.4", "5.4.0"), Ordering::Equal);
+    assert_eq!(compare_versions("5.4.1", "5.4"), Ordering::Greater);
+}
+
+#[test]
+fn unknown_versions_sort_last() {
+    let mut versions = vec!["unknown", "5.10", "4.27", "5.9", "5.0"];
+    versions.sort_by(|a, b| compare_versions(a, b));
+    assert_eq!(versions, vec!["4.27", "5.0", "5.9", "5.10", "unknown"]);
+}
+
+#[test]
+fn partial_requests_pick_the_newest_patch() {
+    let installed = ["5.4.0", "5.3.2", "5.4.3", "5.10.1"];
+    assert_eq!(semver::pick_version(installed, "5.4", false), Some(2));
+    assert_eq!(semver::pick_version(installed, "5.4.0", false), Some(0));
+    assert_eq!(semver::pick_version(installed, "5.1", false), None, "5.1 must not match 5.10");
+    assert_eq!(semver::pick_version(installed, "5", false), Some(3));
+    assert_eq!(semver::pick_version(installed, "5.4.1", false), None);
+    // A folder-name version without a patch is 5.4.0
+    assert_eq!(semver::pick_version(["5.4", "5.4.3"], "5.4", false), Some(1));
+    assert_eq!(semver::pick_version(["5.4.3", "5.4.3"], "5.4", false), Some(0));
+}
+
+#[test]
+fn exact_requests_need_the_same_version() {
+    let installed = ["5.4.0", "5.4.3"];
+    assert_eq!(semver::pick_version(installed, "5.4", true), Some(0));
+    assert_eq!(semver::pick_version(installed, "5.4.3", true), Some(1));
+    assert_eq!(semver::pick_version(installed, "5.4.2", true), None);
+    assert!(semver::satisfies("unknown", "unknown", true));
+    assert!(!semver::satisfies("unknown-build", "unknown", true));
+    assert!(semver::satisfies("unknown-build", "unknown", false));
+}
@@ -0,0 +1,68 @@
+// Seeding and rolling back staged downloads (src/utils/staging.rs).
+
+#[path = "../src/utils/staging.rs"]
+mod staging;
+
+use std::fs;
+
+#[test]
+fn failed_seeded_download_restores_the_original_files() {
+    let tmp = tempfile::tempdir().unwrap();
+    let final_dir = tmp.path().join("downloads/Stack O Bot/5.3");
+    let staged = tmp.path().join("downloads/.staging/abc/asset");
+    fs::create_dir_all(final_dir.join("data/Content")).unwrap();
+    fs::write(final_dir.join("data/Content/Map.umap"), b"old map").unwrap();
+    fs::write(final_dir.join(staging::COMPLETE_MARKER), b"{\"status\":\"ok\"}").unwrap();
+
+    let seeded = staging::seed(&final_dir, &staged).expect("seeded from the existing folder");
+    assert!(!final_dir.exists());
+
+    // The run clears the marker, gets part way and fails
+    fs::remove_file(staged.join(staging::COMPLETE_MARKER)).unwrap();
+    fs::write(staged.join("data/Content/New.uasset.part"), b"partial").unwrap();
+    fs::create_dir_all(staged.parent().unwrap().join("temp")).unwrap();
+
+    staging::roll_back(&staged, &final_dir, Some(&seeded)).unwrap();
+    assert_eq!(fs::read(final_dir.join("data/Content/Map.umap")).unwrap(), b"old map");
+    assert_eq!(fs::read(final_dir.join(staging::COMPLETE_MARKER)).unwrap(), b"{\"status\":\"ok\"}");
+    assert!(!staged.exists());
+    assert!(staged.parent().unwrap().join("temp").is_dir(), "chunks are left to the temp/ policy");
+}
+
+#[test]
+fn unseeded_staging_is_discarded_on_failure() {
+    let tmp = tempfile::tempdir().unwrap();
+    let final_dir = tmp.path().join("downloads/New Asset");
+    let staged = tmp.path().join("downloads/.staging/def/asset");
+    assert!(staging::seed(&final_dir, &staged).is_none(), "nothing to seed from");
+
+    fs::create_dir_all(staged.join("data")).unwrap();
+    fs::write(staged.join("data/a.bin"), b"partial").unwrap();
+    staging::roll_back(&staged, &final_dir, None).unwrap();
+    assert!(!staged.exists());
+    assert!(!final_dir.exists());
+    // Rolling back twice (nothing staged) is fine
+    staging::roll_back(&staged, &final_dir, None).unwrap();
+}
+
+#[test]
+fn seeding_never_replaces_an_existing_staged_folder() {
+    let tmp = tempfile::tempdir().unwrap();
+    let final_dir = tmp.path().join("final");
+    let staged = tmp.path().join("staged");
+    fs::create_dir_all(&final_dir).unwrap();
+    fs::create_dir_all(&staged).unwrap();
+    assert!(staging::seed(&final_dir, &staged).is_none());
+    assert!(final_dir.is_dir());
+}
+
+#[test]
+fn cancelled_runs_drop_their_chunks_whatever_the_keep_policy() {
+    // The run's own error decides: the cancel flag is already acknowledged when it returns
+    assert!(staging::drop_chunks_after_failure(staging::CANCELLED, true));
+    assert!(staging::drop_chunks_after_failure(staging::CANCELLED, false));
+    // Other failures keep chunks for a retry unless EAM_KEEP_TEMP_ON_FAILURE is off
+    assert!(!staging::drop_chunks_after_failure("Chunk abc failed after 3 retries", true));
+    assert!(staging::drop_chunks_after_failure("Chunk abc failed after 3 retries", false));
+    assert!(!staging::drop_chunks_after_failure("cancelled by upstream: 503", true));
+}
@@ -0,0 +1,75 @@
+// Shared temp/ chunk folders survive cleanup while another job uses them (src/utils/temp_users.rs).
+
+#[path = "../src/utils/temp_users.rs"]
+mod temp_users;
+
+use std::path::Path;
+use temp_users::TempDirRegistry;
+
+#[test]
+fn removal_skipped_while_another_job_holds_the_folder() {
+    let reg = TempDirRegistry::new();
+    let shared = Path::new("/downloads/temp");
+    let other = Path::new("/downloads/Other/temp");
+
+    let lease = reg.acquire(shared);
+    let second = reg.acquire(shared);
+    assert_eq!(reg.users(shared), 2);
+
+    let mut removed = Vec::new();
+    assert!(!reg.remove_if_unused(shared, |d| removed.push(d.to_path_buf())));
+    // Unrelated folders are not affected by the lease
+    assert!(reg.remove_if_unused(other, |d| removed.push(d.to_path_buf())));
+    assert_eq!(removed, vec![other.to_path_buf()]);
+
+    drop(second);
+    assert!(!reg.remove_if_unused(shared, |d| removed.push(d.to_path_buf())));
+    drop(lease);
+    assert_eq!(reg.users(shared), 0);
+    assert!(reg.remove_if_unused(shared, |d| removed.push(d.to_path_buf())));
+    assert_eq!(removed.len(), 2);
+}
+
+#[test]
+fn cancelled_job_cleans_up_its_own_folder_on_disk() {
+    let root = tempfile::tempdir().unwrap();
+    let temp = root.path().join("temp");
+    std::fs::create_dir_all(&temp).unwrap();
+    std::fs::write(temp.join("A.chunk"), b"chunk").unwrap();
+
+    let reg = TempDirRegistry::new();
+    {
+        let _lease = reg.acquire(&temp);
+        // Job still running: a concurrent cancel elsewhere must leave it alone
+        assert!(!reg.remove_if_unused(&temp, |d| std::fs::remove_dir_all(d).unwrap()));
+        assert!(temp.join("A.chunk").exists());
+    }
+    assert!(reg.remove_if_unused(&temp, |d| std::fs::remove_dir_all(d).unwrap()));
+    assert!(!temp.exists());
+}
+
+#[test]
+fn leases_inside_a_folder_are_found() {
+    let reg = TempDirRegistry::new();
+    let lease = reg.acquire(Path::new("/downloads/Stack O Bot/temp"));
+    assert!(reg.any_in_use_under(Path::new("/downloads/Stack O Bot")));
+    assert!(!reg.any_in_use_under(Path::new("/downloads/Stack")), "whole path components only");
+    assert!(!reg.any_in_use_under(Path::new("/downloads/Other")));
+    drop(lease);
+    assert!(!reg.any_in_use_under(Path::new("/downloads/Stack O Bot")));
+}
+
+#[test]
+fn download_outside_its_folder_is_found_by_its_final_folder() {
+    // An unversioned asset keeps chunks in the shared downloads/temp; a staged one under .staging
+    let chunks = TempDirRegistry::new();
+    let finals = TempDirRegistry::new();
+    let _temp = chunks.acquire(Path::new("/downloads/temp"));
+    let _staged = chunks.acquire(Path::new("/downloads/.staging/temp"));
+    let target = finals.acquire(Path::new("/downloads/Stack O Bot"));
+    let running = |dir: &str| finals.any_in_use_under(Path::new(dir)) || chunks.any_in_use_under(Path::new(dir));
+    assert!(running("/downloads/Stack O Bot"));
+    assert!(!running("/downloads/Other"));
+    drop(target);
+    assert!(!running("/downloads/Stack O Bot"));
+}
@@ -0,0 +1,33 @@
+// Rolling throughput and ETA for download progress (src/utils/throughput.rs).
+
+#[path = "../src/utils/throughput.rs"]
+mod throughput;
+
+use std::time::{Duration, Instant};
+use throughput::{eta_seconds, Throughput};
+
+#[test]
+fn rate_is_measured_over_the_window() {
+    let start = Instant::now();
+    let mut t = Throughput::new(Duration::from_secs(3));
+    assert_eq!(t.record(start, 0), 0.0, "a single sample has no rate");
+
+    // 1 MB/s for 5 seconds, then a 2 MB/s burst: only the last ~3s count
+    for s in 1..=5u64 {
+        t.record(start + Duration::from_secs(s), s * 1_000_000);
+    }
+    let rate = t.record(start + Duration::from_secs(6), 7_000_000);
+    assert!((rate - 4_000_000.0 / 3.0).abs() < 1.0, "{}", rate);
+
+    // Stalled: no new bytes pulls the rate down rather than freezing it
+    let stalled = t.record(start + Duration::from_secs(9), 7_000_000);
+    assert!(stalled < rate);
+}
+
+#[test]
+fn eta_is_none_instead_of_infinite() {
+    assert_eq!(eta_seconds(10_000, 0.0), None);
+    assert_eq!(eta_seconds(10_000, f64::NAN), None);
+    assert_eq!(eta_seconds(0, 0.0), Some(0));
+    assert_eq!(eta_seconds(10_000, 3_000.0), Some(4));
+}
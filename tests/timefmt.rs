@@ -0,0 +1,34 @@
+// RFC 3339 parsing used for cached token expiry (src/utils/timefmt.rs).
+
+#[path = "../src/utils/timefmt.rs"]
+mod timefmt;
+
+#[test]
+fn parses_utc_offsets_and_fractions() {
+    assert_eq!(timefmt::parse_rfc3339("1970-01-01T00:00:00Z"), Some(0));
+    assert_eq!(timefmt::parse_rfc3339("2024-02-29T12:00:00Z"), Some(1_709_208_000));
+    assert_eq!(timefmt::parse_rfc3339("2024-02-29T12:00:00.987654Z"), Some(1_709_208_000));
+    assert_eq!(timefmt::parse_rfc3339("2024-02-29T14:00:00+02:00"), Some(1_709_208_000));
+    assert_eq!(timefmt::parse_rfc3339("2024-02-29T07:30:00-04:30"), Some(1_709_208_000));
+}
+
+#[test]
+fn rejects_malformed() {
+    for bad in ["", "2024-02-29", "2024-13-01T00:00:00Z", "2024-02-29T12:00:00", "2024-02-29T12:00:00.Z", "not a date at all!!"] {
+        assert_eq!(timefmt::parse_rfc3339(bad), None, "{}", bad);
+    }
+}
+
+#[test]
+fn expiry_threshold() {
+    let now = 1_000_000;
+    // Five-minute threshold: exactly 300s left already counts as expiring
+    assert!(timefmt::expires_within(Some(now + 299), now, 300));
+    assert!(timefmt::expires_within(Some(now + 300), now, 300));
+    assert!(!timefmt::expires_within(Some(now + 301), now, 300));
+    // Already expired
+    assert!(timefmt::expires_within(Some(now - 10), now, 300));
+    // Unknown expiry is left to the regular login flow
+    assert!(!timefmt::expires_within(None, now, 300));
+    assert!(timefmt::expires_within(Some(i64::MIN), now, 300));
+}
@@ -0,0 +1,50 @@
+// Project naming and .uproject DisplayName/FriendlyName for created projects (src/utils/project_copy.rs).
+
+#[path = "../src/utils/project_copy.rs"]
+mod project_copy;
+
+use project_copy::set_display_names;
+
+#[test]
+fn names_are_replaced_not_prefixed() {
+    let text = r#"{"FileVersion":3,"EngineAssociation":"5.4","DisplayName":"OldName","FriendlyName":"Old Name","Category":""}"#;
+    let updated = set_display_names(text, "MyGame").unwrap();
+    let json: serde_json::Value = serde_json::from_str(&updated).unwrap();
+    assert_eq!(json["DisplayName"], "MyGame");
+    assert_eq!(json["FriendlyName"], "MyGame");
+    assert_eq!(json["EngineAssociation"], "5.4");
+    assert_eq!(json["Category"], "");
+}
+
+#[test]
+fn only_existing_fields_are_set() {
+    let text = r#"{
+	"FileVersion": 3,
+	"DisplayName": "Template"
+}"#;
+    let json: serde_json::Value = serde_json::from_str(&set_display_names(text, "MyGame").unwrap()).unwrap();
+    assert_eq!(json["DisplayName"], "MyGame");
+    assert!(json.get("FriendlyName").is_none());
+}
+
+#[test]
+fn unchanged_or_foreign_files_are_left_alone() {
+    assert_eq!(set_display_names(r#"{"FileVersion":3,"DisplayName":"MyGame"}"#, "MyGame"), None);
+    assert_eq!(set_display_names(r#"{"FileVersion":3}"#, "MyGame"), None);
+    assert_eq!(set_display_names(r#"{"DisplayName":"x"}"#, "MyGame"), None);
+    assert_eq!(set_display_names("not json", "MyGame"), None);
+}
+
+#[test]
+fn project_names_follow_unreal_rules() {
+    use project_copy::validate_ue_project_name;
+    for ok in ["MyGame", "_Game", "Shooter_2", "ABCDEFGHIJKLMNOPQRST"] {
+        assert_eq!(validate_ue_project_name(ok), Ok(()), "{}", ok);
+    }
+    assert!(validate_ue_project_name("My Game").unwrap_err().contains("spaces"));
+    assert!(validate_ue_project_name("3dScene").unwrap_err().contains("digit"));
+    assert!(validate_ue_project_name("Proj-Name").unwrap_err().contains("'-'"));
+    assert!(validate_ue_project_name("Café").unwrap_err().contains("'é'"));
+    assert!(validate_ue_project_name("ABCDEFGHIJKLMNOPQRSTU").unwrap_err().contains("longer than 20"));
+    assert!(validate_ue_project_name("").is_err());
+}
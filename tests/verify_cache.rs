@@ -0,0 +1,56 @@
+// Remembered hash checks for existing files (src/utils/verify_cache.rs).
+
+#[path = "../src/utils/verify_cache.rs"]
+mod verify_cache;
+
+use verify_cache::{file_stamp, VerifyCache, VERIFY_CACHE_FILE};
+
+const HASH: &str = "2AAE6C35C94FCFB415DBE95F408B9CE91EE846ED";
+
+#[test]
+fn unchanged_file_is_fresh_after_reload() {
+    let root = tempfile::tempdir().unwrap();
+    let file = root.path().join("Content.uasset");
+    std::fs::write(&file, b"hello world").unwrap();
+    let stamp = file_stamp(&file).unwrap();
+    assert_eq!(stamp.0, 11);
+
+    let mut cache = VerifyCache::load(root.path());
+    assert!(!cache.is_fresh("Content.uasset", HASH, stamp));
+    cache.record("Content.uasset", HASH, stamp);
+    cache.save().unwrap();
+    assert!(root.path().join(VERIFY_CACHE_FILE).is_file());
+
+    let cache = VerifyCache::load(root.path());
+    assert!(cache.is_fresh("Content.uasset", HASH, stamp));
+    // Hash comparison ignores case and surrounding whitespace
+    assert!(cache.is_fresh("Content.uasset", &format!(" {} ", HASH.to_lowercase()), stamp));
+    // A different expected hash (new manifest version) needs a real check
+    assert!(!cache.is_fresh("Content.uasset", "0000000000000000000000000000000000000000", stamp));
+    assert!(!cache.is_fresh("Other.uasset", HASH, stamp));
+}
+
+#[test]
+fn changed_size_or_mtime_is_stale() {
+    let root = tempfile::tempdir().unwrap();
+    let mut cache = VerifyCache::load(root.path());
+    cache.record("a.bin", HASH, (100, 5_000));
+    assert!(cache.is_fresh("a.bin", HASH, (100, 5_000)));
+    assert!(!cache.is_fresh("a.bin", HASH, (101, 5_000)));
+    assert!(!cache.is_fresh("a.bin", HASH, (100, 5_001)));
+
+    cache.forget("a.bin");
+    assert!(!cache.is_fresh("a.bin", HASH, (100, 5_000)));
+}
+
+#[test]
+fn save_skips_write_when_nothing_changed_and_tolerates_garbage() {
+    let root = tempfile::tempdir().unwrap();
+    let mut cache = VerifyCache::load(root.path());
+    cache.save().unwrap();
+    assert!(!root.path().join(VERIFY_CACHE_FILE).exists());
+
+    std::fs::write(root.path().join(VERIFY_CACHE_FILE), b"not json").unwrap();
+    let cache = VerifyCache::load(root.path());
+    assert!(!cache.is_fresh("a.bin", HASH, (1, 1)));
+}
@@ -0,0 +1,116 @@
+// Late WebSocket subscribers must still receive a job's events, including its terminal one, and
+// each event only once (src/utils/event_buffer.rs).
+
+#[path = "../src/utils/event_buffer.rs"]
+mod event_buffer;
+
+use event_buffer::{EventBuffer, JobChannel};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+#[test]
+fn terminal_event_survives_progress_burst() {
+    let mut buf = EventBuffer::new(32);
+    buf.push("start".to_string(), None);
+    for i in 0..10 {
+        buf.push(format!("progress {}", i), None);
+    }
+    buf.push("complete".to_string(), Some(Duration::from_secs(60)));
+    // Progress emitted after the terminal event (e.g. a trailing tick) must not push it out
+    for i in 10..100 {
+        buf.push(format!("progress {}", i), None);
+    }
+    assert!(buf.len() <= 32);
+    assert!(buf.snapshot().contains(&"complete".to_string()));
+}
+
+#[test]
+fn replay_is_not_consumed_by_a_subscriber() {
+    let mut buf = EventBuffer::new(8);
+    buf.push("start".to_string(), None);
+    buf.push("progress".to_string(), None);
+    // Two tabs (or a reconnect) subscribing to the same job get the same replay
+    let first = buf.snapshot();
+    let second = buf.snapshot();
+    assert_eq!(first, vec!["start".to_string(), "progress".to_string()]);
+    assert_eq!(first, second);
+    assert!(!buf.is_empty());
+    assert!(!buf.is_expired(Instant::now()));
+}
+
+#[test]
+fn buffer_expires_only_after_terminal_grace() {
+    let mut buf = EventBuffer::new(4);
+    buf.push("progress".to_string(), None);
+    assert!(!buf.is_expired(Instant::now() + Duration::from_secs(3600)), "running jobs never expire");
+    buf.push("error".to_string(), Some(Duration::from_millis(20)));
+    assert!(!buf.is_expired(Instant::now()));
+    std::thread::sleep(Duration::from_millis(40));
+    assert!(buf.is_expired(Instant::now()));
+    assert_eq!(buf.snapshot(), vec!["progress".to_string(), "error".to_string()]);
+}
+
+/// Everything a subscriber has received so far: its replay, then whatever is waiting live.
+fn drain(replay: Vec<String>, rx: &mut tokio::sync::broadcast::Receiver<String>) -> Vec<String> {
+    let mut seen = replay;
+    while let Ok(ev) = rx.try_recv() {
+        seen.push(ev);
+    }
+    seen
+}
+
+#[test]
+fn subscriber_gets_each_event_once() {
+    let mut channel = JobChannel::new(32);
+    channel.publish("start".to_string(), None);
+    let (replay, mut rx) = channel.subscribe();
+    channel.publish("progress".to_string(), None);
+    channel.publish("complete".to_string(), Some(Duration::from_secs(60)));
+    assert_eq!(drain(replay, &mut rx), vec!["start", "progress", "complete"]);
+}
+
+#[test]
+fn subscribing_while_events_are_published_loses_and_repeats_nothing() {
+    let channel = Arc::new(Mutex::new(JobChannel::new(4096)));
+    let publisher = {
+        let channel = channel.clone();
+        std::thread::spawn(move || {
+            for i in 0..2000 {
+                channel.lock().unwrap().publish(format!("ev {}", i), None);
+                // Paced so the live receiver (128 slots) keeps up
+                std::thread::sleep(Duration::from_micros(20));
+            }
+        })
+    };
+    // Wait until the publisher is under way so the subscribe lands mid-stream
+    while channel.lock().unwrap().replay_is_empty() {
+        std::thread::yield_now();
+    }
+    let (replay, mut rx) = channel.lock().unwrap().subscribe();
+    let mut seen = replay;
+    while seen.len() < 2000 {
+        match rx.try_recv() {
+            Ok(ev) => seen.push(ev),
+            Err(tokio::sync::broadcast::error::TryRecvError::Empty) => std::thread::yield_now(),
+            Err(e) => panic!("receiver failed: {:?}", e),
+        }
+    }
+    publisher.join().unwrap();
+    assert!(rx.try_recv().is_err(), "no event may arrive twice");
+    let unique: HashSet<&String> = seen.iter().collect();
+    assert_eq!(unique.len(), 2000);
+}
+
+#[test]
+fn expired_channel_is_kept_while_someone_listens() {
+    let mut channel = JobChannel::new(8);
+    channel.publish("error".to_string(), Some(Duration::from_millis(10)));
+    let later = Instant::now() + Duration::from_secs(1);
+    let (_, rx) = channel.subscribe();
+    assert!(!channel.expire(later), "a subscriber is still attached");
+    assert!(channel.replay_is_empty(), "the replay is dropped after the grace window");
+    drop(rx);
+    channel.publish("error".to_string(), Some(Duration::from_millis(10)));
+    assert!(channel.expire(later));
+}